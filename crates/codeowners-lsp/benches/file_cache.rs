@@ -51,6 +51,20 @@ fn bench_file_cache(c: &mut Criterion) {
         b.iter(|| cache.get_unowned_files(&rules));
     });
 
+    // get_unowned_files against a generated-style CODEOWNERS of literal
+    // paths, which the rule index turns into hash lookups
+    let literal_content: String = data
+        .file_list
+        .iter()
+        .step_by(10)
+        .map(|file| format!("/{} @org/team\n", file))
+        .collect();
+    let literal_rules = parse_codeowners_file_with_positions(&literal_content);
+    group.bench_function("get_unowned_files_50k_literal_rules", |b| {
+        let cache = FileCache::from_files(data.file_list.clone());
+        b.iter(|| cache.get_unowned_files(&literal_rules));
+    });
+
     group.finish();
 }
 
@@ -0,0 +1,58 @@
+mod testdata;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+use codeowners_lsp::ownership::{check_file_ownership, CodeownersFile};
+
+/// Number of files checked per iteration
+const NUM_FILES: usize = 3000;
+
+fn bench_ownership(c: &mut Criterion) {
+    let data = testdata::generate(&testdata::TestDataConfig::default());
+    let content = testdata::content_with_n_rules(&data, 500);
+    let files: Vec<&str> = data
+        .file_list
+        .iter()
+        .take(NUM_FILES)
+        .map(|s| s.as_str())
+        .collect();
+
+    // Both paths must agree before we compare their speed
+    let codeowners = CodeownersFile::parse(&content);
+    for file in &files {
+        let naive = check_file_ownership(&content, file).map(|r| (r.line_number, r.owners));
+        let reused = codeowners
+            .owners_for(file)
+            .map(|r| (r.line_number, r.owners));
+        assert_eq!(naive, reused, "ownership mismatch for {}", file);
+    }
+
+    let mut group = c.benchmark_group("ownership");
+    group.throughput(Throughput::Elements(files.len() as u64));
+
+    // Re-parses the content and re-matches every pattern for each file
+    group.bench_function("naive_check_file_ownership_3k", |b| {
+        b.iter(|| {
+            files
+                .iter()
+                .filter(|f| check_file_ownership(&content, f).is_some())
+                .count()
+        });
+    });
+
+    // Parse and compile once, then reuse for every file
+    group.bench_function("codeowners_file_reused_3k", |b| {
+        b.iter(|| {
+            let codeowners = CodeownersFile::parse(&content);
+            files
+                .iter()
+                .filter(|f| codeowners.owners_for(f).is_some())
+                .count()
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_ownership);
+criterion_main!(benches);
@@ -13,6 +13,17 @@ fn bench_pattern_matching(c: &mut Criterion) {
         b.iter(|| pattern_matches("src/**/*.rs", "src/packages/auth/lib/validate.rs"));
     });
 
+    // Single-segment glob (not a plain *.ext suffix) matched at any depth.
+    // The "prefixed" variant reproduces the old per-call `format!("**/{}")`
+    // allocation for comparison.
+    let path = "src/packages/auth/lib/test_validate.rs";
+    group.bench_function("single_segment_glob", |b| {
+        b.iter(|| pattern_matches("test_*.rs", path));
+    });
+    group.bench_function("single_segment_glob_prefixed", |b| {
+        b.iter(|| pattern_matches(&format!("**/{}", "test_*.rs"), path));
+    });
+
     // CompiledPattern single
     group.bench_function("compiled_pattern_single", |b| {
         let compiled = CompiledPattern::new("src/**/*.rs");
@@ -23,6 +34,7 @@ fn bench_pattern_matching(c: &mut Criterion) {
     let representative_patterns = [
         ("extension", "*.rs"),
         ("directory", "src/"),
+        ("single_segment_glob", "test_*"),
         ("deep_wildcard", "src/**/*.rs"),
         ("catch_all", "*"),
         ("anchored_deep", "docs/**/*.md"),
@@ -4,9 +4,17 @@
 //! contributors are to files and directories, which helps suggest
 //! appropriate code owners.
 
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rayon::prelude::*;
+
+/// Default number of git analyses run at once
+pub const DEFAULT_GIT_CONCURRENCY: usize = 4;
 
 /// Statistics about a contributor's involvement with a file or directory
 #[derive(Debug, Clone)]
@@ -17,8 +25,10 @@ pub struct ContributorStats {
     pub name: String,
     /// Number of commits touching this path
     pub commit_count: usize,
-    /// Percentage of total commits (0-100)
+    /// Percentage of total commits (0-100), discounted if `stale`
     pub percentage: f64,
+    /// No commits anywhere in the repo within the activity window
+    pub stale: bool,
 }
 
 /// Suggested owner for a path based on git history
@@ -36,30 +46,237 @@ pub struct OwnerSuggestion {
     pub total_commits: usize,
 }
 
-/// Analyze git blame/log for a single file
-pub fn analyze_file(repo_root: &Path, file_path: &str) -> Option<OwnerSuggestion> {
-    let full_path = repo_root.join(file_path);
-    if !full_path.exists() {
-        return None;
+/// How confidence combines the top contributor's share of commits
+/// (dominance) with how many commits there are (volume, up to `volume_cap`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConfidenceWeights {
+    /// Weight of the top contributor's share of commits
+    pub dominance: f64,
+    /// Weight of the commit count
+    pub volume: f64,
+    /// Commit count at which volume stops adding confidence
+    pub volume_cap: usize,
+}
+
+impl Default for ConfidenceWeights {
+    fn default() -> Self {
+        Self {
+            dominance: 0.7,
+            volume: 0.3,
+            volume_cap: 100,
+        }
+    }
+}
+
+impl ConfidenceWeights {
+    /// Why these weights won't give a 0-100 confidence, if they won't
+    pub fn warning(&self) -> Option<String> {
+        if self.dominance < 0.0 || self.volume < 0.0 {
+            return Some(format!(
+                "confidence weights must not be negative (dominance {}, volume {})",
+                self.dominance, self.volume
+            ));
+        }
+        let sum = self.dominance + self.volume;
+        if (sum - 1.0).abs() > 0.01 {
+            return Some(format!(
+                "confidence weights sum to {} rather than 1, so confidence won't span 0-100 \
+                 (dominance {}, volume {})",
+                sum, self.dominance, self.volume
+            ));
+        }
+        if self.volume_cap == 0 {
+            return Some("volume_cap of 0 is treated as 1".to_string());
+        }
+        None
     }
+}
+
+/// How to analyze history and score the suggestions
+#[derive(Debug, Clone, Copy)]
+pub struct AnalysisOptions {
+    /// Group files by their first N directory segments instead of parent
+    pub depth: Option<usize>,
+    /// Maximum git analyses to run at once
+    pub concurrency: usize,
+    /// How confidence is computed
+    pub weights: ConfidenceWeights,
+}
 
-    // Use git shortlog to get commit counts per author
-    let output = Command::new("git")
-        .args(["shortlog", "-sne", "--no-merges", "HEAD", "--", file_path])
-        .current_dir(repo_root)
+impl Default for AnalysisOptions {
+    fn default() -> Self {
+        Self {
+            depth: None,
+            concurrency: DEFAULT_GIT_CONCURRENCY,
+            weights: ConfidenceWeights::default(),
+        }
+    }
+}
+
+/// How far a history analysis has got, e.g. for a progress bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnalysisProgress {
+    /// About to analyze this many directories
+    Directories(usize),
+    /// About to analyze this many files one at a time
+    Files(usize),
+    /// One more directory or file analyzed
+    Analyzed,
+}
+
+/// Why git history couldn't be analyzed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlameError {
+    /// `git` couldn't be run (not installed or not on PATH)
+    GitMissing,
+    /// The directory isn't inside a git repository
+    NotARepo,
+    /// No commits touch the path (new, untracked or missing)
+    NoHistory,
+    /// Git refuses a repository owned by another user until it's listed in
+    /// `safe.directory`
+    DubiousOwnership,
+}
+
+impl std::fmt::Display for BlameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            BlameError::GitMissing => "git is not installed or not on PATH",
+            BlameError::NotARepo => "not a git repository",
+            BlameError::NoHistory => "no git history for this path",
+            BlameError::DubiousOwnership => "git refuses a repository owned by another user",
+        })
+    }
+}
+
+impl std::error::Error for BlameError {}
+
+const GIT: &str = "git";
+
+/// Run git in `repo_root` and return its stdout
+fn git(repo_root: &Path, args: &[&str]) -> Result<String, BlameError> {
+    run(GIT, repo_root, args)
+}
+
+fn run(program: &str, repo_root: &Path, args: &[&str]) -> Result<String, BlameError> {
+    // Spawning in a missing directory also fails with NotFound
+    if !repo_root.is_dir() {
+        return Err(BlameError::NotARepo);
+    }
+
+    let output = git_command(program, repo_root)
+        .args(args)
         .output()
-        .ok()?;
+        .map_err(|_| BlameError::GitMissing)?;
 
     if !output.status.success() {
-        return None;
+        return Err(classify_git_failure(&String::from_utf8_lossy(
+            &output.stderr,
+        )));
     }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// `program` set up to run in `repo_root`, honoring a separate git dir or
+/// work tree given through `GIT_DIR`/`GIT_WORK_TREE`
+fn git_command(program: &str, repo_root: &Path) -> Command {
+    let mut command = Command::new(program);
+    command.current_dir(repo_root);
+    if let Ok(cwd) = env::current_dir() {
+        command.envs(absolute_git_env(&cwd, |var| env::var_os(var)));
+    }
+    command
+}
+
+/// `GIT_DIR` and `GIT_WORK_TREE` resolved against `cwd`. Git resolves
+/// relative values against its own working directory, which is
+/// `repo_root` rather than where we were started.
+fn absolute_git_env(
+    cwd: &Path,
+    var: impl Fn(&str) -> Option<OsString>,
+) -> Vec<(&'static str, PathBuf)> {
+    ["GIT_DIR", "GIT_WORK_TREE"]
+        .into_iter()
+        .filter_map(|name| Some((name, cwd.join(var(name)?))))
+        .collect()
+}
+
+/// Any git failure other than being outside a repository or refusing it
+/// (unborn HEAD, pathspec outside the repo, ...) means there's no usable
+/// history
+fn classify_git_failure(stderr: &str) -> BlameError {
+    if stderr.contains("not a git repository") {
+        BlameError::NotARepo
+    } else if stderr.contains("dubious ownership") {
+        BlameError::DubiousOwnership
+    } else {
+        BlameError::NoHistory
+    }
+}
+
+/// The command that tells git to trust `repo_root` despite its owner
+pub fn safe_directory_command(repo_root: &Path) -> String {
+    format!(
+        "git config --global --add safe.directory {}",
+        repo_root.display()
+    )
+}
+
+/// Check that git is installed and `repo_root` is inside a repository, so
+/// callers can report setup problems before analyzing anything
+pub fn ensure_repo(repo_root: &Path) -> Result<(), BlameError> {
+    git(repo_root, &["rev-parse", "--git-dir"]).map(|_| ())
+}
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    parse_shortlog_output(&stdout, file_path)
+/// Root of the working tree containing `dir`
+pub fn repo_toplevel(dir: &Path) -> Result<PathBuf, BlameError> {
+    git(dir, &["rev-parse", "--show-toplevel"]).map(|out| PathBuf::from(out.trim_end()))
+}
+
+/// Whether the repository is a shallow clone, whose history (and so any
+/// suggestion) stops at the clone depth
+pub fn is_shallow(repo_root: &Path) -> bool {
+    git(repo_root, &["rev-parse", "--is-shallow-repository"])
+        .is_ok_and(|out| out.trim_end() == "true")
+}
+
+/// Warning for callers to print when `repo_root` is a shallow clone
+pub const SHALLOW_WARNING: &str =
+    "shallow clone: git history is truncated, so suggestions only reflect recent commits \
+     (run `git fetch --unshallow` for full history)";
+
+/// Directories with more tracked files than this skip per-file `--follow`
+/// and fall back to a single pathspec shortlog, trading pre-rename history
+/// for one git call instead of hundreds.
+const MAX_FOLLOW_FILES: usize = 200;
+
+/// Commit hashes per author ("Name <email>"), so commits reached through
+/// several files are only counted once
+type AuthorCommits = HashMap<String, HashSet<String>>;
+
+/// Analyze git blame/log for a single file, following it across renames
+pub fn analyze_file(repo_root: &Path, file_path: &str) -> Result<OwnerSuggestion, BlameError> {
+    let full_path = repo_root.join(file_path);
+    if !full_path.exists() {
+        return Err(BlameError::NoHistory);
+    }
+
+    let mut authors = AuthorCommits::new();
+    follow_file(repo_root, file_path, &mut authors)?;
+    if let Some(suggestion) = suggestion_from_author_commits(&authors, file_path) {
+        return Ok(suggestion);
+    }
+
+    // `--follow` found nothing (e.g. untracked or unusual path); use shortlog
+    shortlog(repo_root, file_path, file_path)
 }
 
 /// Analyze git history for a directory (all files within)
-pub fn analyze_directory(repo_root: &Path, dir_path: &str) -> Option<OwnerSuggestion> {
+///
+/// `--follow` only accepts a single path, so each tracked file is followed
+/// separately and the commits are merged. Large directories use a plain
+/// pathspec shortlog instead.
+pub fn analyze_directory(repo_root: &Path, dir_path: &str) -> Result<OwnerSuggestion, BlameError> {
     // Normalize directory path
     let dir_pattern = if dir_path.ends_with('/') {
         format!("{}*", dir_path)
@@ -67,33 +284,160 @@ pub fn analyze_directory(repo_root: &Path, dir_path: &str) -> Option<OwnerSugges
         format!("{}/*", dir_path)
     };
 
-    let output = Command::new("git")
-        .args([
-            "shortlog",
-            "-sne",
+    let files = tracked_files(repo_root, &dir_pattern)?;
+    if !files.is_empty() && files.len() <= MAX_FOLLOW_FILES {
+        let mut authors = AuthorCommits::new();
+        for file in &files {
+            follow_file(repo_root, file, &mut authors)?;
+        }
+        if let Some(suggestion) = suggestion_from_author_commits(&authors, dir_path) {
+            return Ok(suggestion);
+        }
+    }
+
+    shortlog(repo_root, &dir_pattern, dir_path)
+}
+
+/// Commit counts per author for a pathspec, without rename tracking
+fn shortlog(repo_root: &Path, pathspec: &str, path: &str) -> Result<OwnerSuggestion, BlameError> {
+    let stdout = git(
+        repo_root,
+        &["shortlog", "-sne", "--no-merges", "HEAD", "--", pathspec],
+    )?;
+    parse_shortlog_output(&stdout, path).ok_or(BlameError::NoHistory)
+}
+
+/// Tracked files matching a pathspec, relative to the repo root
+fn tracked_files(repo_root: &Path, pathspec: &str) -> Result<Vec<String>, BlameError> {
+    let stdout = git(repo_root, &["ls-files", "--", pathspec])?;
+    Ok(stdout.lines().map(str::to_string).collect())
+}
+
+/// Add the commits touching `file_path`, including those from before it was
+/// renamed, to `authors`. A path git can't follow adds nothing.
+fn follow_file(
+    repo_root: &Path,
+    file_path: &str,
+    authors: &mut AuthorCommits,
+) -> Result<(), BlameError> {
+    let stdout = git(
+        repo_root,
+        &[
+            "log",
+            "--follow",
             "--no-merges",
+            "--format=%H%x09%aN <%aE>",
             "HEAD",
             "--",
-            &dir_pattern,
-        ])
-        .current_dir(repo_root)
-        .output()
-        .ok()?;
+            file_path,
+        ],
+    );
+    match stdout {
+        Ok(stdout) => parse_follow_output(&stdout, authors),
+        Err(BlameError::NoHistory) => {}
+        Err(e) => return Err(e),
+    }
+    Ok(())
+}
 
-    if !output.status.success() {
-        return None;
+/// Parse `git log --format=%H%x09%aN <%aE>` output into `authors`
+fn parse_follow_output(output: &str, authors: &mut AuthorCommits) {
+    for line in output.lines() {
+        if let Some((hash, author)) = line.trim().split_once('\t') {
+            authors
+                .entry(author.trim().to_string())
+                .or_default()
+                .insert(hash.to_string());
+        }
     }
+}
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    parse_shortlog_output(&stdout, dir_path)
+fn suggestion_from_author_commits(authors: &AuthorCommits, path: &str) -> Option<OwnerSuggestion> {
+    let contributors = authors
+        .iter()
+        .filter(|(_, commits)| !commits.is_empty())
+        .map(|(author, commits)| {
+            let (name, email) = parse_author(author);
+            ContributorStats {
+                email,
+                name,
+                commit_count: commits.len(),
+                percentage: 0.0,
+                stale: false,
+            }
+        })
+        .collect();
+    build_suggestion(contributors, path)
 }
 
 /// Analyze multiple files and aggregate results by directory
+///
+/// Files are grouped by their parent directory, or with `depth` by its first
+/// `depth` segments (e.g. `src/billing/` for depth 2) for coarser suggestions.
+///
+/// Directories without history are left out; git setup problems are errors.
 pub fn analyze_files_by_directory(
     repo_root: &Path,
     files: &[String],
-) -> HashMap<String, OwnerSuggestion> {
-    // Group files by their parent directory
+    depth: Option<usize>,
+    concurrency: usize,
+) -> Result<HashMap<String, OwnerSuggestion>, BlameError> {
+    analyze_directories(repo_root, files, depth, concurrency, &|_| {})
+}
+
+/// [`analyze_files_by_directory`], reporting progress to `on_progress`
+fn analyze_directories(
+    repo_root: &Path,
+    files: &[String],
+    depth: Option<usize>,
+    concurrency: usize,
+    on_progress: &(dyn Fn(AnalysisProgress) + Sync),
+) -> Result<HashMap<String, OwnerSuggestion>, BlameError> {
+    let dir_files = group_by_directory(files, depth);
+    let dirs: Vec<&String> = dir_files.keys().collect();
+
+    // Analyze each directory
+    on_progress(AnalysisProgress::Directories(dirs.len()));
+    let analyzed = map_bounded(&dirs, concurrency, |dir| {
+        let result = analyze_directory(repo_root, dir);
+        on_progress(AnalysisProgress::Analyzed);
+        result
+    });
+    let mut results = HashMap::new();
+
+    for (dir, result) in dirs.into_iter().zip(analyzed) {
+        match result {
+            Ok(suggestion) => {
+                results.insert(dir.clone(), suggestion);
+            }
+            Err(BlameError::NoHistory) => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(results)
+}
+
+/// Map `f` over `items` on at most `concurrency` threads, keeping their
+/// order. Each call spawns git, so this also bounds the subprocesses.
+fn map_bounded<T: Sync, R: Send>(
+    items: &[T],
+    concurrency: usize,
+    f: impl Fn(&T) -> R + Sync + Send,
+) -> Vec<R> {
+    let threads = concurrency.min(items.len());
+    if threads > 1 {
+        if let Ok(pool) = rayon::ThreadPoolBuilder::new().num_threads(threads).build() {
+            return pool.install(|| items.par_iter().map(&f).collect());
+        }
+    }
+    items.iter().map(f).collect()
+}
+
+/// Group files by parent directory ("/" for the repo root), truncated to
+/// `depth` segments. Files shallower than `depth` group at their own
+/// directory.
+fn group_by_directory(files: &[String], depth: Option<usize>) -> HashMap<String, Vec<String>> {
     let mut dir_files: HashMap<String, Vec<String>> = HashMap::new();
 
     for file in files {
@@ -102,33 +446,183 @@ pub fn analyze_files_by_directory(
             .map(|p| p.to_string_lossy().to_string())
             .unwrap_or_default();
 
+        let dir = match depth {
+            Some(depth) => dir.split('/').take(depth).collect::<Vec<_>>().join("/"),
+            None => dir,
+        };
+
         let dir = if dir.is_empty() { "/".to_string() } else { dir };
 
         dir_files.entry(dir).or_default().push(file.clone());
     }
 
-    // Analyze each directory
-    let mut results = HashMap::new();
+    dir_files
+}
 
-    for dir in dir_files.keys() {
-        if let Some(suggestion) = analyze_directory(repo_root, dir) {
-            results.insert(dir.clone(), suggestion);
+/// Share of a stale contributor's commits that still count towards their
+/// percentage and the suggestion's confidence
+const STALE_WEIGHT: f64 = 0.1;
+
+const SECONDS_PER_MONTH: u64 = 30 * 24 * 60 * 60;
+
+/// Each contributor's most recent commit anywhere in the repo, used to
+/// down-weight people who have likely moved on. Lookups are cached per email.
+pub struct AuthorActivity {
+    repo_root: PathBuf,
+    /// Unix time before which a contributor's last commit counts as stale
+    cutoff: u64,
+    /// Email -> author time of their latest commit (None if git found none)
+    last_commit: HashMap<String, Option<u64>>,
+}
+
+impl AuthorActivity {
+    /// Treat contributors with no commits in the last `stale_after_months`
+    /// months as stale
+    pub fn new(repo_root: &Path, stale_after_months: u32) -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Self {
+            repo_root: repo_root.to_path_buf(),
+            cutoff: now.saturating_sub(stale_after_months as u64 * SECONDS_PER_MONTH),
+            last_commit: HashMap::new(),
         }
     }
 
-    results
+    /// Author time of `email`'s most recent commit on HEAD
+    fn last_commit(&mut self, email: &str) -> Option<u64> {
+        if let Some(&cached) = self.last_commit.get(email) {
+            return cached;
+        }
+
+        let timestamp = git_command(GIT, &self.repo_root)
+            .args([
+                "log",
+                "-1",
+                "--fixed-strings",
+                &format!("--author=<{}>", email),
+                "--format=%at",
+                "HEAD",
+            ])
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .and_then(|o| String::from_utf8_lossy(&o.stdout).trim().parse().ok());
+
+        self.last_commit.insert(email.to_string(), timestamp);
+        timestamp
+    }
+
+    /// Contributors without an email, or whose history can't be read, are
+    /// given the benefit of the doubt
+    fn is_stale(&mut self, email: &str) -> bool {
+        !email.is_empty()
+            && self
+                .last_commit(email)
+                .is_some_and(|last| last < self.cutoff)
+    }
+
+    /// Mark stale contributors and re-rank the suggestion around them
+    pub fn reweight(&mut self, suggestion: &mut OwnerSuggestion, weights: &ConfidenceWeights) {
+        for contrib in &mut suggestion.contributors {
+            contrib.stale = self.is_stale(&contrib.email);
+        }
+        score(suggestion, weights);
+    }
 }
 
 /// Batch analyze unowned files and suggest owners
+///
+/// `options.depth` is passed to [`analyze_files_by_directory`]. Up to
+/// `options.concurrency` directories or files are analyzed at once, and
+/// each suggestion is scored with `options.weights`. With `activity`,
+/// contributors who have gone quiet across the whole repo are down-weighted
+/// before suggestions are filtered by `min_confidence`.
+///
+/// Fails with [`BlameError::NoHistory`] only if none of the files have any
+/// commits; otherwise files without history are skipped.
 pub fn suggest_owners_for_files(
     repo_root: &Path,
     unowned_files: &[String],
     min_confidence: f64,
-) -> Vec<OwnerSuggestion> {
+    options: &AnalysisOptions,
+    activity: Option<&mut AuthorActivity>,
+) -> Result<Vec<OwnerSuggestion>, BlameError> {
+    suggest_owners_with_fallback(
+        repo_root,
+        unowned_files,
+        min_confidence,
+        min_confidence,
+        options,
+        activity,
+    )
+}
+
+/// Score `suggestion` with `weights`, marking stale contributors first when
+/// tracking `activity`
+fn rescore(
+    suggestion: &mut OwnerSuggestion,
+    weights: &ConfidenceWeights,
+    activity: Option<&mut AuthorActivity>,
+) {
+    match activity {
+        Some(activity) => activity.reweight(suggestion, weights),
+        None => score(suggestion, weights),
+    }
+}
+
+/// [`suggest_owners_for_files`] with separate thresholds: directories below
+/// `min_confidence` fall back to per-file suggestions, which only need
+/// `file_min_confidence` (e.g. 0 to suggest something for every file with
+/// history)
+pub fn suggest_owners_with_fallback(
+    repo_root: &Path,
+    unowned_files: &[String],
+    min_confidence: f64,
+    file_min_confidence: f64,
+    options: &AnalysisOptions,
+    activity: Option<&mut AuthorActivity>,
+) -> Result<Vec<OwnerSuggestion>, BlameError> {
+    suggest_owners_with_progress(
+        repo_root,
+        unowned_files,
+        min_confidence,
+        file_min_confidence,
+        options,
+        activity,
+        |_| {},
+    )
+}
+
+/// [`suggest_owners_with_fallback`], calling `on_progress` as directories
+/// and files are analyzed (from several threads at once)
+pub fn suggest_owners_with_progress<F>(
+    repo_root: &Path,
+    unowned_files: &[String],
+    min_confidence: f64,
+    file_min_confidence: f64,
+    options: &AnalysisOptions,
+    mut activity: Option<&mut AuthorActivity>,
+    on_progress: F,
+) -> Result<Vec<OwnerSuggestion>, BlameError>
+where
+    F: Fn(AnalysisProgress) + Sync,
+{
     let mut suggestions = Vec::new();
 
     // First try to get directory-level suggestions
-    let dir_suggestions = analyze_files_by_directory(repo_root, unowned_files);
+    let mut dir_suggestions = analyze_directories(
+        repo_root,
+        unowned_files,
+        options.depth,
+        options.concurrency,
+        &on_progress,
+    )?;
+    let mut found_history = !dir_suggestions.is_empty();
+    for suggestion in dir_suggestions.values_mut() {
+        rescore(suggestion, &options.weights, activity.as_deref_mut());
+    }
 
     // For directories with good confidence, use directory suggestion
     let mut covered_dirs: Vec<String> = Vec::new();
@@ -147,39 +641,56 @@ pub fn suggest_owners_for_files(
     }
 
     // For remaining files not covered by directory suggestions, analyze individually
-    for file in unowned_files {
-        let parent_dir = Path::new(file)
-            .parent()
-            .map(|p| format!("{}/", p.to_string_lossy()))
-            .unwrap_or_default();
-
-        // Skip if parent directory already has a suggestion
-        // Compare with trailing / to avoid "src-extra/" matching "src/"
-        if covered_dirs.iter().any(|d| {
-            let prefix = d.trim_end_matches('/');
-            parent_dir == format!("{}/", prefix) || parent_dir.starts_with(&format!("{}/", prefix))
-        }) {
-            continue;
+    let remaining: Vec<&String> = unowned_files
+        .iter()
+        .filter(|file| {
+            let parent_dir = Path::new(file)
+                .parent()
+                .map(|p| format!("{}/", p.to_string_lossy()))
+                .unwrap_or_default();
+
+            // Skip if parent directory already has a suggestion
+            // Compare with trailing / to avoid "src-extra/" matching "src/"
+            !covered_dirs.iter().any(|d| {
+                let prefix = d.trim_end_matches('/');
+                parent_dir == format!("{}/", prefix)
+                    || parent_dir.starts_with(&format!("{}/", prefix))
+            })
+        })
+        .collect();
+    on_progress(AnalysisProgress::Files(remaining.len()));
+    let analyzed = map_bounded(&remaining, options.concurrency, |file| {
+        let result = analyze_file(repo_root, file);
+        on_progress(AnalysisProgress::Analyzed);
+        result
+    });
+
+    for result in analyzed {
+        let mut suggestion = match result {
+            Ok(suggestion) => suggestion,
+            Err(BlameError::NoHistory) => continue,
+            Err(e) => return Err(e),
+        };
+        found_history = true;
+        rescore(&mut suggestion, &options.weights, activity.as_deref_mut());
+        if suggestion.confidence >= file_min_confidence {
+            suggestions.push(suggestion);
         }
+    }
 
-        // Analyze individual file
-        if let Some(suggestion) = analyze_file(repo_root, file) {
-            if suggestion.confidence >= min_confidence {
-                suggestions.push(suggestion);
-            }
-        }
+    if !found_history && !unowned_files.is_empty() {
+        return Err(BlameError::NoHistory);
     }
 
     // Sort by confidence (highest first)
     suggestions.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
 
-    suggestions
+    Ok(suggestions)
 }
 
 /// Parse git shortlog output into contributor stats
 fn parse_shortlog_output(output: &str, path: &str) -> Option<OwnerSuggestion> {
     let mut contributors: Vec<ContributorStats> = Vec::new();
-    let mut total_commits = 0usize;
 
     for line in output.lines() {
         let line = line.trim();
@@ -197,41 +708,68 @@ fn parse_shortlog_output(output: &str, path: &str) -> Option<OwnerSuggestion> {
             Ok(c) => c,
             Err(_) => continue, // skip malformed lines instead of aborting all results
         };
-        let author = parts[1].trim();
-
-        // Parse "Name <email>"
-        let (name, email) = if let Some(start) = author.find('<') {
-            if let Some(end) = author.find('>') {
-                let name = author[..start].trim().to_string();
-                let email = author[start + 1..end].to_string();
-                (name, email)
-            } else {
-                (author.to_string(), String::new())
-            }
-        } else {
-            (author.to_string(), String::new())
-        };
+        let (name, email) = parse_author(parts[1].trim());
 
-        total_commits += count;
         contributors.push(ContributorStats {
             email,
             name,
             commit_count: count,
             percentage: 0.0, // Will calculate after
+            stale: false,
         });
     }
 
+    build_suggestion(contributors, path)
+}
+
+/// Split "Name <email>" into its parts; anything else is all name
+fn parse_author(author: &str) -> (String, String) {
+    if let Some(start) = author.find('<') {
+        if let Some(end) = author.find('>') {
+            let name = author[..start].trim().to_string();
+            let email = author[start + 1..end].to_string();
+            return (name, email);
+        }
+    }
+    (author.to_string(), String::new())
+}
+
+/// Compute percentages and confidence from per-author commit counts
+fn build_suggestion(contributors: Vec<ContributorStats>, path: &str) -> Option<OwnerSuggestion> {
     if contributors.is_empty() {
         return None;
     }
 
+    let total_commits: usize = contributors.iter().map(|c| c.commit_count).sum();
+    let mut suggestion = OwnerSuggestion {
+        path: path.to_string(),
+        suggested_owner: String::new(), // Filled by suggest command using lookup
+        confidence: 0.0,
+        contributors,
+        total_commits,
+    };
+    score(&mut suggestion, &ConfidenceWeights::default());
+    Some(suggestion)
+}
+
+/// Recompute contributor percentages, ranking and confidence, discounting
+/// stale contributors' commits by [`STALE_WEIGHT`]
+fn score(suggestion: &mut OwnerSuggestion, weights: &ConfidenceWeights) {
+    let total_commits = suggestion.total_commits;
+    let contributors = &mut suggestion.contributors;
+
     // Calculate percentages
-    for contrib in &mut contributors {
-        contrib.percentage = (contrib.commit_count as f64 / total_commits as f64) * 100.0;
+    for contrib in contributors.iter_mut() {
+        let weight = if contrib.stale { STALE_WEIGHT } else { 1.0 };
+        contrib.percentage = (contrib.commit_count as f64 * weight / total_commits as f64) * 100.0;
     }
 
-    // Sort by commit count (highest first)
-    contributors.sort_by_key(|c| std::cmp::Reverse(c.commit_count));
+    // Sort by weighted share (highest first), then by name for stable output
+    contributors.sort_by(|a, b| {
+        b.percentage
+            .total_cmp(&a.percentage)
+            .then_with(|| a.name.cmp(&b.name))
+    });
 
     // Determine confidence
     let top_contributor = &contributors[0];
@@ -240,21 +778,16 @@ fn parse_shortlog_output(output: &str, path: &str) -> Option<OwnerSuggestion> {
     // - Top contributor's percentage of commits
     // - Total number of commits (more commits = more confidence)
     let percentage_factor = top_contributor.percentage / 100.0;
-    let volume_factor = (total_commits as f64).min(100.0) / 100.0;
-    let confidence = (percentage_factor * 0.7 + volume_factor * 0.3) * 100.0;
-
-    Some(OwnerSuggestion {
-        path: path.to_string(),
-        suggested_owner: String::new(), // Filled by suggest command using lookup
-        confidence,
-        contributors,
-        total_commits,
-    })
+    let volume_cap = weights.volume_cap.max(1) as f64;
+    let volume_factor = (total_commits as f64).min(volume_cap) / volume_cap;
+    let confidence = percentage_factor * weights.dominance + volume_factor * weights.volume;
+    suggestion.confidence = (confidence * 100.0).clamp(0.0, 100.0);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_git::{commit_as, git};
 
     #[test]
     fn test_parse_shortlog() {
@@ -433,6 +966,597 @@ mod tests {
         assert_eq!(suggestion.contributors[1].name, "Bob");
     }
 
+    #[test]
+    fn test_parse_follow_output_dedupes_commits() {
+        let mut authors = AuthorCommits::new();
+        parse_follow_output(
+            "aaa\tAlice <alice@test.com>\nbbb\tBob <bob@test.com>\n",
+            &mut authors,
+        );
+        // Same commit seen again via another file in the directory
+        parse_follow_output(
+            "aaa\tAlice <alice@test.com>\nccc\tAlice <alice@test.com>\n",
+            &mut authors,
+        );
+
+        let suggestion = suggestion_from_author_commits(&authors, "src").unwrap();
+        assert_eq!(suggestion.total_commits, 3);
+        assert_eq!(suggestion.contributors[0].email, "alice@test.com");
+        assert_eq!(suggestion.contributors[0].commit_count, 2);
+        assert_eq!(suggestion.contributors[1].commit_count, 1);
+    }
+
+    /// Alice writes `old/lib.rs` over three commits, then Bob moves it to
+    /// `new/lib.rs`
+    fn renamed_repo() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        git(root, &["init", "-q"]);
+        std::fs::create_dir_all(root.join("old")).unwrap();
+
+        let mut body = String::new();
+        for i in 0..3 {
+            body.push_str(&format!("pub fn f{}() -> u32 {{\n    {}\n}}\n", i, i));
+            std::fs::write(root.join("old/lib.rs"), &body).unwrap();
+            commit_as(root, "Alice", &format!("add f{}", i));
+        }
+
+        git(root, &["mv", "old", "new"]);
+        commit_as(root, "Bob", "move old to new");
+        dir
+    }
+
+    #[test]
+    fn test_analyze_file_follows_renames() {
+        let repo = renamed_repo();
+        let suggestion = analyze_file(repo.path(), "new/lib.rs").unwrap();
+
+        assert_eq!(suggestion.total_commits, 4);
+        assert_eq!(suggestion.contributors[0].name, "Alice");
+        assert_eq!(suggestion.contributors[0].commit_count, 3);
+        assert_eq!(suggestion.contributors[1].name, "Bob");
+    }
+
+    #[test]
+    fn test_analyze_directory_follows_renames() {
+        let repo = renamed_repo();
+        let suggestion = analyze_directory(repo.path(), "new").unwrap();
+
+        assert_eq!(suggestion.path, "new");
+        assert_eq!(suggestion.total_commits, 4);
+        assert_eq!(suggestion.contributors[0].name, "Alice");
+    }
+
+    #[test]
+    fn test_analyze_file_without_history() {
+        let repo = renamed_repo();
+        std::fs::write(repo.path().join("scratch.rs"), "").unwrap();
+
+        assert_eq!(
+            analyze_file(repo.path(), "scratch.rs").unwrap_err(),
+            BlameError::NoHistory
+        );
+        assert_eq!(
+            analyze_file(repo.path(), "missing.rs").unwrap_err(),
+            BlameError::NoHistory
+        );
+    }
+
+    #[test]
+    fn test_concurrent_suggestions_match_sequential() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        git(root, &["init", "-q"]);
+        let mut unowned = Vec::new();
+        for (i, author) in ["Alice", "Bob", "Carol", "Dan"].iter().enumerate() {
+            for file in ["a.rs", "b.rs"] {
+                let path = format!("dir{}/{}", i, file);
+                std::fs::create_dir_all(root.join(format!("dir{}", i))).unwrap();
+                std::fs::write(root.join(&path), author).unwrap();
+                commit_as(root, author, &path);
+                unowned.push(path);
+            }
+        }
+        // Two authors in one directory, so its files get their own rules
+        std::fs::create_dir_all(root.join("mixed")).unwrap();
+        for (file, author) in [("x.rs", "Alice"), ("y.rs", "Bob")] {
+            std::fs::write(root.join("mixed").join(file), file).unwrap();
+            commit_as(root, author, file);
+            unowned.push(format!("mixed/{}", file));
+        }
+
+        let run = |concurrency| {
+            let mut suggestions = suggest_owners_for_files(
+                root,
+                &unowned,
+                60.0,
+                &AnalysisOptions {
+                    concurrency,
+                    ..Default::default()
+                },
+                None,
+            )
+            .unwrap();
+            // Equal confidences tie in directory (hash) order
+            suggestions.sort_by(|a, b| a.path.cmp(&b.path));
+            format!("{:?}", suggestions)
+        };
+        let sequential = run(1);
+        assert!(sequential.contains("mixed/x.rs"));
+        assert_eq!(run(4), sequential);
+    }
+
+    #[test]
+    fn test_suggest_reports_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        git(root, &["init", "-q"]);
+        std::fs::create_dir_all(root.join("solo")).unwrap();
+        std::fs::create_dir_all(root.join("mixed")).unwrap();
+        for (path, author) in [
+            ("solo/a.rs", "Alice"),
+            ("mixed/x.rs", "Alice"),
+            ("mixed/y.rs", "Bob"),
+        ] {
+            std::fs::write(root.join(path), path).unwrap();
+            commit_as(root, author, path);
+        }
+        let unowned: Vec<String> = ["solo/a.rs", "mixed/x.rs", "mixed/y.rs"]
+            .iter()
+            .map(|f| f.to_string())
+            .collect();
+
+        let events = std::sync::Mutex::new(Vec::new());
+        suggest_owners_with_progress(
+            root,
+            &unowned,
+            60.0,
+            60.0,
+            &AnalysisOptions {
+                concurrency: 4,
+                ..Default::default()
+            },
+            None,
+            |event| events.lock().unwrap().push(event),
+        )
+        .unwrap();
+
+        // Both directories, then the two files of the split one
+        let events = events.into_inner().unwrap();
+        use AnalysisProgress::*;
+        assert_eq!(
+            events,
+            [
+                Directories(2),
+                Analyzed,
+                Analyzed,
+                Files(2),
+                Analyzed,
+                Analyzed
+            ]
+        );
+    }
+
+    #[test]
+    fn test_weights_change_ranking() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        git(root, &["init", "-q"]);
+        // solo/: all of 2 commits by Alice. shared/: 6 commits split evenly
+        let commits = [
+            ("solo/a.rs", "Alice"),
+            ("solo/b.rs", "Alice"),
+            ("shared/a.rs", "Alice"),
+            ("shared/b.rs", "Bob"),
+            ("shared/c.rs", "Alice"),
+            ("shared/d.rs", "Bob"),
+            ("shared/e.rs", "Alice"),
+            ("shared/f.rs", "Bob"),
+        ];
+        let mut unowned = Vec::new();
+        for (path, author) in commits {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, author).unwrap();
+            commit_as(root, author, "add");
+        }
+        for (path, _) in commits {
+            unowned.push(path.to_string());
+        }
+
+        let ranking = |weights| {
+            let options = AnalysisOptions {
+                weights,
+                ..Default::default()
+            };
+            suggest_owners_for_files(root, &unowned, 0.0, &options, None)
+                .unwrap()
+                .into_iter()
+                .map(|s| s.path)
+                .collect::<Vec<_>>()
+        };
+
+        // Dominance wins by default: a sole author beats a 50/50 split
+        assert_eq!(ranking(ConfidenceWeights::default()), ["solo/", "shared/"]);
+        // Weighting volume, capped at 6 commits, favours the busier directory
+        let volume = ConfidenceWeights {
+            dominance: 0.1,
+            volume: 0.9,
+            volume_cap: 6,
+        };
+        assert_eq!(volume.warning(), None);
+        assert_eq!(ranking(volume), ["shared/", "solo/"]);
+    }
+
+    #[test]
+    fn test_confidence_weights() {
+        let output = "    10\tAlice <alice@test.com>\n    10\tBob <bob@test.com>\n";
+        let mut suggestion = parse_shortlog_output(output, "src/").unwrap();
+        // 50% share * 0.7 + 20 of 100 commits * 0.3
+        assert!((suggestion.confidence - 41.0).abs() < 1e-9);
+
+        let weights = ConfidenceWeights {
+            dominance: 0.5,
+            volume: 0.5,
+            volume_cap: 20,
+        };
+        score(&mut suggestion, &weights);
+        assert!((suggestion.confidence - 75.0).abs() < 1e-9);
+
+        // Weights summing past 1 are clamped to 100
+        let heavy = ConfidenceWeights {
+            dominance: 2.0,
+            volume: 2.0,
+            volume_cap: 20,
+        };
+        score(&mut suggestion, &heavy);
+        assert_eq!(suggestion.confidence, 100.0);
+    }
+
+    #[test]
+    fn test_confidence_weights_warning() {
+        assert_eq!(ConfidenceWeights::default().warning(), None);
+        let weights = |dominance, volume, volume_cap| ConfidenceWeights {
+            dominance,
+            volume,
+            volume_cap,
+        };
+        assert!(weights(0.8, 0.8, 100)
+            .warning()
+            .unwrap()
+            .contains("sum to 1.6"));
+        assert!(weights(1.2, -0.2, 100)
+            .warning()
+            .unwrap()
+            .contains("negative"));
+        assert!(weights(0.5, 0.5, 0)
+            .warning()
+            .unwrap()
+            .contains("volume_cap"));
+        // Rounding in hand-written configs is fine
+        assert_eq!(weights(0.333, 0.667, 50).warning(), None);
+    }
+
+    #[test]
+    fn test_suggest_without_any_history() {
+        let repo = renamed_repo();
+        std::fs::write(repo.path().join("scratch.rs"), "").unwrap();
+        let unowned = vec!["scratch.rs".to_string()];
+
+        assert_eq!(
+            suggest_owners_for_files(
+                repo.path(),
+                &unowned,
+                0.0,
+                &AnalysisOptions::default(),
+                None
+            )
+            .unwrap_err(),
+            BlameError::NoHistory
+        );
+
+        // Files without history are skipped when others have some
+        let unowned = vec!["scratch.rs".to_string(), "new/lib.rs".to_string()];
+        let suggestions = suggest_owners_for_files(
+            repo.path(),
+            &unowned,
+            0.0,
+            &AnalysisOptions::default(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(suggestions.len(), 1);
+    }
+
+    #[test]
+    fn test_unborn_repo_has_no_history() {
+        let dir = tempfile::tempdir().unwrap();
+        git(dir.path(), &["init", "-q"]);
+        std::fs::write(dir.path().join("lib.rs"), "").unwrap();
+
+        assert_eq!(ensure_repo(dir.path()), Ok(()));
+        assert_eq!(
+            analyze_file(dir.path(), "lib.rs").unwrap_err(),
+            BlameError::NoHistory
+        );
+    }
+
+    #[test]
+    fn test_not_a_repo() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "").unwrap();
+
+        assert_eq!(ensure_repo(dir.path()), Err(BlameError::NotARepo));
+        assert_eq!(
+            analyze_file(dir.path(), "lib.rs").unwrap_err(),
+            BlameError::NotARepo
+        );
+        assert_eq!(
+            ensure_repo(&dir.path().join("missing")),
+            Err(BlameError::NotARepo)
+        );
+    }
+
+    #[test]
+    fn test_separate_git_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let work = dir.path().join("work");
+        let git_dir = dir.path().join("meta.git");
+        git(
+            dir.path(),
+            &[
+                "init",
+                "-q",
+                "--separate-git-dir",
+                git_dir.to_str().unwrap(),
+                work.to_str().unwrap(),
+            ],
+        );
+        std::fs::write(work.join("lib.rs"), "pub fn f() {}\n").unwrap();
+        commit_as(&work, "Alice", "add lib");
+
+        assert!(!work.join(".git").is_dir());
+        assert_eq!(ensure_repo(&work), Ok(()));
+        assert_eq!(repo_toplevel(&work).unwrap(), work.canonicalize().unwrap());
+        let suggestion = analyze_file(&work, "lib.rs").unwrap();
+        assert_eq!(suggestion.contributors[0].name, "Alice");
+        assert!(!is_shallow(&work));
+    }
+
+    #[test]
+    fn test_absolute_git_env() {
+        let cwd = Path::new("/ci/checkout");
+        let env = absolute_git_env(cwd, |var| match var {
+            "GIT_DIR" => Some("../meta.git".into()),
+            "GIT_WORK_TREE" => Some("/ci/checkout/src".into()),
+            _ => None,
+        });
+        assert_eq!(
+            env,
+            vec![
+                ("GIT_DIR", PathBuf::from("/ci/checkout/../meta.git")),
+                ("GIT_WORK_TREE", PathBuf::from("/ci/checkout/src")),
+            ]
+        );
+        assert!(absolute_git_env(cwd, |_| None).is_empty());
+    }
+
+    #[test]
+    fn test_shallow_clone() {
+        let repo = renamed_repo();
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("file://{}", repo.path().display());
+        git(dir.path(), &["clone", "-q", "--depth", "1", &url, "clone"]);
+        let clone = dir.path().join("clone");
+
+        assert!(is_shallow(&clone));
+        assert!(!is_shallow(repo.path()));
+        // Only the last commit survives the clone
+        let suggestion = analyze_file(&clone, "new/lib.rs").unwrap();
+        assert_eq!(suggestion.total_commits, 1);
+    }
+
+    #[test]
+    fn test_git_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            run("git-not-installed-here", dir.path(), &["status"]),
+            Err(BlameError::GitMissing)
+        );
+    }
+
+    #[test]
+    fn test_classify_git_failure() {
+        assert_eq!(
+            classify_git_failure(
+                "fatal: not a git repository (or any of the parent directories): .git\n"
+            ),
+            BlameError::NotARepo
+        );
+        assert_eq!(
+            classify_git_failure(
+                "fatal: your current branch 'main' does not have any commits yet\n"
+            ),
+            BlameError::NoHistory
+        );
+        assert_eq!(
+            classify_git_failure(
+                "fatal: detected dubious ownership in repository at '/work/repo'\n\
+                 To add an exception for this directory, call:\n\n\
+                 \tgit config --global --add safe.directory /work/repo\n"
+            ),
+            BlameError::DubiousOwnership
+        );
+    }
+
+    fn activity(cutoff: u64, last_commits: &[(&str, Option<u64>)]) -> AuthorActivity {
+        AuthorActivity {
+            // Not a repo: any lookup that misses the cache finds no history
+            repo_root: PathBuf::from("/nonexistent"),
+            cutoff,
+            last_commit: last_commits
+                .iter()
+                .map(|(email, last)| (email.to_string(), *last))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_reweight_active_minor_outranks_departed_majority() {
+        let output = "    90\tAlice <alice@test.com>\n    10\tBob <bob@test.com>\n";
+        let mut suggestion = parse_shortlog_output(output, "src/").unwrap();
+        let before = suggestion.confidence;
+
+        let mut activity = activity(
+            1_000,
+            &[("alice@test.com", Some(500)), ("bob@test.com", Some(1_500))],
+        );
+        activity.reweight(&mut suggestion, &ConfidenceWeights::default());
+
+        assert_eq!(suggestion.contributors[0].name, "Bob");
+        assert!(!suggestion.contributors[0].stale);
+        assert_eq!(suggestion.contributors[1].name, "Alice");
+        assert!(suggestion.contributors[1].stale);
+        assert_eq!(suggestion.contributors[1].commit_count, 90);
+        assert!(suggestion.confidence < before);
+        // Raw history is unchanged
+        assert_eq!(suggestion.total_commits, 100);
+    }
+
+    #[test]
+    fn test_reweight_all_active_is_unchanged() {
+        let output = "    30\tAlice <alice@test.com>\n    10\tBob <bob@test.com>\n";
+        let mut suggestion = parse_shortlog_output(output, "src/").unwrap();
+        let before = suggestion.confidence;
+
+        activity(
+            1_000,
+            &[
+                ("alice@test.com", Some(1_000)),
+                ("bob@test.com", Some(2_000)),
+            ],
+        )
+        .reweight(&mut suggestion, &ConfidenceWeights::default());
+
+        assert_eq!(suggestion.confidence, before);
+        assert_eq!(suggestion.contributors[0].percentage, 75.0);
+    }
+
+    #[test]
+    fn test_reweight_unknown_activity_not_stale() {
+        let output = "    10\tNo Email\n     5\tAlice <alice@test.com>\n";
+        let mut suggestion = parse_shortlog_output(output, "file.rs").unwrap();
+
+        let mut activity = activity(1_000, &[]);
+        activity.reweight(&mut suggestion, &ConfidenceWeights::default());
+
+        assert!(suggestion.contributors.iter().all(|c| !c.stale));
+        // Failed lookup is cached, email-less contributors aren't looked up
+        assert_eq!(activity.last_commit.len(), 1);
+        assert_eq!(activity.last_commit["alice@test.com"], None);
+    }
+
+    #[test]
+    fn test_author_activity_reads_last_commit_date() {
+        let repo = renamed_repo();
+        // Carol only has a backdated commit; Bob committed just now
+        let old = Command::new("git")
+            .args(["-c", "commit.gpgsign=false"])
+            .args(["-c", "user.name=Carol", "-c", "user.email=carol@test.com"])
+            .args(["commit", "-q", "--allow-empty", "-m", "old work"])
+            .env("GIT_AUTHOR_DATE", "2001-01-01T00:00:00Z")
+            .current_dir(repo.path())
+            .status()
+            .unwrap();
+        assert!(old.success());
+
+        let mut activity = AuthorActivity::new(repo.path(), 6);
+        assert!(activity.is_stale("carol@test.com"));
+        assert!(!activity.is_stale("bob@test.com"));
+        assert_eq!(activity.last_commit("carol@test.com"), Some(978_307_200));
+        assert!(!activity.is_stale("nobody@test.com"));
+    }
+
+    fn grouped(files: &[&str], depth: Option<usize>) -> Vec<(String, Vec<String>)> {
+        let files: Vec<String> = files.iter().map(|f| f.to_string()).collect();
+        let mut groups: Vec<_> = group_by_directory(&files, depth).into_iter().collect();
+        groups.sort();
+        groups
+    }
+
+    const TREE: &[&str] = &[
+        "README.md",
+        "src/main.rs",
+        "src/billing/invoice.rs",
+        "src/billing/tax/vat.rs",
+        "src/auth/oauth/google/client.rs",
+    ];
+
+    #[test]
+    fn test_group_by_parent_directory() {
+        let groups = grouped(TREE, None);
+        let dirs: Vec<&str> = groups.iter().map(|(d, _)| d.as_str()).collect();
+        assert_eq!(
+            dirs,
+            vec![
+                "/",
+                "src",
+                "src/auth/oauth/google",
+                "src/billing",
+                "src/billing/tax"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_by_depth_one() {
+        assert_eq!(
+            grouped(TREE, Some(1)),
+            vec![
+                ("/".to_string(), vec!["README.md".to_string()]),
+                (
+                    "src".to_string(),
+                    vec![
+                        "src/main.rs".to_string(),
+                        "src/billing/invoice.rs".to_string(),
+                        "src/billing/tax/vat.rs".to_string(),
+                        "src/auth/oauth/google/client.rs".to_string(),
+                    ]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_by_depth_two() {
+        let groups = grouped(TREE, Some(2));
+        assert_eq!(
+            groups,
+            vec![
+                ("/".to_string(), vec!["README.md".to_string()]),
+                // Shallower than the depth: stays at its own directory
+                ("src".to_string(), vec!["src/main.rs".to_string()]),
+                (
+                    "src/auth".to_string(),
+                    vec!["src/auth/oauth/google/client.rs".to_string()]
+                ),
+                (
+                    "src/billing".to_string(),
+                    vec![
+                        "src/billing/invoice.rs".to_string(),
+                        "src/billing/tax/vat.rs".to_string(),
+                    ]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_by_depth_deeper_than_tree() {
+        // Every file is shallower than the depth, so it's the same as no depth
+        assert_eq!(grouped(TREE, Some(10)), grouped(TREE, None));
+    }
+
     #[test]
     fn test_confidence_calculation_max_volume() {
         // 100+ commits maxes out volume factor at 1.0
@@ -1,4 +1,6 @@
 mod commands;
+#[cfg(test)]
+mod test_git;
 
 // Re-export shared modules so `crate::*` paths in commands/ submodules still resolve
 use codeowners_lsp as lib;
@@ -6,16 +8,22 @@ pub use lib::blame;
 pub use lib::diagnostics;
 pub use lib::file_cache;
 pub use lib::github;
+pub use lib::identities;
 pub use lib::lookup;
+pub use lib::nested;
+pub use lib::owner_index;
 pub use lib::ownership;
 pub use lib::parser;
 pub use lib::pattern;
 pub use lib::settings;
+pub use lib::skip_owners;
 pub use lib::validation;
 
 use std::io;
 use std::path::PathBuf;
 use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Instant;
 
 use clap::{CommandFactory, Parser, Subcommand};
 use clap_complete::{generate, Shell};
@@ -24,7 +32,24 @@ use clap_complete::{generate, Shell};
 #[command(name = "codeowners-cli")]
 #[command(about = "Lint and inspect CODEOWNERS files", long_about = None)]
 #[command(version)]
+#[command(after_help = commands::exit::HELP)]
 struct Cli {
+    /// Print nothing to stdout and rely on the exit code (errors still go to stderr)
+    #[arg(short, long, global = true)]
+    quiet: bool,
+    /// Print per-phase timings (walk, parse, match, validate, blame) to stderr
+    #[arg(long, global = true)]
+    profile: bool,
+    /// Color output: auto (terminal, honoring NO_COLOR and CLICOLOR), always, or never
+    #[arg(long, global = true, default_value = "auto", value_parser = ["auto", "always", "never"])]
+    color: String,
+    /// Same as --color never
+    #[arg(long, global = true)]
+    no_color: bool,
+    /// Run as if started in this directory (like git -C): CODEOWNERS
+    /// discovery, settings and file listing all start here
+    #[arg(long, global = true, value_name = "PATH")]
+    repo_root: Option<PathBuf>,
     #[command(subcommand)]
     command: Commands,
 }
@@ -33,8 +58,11 @@ struct Cli {
 enum Commands {
     /// Check CODEOWNERS for issues
     Lint {
-        /// Path to CODEOWNERS file (default: auto-detect)
+        /// Path to CODEOWNERS file (default: auto-detect; - reads stdin)
         path: Option<PathBuf>,
+        /// Same as the positional path, for symmetry with check (- reads stdin)
+        #[arg(long, value_name = "PATH", conflicts_with = "path")]
+        codeowners: Option<PathBuf>,
         /// Output as JSON
         #[arg(long)]
         json: bool,
@@ -47,6 +75,12 @@ enum Commands {
         /// Output GitHub Actions annotations (::error, ::warning)
         #[arg(long)]
         github_actions: bool,
+        /// Flag owners that are not @org/team (users and emails)
+        #[arg(long)]
+        require_teams: bool,
+        /// Ownership semantics for codeowners-file-owner: github (last match wins) or gitlab (union of section winners)
+        #[arg(long, default_value = "github", value_parser = ["github", "gitlab"])]
+        semantics: String,
     },
     /// Format CODEOWNERS file (normalizes spacing)
     #[command(alias = "format")]
@@ -57,23 +91,67 @@ enum Commands {
         #[arg(short, long)]
         write: bool,
     },
+    /// Replace individual owners with teams using a user -> team mapping
+    MigrateTeams {
+        /// JSON or TOML file mapping users to teams, e.g. {"@alice": "@org/team"}
+        #[arg(long, value_name = "FILE")]
+        mapping: PathBuf,
+        /// Path to CODEOWNERS file (default: auto-detect)
+        path: Option<PathBuf>,
+        /// Write changes to file (default: show a diff)
+        #[arg(short, long)]
+        write: bool,
+    },
     /// Show which rule owns a specific file (or multiple files)
     Check {
-        /// File path(s) to check ownership of (positional)
+        /// File path(s) to check ownership of (positional); directories expand to their files
         #[arg(num_args = 0..)]
         paths: Vec<String>,
         /// File path(s) to check (named, like coverage)
         #[arg(long, num_args = 1..)]
         files: Option<Vec<String>>,
-        /// Output as JSON
+        /// Output as JSON (shorthand for --format json)
         #[arg(long)]
         json: bool,
+        /// Output format: human, json, github (Actions annotations), csv (file,owners,rule,line), or tsv (path, rule, line, owners; no header)
+        #[arg(long, default_value = "human", value_parser = ["human", "json", "github", "csv", "tsv"], conflicts_with = "json")]
+        format: String,
         /// Read files to check from a file (one per line)
         #[arg(long, value_name = "PATH")]
         files_from: Option<PathBuf>,
         /// Read files to check from stdin (one per line)
         #[arg(long)]
         stdin: bool,
+        /// Split --files-from/--stdin/--stream input on NUL instead of newlines (git diff -z, find -print0)
+        #[arg(short = 'z', long)]
+        null: bool,
+        /// Only show files owned by this owner (repeatable, leading @ optional; @me is you, via GITHUB_TOKEN)
+        #[arg(long = "owner", value_name = "OWNER")]
+        owners: Vec<String>,
+        /// Show every matching rule and which one wins (near misses if unowned)
+        #[arg(long)]
+        explain: bool,
+        /// Ownership semantics: github (last match wins) or gitlab (union of section winners)
+        #[arg(long, default_value = "github", value_parser = ["github", "gitlab"])]
+        semantics: String,
+        /// Output one JSON object per file (NDJSON), in input order
+        #[arg(long, conflicts_with_all = ["json", "format"])]
+        json_lines: bool,
+        /// Print only the owners, one per line (exit 1 if unowned)
+        #[arg(long, conflicts_with_all = ["json", "format", "json_lines", "explain", "stream"])]
+        owners_only: bool,
+        /// Check paths from stdin as they arrive, one result per line (NDJSON with --json)
+        #[arg(long, conflicts_with_all = ["paths", "files", "files_from", "stdin"])]
+        stream: bool,
+        /// CODEOWNERS file to use instead of auto-detecting (- reads it from stdin)
+        #[arg(long, value_name = "PATH")]
+        codeowners: Option<PathBuf>,
+        /// Also apply per-package CODEOWNERS files, scoped to their directories (GitHub ignores them)
+        #[arg(long)]
+        merge_nested: bool,
+        /// Paths in output: as-is (as given) or relative (to the repository root)
+        #[arg(long, default_value = "as-is", value_parser = ["as-is", "relative"])]
+        path_style: String,
     },
     /// Show files without owners and coverage percentage
     Coverage {
@@ -86,12 +164,54 @@ enum Commands {
         /// Read files to check from stdin (one per line)
         #[arg(long)]
         stdin: bool,
+        /// Split --files-from/--stdin lists on NUL instead of newlines (git diff -z, find -print0)
+        #[arg(short = 'z', long)]
+        null: bool,
         /// Show unowned files as a directory tree with per-directory counts
         #[arg(long)]
         tree: bool,
-        /// Output as JSON
+        /// Summarize owned/total per directory, worst coverage first
+        #[arg(long, conflicts_with = "tree")]
+        by_dir: bool,
+        /// Directory depth to group files by (with --by-dir)
+        #[arg(long, default_value = "1", requires = "by_dir", value_parser = clap::value_parser!(u32).range(1..))]
+        depth: u32,
+        /// Output as JSON (shorthand for --format json)
         #[arg(long)]
         json: bool,
+        /// Output format: human, json, github (Actions annotations for unowned files), or tsv (path, owned/unowned; no header). Not with --owner; use --json
+        #[arg(long, default_value = "human", value_parser = ["human", "json", "github", "tsv"], conflicts_with_all = ["json", "owner"])]
+        format: String,
+        /// Leave out files marked export-ignore in .gitattributes
+        #[arg(long)]
+        exclude_export_ignore: bool,
+        /// Leave out files matching this CODEOWNERS-style pattern (repeatable)
+        #[arg(long, value_name = "GLOB")]
+        exclude: Vec<String>,
+        /// Print at most N unowned files or directories (0 = all; JSON always has all)
+        #[arg(long, value_name = "N", default_value = "0")]
+        top: usize,
+        /// Order unowned files by name or by size on disk (largest first)
+        #[arg(long, default_value = "name", value_parser = ["name", "size"])]
+        sort: String,
+        /// Only fail on unowned files missing from this list (one path per line)
+        #[arg(long, value_name = "FILE")]
+        baseline: Option<PathBuf>,
+        /// Write the current unowned files to the --baseline file and exit
+        #[arg(long, requires = "baseline")]
+        update_baseline: bool,
+        /// Only the rules listing this owner: files they win and rules matching nothing (@me is you, via GITHUB_TOKEN)
+        #[arg(long, value_name = "OWNER", conflicts_with_all = ["files", "files_from", "stdin", "tree", "by_dir", "baseline"])]
+        owner: Option<String>,
+        /// Ownership semantics for --owner: github (last match wins) or gitlab (union of section winners)
+        #[arg(long, default_value = "github", value_parser = ["github", "gitlab"], requires = "owner")]
+        semantics: String,
+        /// Also apply per-package CODEOWNERS files, scoped to their directories (GitHub ignores them)
+        #[arg(long)]
+        merge_nested: bool,
+        /// Paths in output: as-is (as given) or relative (to the repository root)
+        #[arg(long, default_value = "as-is", value_parser = ["as-is", "relative"])]
+        path_style: String,
     },
     /// Generate shell completions
     Completions {
@@ -100,6 +220,7 @@ enum Commands {
         shell: Shell,
     },
     /// Validate all owners against GitHub API
+    #[command(visible_alias = "validate")]
     ValidateOwners {
         /// GitHub token (or use GITHUB_TOKEN env var)
         #[arg(long, env = "GITHUB_TOKEN")]
@@ -113,14 +234,100 @@ enum Commands {
         /// Read files to filter by from stdin (one per line)
         #[arg(long)]
         stdin: bool,
+        /// Split --files-from/--stdin lists on NUL instead of newlines (git diff -z, find -print0)
+        #[arg(short = 'z', long)]
+        null: bool,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+        /// Maximum concurrent GitHub API requests (default: config or 4)
+        #[arg(long, value_name = "N")]
+        concurrency: Option<usize>,
+        /// Also fail when an owner couldn't be validated (rate limits, permissions, emails)
+        #[arg(long)]
+        fail_on_unknown: bool,
+        /// Don't validate this owner; `*` matches within a name, e.g. '@org/*-bot' (repeatable)
+        #[arg(long = "skip-owner", value_name = "OWNER")]
+        skip_owners: Vec<String>,
+    },
+    /// Drop cached GitHub validation results for owners no longer in CODEOWNERS
+    PruneCache {
+        /// Show what would be removed without saving
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show how many files each owner is responsible for
+    Stats {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+        /// Report directories that depend on a single owner
+        #[arg(long)]
+        bus_factor: bool,
+        /// Directory depth to group files by (with --bus-factor or --last-review)
+        #[arg(long, default_value = "1", value_parser = clap::value_parser!(u32).range(1..))]
+        depth: u32,
+        /// Also flag directories owned by a single team (with --bus-factor)
+        #[arg(long)]
+        flag_teams: bool,
+        /// List owners who own no files (rules match nothing, or are always overridden)
+        #[arg(long, conflicts_with = "bus_factor")]
+        idle_owners: bool,
+        /// Expand teams to their GitHub members and show each person's share of files
+        #[arg(long, conflicts_with_all = ["bus_factor", "idle_owners"])]
+        members: bool,
+        /// Show when each directory's owners last reviewed a PR touching it (directories by --depth)
+        #[arg(long, conflicts_with_all = ["bus_factor", "idle_owners", "members"])]
+        last_review: bool,
+        /// Recent PRs to look at per directory (with --last-review)
+        #[arg(long, value_name = "N", default_value = "20", value_parser = clap::value_parser!(u32).range(1..))]
+        prs: u32,
+        /// GitHub repository as OWNER/NAME (with --last-review; default: the origin remote)
+        #[arg(long, value_name = "OWNER/NAME", env = "GITHUB_REPOSITORY")]
+        repo: Option<String>,
+        /// GitHub token for --members and --last-review (or use GITHUB_TOKEN env var)
+        #[arg(long, env = "GITHUB_TOKEN", hide_env_values = true)]
+        token: Option<String>,
+        /// Print at most N rows (0 = all; JSON always has all)
+        #[arg(long, value_name = "N", default_value = "0")]
+        top: usize,
+        /// Order rows by file count (largest first) or by name
+        #[arg(long, default_value = "count", value_parser = ["count", "name"])]
+        sort: String,
+        /// Ownership semantics: github (last match wins) or gitlab (union of section winners)
+        #[arg(long, default_value = "github", value_parser = ["github", "gitlab"])]
+        semantics: String,
+    },
+    /// Find files where a later rule overrides an earlier one owned by different people
+    Overlaps {
         /// Output as JSON
         #[arg(long)]
         json: bool,
     },
     /// Show all files color-coded by owner
     Tree,
+    /// Export who owns every file as a nested JSON tree or a flat CSV, or
+    /// each owner's patterns
+    Export {
+        /// Output format: json (directory tree), csv (one row per file), or owners (patterns per owner)
+        #[arg(long, default_value = "json", value_parser = ["json", "csv", "owners"])]
+        format: String,
+        /// Omit the contents of directories whose files all share the same owners
+        #[arg(long)]
+        collapse: bool,
+        /// Only expand directories this many levels deep (JSON tree)
+        #[arg(long, value_name = "N")]
+        max_depth: Option<usize>,
+    },
     /// Show config file paths and merged settings
     Config,
+    /// Print the JSON Schema for a command's JSON output
+    #[command(hide = true)]
+    Schema {
+        /// Command whose output to describe
+        #[arg(value_parser = commands::SCHEMA_COMMANDS)]
+        command: String,
+    },
     /// Suggest owners for unowned files based on git history
     Suggest {
         /// Minimum confidence threshold (0-100)
@@ -132,12 +339,54 @@ enum Commands {
         /// Maximum number of suggestions
         #[arg(long, default_value = "50")]
         limit: usize,
-        /// Write suggestions to CODEOWNERS file
-        #[arg(short, long)]
+        /// Write suggestions to CODEOWNERS file (skips patterns that already exist)
+        #[arg(short, long, alias = "apply")]
         write: bool,
+        /// With --write/--apply, print the resulting file instead of writing it
+        #[arg(long, requires = "write")]
+        dry_run: bool,
+        /// Print a unified diff adding the suggestions to CODEOWNERS (for `git apply`)
+        #[arg(long, conflicts_with_all = ["write", "format"])]
+        patch: bool,
         /// Prepend / to paths (anchored patterns like /src/ instead of src/)
         #[arg(long)]
         anchored: bool,
+        /// Down-weight contributors with no commits anywhere in the repo in this many months
+        #[arg(long, value_name = "MONTHS")]
+        stale_after_months: Option<u32>,
+        /// Suggest at most N directories deep (e.g. 2 for src/billing/) instead of per leaf directory
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+        depth: Option<u32>,
+        /// Leave out files matching this CODEOWNERS-style pattern (repeatable)
+        #[arg(long, value_name = "GLOB")]
+        exclude: Vec<String>,
+        /// Maximum git history analyses to run at once
+        #[arg(long, value_name = "N", default_value = "4", value_parser = clap::value_parser!(u32).range(1..))]
+        concurrency: u32,
+        /// The CODEOWNERS path in output: as-is (as given) or relative (to the repository root)
+        #[arg(long, default_value = "as-is", value_parser = ["as-is", "relative"])]
+        path_style: String,
+    },
+    /// Draft a CODEOWNERS for the whole repository from git history
+    Init {
+        /// Minimum confidence (0-100) for a directory rule; below it, files get their own rules
+        #[arg(long, default_value = "50")]
+        min_confidence: f64,
+        /// Group files at most N directories deep (e.g. 2 for src/billing/) instead of per leaf directory
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+        depth: Option<u32>,
+        /// Write the draft to this file instead of stdout
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
+        /// Overwrite --output if it already exists
+        #[arg(long, requires = "output")]
+        force: bool,
+        /// Down-weight contributors with no commits anywhere in the repo in this many months
+        #[arg(long, value_name = "MONTHS")]
+        stale_after_months: Option<u32>,
+        /// Leave out files matching this CODEOWNERS-style pattern (repeatable)
+        #[arg(long, value_name = "GLOB")]
+        exclude: Vec<String>,
     },
     /// Suggest optimizations to simplify CODEOWNERS patterns
     Optimize {
@@ -163,6 +412,9 @@ enum Commands {
         /// Read changed files from stdin (one per line)
         #[arg(long)]
         changed_files_stdin: bool,
+        /// Split the changed files list on NUL instead of newlines (git diff -z)
+        #[arg(short = 'z', long)]
+        null: bool,
         /// Skip coverage check for changed files
         #[arg(long)]
         no_coverage_changed: bool,
@@ -193,40 +445,258 @@ enum Commands {
     },
 }
 
+fn parse_semantics(value: &str) -> ownership::Semantics {
+    match value {
+        "gitlab" => ownership::Semantics::GitLab,
+        _ => ownership::Semantics::GitHub,
+    }
+}
+
+fn parse_path_style(value: &str) -> commands::files::PathStyle {
+    match value {
+        "relative" => commands::files::PathStyle::Relative,
+        _ => commands::files::PathStyle::AsIs,
+    }
+}
+
+fn parse_sort(value: &str) -> commands::SortBy {
+    match value {
+        "size" => commands::SortBy::Size,
+        "count" => commands::SortBy::Count,
+        _ => commands::SortBy::Name,
+    }
+}
+
+/// Expand `@me` (or `me`) in owner filters to the GitHub token's login
+/// (`GITHUB_TOKEN`, else `github_token` from config)
+async fn resolve_me(
+    github: &github::GitHubClient,
+    owners: Vec<String>,
+) -> Result<Vec<String>, ExitCode> {
+    if !owners.iter().any(|o| github::is_me_alias(o)) {
+        return Ok(owners);
+    }
+    let token = std::env::var("GITHUB_TOKEN")
+        .ok()
+        .filter(|token| !token.is_empty())
+        .or_else(|| settings::load_settings().resolve_token());
+    github
+        .expand_me(owners, token.as_deref())
+        .await
+        .map_err(|e| {
+            eprintln!("Error: {}", e);
+            commands::exit::Exit::Error.into()
+        })
+}
+
+fn parse_color(value: &str) -> commands::color::ColorChoice {
+    match value {
+        "always" => commands::color::ColorChoice::Always,
+        "never" => commands::color::ColorChoice::Never,
+        _ => commands::color::ColorChoice::Auto,
+    }
+}
+
+impl Commands {
+    /// Output meant for other programs (JSON, CSV, annotations, scripts),
+    /// which must never carry ANSI codes
+    fn machine_readable(&self) -> bool {
+        match self {
+            Commands::Lint {
+                json,
+                github_actions,
+                ..
+            } => *json || *github_actions,
+            Commands::Check {
+                json,
+                format,
+                json_lines,
+                owners_only,
+                ..
+            } => *json || *json_lines || *owners_only || format != "human",
+            Commands::Coverage { json, format, .. } => *json || format != "human",
+            Commands::ValidateOwners { json, .. }
+            | Commands::Stats { json, .. }
+            | Commands::Overlaps { json }
+            | Commands::Optimize { json, .. } => *json,
+            Commands::Export { format, .. } => format != "owners",
+            Commands::Suggest { format, patch, .. } => *patch || format != "human",
+            Commands::Completions { .. } | Commands::Schema { .. } | Commands::Gha { .. } => true,
+            Commands::Init { output, .. } => output.is_none(),
+            Commands::Fmt { .. }
+            | Commands::MigrateTeams { .. }
+            | Commands::PruneCache { .. }
+            | Commands::Tree
+            | Commands::Config => false,
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> ExitCode {
     let args = Cli::parse();
+    let color = if args.no_color {
+        commands::color::ColorChoice::Never
+    } else {
+        parse_color(&args.color)
+    };
+    commands::color::init(color, args.command.machine_readable());
+    commands::progress::init(!args.quiet && !args.command.machine_readable());
+    if args.quiet {
+        commands::exit::silence_stdout();
+    }
+    if args.profile {
+        commands::profile::enable();
+    }
+    if let Some(root) = &args.repo_root {
+        let require_codeowners = !matches!(args.command, Commands::Init { .. });
+        if let Err(e) = commands::repo_root::enter(root, require_codeowners) {
+            eprintln!("Error: {}", e);
+            return commands::exit::Exit::Error.into();
+        }
+    }
+    let start = Instant::now();
+    let code = run(args.command).await;
+    commands::profile::print_report(start.elapsed());
+    code
+}
 
-    match args.command {
+async fn run(command: Commands) -> ExitCode {
+    // One client per process, so commands share its cache of GitHub lookups
+    let github = Arc::new(github::GitHubClient::new());
+    match command {
         Commands::Lint {
             path,
+            codeowners,
             json,
             fix,
             strict,
             github_actions,
-        } => commands::lint(path, json, fix, strict, github_actions).await,
+            require_teams,
+            semantics,
+        } => {
+            commands::lint(
+                commands::LintOptions {
+                    path: codeowners.or(path),
+                    json,
+                    fix,
+                    strict,
+                    github_actions,
+                    require_teams,
+                    semantics: parse_semantics(&semantics),
+                },
+                github,
+            )
+            .await
+        }
         Commands::Fmt { path, write } => commands::fmt(path, write),
+        Commands::MigrateTeams {
+            mapping,
+            path,
+            write,
+        } => commands::migrate_teams(commands::MigrateTeamsOptions {
+            mapping,
+            path,
+            write,
+        }),
         Commands::Check {
             paths,
             files,
             json,
+            format,
             files_from,
             stdin,
-        } => commands::check(paths, files, json, files_from, stdin),
+            null,
+            owners,
+            json_lines,
+            owners_only,
+            explain,
+            semantics,
+            stream,
+            codeowners,
+            merge_nested,
+            path_style,
+        } => {
+            let owners = match resolve_me(&github, owners).await {
+                Ok(owners) => owners,
+                Err(code) => return code,
+            };
+            commands::check(commands::CheckOptions {
+                paths,
+                files,
+                files_from,
+                stdin,
+                null,
+                format: match (json, format.as_str()) {
+                    (true, _) | (_, "json") => commands::CheckFormat::Json,
+                    (_, "github") => commands::CheckFormat::Github,
+                    (_, "csv") => commands::CheckFormat::Csv,
+                    (_, "tsv") => commands::CheckFormat::Tsv,
+                    _ => commands::CheckFormat::Human,
+                },
+                json_lines,
+                owners_only,
+                owners,
+                explain,
+                semantics: parse_semantics(&semantics),
+                stream,
+                codeowners,
+                merge_nested,
+                path_style: parse_path_style(&path_style),
+            })
+        }
         Commands::Coverage {
             files,
             files_from,
             stdin,
+            null,
             tree,
+            by_dir,
+            depth,
             json,
-        } => commands::coverage(files, files_from, stdin, tree, json),
+            format,
+            exclude_export_ignore,
+            exclude,
+            top,
+            sort,
+            baseline,
+            update_baseline,
+            owner,
+            semantics,
+            merge_nested,
+            path_style,
+        } => {
+            let owner = match resolve_me(&github, owner.into_iter().collect()).await {
+                Ok(mut owners) => owners.pop(),
+                Err(code) => return code,
+            };
+            commands::coverage(commands::CoverageOptions {
+                files,
+                files_from,
+                stdin,
+                null,
+                tree,
+                by_dir: by_dir.then_some(depth as usize),
+                format: match (json, format.as_str()) {
+                    (true, _) | (_, "json") => commands::CoverageFormat::Json,
+                    (_, "github") => commands::CoverageFormat::Github,
+                    (_, "tsv") => commands::CoverageFormat::Tsv,
+                    _ => commands::CoverageFormat::Human,
+                },
+                exclude_export_ignore,
+                exclude,
+                top,
+                sort: parse_sort(&sort),
+                baseline,
+                update_baseline,
+                owner,
+                semantics: parse_semantics(&semantics),
+                merge_nested,
+                path_style: parse_path_style(&path_style),
+            })
+        }
         Commands::Completions { shell } => {
-            generate(
-                shell,
-                &mut Cli::command(),
-                "codeowners-cli",
-                &mut io::stdout(),
-            );
+            write_completions(shell, &mut io::stdout());
             ExitCode::SUCCESS
         }
         Commands::ValidateOwners {
@@ -234,16 +704,92 @@ async fn main() -> ExitCode {
             files,
             files_from,
             stdin,
+            null,
             json,
-        } => commands::validate_owners(&token, files, files_from, stdin, json).await,
+            concurrency,
+            fail_on_unknown,
+            skip_owners,
+        } => {
+            commands::validate_owners(commands::ValidateOwnersOptions {
+                token,
+                files,
+                files_from,
+                stdin,
+                null,
+                json,
+                concurrency,
+                fail_on_unknown,
+                skip_owners,
+                github,
+            })
+            .await
+        }
+        Commands::PruneCache { dry_run } => {
+            commands::prune_cache(commands::PruneCacheOptions { dry_run })
+        }
+        Commands::Stats {
+            json,
+            bus_factor,
+            depth,
+            flag_teams,
+            idle_owners,
+            members,
+            last_review,
+            prs,
+            repo,
+            token,
+            top,
+            sort,
+            semantics,
+        } => {
+            commands::stats(commands::StatsOptions {
+                json,
+                bus_factor,
+                depth: depth as usize,
+                flag_teams,
+                idle_owners,
+                members,
+                last_review,
+                prs: prs as usize,
+                repo,
+                token,
+                top,
+                sort: parse_sort(&sort),
+                semantics: parse_semantics(&semantics),
+                github,
+            })
+            .await
+        }
+        Commands::Overlaps { json } => commands::overlaps(commands::OverlapsOptions { json }),
         Commands::Tree => commands::tree(),
+        Commands::Export {
+            format,
+            collapse,
+            max_depth,
+        } => commands::export(commands::ExportOptions {
+            format: match format.as_str() {
+                "csv" => commands::ExportFormat::Csv,
+                "owners" => commands::ExportFormat::Owners,
+                _ => commands::ExportFormat::Json,
+            },
+            collapse,
+            max_depth,
+        }),
         Commands::Config => commands::config(),
+        Commands::Schema { command } => commands::schema(&command),
         Commands::Suggest {
             min_confidence,
             format,
             limit,
             write,
+            dry_run,
+            patch,
             anchored,
+            stale_after_months,
+            depth,
+            exclude,
+            concurrency,
+            path_style,
         } => {
             let format = match format.to_lowercase().as_str() {
                 "json" => commands::SuggestFormat::Json,
@@ -256,9 +802,33 @@ async fn main() -> ExitCode {
                 limit,
                 include_owned: false,
                 write,
+                dry_run,
+                patch,
                 anchored,
+                stale_after_months,
+                depth: depth.map(|d| d as usize),
+                exclude,
+                concurrency: concurrency as usize,
+                weights: None,
+                path_style: parse_path_style(&path_style),
             })
         }
+        Commands::Init {
+            min_confidence,
+            depth,
+            output,
+            force,
+            stale_after_months,
+            exclude,
+        } => commands::init(commands::InitOptions {
+            min_confidence,
+            depth: depth.map(|d| d as usize),
+            output,
+            force,
+            stale_after_months,
+            exclude,
+            weights: None,
+        }),
         Commands::Optimize {
             write,
             json,
@@ -276,6 +846,7 @@ async fn main() -> ExitCode {
             token,
             changed_files_from,
             changed_files_stdin,
+            null,
             no_coverage_changed,
             no_coverage_all,
             no_owners_changed,
@@ -287,15 +858,18 @@ async fn main() -> ExitCode {
             no_outputs,
         } => {
             // Read changed files
-            let changed_files =
-                match commands::files::collect_files(None, changed_files_from, changed_files_stdin)
-                {
-                    Ok(f) => f.map(|s| s.into_iter().collect()),
-                    Err(e) => {
-                        eprintln!("::error::{}", e);
-                        return ExitCode::from(1);
-                    }
-                };
+            let changed_files = match commands::files::collect_files(
+                None,
+                changed_files_from,
+                changed_files_stdin,
+                null,
+            ) {
+                Ok(f) => f.map(|s| s.into_iter().collect()),
+                Err(e) => {
+                    eprintln!("::error::{}", e);
+                    return commands::exit::Exit::Error.into();
+                }
+            };
 
             commands::gha(commands::GhaOptions {
                 token,
@@ -309,8 +883,108 @@ async fn main() -> ExitCode {
                 output_annotations: !no_annotations,
                 output_summary: !no_summary,
                 output_vars: !no_outputs,
+                github,
             })
             .await
         }
     }
 }
+
+/// Write the completion script for `shell`, introspecting the clap command
+fn write_completions(shell: Shell, out: &mut dyn io::Write) {
+    generate(shell, &mut Cli::command(), "codeowners-cli", out);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_completions_for_each_shell() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell] {
+            let mut out = Vec::new();
+            write_completions(shell, &mut out);
+            let script = String::from_utf8(out).unwrap();
+            assert!(
+                script.contains("codeowners-cli"),
+                "{shell} completions are missing the binary name"
+            );
+            assert!(
+                script.contains("validate-owners"),
+                "{shell} completions are missing subcommands"
+            );
+        }
+    }
+
+    fn parse(args: &[&str]) -> Cli {
+        Cli::try_parse_from(std::iter::once("codeowners-cli").chain(args.iter().copied())).unwrap()
+    }
+
+    #[test]
+    fn test_color_flags() {
+        assert_eq!(parse(&["check", "x"]).color, "auto");
+        assert_eq!(parse(&["check", "--color", "always", "x"]).color, "always");
+        assert!(parse(&["check", "--no-color", "x"]).no_color);
+        assert!(Cli::try_parse_from(["codeowners-cli", "--color", "sometimes", "tree"]).is_err());
+        assert_eq!(parse_color("never"), commands::color::ColorChoice::Never);
+    }
+
+    #[test]
+    fn test_repo_root_is_global() {
+        assert_eq!(parse(&["tree"]).repo_root, None);
+        for args in [
+            &["--repo-root", "/repo", "tree"][..],
+            &["coverage", "--repo-root", "/repo"],
+        ] {
+            assert_eq!(parse(args).repo_root, Some(PathBuf::from("/repo")));
+        }
+    }
+
+    #[test]
+    fn test_stats_depth_and_prs_must_be_positive() {
+        let rejects = |args: &[&str]| {
+            Cli::try_parse_from(std::iter::once("codeowners-cli").chain(args.iter().copied()))
+                .is_err()
+        };
+        assert!(rejects(&["stats", "--bus-factor", "--depth", "0"]));
+        assert!(rejects(&["stats", "--last-review", "--prs", "0"]));
+        assert!(!rejects(&[
+            "stats",
+            "--last-review",
+            "--depth",
+            "2",
+            "--prs",
+            "1"
+        ]));
+    }
+
+    #[test]
+    fn test_machine_readable_output() {
+        for args in [
+            &["check", "--json", "x"][..],
+            &["check", "--format", "csv", "x"],
+            &["check", "--owners-only", "x"],
+            &["coverage", "--format", "github"],
+            &["lint", "--json"],
+            &["export"],
+            &["suggest", "--format", "codeowners"],
+            &["completions", "zsh"],
+        ] {
+            assert!(parse(args).command.machine_readable(), "{:?}", args);
+        }
+        for args in [
+            &["check", "x"][..],
+            &["coverage"],
+            &["lint"],
+            &["export", "--format", "owners"],
+            &["tree"],
+        ] {
+            assert!(!parse(args).command.machine_readable(), "{:?}", args);
+        }
+    }
+}
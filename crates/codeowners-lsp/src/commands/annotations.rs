@@ -0,0 +1,123 @@
+//! GitHub Actions workflow-command annotations (`::warning file=...::message`).
+//!
+//! Values are escaped per the Actions toolkit: `%`, `\r` and `\n` in the
+//! message, plus `:` and `,` in properties, so a path or message can never
+//! terminate the command early.
+
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString};
+
+/// Escape an annotation message
+fn escape_data(s: &str) -> String {
+    s.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape an annotation property value (file, title, ...)
+fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// Format a workflow command, e.g. `::warning file=a.rs,line=3::message`
+pub fn annotation(level: &str, properties: &[(&str, String)], message: &str) -> String {
+    let properties = properties
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, escape_property(value)))
+        .collect::<Vec<_>>()
+        .join(",");
+    if properties.is_empty() {
+        format!("::{}::{}", level, escape_data(message))
+    } else {
+        format!("::{} {}::{}", level, properties, escape_data(message))
+    }
+}
+
+/// Annotation for a diagnostic in the CODEOWNERS file at `file`
+pub fn diagnostic_annotation(diagnostic: &Diagnostic, file: &str) -> String {
+    let level = match diagnostic.severity {
+        Some(DiagnosticSeverity::ERROR) => "error",
+        Some(DiagnosticSeverity::WARNING) => "warning",
+        _ => "notice", // hints and info become notices
+    };
+    let title = diagnostic
+        .code
+        .as_ref()
+        .map(|c| match c {
+            NumberOrString::String(s) => s.clone(),
+            NumberOrString::Number(n) => n.to_string(),
+        })
+        .unwrap_or_default();
+    annotation(
+        level,
+        &[
+            ("file", file.to_string()),
+            ("line", (diagnostic.range.start.line + 1).to_string()),
+            ("col", (diagnostic.range.start.character + 1).to_string()),
+            ("title", title),
+        ],
+        &diagnostic.message,
+    )
+}
+
+/// Warning annotation for a file with no CODEOWNERS owners
+pub fn unowned_file_annotation(file: &str) -> String {
+    annotation(
+        "warning",
+        &[
+            ("file", file.to_string()),
+            ("title", "unowned-file".to_string()),
+        ],
+        "File has no CODEOWNERS owners",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower_lsp::lsp_types::{Position, Range};
+
+    #[test]
+    fn test_annotation_without_properties() {
+        assert_eq!(annotation("error", &[], "boom"), "::error::boom");
+    }
+
+    #[test]
+    fn test_escape_message() {
+        assert_eq!(
+            annotation("warning", &[], "100% broken\r\nnext: line, ok"),
+            "::warning::100%25 broken%0D%0Anext: line, ok"
+        );
+    }
+
+    #[test]
+    fn test_escape_properties() {
+        assert_eq!(
+            unowned_file_annotation("dir,with:odd%chars/a\nb.rs"),
+            "::warning file=dir%2Cwith%3Aodd%25chars/a%0Ab.rs,title=unowned-file::File has no CODEOWNERS owners"
+        );
+    }
+
+    #[test]
+    fn test_diagnostic_annotation() {
+        let diagnostic = Diagnostic {
+            range: Range {
+                start: Position {
+                    line: 2,
+                    character: 5,
+                },
+                end: Position {
+                    line: 2,
+                    character: 10,
+                },
+            },
+            severity: Some(DiagnosticSeverity::ERROR),
+            code: Some(NumberOrString::String("invalid-owner".to_string())),
+            message: "Invalid owner format 'bad_owner'.\nExpected @user".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            diagnostic_annotation(&diagnostic, ".github/CODEOWNERS"),
+            "::error file=.github/CODEOWNERS,line=3,col=6,title=invalid-owner::Invalid owner format 'bad_owner'.%0AExpected @user"
+        );
+    }
+}
@@ -1,47 +1,351 @@
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 use std::{env, fs};
 
 use colored::Colorize;
+use schemars::JsonSchema;
 use serde::Serialize;
+use tower_lsp::lsp_types::NumberOrString;
 
-use super::files::collect_files;
-use crate::ownership::{check_file_ownership_parsed, find_codeowners};
-use crate::parser::parse_codeowners_file_with_positions;
+use super::annotations::{diagnostic_annotation, unowned_file_annotation};
+use super::exit::Exit;
+use super::files::{
+    collect_files_ordered, is_stdin_arg, read_codeowners_arg, read_file_list, OutputPaths,
+    PathStyle,
+};
+use super::tsv;
+use super::{profile, schema};
+use crate::diagnostics::{codes, compute_diagnostics_sync, DiagnosticConfig};
+use crate::file_cache::FileCache;
+use crate::nested::{merge_nested_codeowners, MergedCodeowners};
+use crate::ownership::{
+    check_file_ownership_sections_with_options, find_all_codeowners, get_repo_root,
+    resolve_owners_parsed_with_options, CodeownersFile, FileOwnership, OwnershipResult,
+    SectionOwnership, Semantics,
+};
+use crate::parser::{CodeownersLine, ParsedLine};
+use crate::pattern::{normalize_path, CompiledPattern, PatternOptions};
+use crate::settings::load_settings_from_path;
+use crate::validation::OwnerError;
 
-#[derive(Serialize)]
+/// Maximum number of near-miss rules shown for an unowned file
+const MAX_NEAR_MISSES: usize = 3;
+
+const CSV_HEADER: [&str; 4] = ["file", "owners", "rule", "line"];
+
+/// Ownership of one file
+#[derive(Serialize, JsonSchema)]
+#[schemars(rename = "CheckResult", deny_unknown_fields)]
 struct CheckResultJson {
     owned: bool,
+    /// Winning rule's pattern
     rule: Option<String>,
+    #[schemars(range(min = 1))]
     line: Option<u32>,
+    /// Nested CODEOWNERS the winning rule is in (--merge-nested); omitted
+    /// for the root one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
     owners: Vec<String>,
+    /// Every matching rule (--explain)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    matches: Option<Vec<RuleJson>>,
+    /// Rules that almost matched an unowned file (--explain)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    near_misses: Option<Vec<NearMissJson>>,
+    /// Winning rule per section (--semantics gitlab)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sections: Option<Vec<SectionJson>>,
+    /// Malformed owners on the winning rule
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    owner_errors: Vec<OwnerErrorJson>,
 }
 
-pub fn check(
-    paths: Vec<String>,
-    files: Option<Vec<String>>,
-    json: bool,
-    files_from: Option<PathBuf>,
-    stdin: bool,
-) -> ExitCode {
-    let cwd = env::current_dir().expect("Failed to get current directory");
+#[derive(Serialize, JsonSchema)]
+#[schemars(rename = "OwnerError", deny_unknown_fields)]
+struct OwnerErrorJson {
+    owner: String,
+    /// 1-indexed column of the owner within the rule line
+    #[schemars(range(min = 1))]
+    column: usize,
+    message: String,
+}
 
-    let codeowners_path = match find_codeowners(&cwd) {
-        Some(p) => p,
-        None => {
-            eprintln!("No CODEOWNERS file found");
-            return ExitCode::from(1);
+impl OwnerErrorJson {
+    /// `error` on the rule at `rule`, with its column in the file the rule
+    /// was written in
+    fn new(error: &OwnerError, rule: &RuleLine) -> Self {
+        OwnerErrorJson {
+            owner: error.owner.clone(),
+            column: error.offset - rule.shift + 1,
+            message: error.message(),
         }
+    }
+}
+
+#[derive(Serialize, JsonSchema)]
+#[schemars(rename = "Section", deny_unknown_fields)]
+struct SectionJson {
+    section: Option<String>,
+    rule: String,
+    #[schemars(range(min = 1))]
+    line: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+    owners: Vec<String>,
+}
+
+/// One NDJSON record in streaming mode
+#[derive(Serialize, JsonSchema)]
+struct CheckLineJson<'a> {
+    file: &'a str,
+    #[serde(flatten)]
+    result: CheckResultJson,
+}
+
+#[derive(Serialize, JsonSchema)]
+#[schemars(rename = "Rule", deny_unknown_fields)]
+struct RuleJson {
+    rule: String,
+    #[schemars(range(min = 1))]
+    line: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+    owners: Vec<String>,
+}
+
+#[derive(Serialize, JsonSchema)]
+#[schemars(rename = "NearMiss", deny_unknown_fields)]
+struct NearMissJson {
+    rule: String,
+    #[schemars(range(min = 1))]
+    line: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+    owners: Vec<String>,
+    reason: &'static str,
+}
+
+/// JSON Schema for `--json` output, derived from [`CheckResultJson`]
+pub(super) fn json_schema() -> serde_json::Value {
+    schema::document::<BTreeMap<String, CheckResultJson>>(
+        "codeowners-cli check --json",
+        "Ownership of each checked file, keyed by path. --json-lines and --stream --json write one CheckResult per line with an added \"file\".",
+    )
+}
+
+/// A rule that didn't match a file but came close
+struct NearMiss<'a> {
+    line_number: u32,
+    pattern: &'a str,
+    owners: &'a [String],
+    reason: &'static str,
+}
+
+/// Where reported rule lines point: the CODEOWNERS itself, or with
+/// `--merge-nested`, the file each merged line came from
+#[derive(Debug, Clone, Copy, Default)]
+struct LineMap<'a>(Option<&'a MergedCodeowners>);
+
+/// A rule's 1-indexed line in the file it was written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct RuleLine<'a> {
+    /// Nested CODEOWNERS the rule is in, `None` for the root one
+    source: Option<&'a str>,
+    line: u32,
+    /// Bytes the merge added before the rule's owners
+    shift: usize,
+}
+
+impl<'a> LineMap<'a> {
+    /// Where the rule on the 0-indexed `line_number` was written
+    fn locate(self, line_number: u32) -> RuleLine<'a> {
+        match self
+            .0
+            .and_then(|merged| Some((merged, merged.origin(line_number)?)))
+        {
+            Some((merged, origin)) => RuleLine {
+                source: merged.source(origin),
+                line: origin.line + 1,
+                shift: origin.shift,
+            },
+            None => RuleLine {
+                source: None,
+                line: line_number + 1,
+                shift: 0,
+            },
+        }
+    }
+}
+
+impl RuleLine<'_> {
+    /// The line for a CSV/TSV column: `3`, or `pkg/CODEOWNERS:3` in a
+    /// nested file
+    fn column(&self) -> String {
+        match self.source {
+            Some(source) => format!("{}:{}", source, self.line),
+            None => self.line.to_string(),
+        }
+    }
+}
+
+/// `line 3`, or `line 3 of pkg/CODEOWNERS` in a nested file
+impl fmt::Display for RuleLine<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}", self.line)?;
+        if let Some(source) = self.source {
+            write!(f, " of {}", source)?;
+        }
+        Ok(())
+    }
+}
+
+/// Output format for the check command
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Human,
+    Json,
+    /// GitHub Actions workflow-command annotations
+    Github,
+    /// `file,owners,rule,line` rows for spreadsheets
+    Csv,
+    /// Headerless `path<TAB>rule<TAB>line<TAB>owners` rows for `cut`/`awk`
+    Tsv,
+}
+
+/// Options for the check command
+#[derive(Debug, Clone, Default)]
+pub struct CheckOptions {
+    /// File paths given positionally
+    pub paths: Vec<String>,
+    /// File paths given via --files
+    pub files: Option<Vec<String>>,
+    /// Read files to check from a file (one per line)
+    pub files_from: Option<PathBuf>,
+    /// Read files to check from stdin (one per line)
+    pub stdin: bool,
+    /// File lists (and `--stream` input) are NUL-separated, not one per line
+    pub null: bool,
+    /// Output format
+    pub format: OutputFormat,
+    /// Output one JSON object per file (NDJSON), in input order
+    pub json_lines: bool,
+    /// Print only owners, one per line (prefixed with the path for multiple files)
+    pub owners_only: bool,
+    /// Only report files whose winning rule includes one of these owners
+    pub owners: Vec<String>,
+    /// Show every matching rule (and near misses for unowned files)
+    pub explain: bool,
+    /// How owners are resolved when several rules match
+    pub semantics: Semantics,
+    /// Check paths from stdin as they arrive, printing one result per line
+    pub stream: bool,
+    /// CODEOWNERS to use instead of auto-detecting; `-` reads it from stdin
+    pub codeowners: Option<PathBuf>,
+    /// Append the repository's per-package CODEOWNERS files, scoped to their directories
+    pub merge_nested: bool,
+    /// Print input paths as given or relative to the repository root
+    pub path_style: PathStyle,
+}
+
+pub fn check(options: CheckOptions) -> ExitCode {
+    let CheckOptions {
+        paths,
+        files,
+        files_from,
+        stdin,
+        null,
+        format,
+        json_lines,
+        owners_only,
+        owners,
+        explain,
+        semantics,
+        stream,
+        codeowners,
+        merge_nested,
+        path_style,
+    } = options;
+
+    let cwd = env::current_dir().expect("Failed to get current directory");
+
+    let codeowners_from_stdin = codeowners.as_deref().is_some_and(is_stdin_arg);
+    if codeowners_from_stdin && (stdin || stream) {
+        eprintln!(
+            "--codeowners - reads CODEOWNERS from stdin, so it can't also provide the file list; use --files or --files-from"
+        );
+        return Exit::Error.into();
+    }
+
+    let (codeowners_path, content) = match codeowners {
+        Some(path) => match read_codeowners_arg(&path, io::stdin().lock()) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                eprintln!("{}", e);
+                return Exit::Error.into();
+            }
+        },
+        None => match find_codeowners_with_warnings(&cwd) {
+            Some(found) => found,
+            None => return Exit::Error.into(),
+        },
     };
 
-    let content = match fs::read_to_string(&codeowners_path) {
-        Ok(c) => c,
-        Err(e) => {
-            eprintln!("Failed to read {}: {}", codeowners_path.display(), e);
-            return ExitCode::from(1);
+    let repo_root = if codeowners_from_stdin {
+        cwd.clone()
+    } else {
+        get_repo_root(&codeowners_path, &cwd)
+    };
+    let pattern_options = load_settings_from_path(&repo_root).pattern_options();
+
+    let output_paths = OutputPaths::new(path_style, &cwd, &repo_root);
+
+    // Rules come from the merged content, but lines are reported (and
+    // annotated) in the files they were written in
+    let merged = if merge_nested {
+        let file_cache = profile::time(profile::WALK, || FileCache::new(&repo_root));
+        match merge_nested_codeowners(&repo_root, &content, file_cache.all_files(), semantics) {
+            Ok(merged) => Some(merged),
+            Err(e) => {
+                eprintln!("{}", e);
+                return Exit::Error.into();
+            }
         }
+    } else {
+        None
     };
+    let rules_content = merged
+        .as_ref()
+        .map_or(content.as_str(), |m| m.content.as_str());
+    let lines = LineMap(merged.as_ref());
+
+    if stream {
+        let codeowners = CodeownersFile::parse_with_options(rules_content, pattern_options);
+        let stdin = io::stdin();
+        let mut stdout = io::stdout();
+        return finish_streaming(stream_check(
+            &codeowners,
+            stdin.lock(),
+            &mut stdout,
+            &StreamOptions {
+                format: if json_lines {
+                    OutputFormat::Json
+                } else {
+                    format
+                },
+                owners: &owners,
+                explain,
+                semantics,
+                null,
+                paths: &output_paths,
+                lines,
+            },
+        ));
+    }
 
     // Merge positional paths with --files for consistent interface
     let files_arg = if paths.is_empty() {
@@ -54,76 +358,729 @@ pub fn check(
         Some(merged)
     };
 
-    let all_files: Vec<String> = match collect_files(files_arg, files_from, stdin) {
-        Ok(Some(set)) => set.into_iter().collect(),
+    let all_files: Vec<String> = match collect_files_ordered(files_arg, files_from, stdin, null) {
+        Ok(Some(files)) => files.into_iter().map(|f| output_paths.input(f)).collect(),
         Ok(None) => {
             eprintln!("No files specified");
-            return ExitCode::from(1);
+            return Exit::Error.into();
         }
         Err(e) => {
             eprintln!("{}", e);
-            return ExitCode::from(1);
+            return Exit::Error.into();
         }
     };
 
-    if json {
-        output_json(&content, &all_files)
+    // A directory stands for every file under it
+    let all_files = if all_files.iter().any(|f| is_directory_input(f, &repo_root)) {
+        let file_cache = profile::time(profile::WALK, || FileCache::new(&repo_root));
+        expand_directories(all_files, &repo_root, file_cache.all_files())
     } else {
-        output_human(&content, &all_files)
+        all_files
+    };
+
+    let codeowners = profile::time(profile::PARSE, || {
+        CodeownersFile::parse_with_options(rules_content, pattern_options)
+    });
+
+    // Results are written as files are matched, so this times both
+    profile::time(profile::MATCH, || {
+        let all_files = if owners.is_empty() {
+            all_files
+        } else {
+            all_files
+                .into_iter()
+                .filter(|f| {
+                    owner_filter_matches(&owners, &resolve_owners(&codeowners, f, semantics))
+                })
+                .collect()
+        };
+
+        if owners_only {
+            let mut stdout = io::stdout().lock();
+            return match write_owners_only(&mut stdout, &codeowners, &all_files, semantics) {
+                Ok(true) => ExitCode::SUCCESS,
+                Ok(false) => Exit::Findings.into(),
+                Err(e) => finish_streaming(Err(e)),
+            };
+        }
+
+        if json_lines {
+            let mut stdout = io::stdout().lock();
+            finish_streaming(all_files.iter().try_for_each(|file_path| {
+                write_json_line(
+                    &mut stdout,
+                    &codeowners,
+                    lines,
+                    file_path,
+                    explain,
+                    semantics,
+                )
+            }))
+        } else if format == OutputFormat::Json {
+            output_json(&codeowners, lines, &all_files, explain, semantics)
+        } else if format == OutputFormat::Csv {
+            let mut stdout = io::stdout().lock();
+            finish_streaming(write_csv(
+                &mut stdout,
+                &codeowners,
+                lines,
+                &all_files,
+                semantics,
+            ))
+        } else if format == OutputFormat::Tsv {
+            let mut stdout = io::stdout().lock();
+            finish_streaming(all_files.iter().try_for_each(|file_path| {
+                let owners = resolve_owners(&codeowners, file_path, semantics);
+                write_tsv_row(
+                    &mut stdout,
+                    &codeowners,
+                    lines,
+                    file_path,
+                    &owners,
+                    semantics,
+                )
+            }))
+        } else if format == OutputFormat::Github {
+            let codeowners_file = codeowners_path
+                .strip_prefix(&repo_root)
+                .unwrap_or(&codeowners_path)
+                .display()
+                .to_string();
+            let annotations = github_annotations(
+                &codeowners_file,
+                &content,
+                &codeowners,
+                lines,
+                &all_files,
+                semantics,
+            );
+            for annotation in &annotations.lines {
+                println!("{}", annotation);
+            }
+            if annotations.any_unowned {
+                Exit::Findings.into()
+            } else {
+                ExitCode::SUCCESS
+            }
+        } else if all_files.is_empty() && !owners.is_empty() {
+            println!("No matching files owned by {}", owners.join(", "));
+            ExitCode::SUCCESS
+        } else if semantics == Semantics::GitLab {
+            let shown_path = PathBuf::from(output_paths.display(&codeowners_path));
+            output_human_sections(&shown_path, &codeowners, lines, &all_files, explain)
+        } else {
+            let shown_path = PathBuf::from(output_paths.display(&codeowners_path));
+            output_human(&shown_path, &codeowners, lines, &all_files, explain)
+        }
+    })
+}
+
+/// Whether a check input names a directory: it ends in `/` or is a
+/// directory on disk under `root`
+fn is_directory_input(path: &str, root: &Path) -> bool {
+    path.ends_with('/') || root.join(normalize_path(path).as_ref()).is_dir()
+}
+
+/// Replace directory inputs with the repo `files` under them, dropping
+/// repeats. A directory with no files is kept, so it's still reported.
+fn expand_directories(inputs: Vec<String>, root: &Path, files: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut expanded = Vec::new();
+    for input in inputs {
+        if !is_directory_input(&input, root) {
+            if seen.insert(input.clone()) {
+                expanded.push(input);
+            }
+            continue;
+        }
+
+        let dir = normalize_path(&input).trim_end_matches('/').to_string();
+        let mut under = files
+            .iter()
+            .filter(|f| {
+                dir.is_empty()
+                    || dir == "."
+                    || f.strip_prefix(&dir)
+                        .is_some_and(|rest| rest.starts_with('/'))
+            })
+            .peekable();
+        if under.peek().is_none() {
+            if seen.insert(input.clone()) {
+                expanded.push(input);
+            }
+            continue;
+        }
+        for file in under {
+            if seen.insert(file.clone()) {
+                expanded.push(file.clone());
+            }
+        }
     }
+    expanded
 }
 
-fn output_json(content: &str, files: &[String]) -> ExitCode {
-    let parsed = parse_codeowners_file_with_positions(content);
-    let mut results: HashMap<&str, CheckResultJson> = HashMap::new();
+/// Auto-detect CODEOWNERS from `cwd` and read it, warning about copies that
+/// GitHub ignores. Errors are reported here; `None` means give up.
+fn find_codeowners_with_warnings(cwd: &Path) -> Option<(PathBuf, String)> {
+    let all_codeowners = find_all_codeowners(cwd);
+    let codeowners_path = match all_codeowners.first() {
+        Some(p) => p.clone(),
+        None => {
+            eprintln!("No CODEOWNERS file found");
+            return None;
+        }
+    };
+    for ignored in &all_codeowners[1..] {
+        eprintln!(
+            "{} ignoring {} ({} takes precedence)",
+            "warning:".yellow().bold(),
+            ignored.display(),
+            codeowners_path.display()
+        );
+    }
 
-    for file_path in files {
-        let result = check_file_ownership_parsed(&parsed, file_path);
-        results.insert(
+    match fs::read_to_string(&codeowners_path) {
+        Ok(content) => Some((codeowners_path, content)),
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", codeowners_path.display(), e);
+            None
+        }
+    }
+}
+
+/// Owners of `file_path` under the requested semantics
+fn resolve_owners(
+    codeowners: &CodeownersFile,
+    file_path: &str,
+    semantics: Semantics,
+) -> Vec<String> {
+    match semantics {
+        Semantics::GitHub => codeowners
+            .owners_for(file_path)
+            .map(|r| r.owners)
+            .unwrap_or_default(),
+        Semantics::GitLab => resolve_owners_parsed_with_options(
+            codeowners.lines(),
             file_path,
-            match result {
-                Some(r) => CheckResultJson {
-                    owned: true,
-                    rule: Some(r.pattern),
-                    line: Some(r.line_number + 1),
-                    owners: r.owners,
-                },
-                None => CheckResultJson {
-                    owned: false,
-                    rule: None,
-                    line: None,
-                    owners: vec![],
-                },
-            },
-        );
+            semantics,
+            codeowners.pattern_options(),
+        ),
     }
+}
+
+/// Whether any of `rule_owners` matches one of the `--owner` filters.
+/// Matching is exact on the owner token; the leading `@` is optional.
+fn owner_filter_matches(filters: &[String], rule_owners: &[String]) -> bool {
+    let normalize = |o: &str| o.trim_start_matches('@').to_string();
+    rule_owners.iter().any(|owner| {
+        let owner = normalize(owner);
+        filters.iter().any(|f| normalize(f) == owner)
+    })
+}
 
+fn output_json(
+    codeowners: &CodeownersFile,
+    lines: LineMap,
+    files: &[String],
+    explain: bool,
+    semantics: Semantics,
+) -> ExitCode {
     println!(
         "{}",
-        serde_json::to_string(&results).expect("Failed to serialize JSON")
+        serde_json::to_string(&results_json(codeowners, lines, files, explain, semantics))
+            .expect("Failed to serialize JSON")
     );
     ExitCode::SUCCESS
 }
 
-fn output_human(content: &str, files: &[String]) -> ExitCode {
-    let parsed = parse_codeowners_file_with_positions(content);
+/// JSON results keyed by file, sorted so output is byte-stable across runs
+fn results_json<'a>(
+    codeowners: &CodeownersFile,
+    lines: LineMap,
+    files: &'a [String],
+    explain: bool,
+    semantics: Semantics,
+) -> BTreeMap<&'a str, CheckResultJson> {
+    files
+        .iter()
+        .map(|file_path| {
+            (
+                file_path.as_str(),
+                check_result_json(codeowners, lines, file_path, explain, semantics),
+            )
+        })
+        .collect()
+}
+
+/// Build the JSON result for one file, with explain details if requested
+fn check_result_json(
+    codeowners: &CodeownersFile,
+    lines: LineMap,
+    file_path: &str,
+    explain: bool,
+    semantics: Semantics,
+) -> CheckResultJson {
+    let parsed = codeowners.lines();
+    let mut entry = match semantics {
+        Semantics::GitHub => github_result_json(codeowners, lines, file_path),
+        Semantics::GitLab => gitlab_result_json(codeowners, lines, file_path),
+    };
+
+    if explain {
+        entry.matches = Some(
+            explain_ownership(codeowners, file_path)
+                .all_matches
+                .into_iter()
+                .map(|m| {
+                    let at = lines.locate(m.line_number);
+                    RuleJson {
+                        rule: m.pattern,
+                        line: at.line,
+                        source: at.source.map(str::to_string),
+                        owners: m.owners,
+                    }
+                })
+                .collect(),
+        );
+        if !entry.owned {
+            entry.near_misses = Some(
+                find_near_misses(parsed, file_path, codeowners.pattern_options())
+                    .into_iter()
+                    .map(|m| {
+                        let at = lines.locate(m.line_number);
+                        NearMissJson {
+                            rule: m.pattern.to_string(),
+                            line: at.line,
+                            source: at.source.map(str::to_string),
+                            owners: m.owners.to_vec(),
+                            reason: m.reason,
+                        }
+                    })
+                    .collect(),
+            );
+        }
+    }
+
+    entry
+}
+
+/// Annotations produced for `--format github`
+struct GithubAnnotations {
+    lines: Vec<String>,
+    any_unowned: bool,
+}
+
+/// Warn on each unowned file, and flag invalid owners on the CODEOWNERS
+/// rules that decided ownership of the checked files. `content` is the
+/// CODEOWNERS at `codeowners_file`; rules merged in from nested files are
+/// annotated in those files.
+fn github_annotations(
+    codeowners_file: &str,
+    content: &str,
+    codeowners: &CodeownersFile,
+    lines: LineMap,
+    files: &[String],
+    semantics: Semantics,
+) -> GithubAnnotations {
+    let mut annotations = Vec::new();
+    let mut any_unowned = false;
+    let mut rule_lines: HashSet<(Option<&str>, u32)> = HashSet::new();
+    let mut use_rule = |line_number: u32| {
+        let at = lines.locate(line_number);
+        rule_lines.insert((at.source, at.line - 1));
+    };
+
+    for file_path in files {
+        if resolve_owners(codeowners, file_path, semantics).is_empty() {
+            any_unowned = true;
+            annotations.push(unowned_file_annotation(file_path));
+        }
+        match semantics {
+            Semantics::GitHub => codeowners
+                .rule_for(file_path)
+                .into_iter()
+                .for_each(|l| use_rule(l.line_number)),
+            Semantics::GitLab => gitlab_sections(codeowners, file_path)
+                .iter()
+                .for_each(|s| use_rule(s.result.line_number)),
+        }
+    }
+
+    let nested = lines.0.map_or(&[][..], |merged| merged.nested.as_slice());
+    let sources = std::iter::once((None, codeowners_file, content)).chain(nested.iter().map(
+        |(file, content)| {
+            (
+                Some(file.path.as_str()),
+                file.path.as_str(),
+                content.as_str(),
+            )
+        },
+    ));
+    for (source, shown, content) in sources {
+        let (diagnostics, _) =
+            compute_diagnostics_sync(content, None, &DiagnosticConfig::default());
+        annotations.extend(
+            diagnostics
+                .iter()
+                .filter(|d| {
+                    d.code == Some(NumberOrString::String(codes::INVALID_OWNER.to_string()))
+                        && rule_lines.contains(&(source, d.range.start.line))
+                })
+                .map(|d| diagnostic_annotation(d, shown)),
+        );
+    }
+
+    GithubAnnotations {
+        lines: annotations,
+        any_unowned,
+    }
+}
+
+/// Output settings for [`stream_check`]
+struct StreamOptions<'a> {
+    /// `Json` writes NDJSON; `Human` writes `path owners` lines
+    format: OutputFormat,
+    owners: &'a [String],
+    explain: bool,
+    semantics: Semantics,
+    /// Paths are NUL-separated instead of one per line
+    null: bool,
+    /// How input paths are rewritten before matching and printing
+    paths: &'a OutputPaths,
+    /// Where rule lines are reported
+    lines: LineMap<'a>,
+}
+
+/// Check each path read from `reader` as soon as its line arrives and write
+/// one result line per path (NDJSON for `Json`), flushing after each so
+/// downstream consumers see results immediately. Blank lines are skipped
+/// and a final line without a trailing newline is still checked.
+fn stream_check<R: BufRead, W: Write>(
+    codeowners: &CodeownersFile,
+    reader: R,
+    out: &mut W,
+    options: &StreamOptions,
+) -> io::Result<()> {
+    let mut wrote_header = false;
+    for file_path in read_file_list(reader, options.null) {
+        let file_path = options.paths.input(file_path?);
+        let file_path = file_path.as_str();
+
+        let owners = resolve_owners(codeowners, file_path, options.semantics);
+        if !options.owners.is_empty() && !owner_filter_matches(options.owners, &owners) {
+            continue;
+        }
+
+        match options.format {
+            OutputFormat::Json => write_json_line(
+                out,
+                codeowners,
+                options.lines,
+                file_path,
+                options.explain,
+                options.semantics,
+            )?,
+            OutputFormat::Github => {
+                if owners.is_empty() {
+                    writeln!(out, "{}", unowned_file_annotation(file_path))?;
+                }
+            }
+            OutputFormat::Csv => {
+                let mut csv = csv_writer(&mut *out);
+                if !wrote_header {
+                    csv.write_record(CSV_HEADER)?;
+                    wrote_header = true;
+                }
+                write_csv_row(
+                    &mut csv,
+                    codeowners,
+                    options.lines,
+                    file_path,
+                    &owners,
+                    options.semantics,
+                )?;
+                csv.flush()?;
+            }
+            OutputFormat::Tsv => write_tsv_row(
+                out,
+                codeowners,
+                options.lines,
+                file_path,
+                &owners,
+                options.semantics,
+            )?,
+            OutputFormat::Human => {
+                if owners.is_empty() {
+                    writeln!(out, "{} {}", file_path, "(no owners)".yellow())?;
+                } else {
+                    writeln!(out, "{} {}", file_path, owners.join(" ").green())?;
+                }
+            }
+        }
+        out.flush()?;
+    }
+    Ok(())
+}
+
+/// The winning rule's pattern and 1-based line for `file_path`, both empty
+/// if unowned. Under GitLab semantics they list every section's winning
+/// rule, space-separated.
+fn rule_and_line(
+    codeowners: &CodeownersFile,
+    lines: LineMap,
+    file_path: &str,
+    semantics: Semantics,
+) -> (String, String) {
+    match semantics {
+        Semantics::GitHub => match codeowners.rule_for(file_path) {
+            Some(ParsedLine {
+                content: CodeownersLine::Rule { pattern, .. },
+                line_number,
+                ..
+            }) => (pattern.clone(), lines.locate(*line_number).column()),
+            _ => (String::new(), String::new()),
+        },
+        Semantics::GitLab => {
+            let sections = gitlab_sections(codeowners, file_path);
+            (
+                sections
+                    .iter()
+                    .map(|s| s.result.pattern.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                sections
+                    .iter()
+                    .map(|s| lines.locate(s.result.line_number).column())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            )
+        }
+    }
+}
+
+/// CSV writer ending records with `\n` rather than the RFC's `\r\n`
+fn csv_writer<W: Write>(out: W) -> csv::Writer<W> {
+    csv::WriterBuilder::new()
+        .terminator(csv::Terminator::Any(b'\n'))
+        .from_writer(out)
+}
+
+/// Write one CSV row for `file_path`, with owners space-separated
+fn write_csv_row<W: Write>(
+    csv: &mut csv::Writer<W>,
+    codeowners: &CodeownersFile,
+    lines: LineMap,
+    file_path: &str,
+    owners: &[String],
+    semantics: Semantics,
+) -> io::Result<()> {
+    let (rule, line) = rule_and_line(codeowners, lines, file_path, semantics);
+    csv.write_record([file_path, &owners.join(" "), &rule, &line])?;
+    Ok(())
+}
+
+/// Write one TSV row for `file_path`: path, rule, line, owners. The column
+/// order is fixed for scripts, unlike CSV's spreadsheet-friendly one.
+fn write_tsv_row<W: Write>(
+    out: &mut W,
+    codeowners: &CodeownersFile,
+    lines: LineMap,
+    file_path: &str,
+    owners: &[String],
+    semantics: Semantics,
+) -> io::Result<()> {
+    let (rule, line) = rule_and_line(codeowners, lines, file_path, semantics);
+    tsv::write_record(out, [file_path, &rule, &line, &owners.join(" ")])
+}
+
+/// Write a CSV header and one row per file
+fn write_csv<W: Write>(
+    out: &mut W,
+    codeowners: &CodeownersFile,
+    lines: LineMap,
+    files: &[String],
+    semantics: Semantics,
+) -> io::Result<()> {
+    let mut csv = csv_writer(out);
+    csv.write_record(CSV_HEADER)?;
+    for file_path in files {
+        let owners = resolve_owners(codeowners, file_path, semantics);
+        write_csv_row(&mut csv, codeowners, lines, file_path, &owners, semantics)?;
+    }
+    csv.flush()
+}
+
+/// Write a single NDJSON record for `file_path` and flush it
+fn write_json_line<W: Write>(
+    out: &mut W,
+    codeowners: &CodeownersFile,
+    lines: LineMap,
+    file_path: &str,
+    explain: bool,
+    semantics: Semantics,
+) -> io::Result<()> {
+    let record = CheckLineJson {
+        file: file_path,
+        result: check_result_json(codeowners, lines, file_path, explain, semantics),
+    };
+    let json = serde_json::to_string(&record).expect("Failed to serialize JSON");
+    writeln!(out, "{}", json)?;
+    out.flush()
+}
+
+/// Print bare owners for scripting: one per line, prefixed with the path
+/// when more than one file was given. Unowned files print nothing.
+/// Returns whether every file has owners.
+fn write_owners_only<W: Write>(
+    out: &mut W,
+    codeowners: &CodeownersFile,
+    files: &[String],
+    semantics: Semantics,
+) -> io::Result<bool> {
+    let prefix_path = files.len() > 1;
+    let mut all_owned = true;
+
+    for file_path in files {
+        let owners = resolve_owners(codeowners, file_path, semantics);
+        if owners.is_empty() {
+            all_owned = false;
+        }
+        for owner in &owners {
+            if prefix_path {
+                writeln!(out, "{} {}", file_path, owner)?;
+            } else {
+                writeln!(out, "{}", owner)?;
+            }
+        }
+    }
+
+    out.flush()?;
+    Ok(all_owned)
+}
+
+/// Exit code for incremental output, treating a closed pipe as success
+fn finish_streaming(result: io::Result<()>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        // Downstream closed the pipe (e.g. `| head`) - nothing left to do
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Failed to write results: {}", e);
+            Exit::Error.into()
+        }
+    }
+}
+
+/// Result for one file under GitHub semantics (last match wins)
+fn github_result_json(
+    codeowners: &CodeownersFile,
+    lines: LineMap,
+    file_path: &str,
+) -> CheckResultJson {
+    match codeowners.owners_for(file_path) {
+        Some(r) => {
+            let at = lines.locate(r.line_number);
+            CheckResultJson {
+                owned: true,
+                rule: Some(r.pattern),
+                line: Some(at.line),
+                source: at.source.map(str::to_string),
+                owners: r.owners,
+                matches: None,
+                near_misses: None,
+                sections: None,
+                owner_errors: codeowners
+                    .owner_errors(r.line_number)
+                    .iter()
+                    .map(|error| OwnerErrorJson::new(error, &at))
+                    .collect(),
+            }
+        }
+        None => CheckResultJson {
+            owned: false,
+            rule: None,
+            line: None,
+            source: None,
+            owners: vec![],
+            matches: None,
+            near_misses: None,
+            sections: None,
+            owner_errors: vec![],
+        },
+    }
+}
+
+/// Result for one file under GitLab semantics. There is no single winning
+/// rule, so `rule`/`line` are omitted and each section's winner is listed.
+fn gitlab_result_json(
+    codeowners: &CodeownersFile,
+    lines: LineMap,
+    file_path: &str,
+) -> CheckResultJson {
+    let owners = resolve_owners(codeowners, file_path, Semantics::GitLab);
+    let sections = gitlab_sections(codeowners, file_path)
+        .into_iter()
+        .map(|s| {
+            let at = lines.locate(s.result.line_number);
+            SectionJson {
+                section: s.section,
+                rule: s.result.pattern,
+                line: at.line,
+                source: at.source.map(str::to_string),
+                owners: s.result.owners,
+            }
+        })
+        .collect();
+    CheckResultJson {
+        owned: !owners.is_empty(),
+        rule: None,
+        line: None,
+        source: None,
+        owners,
+        matches: None,
+        near_misses: None,
+        sections: Some(sections),
+        owner_errors: vec![],
+    }
+}
+
+fn output_human(
+    codeowners_path: &Path,
+    codeowners: &CodeownersFile,
+    lines: LineMap,
+    files: &[String],
+    explain: bool,
+) -> ExitCode {
     let mut any_unowned = false;
 
+    println!("{} {}", "Using:".bold(), codeowners_path.display());
+    println!();
+
     for (i, file_path) in files.iter().enumerate() {
         if i > 0 {
             println!();
         }
 
-        match check_file_ownership_parsed(&parsed, file_path) {
+        if explain {
+            if !explain_human(codeowners, lines, file_path) {
+                any_unowned = true;
+            }
+            continue;
+        }
+
+        match codeowners.owners_for(file_path) {
             Some(result) => {
                 println!("{} {}", "File:".bold(), file_path);
                 println!(
                     "{} {} {}",
                     "Rule:".bold(),
                     result.pattern.cyan(),
-                    format!("(line {})", result.line_number + 1).dimmed()
+                    format!("({})", lines.locate(result.line_number)).dimmed()
                 );
                 println!("{} {}", "Owners:".bold(), result.owners.join(" ").green());
+                for error in codeowners.owner_errors(result.line_number) {
+                    println!("{} {}", "⚠".yellow(), error.message().yellow());
+                }
             }
             None => {
                 any_unowned = true;
@@ -137,11 +1094,1061 @@ fn output_human(content: &str, files: &[String]) -> ExitCode {
         }
     }
 
-    // Return success even if some files are unowned (for multi-file mode)
-    // Users can use --strict in lint command if they want to fail on missing owners
-    if files.len() == 1 && any_unowned {
-        ExitCode::from(1)
+    if any_unowned {
+        Exit::Findings.into()
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Each GitLab section's winning rule for `file_path`, matched with the
+/// file's pattern options
+fn gitlab_sections(codeowners: &CodeownersFile, file_path: &str) -> Vec<SectionOwnership> {
+    check_file_ownership_sections_with_options(
+        codeowners.lines(),
+        file_path,
+        codeowners.pattern_options(),
+    )
+}
+
+/// Human output under GitLab semantics: each section's winning rule, then
+/// the combined owners.
+fn output_human_sections(
+    codeowners_path: &Path,
+    codeowners: &CodeownersFile,
+    lines: LineMap,
+    files: &[String],
+    explain: bool,
+) -> ExitCode {
+    let parsed = codeowners.lines();
+    let mut any_unowned = false;
+
+    println!(
+        "{} {} {}",
+        "Using:".bold(),
+        codeowners_path.display(),
+        "(GitLab section semantics)".dimmed()
+    );
+    println!();
+
+    for (i, file_path) in files.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{} {}", "File:".bold(), file_path);
+
+        let sections = gitlab_sections(codeowners, file_path);
+        for s in &sections {
+            let name = s.section.as_deref().unwrap_or("(default)");
+            println!(
+                "  {} {} {} {}",
+                format!("[{}]", name).bold(),
+                s.result.pattern.cyan(),
+                s.result.owners.join(" "),
+                format!("({})", lines.locate(s.result.line_number)).dimmed()
+            );
+        }
+
+        let owners = resolve_owners(codeowners, file_path, Semantics::GitLab);
+        if owners.is_empty() {
+            any_unowned = true;
+            println!(
+                "{} {}",
+                "✗".red(),
+                "No matching rule - file has no owners".yellow()
+            );
+            if explain {
+                for miss in find_near_misses(parsed, file_path, codeowners.pattern_options()) {
+                    println!(
+                        "  {} {} {} {}",
+                        format!("{}:", lines.locate(miss.line_number)).dimmed(),
+                        miss.pattern.cyan(),
+                        miss.owners.join(" "),
+                        format!("({})", miss.reason).dimmed()
+                    );
+                }
+            }
+        } else {
+            println!("{} {}", "Owners:".bold(), owners.join(" ").green());
+        }
+    }
+
+    if any_unowned {
+        Exit::Findings.into()
     } else {
         ExitCode::SUCCESS
     }
 }
+
+/// Every rule matching `file_path` in file order, and the last one as the
+/// winner, matched with the file's compiled patterns
+fn explain_ownership(codeowners: &CodeownersFile, file_path: &str) -> FileOwnership {
+    let all_matches: Vec<OwnershipResult> = codeowners
+        .matching_rules(file_path)
+        .into_iter()
+        .filter_map(|line| match &line.content {
+            CodeownersLine::Rule { pattern, owners } => Some(OwnershipResult {
+                line_number: line.line_number,
+                pattern: pattern.clone(),
+                owners: owners.clone(),
+            }),
+            _ => None,
+        })
+        .collect();
+    FileOwnership {
+        winner: all_matches.last().cloned(),
+        all_matches,
+    }
+}
+
+/// Print every matching rule for `file_path`, marking the winner, or the
+/// nearest near misses if nothing matched. Returns whether the file is owned.
+fn explain_human(codeowners: &CodeownersFile, lines: LineMap, file_path: &str) -> bool {
+    println!("{} {}", "File:".bold(), file_path);
+
+    let parsed = codeowners.lines();
+    let FileOwnership {
+        winner,
+        all_matches,
+    } = explain_ownership(codeowners, file_path);
+    let Some(winner) = winner else {
+        println!(
+            "{} {}",
+            "✗".red(),
+            "No matching rule - file has no owners".yellow()
+        );
+        let near_misses = find_near_misses(parsed, file_path, codeowners.pattern_options());
+        if !near_misses.is_empty() {
+            println!("{}", "Near misses:".bold());
+            for miss in near_misses {
+                println!(
+                    "  {} {} {} {}",
+                    format!("{}:", lines.locate(miss.line_number)).dimmed(),
+                    miss.pattern.cyan(),
+                    miss.owners.join(" "),
+                    format!("({})", miss.reason).dimmed()
+                );
+            }
+        }
+        return false;
+    };
+
+    println!("{}", "Matching rules (last match wins):".bold());
+    for m in &all_matches {
+        let line = format!("{}:", lines.locate(m.line_number));
+        if *m == winner {
+            println!(
+                "  {} {} {} {}",
+                line.dimmed(),
+                m.pattern.cyan().bold(),
+                m.owners.join(" ").green(),
+                "← winner".green().bold()
+            );
+        } else {
+            println!(
+                "  {} {} {} {}",
+                line.dimmed(),
+                m.pattern.dimmed(),
+                m.owners.join(" ").dimmed(),
+                "(overridden)".dimmed()
+            );
+        }
+    }
+    println!("{} {}", "Owners:".bold(), winner.owners.join(" ").green());
+    true
+}
+
+/// Find rules that almost matched `file_path`: right directory but wrong
+/// file name, or right file name but wrong directory. Later rules first,
+/// since they would win if fixed. Patterns are matched with `options`, as
+/// the rules themselves are.
+fn find_near_misses<'a>(
+    parsed: &'a [ParsedLine],
+    file_path: &str,
+    options: PatternOptions,
+) -> Vec<NearMiss<'a>> {
+    let file_path = normalize_path(file_path);
+    let file_path = file_path.as_ref();
+    parsed
+        .iter()
+        .rev()
+        .filter_map(|line| match &line.content {
+            CodeownersLine::Rule { pattern, owners } => {
+                near_miss_reason(pattern, file_path, options).map(|reason| NearMiss {
+                    line_number: line.line_number,
+                    pattern,
+                    owners,
+                    reason,
+                })
+            }
+            _ => None,
+        })
+        .take(MAX_NEAR_MISSES)
+        .collect()
+}
+
+/// Explain how `pattern` nearly matches `file_path`, if it does
+fn near_miss_reason(
+    pattern: &str,
+    file_path: &str,
+    options: PatternOptions,
+) -> Option<&'static str> {
+    let matches =
+        |pattern: &str, path: &str| CompiledPattern::with_options(pattern, options).matches(path);
+    if matches(pattern, file_path) {
+        return None;
+    }
+
+    let is_dir_pattern = pattern.ends_with('/');
+    let trimmed = pattern.trim_end_matches('/');
+    let (dir, name) = trimmed.rsplit_once('/').unwrap_or(("", trimmed));
+
+    // Right directory, wrong file name (e.g. `/src/*.rs` vs `src/main.py`)
+    if !is_dir_pattern && !dir.is_empty() && matches(&format!("{}/*", dir), file_path) {
+        return Some("matches the directory but not the file name");
+    }
+
+    // Right file name, wrong directory (e.g. `/lib/*.rs` vs `src/main.rs`)
+    let basename = file_path.rsplit('/').next().unwrap_or(file_path);
+    if !is_dir_pattern && !dir.is_empty() && !name.is_empty() && matches(name, basename) {
+        return Some("matches the file name but not the directory");
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::schema::validate;
+    use crate::parser::parse_codeowners_file_with_positions;
+
+    fn owners(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_owner_filter_single() {
+        let filters = owners(&["@org/platform"]);
+        assert!(owner_filter_matches(
+            &filters,
+            &owners(&["@alice", "@org/platform"])
+        ));
+        assert!(!owner_filter_matches(&filters, &owners(&["@alice"])));
+    }
+
+    #[test]
+    fn test_owner_filter_leading_at_optional() {
+        assert!(owner_filter_matches(
+            &owners(&["org/platform"]),
+            &owners(&["@org/platform"])
+        ));
+        assert!(owner_filter_matches(
+            &owners(&["@alice"]),
+            &owners(&["@alice"])
+        ));
+        assert!(owner_filter_matches(
+            &owners(&["alice"]),
+            &owners(&["@alice"])
+        ));
+    }
+
+    #[test]
+    fn test_owner_filter_is_exact() {
+        let filters = owners(&["@org/plat"]);
+        assert!(!owner_filter_matches(&filters, &owners(&["@org/platform"])));
+        assert!(!owner_filter_matches(
+            &owners(&["@alice"]),
+            &owners(&["@alice-bot"])
+        ));
+    }
+
+    #[test]
+    fn test_owner_filter_multiple() {
+        let filters = owners(&["@alice", "@org/docs"]);
+        assert!(owner_filter_matches(&filters, &owners(&["@alice"])));
+        assert!(owner_filter_matches(&filters, &owners(&["@org/docs"])));
+        assert!(!owner_filter_matches(&filters, &owners(&["@bob"])));
+    }
+
+    #[test]
+    fn test_owner_filter_no_owners() {
+        assert!(!owner_filter_matches(&owners(&["@alice"]), &[]));
+    }
+
+    const SECTIONED: &str = "*.rs @rust\n[Security] @security\n/src/auth/\n";
+
+    #[test]
+    fn test_result_json_github_vs_gitlab() {
+        let codeowners = CodeownersFile::parse(SECTIONED);
+
+        let github = github_result_json(&codeowners, LineMap::default(), "src/auth/login.rs");
+        assert_eq!(github.rule.as_deref(), Some("/src/auth/"));
+        // GitHub treats the section header as a rule and the winner has no owners
+        assert!(github.owners.is_empty());
+        assert!(github.sections.is_none());
+
+        let gitlab = gitlab_result_json(&codeowners, LineMap::default(), "src/auth/login.rs");
+        assert!(gitlab.owned);
+        assert_eq!(gitlab.owners, owners(&["@rust", "@security"]));
+        let sections = gitlab.sections.unwrap();
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[1].section.as_deref(), Some("Security"));
+        assert_eq!(sections[1].line, 3);
+    }
+
+    #[test]
+    fn test_result_json_owner_errors_on_winning_rule() {
+        let codeowners = CodeownersFile::parse("* @core\n*.rs @rust @@rust-typo\n");
+        let json = serde_json::to_value(github_result_json(
+            &codeowners,
+            LineMap::default(),
+            "src/main.rs",
+        ))
+        .unwrap();
+        assert_eq!(
+            json["owner_errors"],
+            serde_json::json!([{
+                "owner": "@@rust-typo",
+                "column": 12,
+                "message": "Invalid owner format '@@rust-typo': more than one leading '@'. Expected @user, @org/team, or email@domain.com",
+            }])
+        );
+
+        let json = serde_json::to_value(github_result_json(
+            &codeowners,
+            LineMap::default(),
+            "README.md",
+        ))
+        .unwrap();
+        assert!(json.get("owner_errors").is_none());
+    }
+
+    #[test]
+    fn test_result_json_gitlab_unowned() {
+        let codeowners = CodeownersFile::parse("[Docs]\n*.md @docs\n");
+        let gitlab = gitlab_result_json(&codeowners, LineMap::default(), "src/main.rs");
+        assert!(!gitlab.owned);
+        assert!(gitlab.owners.is_empty());
+        assert!(gitlab.sections.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_json_output_matches_schema() {
+        let schema = json_schema();
+        let codeowners = CodeownersFile::parse("/src/*.rs @rust\n*.md @docs @@typo\n");
+
+        // Every optional field: explain matches, near misses, owner errors
+        let mut results: BTreeMap<&str, CheckResultJson> = BTreeMap::new();
+        for file in ["src/main.rs", "src/main.py", "README.md"] {
+            results.insert(
+                file,
+                check_result_json(
+                    &codeowners,
+                    LineMap::default(),
+                    file,
+                    true,
+                    Semantics::GitHub,
+                ),
+            );
+        }
+        let json = serde_json::to_value(&results).unwrap();
+        assert!(json["src/main.py"]["near_misses"].is_array());
+        assert!(json["README.md"]["owner_errors"].is_array());
+        validate(&schema, &json).unwrap();
+
+        let sectioned = CodeownersFile::parse(SECTIONED);
+        let gitlab = BTreeMap::from([(
+            "src/auth/login.rs",
+            check_result_json(
+                &sectioned,
+                LineMap::default(),
+                "src/auth/login.rs",
+                false,
+                Semantics::GitLab,
+            ),
+        )]);
+        validate(&schema, &serde_json::to_value(&gitlab).unwrap()).unwrap();
+
+        // NDJSON records are CheckResults with a `file`
+        let mut out = Vec::new();
+        write_json_line(
+            &mut out,
+            &codeowners,
+            LineMap::default(),
+            "src/lib.rs",
+            true,
+            Semantics::GitHub,
+        )
+        .unwrap();
+        let record: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let record_schema =
+            schema::document::<CheckLineJson>("codeowners-cli check --json-lines", "");
+        validate(&record_schema, &record).unwrap();
+    }
+
+    #[test]
+    fn test_json_results_are_byte_stable() {
+        let codeowners = CodeownersFile::parse("/src/*.rs @rust\n*.md @docs\n");
+        let files: Vec<String> = ["src/main.rs", "README.md", "src/z.rs", "docs/a.md", "x.py"]
+            .iter()
+            .map(|f| f.to_string())
+            .collect();
+        let render = |files: &[String]| {
+            serde_json::to_string(&results_json(
+                &codeowners,
+                LineMap::default(),
+                files,
+                true,
+                Semantics::GitHub,
+            ))
+            .unwrap()
+        };
+
+        let first = render(&files);
+        for _ in 0..10 {
+            assert_eq!(render(&files), first);
+        }
+        // Keys are sorted, whatever order the files came in
+        let mut reversed = files.clone();
+        reversed.reverse();
+        assert_eq!(render(&reversed), first);
+        assert!(first.starts_with("{\"README.md\":"), "{}", first);
+    }
+
+    #[test]
+    fn test_expand_directories_to_their_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src/app")).unwrap();
+        fs::create_dir_all(dir.path().join("empty")).unwrap();
+        let files: Vec<String> = ["README.md", "src/app/mod.rs", "src/main.rs", "srcs/x.rs"]
+            .iter()
+            .map(|f| f.to_string())
+            .collect();
+        let inputs = |paths: &[&str]| paths.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+
+        // With or without the trailing slash; `srcs/` isn't under `src`
+        for src in ["src", "src/", "./src"] {
+            assert_eq!(
+                expand_directories(inputs(&[src]), dir.path(), &files),
+                ["src/app/mod.rs", "src/main.rs"],
+                "{src}"
+            );
+        }
+        // Plain files pass through; overlapping inputs are reported once
+        assert_eq!(
+            expand_directories(
+                inputs(&["README.md", "src/main.rs", "src/app/"]),
+                dir.path(),
+                &files
+            ),
+            ["README.md", "src/main.rs", "src/app/mod.rs"]
+        );
+        // A trailing slash marks a directory even if it isn't on disk
+        assert_eq!(
+            expand_directories(inputs(&["srcs/"]), dir.path(), &files),
+            ["srcs/x.rs"]
+        );
+        // Directories without files are kept to be reported as-is
+        assert_eq!(
+            expand_directories(inputs(&["empty", "gone/"]), dir.path(), &files),
+            ["empty", "gone/"]
+        );
+        assert_eq!(
+            expand_directories(inputs(&["."]), dir.path(), &files),
+            files
+        );
+    }
+
+    #[test]
+    fn test_codeowners_from_stdin_with_explicit_files() {
+        // `check --codeowners - --files src/main.rs docs/guide.md README`
+        let piped = io::Cursor::new("*.rs @rust\n/docs/ @docs\n");
+        let (path, content) = read_codeowners_arg(Path::new("-"), piped).unwrap();
+        assert_eq!(path, PathBuf::from("<stdin>"));
+
+        let codeowners = CodeownersFile::parse(&content);
+        let files = collect_files_ordered(
+            Some(owners(&["src/main.rs", "docs/guide.md", "README"])),
+            None,
+            false,
+            false,
+        )
+        .unwrap()
+        .unwrap();
+        let results: Vec<CheckResultJson> = files
+            .iter()
+            .map(|f| {
+                check_result_json(&codeowners, LineMap::default(), f, false, Semantics::GitHub)
+            })
+            .collect();
+
+        assert_eq!(results[0].owners, owners(&["@rust"]));
+        assert_eq!(results[1].rule.as_deref(), Some("/docs/"));
+        assert!(!results[2].owned);
+    }
+
+    fn stream(content: &str, input: &str, json: bool, filter: &[String]) -> Vec<String> {
+        let codeowners = CodeownersFile::parse(content);
+        let mut out = Vec::new();
+        stream_check(
+            &codeowners,
+            io::Cursor::new(input),
+            &mut out,
+            &StreamOptions {
+                format: if json {
+                    OutputFormat::Json
+                } else {
+                    OutputFormat::Human
+                },
+                owners: filter,
+                explain: false,
+                semantics: Semantics::GitHub,
+                null: false,
+                paths: &OutputPaths::default(),
+                lines: LineMap::default(),
+            },
+        )
+        .unwrap();
+        String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|l| l.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_stream_check_ndjson_in_input_order() {
+        let lines = stream(
+            "* @default\n*.rs @rust\n/docs/ @docs\n",
+            "src/main.rs\n\n  docs/index.md  \nREADME.md",
+            true,
+            &[],
+        );
+
+        // Blank line skipped; last line has no trailing newline but is still checked
+        assert_eq!(lines.len(), 3);
+        let records: Vec<serde_json::Value> = lines
+            .iter()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        let files: Vec<&str> = records
+            .iter()
+            .map(|r| r["file"].as_str().unwrap())
+            .collect();
+        assert_eq!(files, vec!["src/main.rs", "docs/index.md", "README.md"]);
+        assert_eq!(records[0]["owners"], serde_json::json!(["@rust"]));
+        assert_eq!(records[1]["rule"], "/docs/");
+        assert_eq!(records[2]["line"], 1);
+    }
+
+    #[test]
+    fn test_json_lines_each_line_parseable_in_order() {
+        let codeowners = CodeownersFile::parse("* @default\n*.rs @rust @org/team\n/vendor/\n");
+        let files = ["z.rs", "vendor/lib.c", "a.md"];
+        let mut out = Vec::new();
+        for file in files {
+            write_json_line(
+                &mut out,
+                &codeowners,
+                LineMap::default(),
+                file,
+                false,
+                Semantics::GitHub,
+            )
+            .unwrap();
+        }
+
+        let output = String::from_utf8(out).unwrap();
+        let records: Vec<serde_json::Value> = output
+            .lines()
+            .map(|l| serde_json::from_str(l).expect("each line is standalone JSON"))
+            .collect();
+        assert_eq!(records.len(), 3);
+        for (record, file) in records.iter().zip(files) {
+            assert_eq!(record["file"], file);
+            assert!(record.get("owners").is_some());
+            assert!(record.get("rule").is_some());
+            assert!(record.get("line").is_some());
+        }
+        assert_eq!(
+            records[0]["owners"],
+            serde_json::json!(["@rust", "@org/team"])
+        );
+        assert_eq!(records[1]["rule"], "/vendor/");
+        assert_eq!(records[2]["line"], 1);
+    }
+
+    fn csv(content: &str, files: &[&str], semantics: Semantics) -> String {
+        let mut out = Vec::new();
+        write_csv(
+            &mut out,
+            &CodeownersFile::parse(content),
+            LineMap::default(),
+            &owners(files),
+            semantics,
+        )
+        .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_csv_header_and_rows() {
+        assert_eq!(
+            csv(
+                "* @core\n*.rs @rust @org/reviewers\n/vendor/\n",
+                &["src/main.rs", "vendor/x.c", "docs/a.md"],
+                Semantics::GitHub
+            ),
+            "file,owners,rule,line\n\
+             src/main.rs,@rust @org/reviewers,*.rs,2\n\
+             vendor/x.c,,/vendor/,3\n\
+             docs/a.md,@core,*,1\n"
+        );
+        assert_eq!(
+            csv("*.rs @rust\n", &["README.md"], Semantics::GitHub),
+            "file,owners,rule,line\nREADME.md,,,\n"
+        );
+    }
+
+    #[test]
+    fn test_csv_quotes_path_with_comma() {
+        assert_eq!(
+            csv(
+                "* @core\n",
+                &["docs/q1,q2 \"report\".md"],
+                Semantics::GitHub
+            ),
+            "file,owners,rule,line\n\"docs/q1,q2 \"\"report\"\".md\",@core,*,1\n"
+        );
+    }
+
+    #[test]
+    fn test_csv_gitlab_lists_section_winners() {
+        assert_eq!(
+            csv(SECTIONED, &["src/auth/login.rs"], Semantics::GitLab),
+            "file,owners,rule,line\nsrc/auth/login.rs,@rust @security,*.rs /src/auth/,1 3\n"
+        );
+    }
+
+    fn tsv(content: &str, files: &[&str], semantics: Semantics) -> String {
+        let codeowners = CodeownersFile::parse(content);
+        let mut out = Vec::new();
+        for file in files {
+            let owners = resolve_owners(&codeowners, file, semantics);
+            write_tsv_row(
+                &mut out,
+                &codeowners,
+                LineMap::default(),
+                file,
+                &owners,
+                semantics,
+            )
+            .unwrap();
+        }
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_tsv_column_order() {
+        assert_eq!(
+            tsv(
+                "* @core\n*.rs @rust @org/reviewers\n/vendor/\n",
+                &["src/main.rs", "vendor/x.c", "README.md"],
+                Semantics::GitHub
+            ),
+            "src/main.rs\t*.rs\t2\t@rust @org/reviewers\n\
+             vendor/x.c\t/vendor/\t3\t\n\
+             README.md\t*\t1\t@core\n"
+        );
+        assert_eq!(
+            tsv(SECTIONED, &["src/auth/login.rs"], Semantics::GitLab),
+            "src/auth/login.rs\t*.rs /src/auth/\t1 3\t@rust @security\n"
+        );
+    }
+
+    #[test]
+    fn test_tsv_escapes_tab_in_path() {
+        let out = tsv("*.md @docs\n", &["docs/a\tb.md", "x.rs"], Semantics::GitHub);
+        assert_eq!(out, "docs/a\\tb.md\t*.md\t1\t@docs\nx.rs\t\t\t\n");
+        for row in out.lines() {
+            assert_eq!(row.split('\t').count(), 4, "{row}");
+        }
+    }
+
+    fn owners_only(content: &str, files: &[&str]) -> (String, bool) {
+        let codeowners = CodeownersFile::parse(content);
+        let mut out = Vec::new();
+        let all_owned =
+            write_owners_only(&mut out, &codeowners, &owners(files), Semantics::GitHub).unwrap();
+        (String::from_utf8(out).unwrap(), all_owned)
+    }
+
+    #[test]
+    fn test_owners_only_single_owned() {
+        let (out, all_owned) = owners_only("*.rs @rust @org/team\n", &["src/main.rs"]);
+        assert_eq!(out, "@rust\n@org/team\n");
+        assert!(all_owned);
+    }
+
+    #[test]
+    fn test_owners_only_single_unowned() {
+        let (out, all_owned) = owners_only("*.rs @rust\n/vendor/\n", &["README.md"]);
+        assert_eq!(out, "");
+        assert!(!all_owned);
+
+        // A winning rule with no owners is also unowned
+        let (out, all_owned) = owners_only("*.rs @rust\n/vendor/\n", &["vendor/x.rs"]);
+        assert_eq!(out, "");
+        assert!(!all_owned);
+    }
+
+    #[test]
+    fn test_owners_only_multiple_files_prefixed() {
+        let (out, all_owned) =
+            owners_only("*.rs @rust @bob\n*.md @docs\n", &["a.rs", "b.txt", "c.md"]);
+        assert_eq!(out, "a.rs @rust\na.rs @bob\nc.md @docs\n");
+        assert!(!all_owned);
+
+        let (_, all_owned) = owners_only("* @all\n", &["a.rs", "b.md"]);
+        assert!(all_owned);
+    }
+
+    #[test]
+    fn test_github_annotations_unowned_and_invalid_owners() {
+        let content = "*.rs @rust bad_owner\n*.md @docs\n/vendor/ also,bad\n";
+        let codeowners = CodeownersFile::parse(content);
+        let annotations = github_annotations(
+            ".github/CODEOWNERS",
+            content,
+            &codeowners,
+            LineMap::default(),
+            &owners(&["src/main.rs", "a,b%.txt"]),
+            Semantics::GitHub,
+        );
+
+        assert!(annotations.any_unowned);
+        assert_eq!(
+            annotations.lines,
+            vec![
+                "::warning file=a%2Cb%25.txt,title=unowned-file::File has no CODEOWNERS owners"
+                    .to_string(),
+                "::error file=.github/CODEOWNERS,line=1,col=12,title=invalid-owner::Invalid owner format 'bad_owner': missing leading '@'. Expected @user, @org/team, or email@domain.com".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_github_annotations_only_for_rules_in_use() {
+        let content = "*.rs @rust\n/vendor/ bad_owner\n";
+        let codeowners = CodeownersFile::parse(content);
+        let annotations = github_annotations(
+            "CODEOWNERS",
+            content,
+            &codeowners,
+            LineMap::default(),
+            &owners(&["src/main.rs"]),
+            Semantics::GitHub,
+        );
+        assert!(!annotations.any_unowned);
+        assert!(annotations.lines.is_empty());
+    }
+
+    /// A root CODEOWNERS merged with `pkg/CODEOWNERS`, which has a
+    /// malformed owner on its second line
+    fn merged_fixture(semantics: Semantics) -> (tempfile::TempDir, MergedCodeowners) {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("pkg")).unwrap();
+        fs::write(
+            dir.path().join("pkg/CODEOWNERS"),
+            "# Package\n*.ts @web bad_owner\n",
+        )
+        .unwrap();
+        let merged = merge_nested_codeowners(
+            dir.path(),
+            "* @core\n/docs/ @docs\n",
+            &owners(&["pkg/CODEOWNERS"]),
+            semantics,
+        )
+        .unwrap();
+        (dir, merged)
+    }
+
+    #[test]
+    fn test_merged_rules_report_their_own_file_and_line() {
+        let (_dir, merged) = merged_fixture(Semantics::GitHub);
+        let codeowners = CodeownersFile::parse(&merged.content);
+        let lines = LineMap(Some(&merged));
+
+        let json = serde_json::to_value(check_result_json(
+            &codeowners,
+            lines,
+            "pkg/a.ts",
+            true,
+            Semantics::GitHub,
+        ))
+        .unwrap();
+        assert_eq!(json["line"], 2);
+        assert_eq!(json["source"], "pkg/CODEOWNERS");
+        // Column within the line as written, not as scoped
+        assert_eq!(json["owner_errors"][0]["column"], 11);
+        assert_eq!(json["matches"][0]["line"], 1);
+        assert!(json["matches"][0].get("source").is_none());
+
+        let (rule, line) = rule_and_line(&codeowners, lines, "pkg/a.ts", Semantics::GitHub);
+        assert_eq!(
+            (rule.as_str(), line.as_str()),
+            ("/pkg/**/*.ts", "pkg/CODEOWNERS:2")
+        );
+        assert_eq!(
+            lines.locate(codeowners.rule_for("docs/x.md").unwrap().line_number),
+            RuleLine {
+                source: None,
+                line: 2,
+                shift: 0
+            }
+        );
+        assert_eq!(
+            lines
+                .locate(codeowners.rule_for("pkg/a.ts").unwrap().line_number)
+                .to_string(),
+            "line 2 of pkg/CODEOWNERS"
+        );
+
+        // Invalid owners are annotated in the nested file
+        let annotations = github_annotations(
+            "CODEOWNERS",
+            "* @core\n/docs/ @docs\n",
+            &codeowners,
+            lines,
+            &owners(&["pkg/a.ts"]),
+            Semantics::GitHub,
+        );
+        assert_eq!(annotations.lines.len(), 1, "{:?}", annotations.lines);
+        assert!(
+            annotations.lines[0].starts_with("::error file=pkg/CODEOWNERS,line=2,col=11,"),
+            "{}",
+            annotations.lines[0]
+        );
+    }
+
+    #[test]
+    fn test_merged_rules_under_gitlab_keep_their_own_section() {
+        let (_dir, merged) = merged_fixture(Semantics::GitLab);
+        let codeowners = CodeownersFile::parse(&merged.content);
+
+        let result = gitlab_result_json(&codeowners, LineMap(Some(&merged)), "pkg/a.ts");
+        let sections = result.sections.unwrap();
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[1].section.as_deref(), Some("pkg/CODEOWNERS"));
+        assert_eq!(
+            (sections[1].line, sections[1].source.as_deref()),
+            (2, Some("pkg/CODEOWNERS"))
+        );
+        assert_eq!(result.owners, owners(&["@core", "@web", "bad_owner"]));
+    }
+
+    #[test]
+    fn test_stream_check_unowned_and_owner_filter() {
+        let content = "*.rs @rust\n/docs/ @docs\n";
+
+        let lines = stream(content, "a.rs\nb.txt\r\ndocs/x.md\n", true, &[]);
+        let unowned: serde_json::Value = serde_json::from_str(&lines[1]).unwrap();
+        assert_eq!(unowned["file"], "b.txt");
+        assert_eq!(unowned["owned"], false);
+
+        let lines = stream(
+            content,
+            "a.rs\nb.txt\ndocs/x.md\n",
+            true,
+            &owners(&["docs"]),
+        );
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].contains("docs/x.md"));
+    }
+
+    #[test]
+    fn test_dot_slash_and_backslash_paths_match() {
+        let content = "* @core\n/src/ @backend\n";
+        let lines = stream(
+            content,
+            "./src/main.rs\nsrc\\lib.rs\n.\\README.md\n",
+            true,
+            &[],
+        );
+        let owners: Vec<serde_json::Value> = lines
+            .iter()
+            .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap()["owners"].clone())
+            .collect();
+        assert_eq!(
+            owners,
+            vec![
+                serde_json::json!(["@backend"]),
+                serde_json::json!(["@backend"]),
+                serde_json::json!(["@core"]),
+            ]
+        );
+        // Paths are reported as given
+        assert!(lines[1].contains(r#""file":"src\\lib.rs""#));
+
+        let parsed = parse_codeowners_file_with_positions("/src/*.rs @rust\n");
+        assert_eq!(
+            find_near_misses(&parsed, r".\src\main.py", PatternOptions::default()).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_stream_check_null_delimited_paths() {
+        let codeowners = CodeownersFile::parse("*.rs @rust\n/docs/ @docs\n");
+        let mut out = Vec::new();
+        stream_check(
+            &codeowners,
+            io::Cursor::new("src/my file.rs\0docs/a\nb.md\0\0"),
+            &mut out,
+            &StreamOptions {
+                format: OutputFormat::Json,
+                owners: &[],
+                explain: false,
+                semantics: Semantics::GitHub,
+                null: true,
+                paths: &OutputPaths::default(),
+                lines: LineMap::default(),
+            },
+        )
+        .unwrap();
+
+        let records: Vec<serde_json::Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["file"], "src/my file.rs");
+        assert_eq!(records[0]["owners"][0], "@rust");
+        assert_eq!(records[1]["file"], "docs/a\nb.md");
+        assert_eq!(records[1]["owners"][0], "@docs");
+    }
+
+    #[test]
+    fn test_stream_check_human_one_line_per_path() {
+        let lines = stream("*.rs @rust\n", "a.rs\nb.txt", false, &[]);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("a.rs ") && lines[0].contains("@rust"));
+        assert!(lines[1].starts_with("b.txt ") && lines[1].contains("no owners"));
+    }
+
+    #[test]
+    fn test_near_miss_right_directory_wrong_name() {
+        assert_eq!(
+            near_miss_reason("/src/*.rs", "src/main.py", PatternOptions::default()),
+            Some("matches the directory but not the file name")
+        );
+    }
+
+    #[test]
+    fn test_near_miss_right_name_wrong_directory() {
+        assert_eq!(
+            near_miss_reason("/lib/*.rs", "src/main.rs", PatternOptions::default()),
+            Some("matches the file name but not the directory")
+        );
+    }
+
+    #[test]
+    fn test_near_miss_none_for_matches_and_unrelated() {
+        assert_eq!(
+            near_miss_reason("*.rs", "src/main.rs", PatternOptions::default()),
+            None
+        );
+        assert_eq!(
+            near_miss_reason("/docs/*.md", "src/main.rs", PatternOptions::default()),
+            None
+        );
+        assert_eq!(
+            near_miss_reason("/docs/", "src/main.rs", PatternOptions::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_near_miss_with_brace_expansion() {
+        let braces = PatternOptions {
+            brace_expansion: true,
+        };
+        assert_eq!(
+            near_miss_reason("/lib/*.{rs,ts}", "src/main.rs", braces),
+            Some("matches the file name but not the directory")
+        );
+        assert_eq!(
+            near_miss_reason("/src/*.{js,ts}", "src/main.rs", braces),
+            Some("matches the directory but not the file name")
+        );
+        assert_eq!(
+            near_miss_reason("/src/*.{rs,ts}", "src/main.rs", braces),
+            None
+        );
+        // Without expansion the braces are literal, so the name never matches
+        assert_eq!(
+            near_miss_reason("/lib/*.{rs,ts}", "src/main.rs", PatternOptions::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_near_misses_latest_first_and_capped() {
+        let parsed = parse_codeowners_file_with_positions(
+            "/src/*.rs @a\n/src/*.go @b\n/src/*.ts @c\n/src/*.js @d\n/docs/ @docs\n",
+        );
+        let misses = find_near_misses(&parsed, "src/main.py", PatternOptions::default());
+
+        assert_eq!(misses.len(), MAX_NEAR_MISSES);
+        let lines: Vec<u32> = misses.iter().map(|m| m.line_number).collect();
+        assert_eq!(lines, vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_explain_overlapping_rules_winner_is_last() {
+        let codeowners =
+            CodeownersFile::parse("* @default\n/src/ @src\n*.rs @rust\n/docs/ @docs\n");
+        let FileOwnership {
+            winner,
+            all_matches,
+        } = explain_ownership(&codeowners, "src/main.rs");
+
+        let patterns: Vec<&str> = all_matches.iter().map(|m| m.pattern.as_str()).collect();
+        assert_eq!(patterns, vec!["*", "/src/", "*.rs"]);
+        assert_eq!(winner.unwrap().owners, vec!["@rust".to_string()]);
+    }
+
+    #[test]
+    fn test_brace_expansion_setting_changes_ownership() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = "* @all\n*.{js,ts} @web\n";
+
+        let plain = CodeownersFile::parse_with_options(
+            content,
+            load_settings_from_path(dir.path()).pattern_options(),
+        );
+        assert_eq!(
+            resolve_owners(&plain, "a.ts", Semantics::GitHub),
+            vec!["@all"]
+        );
+
+        fs::write(
+            dir.path().join(".codeowners-lsp.toml"),
+            "brace_expansion = true\n",
+        )
+        .unwrap();
+        let expanded = CodeownersFile::parse_with_options(
+            content,
+            load_settings_from_path(dir.path()).pattern_options(),
+        );
+        assert_eq!(
+            resolve_owners(&expanded, "a.ts", Semantics::GitHub),
+            vec!["@web"]
+        );
+        let explained = explain_ownership(&expanded, "a.ts");
+        assert_eq!(explained.all_matches.len(), 2);
+        assert_eq!(explained.winner.unwrap().pattern, "*.{js,ts}");
+    }
+}
@@ -0,0 +1,150 @@
+//! Whether to color output, decided once before any command runs.
+//!
+//! `--color always|never` wins; with `auto` (the default) the environment
+//! decides (`NO_COLOR`, then `CLICOLOR_FORCE`, then `CLICOLOR=0`), then
+//! whether stdout is a terminal. Machine-readable output (JSON, CSV,
+//! completions...) is never colored, whatever was asked for.
+
+use std::env;
+use std::ffi::OsString;
+use std::io::IsTerminal;
+
+/// `--color` setting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+/// The color-related environment variables
+#[derive(Debug, Default)]
+pub struct ColorEnv {
+    no_color: Option<OsString>,
+    clicolor: Option<OsString>,
+    clicolor_force: Option<OsString>,
+}
+
+impl ColorEnv {
+    fn from_env() -> Self {
+        Self {
+            no_color: env::var_os("NO_COLOR"),
+            clicolor: env::var_os("CLICOLOR"),
+            clicolor_force: env::var_os("CLICOLOR_FORCE"),
+        }
+    }
+}
+
+/// Set and non-empty (https://no-color.org)
+fn is_set(value: &Option<OsString>) -> bool {
+    value.as_ref().is_some_and(|v| !v.is_empty())
+}
+
+/// Set to something other than empty or `0` (https://bixense.com/clicolors)
+fn is_enabled(value: &Option<OsString>) -> bool {
+    is_set(value) && value.as_deref() != Some("0".as_ref())
+}
+
+fn should_color(
+    choice: ColorChoice,
+    env: &ColorEnv,
+    is_terminal: bool,
+    machine_output: bool,
+) -> bool {
+    if machine_output {
+        return false;
+    }
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto if is_set(&env.no_color) => false,
+        ColorChoice::Auto if is_enabled(&env.clicolor_force) => true,
+        ColorChoice::Auto if env.clicolor.as_deref() == Some("0".as_ref()) => false,
+        ColorChoice::Auto => is_terminal,
+    }
+}
+
+/// Turn color on or off for the rest of the process
+pub fn init(choice: ColorChoice, machine_output: bool) {
+    let color = should_color(
+        choice,
+        &ColorEnv::from_env(),
+        std::io::stdout().is_terminal(),
+        machine_output,
+    );
+    colored::control::set_override(color);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(no_color: Option<&str>, clicolor: Option<&str>, force: Option<&str>) -> ColorEnv {
+        ColorEnv {
+            no_color: no_color.map(OsString::from),
+            clicolor: clicolor.map(OsString::from),
+            clicolor_force: force.map(OsString::from),
+        }
+    }
+
+    #[test]
+    fn test_no_color_env_disables_human_output() {
+        let no_color = env(Some("1"), None, None);
+        assert!(!should_color(ColorChoice::Auto, &no_color, true, false));
+        // Empty means unset
+        assert!(should_color(
+            ColorChoice::Auto,
+            &env(Some(""), None, None),
+            true,
+            false
+        ));
+        // NO_COLOR beats CLICOLOR_FORCE
+        assert!(!should_color(
+            ColorChoice::Auto,
+            &env(Some("1"), None, Some("1")),
+            true,
+            false
+        ));
+        // An explicit --color always beats the environment
+        assert!(should_color(ColorChoice::Always, &no_color, true, false));
+    }
+
+    #[test]
+    fn test_clicolor_variables() {
+        let auto = |env: &ColorEnv, tty| should_color(ColorChoice::Auto, env, tty, false);
+        assert!(auto(&env(None, None, Some("1")), false));
+        assert!(!auto(&env(None, None, Some("0")), false));
+        assert!(!auto(&env(None, Some("0"), None), true));
+        assert!(auto(&env(None, Some("1"), None), true));
+    }
+
+    #[test]
+    fn test_auto_follows_terminal() {
+        assert!(should_color(
+            ColorChoice::Auto,
+            &ColorEnv::default(),
+            true,
+            false
+        ));
+        assert!(!should_color(
+            ColorChoice::Auto,
+            &ColorEnv::default(),
+            false,
+            false
+        ));
+        assert!(!should_color(
+            ColorChoice::Never,
+            &ColorEnv::default(),
+            true,
+            false
+        ));
+    }
+
+    #[test]
+    fn test_machine_output_is_never_colored() {
+        let force = env(None, None, Some("1"));
+        for choice in [ColorChoice::Auto, ColorChoice::Always, ColorChoice::Never] {
+            assert!(!should_color(choice, &force, true, true));
+        }
+    }
+}
@@ -1,23 +1,166 @@
-use std::collections::BTreeMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 use std::{env, fs};
 
 use colored::Colorize;
+use rayon::prelude::*;
+use schemars::JsonSchema;
 use serde::Serialize;
+use tower_lsp::lsp_types::NumberOrString;
 
-use super::files::collect_files;
-use crate::file_cache::FileCache;
-use crate::ownership::{find_codeowners, get_repo_root};
-use crate::parser;
+use super::annotations::{diagnostic_annotation, unowned_file_annotation};
+use super::exit::Exit;
+use super::files::{collect_files, OutputPaths, PathStyle};
+use super::listing::{hidden_note, sort_files, top_n, SortBy};
+use super::stats::directory_at_depth;
+use super::tsv;
+use super::{profile, schema};
+use crate::diagnostics::{codes, compute_diagnostics_sync, DiagnosticConfig};
+use crate::file_cache::{matches_any, FileCache, FileCacheOptions};
+use crate::nested::merge_nested_codeowners;
+use crate::ownership::{
+    check_file_ownership_sections_with_options, find_all_codeowners, get_repo_root, CodeownersFile,
+    Semantics,
+};
+use crate::parser::{self, parse_section_header, CodeownersLine};
+use crate::pattern::normalize_path;
+use crate::settings::load_settings_from_path;
+use crate::validation::canonical_owner;
 
-#[derive(Serialize)]
+/// Coverage of the whole CODEOWNERS file
+#[derive(Serialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
 struct CoverageJson {
+    /// Path of the CODEOWNERS file used
+    codeowners: String,
     total: usize,
     owned: usize,
     unowned: usize,
+    /// Owned share of files, rounded to one decimal place
+    #[schemars(range(min = 0, max = 100))]
     coverage_percent: f64,
+    /// "total" for the whole repository, "checked" for the files given
+    #[schemars(extend("enum" = ["total", "checked"]))]
+    mode: &'static str,
+    /// Every unowned file, sorted per --sort
     unowned_files: Vec<String>,
+    /// Per-directory coverage, worst first (only with --by-dir)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    directories: Option<Vec<DirectoryCoverage>>,
+    /// Unowned files missing from `--baseline`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new_unowned_files: Option<Vec<String>>,
+}
+
+impl CoverageJson {
+    /// Counts for `unowned` out of `total` files, listing every unowned file
+    fn new(codeowners: String, total: usize, unowned: &[&str], mode: &'static str) -> Self {
+        let owned = total.saturating_sub(unowned.len());
+        let coverage_percent = if total > 0 {
+            owned as f64 / total as f64 * 100.0
+        } else {
+            100.0
+        };
+        Self {
+            codeowners,
+            total,
+            owned,
+            unowned: unowned.len(),
+            coverage_percent: (coverage_percent * 10.0).round() / 10.0, // 1 decimal place
+            mode,
+            unowned_files: unowned.iter().map(|s| s.to_string()).collect(),
+            directories: None,
+            new_unowned_files: None,
+        }
+    }
+}
+
+/// Owned/total counts for one directory in `--by-dir` mode
+#[derive(Debug, Serialize, JsonSchema, PartialEq)]
+#[schemars(deny_unknown_fields)]
+struct DirectoryCoverage {
+    /// Directory truncated to the requested depth ("/" for the repo root)
+    directory: String,
+    total: usize,
+    owned: usize,
+    unowned: usize,
+    #[schemars(range(min = 0, max = 100))]
+    coverage_percent: f64,
+}
+
+/// Group `files` by directory at `depth` and count how many are owned.
+/// Sorted worst coverage first, then most unowned files, then name.
+fn coverage_by_directory(
+    files: &[&str],
+    unowned: &HashSet<&str>,
+    depth: usize,
+) -> Vec<DirectoryCoverage> {
+    let mut by_dir: HashMap<String, (usize, usize)> = HashMap::new();
+    for file in files {
+        let directory = match directory_at_depth(file, depth) {
+            dir if dir == "." => "/".to_string(),
+            dir => dir,
+        };
+        let (total, owned) = by_dir.entry(directory).or_default();
+        *total += 1;
+        if !unowned.contains(file) {
+            *owned += 1;
+        }
+    }
+
+    let mut dirs: Vec<DirectoryCoverage> = by_dir
+        .into_iter()
+        .map(|(directory, (total, owned))| DirectoryCoverage {
+            directory,
+            total,
+            owned,
+            unowned: total - owned,
+            // 1 decimal place, like the overall percentage
+            coverage_percent: (owned as f64 / total as f64 * 1000.0).round() / 10.0,
+        })
+        .collect();
+    dirs.sort_by(|a, b| {
+        a.coverage_percent
+            .total_cmp(&b.coverage_percent)
+            .then_with(|| b.unowned.cmp(&a.unowned))
+            .then_with(|| a.directory.cmp(&b.directory))
+    });
+    dirs
+}
+
+/// Print `dirs` as a table with a small coverage bar per directory
+fn render_directory_table(dirs: &[DirectoryCoverage]) -> Vec<String> {
+    let width = dirs.iter().map(|d| d.directory.len()).max().unwrap_or(0);
+    dirs.iter()
+        .map(|dir| {
+            format!(
+                "    {} {:>6}  {:<width$}  {}",
+                progress_bar(dir.coverage_percent, 10),
+                format!("{:.1}%", dir.coverage_percent),
+                dir.directory,
+                format!("{}/{} owned", dir.owned, dir.total).dimmed(),
+                width = width
+            )
+        })
+        .collect()
+}
+
+/// `--json` output: whole-file coverage, or one owner's with `--owner`
+#[derive(Serialize, JsonSchema)]
+#[serde(untagged)]
+enum CoverageOutput<'a> {
+    Files(&'a CoverageJson),
+    Owner(&'a OwnerCoverage),
+}
+
+/// JSON Schema for `--json` output, derived from [`CoverageOutput`]
+pub(super) fn json_schema() -> serde_json::Value {
+    schema::document::<CoverageOutput>(
+        "codeowners-cli coverage --json",
+        "Coverage of the whole CODEOWNERS file. With --owner the output is an OwnerCoverage instead.",
+    )
 }
 
 /// Generate a visual progress bar
@@ -130,46 +273,520 @@ fn render_tree(unowned: &[&str]) -> Vec<String> {
     root.render("  ", true, true)
 }
 
-pub fn coverage(
-    files: Option<Vec<String>>,
-    files_from: Option<PathBuf>,
-    stdin: bool,
-    tree: bool,
-    json: bool,
-) -> ExitCode {
+/// Output format for the coverage command
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Human,
+    Json,
+    /// GitHub Actions workflow-command annotations
+    Github,
+    /// Headerless `path<TAB>owned|unowned` rows for `cut`/`awk`
+    Tsv,
+}
+
+/// Write one `path<TAB>owned|unowned` row per file, in the order given
+fn write_tsv<W: Write>(out: &mut W, files: &[&str], unowned: &HashSet<&str>) -> io::Result<()> {
+    for file in files {
+        let status = if unowned.contains(file) {
+            "unowned"
+        } else {
+            "owned"
+        };
+        tsv::write_record(out, [*file, status])?;
+    }
+    Ok(())
+}
+
+/// Annotate each unowned file, plus every invalid owner in CODEOWNERS
+fn github_annotations(codeowners_file: &str, content: &str, unowned: &[&str]) -> Vec<String> {
+    let (diagnostics, _) = compute_diagnostics_sync(content, None, &DiagnosticConfig::default());
+    let invalid_owner = NumberOrString::String(codes::INVALID_OWNER.to_string());
+
+    unowned
+        .iter()
+        .map(|file| unowned_file_annotation(file))
+        .chain(
+            diagnostics
+                .iter()
+                .filter(|d| d.code.as_ref() == Some(&invalid_owner))
+                .map(|d| diagnostic_annotation(d, codeowners_file)),
+        )
+        .collect()
+}
+
+/// Header written at the top of `--update-baseline` files
+const BASELINE_HEADER: &str =
+    "# Unowned files accepted by `codeowners-cli coverage --baseline`, one per line.\n\
+     # Regenerate with --update-baseline.\n";
+
+/// Read a baseline of accepted unowned files: one path per line, blank lines
+/// and `#` comments ignored
+fn read_baseline(path: &Path) -> Result<HashSet<String>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read baseline {}: {}", path.display(), e))?;
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| normalize_path(line).into_owned())
+        .collect())
+}
+
+/// Baseline file content for the current unowned files, sorted
+fn format_baseline(unowned: &[&str]) -> String {
+    let mut files = unowned.to_vec();
+    files.sort_unstable();
+    let mut content = BASELINE_HEADER.to_string();
+    for file in files {
+        content.push_str(file);
+        content.push('\n');
+    }
+    content
+}
+
+/// Unowned files the baseline doesn't know about, in `unowned` order
+fn new_unowned<'a>(unowned: &[&'a str], baseline: &HashSet<String>) -> Vec<&'a str> {
+    unowned
+        .iter()
+        .copied()
+        .filter(|file| !baseline.contains(*file))
+        .collect()
+}
+
+/// How one of an owner's rules fares against the repository
+/// live: wins files; shadowed: later rules win all its matches; dead:
+/// matches nothing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+enum RuleStatus {
+    /// Decides ownership of at least one file
+    Live,
+    /// Matches files, but a later rule wins every one of them
+    Shadowed,
+    /// Matches no files at all
+    Dead,
+}
+
+/// A rule listing the owner from `--owner`
+#[derive(Debug, Serialize, JsonSchema, PartialEq)]
+#[schemars(deny_unknown_fields)]
+struct OwnerRule {
+    /// 1-based line in CODEOWNERS
+    #[schemars(range(min = 1))]
+    line: u32,
+    /// Nested CODEOWNERS the rule is in (--merge-nested); omitted for the
+    /// root one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+    pattern: String,
+    /// Files the pattern matches
+    matched: usize,
+    /// Files this rule decides ownership of
+    won: usize,
+    status: RuleStatus,
+}
+
+/// `--owner` output: the files an owner wins and how each of their rules fares
+#[derive(Debug, Serialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+struct OwnerCoverage {
+    codeowners: String,
+    owner: String,
+    /// Files in the repository
+    total: usize,
+    /// Files whose winning rule lists the owner
+    owned: usize,
+    /// Owned share of all files, rounded to one decimal place
+    #[schemars(range(min = 0, max = 100))]
+    owned_percent: f64,
+    owned_files: Vec<String>,
+    rules: Vec<OwnerRule>,
+    live_rules: usize,
+    shadowed_rules: usize,
+    dead_rules: usize,
+}
+
+/// Restrict coverage to the rules listing `owner` (compared as GitHub does,
+/// ignoring case): the files they win, and which of them are live, shadowed
+/// or dead. Under GitLab semantics a file can be won by one rule per
+/// section. `None` if no rule lists the owner.
+fn owner_coverage(
+    codeowners: &CodeownersFile,
+    file_cache: &FileCache,
+    owner: &str,
+    semantics: Semantics,
+) -> Option<(Vec<String>, Vec<OwnerRule>)> {
+    let owner = canonical_owner(owner);
+    let lists_owner = |owners: &[String]| owners.iter().any(|o| canonical_owner(o) == owner);
+
+    // Per file, the winning rule lines that give it to this owner
+    let won_by: Vec<Vec<u32>> = file_cache
+        .all_files()
+        .par_iter()
+        .map(|file| match semantics {
+            Semantics::GitHub => codeowners
+                .rule_for(file)
+                .filter(|line| {
+                    matches!(&line.content, CodeownersLine::Rule { owners, .. } if lists_owner(owners))
+                })
+                .map(|line| vec![line.line_number])
+                .unwrap_or_default(),
+            Semantics::GitLab => check_file_ownership_sections_with_options(
+                codeowners.lines(),
+                file,
+                codeowners.pattern_options(),
+            )
+            .into_iter()
+            .filter(|section| lists_owner(&section.result.owners))
+            .map(|section| section.result.line_number)
+            .collect(),
+        })
+        .collect();
+
+    let mut owned = Vec::new();
+    // Winning rule line -> files it decides for this owner
+    let mut won: HashMap<u32, usize> = HashMap::new();
+    for (file, lines) in file_cache.all_files().iter().zip(&won_by) {
+        if !lines.is_empty() {
+            owned.push(file.clone());
+        }
+        for line in lines {
+            *won.entry(*line).or_default() += 1;
+        }
+    }
+
+    let rules: Vec<OwnerRule> = codeowners
+        .lines()
+        .iter()
+        .filter_map(|line| match &line.content {
+            CodeownersLine::Rule { pattern, owners }
+                if lists_owner(owners)
+                    && !(semantics == Semantics::GitLab && is_section_header(pattern, owners)) =>
+            {
+                let matched = file_cache.count_matches(pattern);
+                let won = won.get(&line.line_number).copied().unwrap_or(0);
+                let status = match (won, matched) {
+                    (0, 0) => RuleStatus::Dead,
+                    (0, _) => RuleStatus::Shadowed,
+                    _ => RuleStatus::Live,
+                };
+                Some(OwnerRule {
+                    line: line.line_number + 1,
+                    source: None,
+                    pattern: pattern.clone(),
+                    matched,
+                    won,
+                    status,
+                })
+            }
+            _ => None,
+        })
+        .collect();
+
+    (!rules.is_empty()).then_some((owned, rules))
+}
+
+/// Whether a parsed rule is really a GitLab section header (`[Name] @owner`)
+fn is_section_header(pattern: &str, owners: &[String]) -> bool {
+    (pattern.starts_with('[') || pattern.starts_with("^["))
+        && parse_section_header(
+            &std::iter::once(pattern)
+                .chain(owners.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join(" "),
+        )
+        .is_some()
+}
+
+fn output_owner_human(report: &OwnerCoverage, top: usize) {
+    println!();
+    println!("  {}:", "Rules".bold());
+    println!();
+    let width = report
+        .rules
+        .iter()
+        .map(|r| r.pattern.len())
+        .max()
+        .unwrap_or(0);
+    for rule in &report.rules {
+        let line = match &rule.source {
+            Some(source) => format!("{}:L{}", source, rule.line),
+            None => format!("L{:<4}", rule.line),
+        }
+        .dimmed();
+        let (marker, detail) = match rule.status {
+            RuleStatus::Live => ("✓".green(), format!("{} files", rule.won).normal()),
+            RuleStatus::Shadowed => (
+                "!".yellow(),
+                format!(
+                    "shadowed ({} matched, all owned by later rules)",
+                    rule.matched
+                )
+                .yellow(),
+            ),
+            RuleStatus::Dead => ("✗".red(), "dead (matches no files)".red()),
+        };
+        println!(
+            "    {} {} {:<width$}  {}",
+            marker,
+            line,
+            rule.pattern,
+            detail,
+            width = width
+        );
+    }
+
+    if !report.owned_files.is_empty() {
+        println!();
+        println!("  {}:", "Owned files".bold());
+        println!();
+        let (shown, hidden) = top_n(&report.owned_files, top);
+        for file in shown {
+            println!("    {} {}", "•".green(), file);
+        }
+        if let Some(note) = hidden_note(hidden) {
+            println!("{}", note);
+        }
+    }
+
+    println!();
+    println!(
+        "  {} {}",
+        "Coverage for".bold(),
+        report.owner.as_str().cyan().bold()
+    );
+    println!("  {}", report.codeowners.as_str().dimmed());
+    println!();
+    println!(
+        "  {}  {} files owned {}",
+        "✓".green(),
+        report.owned.to_string().green().bold(),
+        format!("({:.1}% of {})", report.owned_percent, report.total).dimmed()
+    );
+    println!(
+        "  {}  {} live rules",
+        "✓".green(),
+        report.live_rules.to_string().green().bold()
+    );
+    if report.shadowed_rules > 0 {
+        println!(
+            "  {}  {} shadowed rules (every match owned by a later rule)",
+            "!".yellow(),
+            report.shadowed_rules.to_string().yellow().bold()
+        );
+    }
+    println!(
+        "  {}  {} dead rules (match no files)",
+        "✗".red(),
+        report.dead_rules.to_string().red().bold()
+    );
+    println!();
+}
+
+/// Options for the coverage command
+#[derive(Debug, Clone, Default)]
+pub struct CoverageOptions {
+    /// Check only these files
+    pub files: Option<Vec<String>>,
+    /// Read files to check from a file (one per line)
+    pub files_from: Option<PathBuf>,
+    /// Read files to check from stdin (one per line)
+    pub stdin: bool,
+    /// File lists are NUL-separated instead of one per line
+    pub null: bool,
+    /// Show unowned files as a directory tree
+    pub tree: bool,
+    /// Summarize coverage per directory, grouped at this depth
+    pub by_dir: Option<usize>,
+    /// Output format
+    pub format: OutputFormat,
+    /// Leave out files marked `export-ignore` in `.gitattributes`
+    pub exclude_export_ignore: bool,
+    /// Leave out files matching any of these patterns
+    pub exclude: Vec<String>,
+    /// Print at most this many unowned files or directories (0 = all)
+    pub top: usize,
+    /// Order of the unowned files
+    pub sort: SortBy,
+    /// Only fail on unowned files missing from this list
+    pub baseline: Option<PathBuf>,
+    /// Write the current unowned files to `baseline` instead of checking
+    pub update_baseline: bool,
+    /// Report on the rules listing this owner instead of the whole file
+    pub owner: Option<String>,
+    /// How the owner's files are resolved (with `owner`)
+    pub semantics: Semantics,
+    /// Append the repository's per-package CODEOWNERS files, scoped to their directories
+    pub merge_nested: bool,
+    /// Print paths as given or relative to the repository root
+    pub path_style: PathStyle,
+}
+
+pub fn coverage(options: CoverageOptions) -> ExitCode {
+    let CoverageOptions {
+        files,
+        files_from,
+        stdin,
+        null,
+        tree,
+        by_dir,
+        format,
+        exclude_export_ignore,
+        exclude,
+        top,
+        sort,
+        baseline,
+        update_baseline,
+        owner,
+        semantics,
+        merge_nested,
+        path_style,
+    } = options;
+
     let cwd = env::current_dir().expect("Failed to get current directory");
 
-    let codeowners_path = match find_codeowners(&cwd) {
-        Some(p) => p,
+    let all_codeowners = find_all_codeowners(&cwd);
+    let codeowners_path = match all_codeowners.first() {
+        Some(p) => p.clone(),
         None => {
             eprintln!("No CODEOWNERS file found");
-            return ExitCode::from(1);
+            return Exit::Error.into();
         }
     };
+    for ignored in &all_codeowners[1..] {
+        eprintln!(
+            "{} ignoring {} ({} takes precedence)",
+            "warning:".yellow().bold(),
+            ignored.display(),
+            codeowners_path.display()
+        );
+    }
 
     let content = match fs::read_to_string(&codeowners_path) {
         Ok(c) => c,
         Err(e) => {
             eprintln!("Failed to read {}: {}", codeowners_path.display(), e);
-            return ExitCode::from(1);
+            return Exit::Error.into();
         }
     };
 
     let repo_root = get_repo_root(&codeowners_path, &cwd);
-    let file_cache = FileCache::new(&repo_root);
-    let lines = parser::parse_codeowners_file_with_positions(&content);
+    let pattern_options = load_settings_from_path(&repo_root).pattern_options();
+    let paths = OutputPaths::new(path_style, &cwd, &repo_root);
+    let shown_path = paths.display(&codeowners_path);
+    let file_cache = profile::time(profile::WALK, || {
+        FileCache::with_options(
+            &repo_root,
+            FileCacheOptions {
+                exclude_export_ignore,
+                exclude: exclude.clone(),
+            },
+        )
+        .with_pattern_options(pattern_options)
+    });
+
+    // Annotations point into the real CODEOWNERS, so they keep its content
+    let merged = if merge_nested {
+        match merge_nested_codeowners(&repo_root, &content, file_cache.all_files(), semantics) {
+            Ok(merged) => Some(merged),
+            Err(e) => {
+                eprintln!("{}", e);
+                return Exit::Error.into();
+            }
+        }
+    } else {
+        None
+    };
+    let rules_content = merged
+        .as_ref()
+        .map_or(content.as_str(), |m| m.content.as_str());
 
-    // Collect files to check (if specified)
-    let files_to_check = match collect_files(files, files_from, stdin) {
-        Ok(f) => f,
+    if let Some(owner) = owner {
+        let codeowners = profile::time(profile::PARSE, || {
+            CodeownersFile::parse_with_options(rules_content, pattern_options)
+        });
+        let Some((owned_files, mut rules)) = profile::time(profile::MATCH, || {
+            owner_coverage(&codeowners, &file_cache, &owner, semantics)
+        }) else {
+            eprintln!("{} is not listed on any rule in {}", owner, shown_path);
+            return Exit::Error.into();
+        };
+        // Report merged rules at their lines in the files they came from
+        if let Some(merged) = &merged {
+            for rule in &mut rules {
+                if let Some(origin) = merged.origin(rule.line - 1) {
+                    rule.line = origin.line + 1;
+                    rule.source = merged.source(origin).map(str::to_string);
+                }
+            }
+        }
+        let total = file_cache.all_files().len();
+        let count = |status| rules.iter().filter(|r| r.status == status).count();
+        let (live_rules, shadowed_rules, dead_rules) = (
+            count(RuleStatus::Live),
+            count(RuleStatus::Shadowed),
+            count(RuleStatus::Dead),
+        );
+        let report = OwnerCoverage {
+            codeowners: shown_path.clone(),
+            owner,
+            total,
+            owned: owned_files.len(),
+            owned_percent: if total > 0 {
+                (owned_files.len() as f64 * 1000.0 / total as f64).round() / 10.0
+            } else {
+                0.0
+            },
+            owned_files,
+            rules,
+            live_rules,
+            shadowed_rules,
+            dead_rules,
+        };
+
+        if format == OutputFormat::Json {
+            println!(
+                "{}",
+                serde_json::to_string(&CoverageOutput::Owner(&report))
+                    .expect("Failed to serialize JSON")
+            );
+        } else {
+            output_owner_human(&report, top);
+        }
+        // Dead rules fail the run like unowned files do
+        return if report.dead_rules == 0 {
+            ExitCode::SUCCESS
+        } else {
+            Exit::Findings.into()
+        };
+    }
+
+    let lines = profile::time(profile::PARSE, || {
+        parser::parse_codeowners_file_with_positions(rules_content)
+    });
+
+    // Collect files to check (if specified), normalized to match the cache's
+    // repo-relative paths (`./src/a.rs` and `src\a.rs` become `src/a.rs`).
+    // Excluded files are dropped here too, so they don't count as owned.
+    let excluded = matches_any(&exclude);
+    let files_to_check = match collect_files(files, files_from, stdin, null) {
+        Ok(f) => f.map(|files| {
+            files
+                .into_iter()
+                .map(|f| normalize_path(&paths.input(f)).into_owned())
+                .filter(|f| !excluded(f))
+                .collect::<HashSet<_>>()
+        }),
         Err(e) => {
             eprintln!("{}", e);
-            return ExitCode::from(1);
+            return Exit::Error.into();
         }
     };
 
     // Get unowned files
-    let all_unowned: Vec<&String> = file_cache.get_unowned_files(&lines);
+    let all_unowned: Vec<&String> =
+        profile::time(profile::MATCH, || file_cache.get_unowned_files(&lines));
 
     // Filter to only requested files if specified
     let (unowned, total_files, mode): (Vec<&str>, usize, &str) =
@@ -189,6 +806,36 @@ pub fn coverage(
             )
         };
 
+    let mut unowned = unowned;
+    sort_files(&mut unowned, sort, &repo_root);
+
+    if let (Some(path), true) = (&baseline, update_baseline) {
+        if let Err(e) = fs::write(path, format_baseline(&unowned)) {
+            eprintln!("Failed to write baseline {}: {}", path.display(), e);
+            return Exit::Error.into();
+        }
+        eprintln!(
+            "Wrote {} unowned files to {}",
+            unowned.len(),
+            path.display()
+        );
+        return ExitCode::SUCCESS;
+    }
+    // With a baseline only files missing from it fail the run
+    let new_unowned = match baseline.as_deref().map(read_baseline).transpose() {
+        Ok(known) => known.map(|known| new_unowned(&unowned, &known)),
+        Err(e) => {
+            eprintln!("{}", e);
+            return Exit::Error.into();
+        }
+    };
+    let failing = new_unowned.as_deref().unwrap_or(&unowned);
+    let exit_code = if failing.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        Exit::Findings.into()
+    };
+
     let owned_count = total_files.saturating_sub(unowned.len());
     let coverage_pct = if total_files > 0 {
         (owned_count as f64 / total_files as f64) * 100.0
@@ -196,24 +843,62 @@ pub fn coverage(
         100.0
     };
 
+    // Every file counted, in path order
+    let counted_files = || -> Vec<&str> {
+        match files_to_check {
+            Some(ref filter) => {
+                let mut files: Vec<&str> = filter.iter().map(String::as_str).collect();
+                files.sort_unstable();
+                files
+            }
+            None => file_cache.all_files().iter().map(String::as_str).collect(),
+        }
+    };
+    let unowned_set = || -> HashSet<&str> { unowned.iter().copied().collect() };
+
+    if format == OutputFormat::Tsv {
+        let written = write_tsv(&mut io::stdout().lock(), &counted_files(), &unowned_set());
+        return match written {
+            Ok(()) => exit_code,
+            // A closed pipe (`| head`) isn't an error
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => exit_code,
+            Err(e) => {
+                eprintln!("Failed to write results: {}", e);
+                Exit::Error.into()
+            }
+        };
+    }
+
+    let directories =
+        by_dir.map(|depth| coverage_by_directory(&counted_files(), &unowned_set(), depth));
+
+    if format == OutputFormat::Github {
+        let codeowners_file = codeowners_path
+            .strip_prefix(&repo_root)
+            .unwrap_or(&codeowners_path)
+            .display()
+            .to_string();
+        for annotation in github_annotations(&codeowners_file, &content, failing) {
+            println!("{}", annotation);
+        }
+        return exit_code;
+    }
+
     // JSON output
-    if json {
+    if format == OutputFormat::Json {
         let output = CoverageJson {
-            total: total_files,
-            owned: owned_count,
-            unowned: unowned.len(),
-            coverage_percent: (coverage_pct * 10.0).round() / 10.0, // 1 decimal place
-            unowned_files: unowned.iter().map(|s| s.to_string()).collect(),
+            directories,
+            new_unowned_files: new_unowned
+                .as_ref()
+                .map(|files| files.iter().map(|s| s.to_string()).collect()),
+            ..CoverageJson::new(shown_path.clone(), total_files, &unowned, mode)
         };
         println!(
             "{}",
-            serde_json::to_string(&output).expect("Failed to serialize JSON")
+            serde_json::to_string(&CoverageOutput::Files(&output))
+                .expect("Failed to serialize JSON")
         );
-        return if unowned.is_empty() {
-            ExitCode::SUCCESS
-        } else {
-            ExitCode::from(1)
-        };
+        return exit_code;
     }
 
     // Color the percentage based on coverage level
@@ -225,21 +910,40 @@ pub fn coverage(
         format!("{:.1}%", coverage_pct).red().bold()
     };
 
-    // Print unowned files first (if any)
-    if !unowned.is_empty() {
+    // Print the per-directory table or unowned files first (if any)
+    if let (Some(depth), Some(dirs)) = (by_dir, &directories) {
+        println!();
+        println!(
+            "  {} {}",
+            "Coverage by directory".bold(),
+            format!("(depth {})", depth).dimmed()
+        );
+        println!();
+        let (shown, hidden) = top_n(dirs, top);
+        for line in render_directory_table(shown) {
+            println!("{}", line);
+        }
+        if let Some(note) = hidden_note(hidden) {
+            println!("{}", note);
+        }
+    } else if !unowned.is_empty() {
         println!();
         println!("  {}:", "Unowned files".yellow().bold());
         println!();
 
+        let (shown, hidden) = top_n(&unowned, top);
         if tree {
-            for line in render_tree(&unowned) {
+            for line in render_tree(shown) {
                 println!("{}", line);
             }
         } else {
-            for file in &unowned {
+            for file in shown {
                 println!("    {} {}", "•".red(), file);
             }
         }
+        if let Some(note) = hidden_note(hidden) {
+            println!("{}", note);
+        }
     }
 
     // Print summary at the end
@@ -249,6 +953,7 @@ pub fn coverage(
         "CODEOWNERS Coverage".bold(),
         format!("({} files)", mode).dimmed()
     );
+    println!("  {}", shown_path.dimmed());
     println!();
 
     // Print progress bar
@@ -275,19 +980,307 @@ pub fn coverage(
     if unowned.is_empty() {
         println!();
         println!("  {} 🎉", "All files have owners!".green().bold());
+    } else if let Some(ref new_unowned) = new_unowned {
+        println!();
+        if new_unowned.is_empty() {
+            println!(
+                "  {}",
+                "No new unowned files (all are in the baseline)".green()
+            );
+        } else {
+            println!(
+                "  {} {}:",
+                new_unowned.len().to_string().red().bold(),
+                "new unowned files not in the baseline".red().bold()
+            );
+            for file in new_unowned {
+                println!("    {} {}", "+".red(), file);
+            }
+        }
     }
     println!();
 
-    if unowned.is_empty() {
-        ExitCode::SUCCESS
-    } else {
-        ExitCode::from(1)
-    }
+    exit_code
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::commands::schema::validate;
+    use crate::pattern::PatternOptions;
+
+    #[test]
+    fn test_json_output_matches_schema() {
+        for (total, unowned) in [(3, vec![]), (2, vec!["Makefile"])] {
+            let output =
+                CoverageJson::new(".github/CODEOWNERS".to_string(), total, &unowned, "total");
+            validate(&json_schema(), &serde_json::to_value(&output).unwrap()).unwrap();
+        }
+
+        let output = CoverageJson {
+            directories: Some(coverage_by_directory(
+                &["Makefile", "src/main.rs"],
+                &HashSet::from(["Makefile"]),
+                1,
+            )),
+            new_unowned_files: Some(vec![]),
+            ..CoverageJson::new("CODEOWNERS".to_string(), 2, &["Makefile"], "checked")
+        };
+        let mut json = serde_json::to_value(&output).unwrap();
+        validate(&json_schema(), &json).unwrap();
+
+        json["mode"] = "partial".into();
+        assert!(validate(&json_schema(), &json).is_err());
+    }
+
+    #[test]
+    fn test_json_counts_are_consistent() {
+        let unowned: Vec<String> = (0..120).map(|i| format!("gen/file{i}.rs")).collect();
+        let unowned: Vec<&str> = unowned.iter().map(String::as_str).collect();
+        let json = serde_json::to_value(CoverageJson::new(
+            "CODEOWNERS".to_string(),
+            160,
+            &unowned,
+            "total",
+        ))
+        .unwrap();
+
+        assert_eq!(json["total"], 160);
+        assert_eq!(json["owned"], 40);
+        assert_eq!(json["unowned"], 120);
+        assert_eq!(json["coverage_percent"], 25.0);
+        assert_eq!(json["mode"], "total");
+        // Every unowned file, not just the first screenful
+        assert_eq!(json["unowned_files"].as_array().unwrap().len(), 120);
+        assert_eq!(json["unowned_files"][119], "gen/file119.rs");
+
+        // Nothing to check counts as fully covered
+        let empty = CoverageJson::new("CODEOWNERS".to_string(), 0, &[], "checked");
+        assert_eq!((empty.owned, empty.unowned), (0, 0));
+        assert_eq!(empty.coverage_percent, 100.0);
+    }
+
+    /// @web has a live rule, a rule shadowed by @security and two dead ones
+    const OWNER_CONTENT: &str = "* @core\n\
+        /web/ @web @core\n\
+        /web/auth/ @web\n\
+        /legacy/ @web\n\
+        *.swf @web\n\
+        /web/auth/ @security\n";
+
+    fn owner_cache() -> FileCache {
+        FileCache::from_files(
+            [
+                "README.md",
+                "web/index.ts",
+                "web/app.ts",
+                "web/auth/login.ts",
+            ]
+            .iter()
+            .map(|f| f.to_string())
+            .collect(),
+        )
+    }
+
+    #[test]
+    fn test_owner_coverage_live_and_dead_rules() {
+        let codeowners = CodeownersFile::parse(OWNER_CONTENT);
+        let (owned, rules) =
+            owner_coverage(&codeowners, &owner_cache(), "@web", Semantics::GitHub).unwrap();
+
+        assert_eq!(owned, ["web/index.ts", "web/app.ts"]);
+        let summary: Vec<_> = rules
+            .iter()
+            .map(|r| (r.line, r.pattern.as_str(), r.matched, r.won, r.status))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (2, "/web/", 3, 2, RuleStatus::Live),
+                (3, "/web/auth/", 1, 0, RuleStatus::Shadowed),
+                (4, "/legacy/", 0, 0, RuleStatus::Dead),
+                (5, "*.swf", 0, 0, RuleStatus::Dead),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_owner_coverage_with_brace_expansion() {
+        let options = PatternOptions {
+            brace_expansion: true,
+        };
+        let codeowners =
+            CodeownersFile::parse_with_options("* @core\n/web/*.{ts,tsx} @web\n", options);
+        let cache = owner_cache().with_pattern_options(options);
+
+        let (owned, rules) =
+            owner_coverage(&codeowners, &cache, "@web", Semantics::GitHub).unwrap();
+        assert_eq!(owned, ["web/index.ts", "web/app.ts"]);
+        assert_eq!((rules[0].matched, rules[0].won), (2, 2));
+    }
+
+    #[test]
+    fn test_owner_coverage_shared_rule_and_unknown_owner() {
+        let codeowners = CodeownersFile::parse(OWNER_CONTENT);
+        let cache = owner_cache();
+
+        // @core wins README via `*` and shares /web/ with @web
+        let (owned, rules) =
+            owner_coverage(&codeowners, &cache, "@core", Semantics::GitHub).unwrap();
+        assert_eq!(owned, ["README.md", "web/index.ts", "web/app.ts"]);
+        assert_eq!(
+            rules.iter().map(|r| r.won).collect::<Vec<_>>(),
+            [1, 2],
+            "{:?}",
+            rules
+        );
+        assert!(rules.iter().all(|r| r.status == RuleStatus::Live));
+
+        assert!(owner_coverage(&codeowners, &cache, "@nobody", Semantics::GitHub).is_none());
+        // Owners are compared ignoring case, as GitHub does
+        let (owned, _) = owner_coverage(&codeowners, &cache, "@WEB", Semantics::GitHub).unwrap();
+        assert_eq!(owned, ["web/index.ts", "web/app.ts"]);
+    }
+
+    #[test]
+    fn test_owner_coverage_gitlab_sections() {
+        let codeowners = CodeownersFile::parse(
+            "* @core\n/web/ @web\n[Security] @security\n/web/auth/\n/legacy/ @web\n",
+        );
+        let cache = owner_cache();
+
+        // @security is only the section default, so its rules aren't listed
+        assert!(owner_coverage(&codeowners, &cache, "@security", Semantics::GitLab).is_none());
+        // Under GitLab the Security section doesn't take web/auth from @web
+        let (owned, rules) =
+            owner_coverage(&codeowners, &cache, "@web", Semantics::GitLab).unwrap();
+        assert_eq!(owned, ["web/index.ts", "web/app.ts", "web/auth/login.ts"]);
+        assert_eq!(
+            rules
+                .iter()
+                .map(|r| (r.line, r.won, r.status))
+                .collect::<Vec<_>>(),
+            [(2, 3, RuleStatus::Live), (5, 0, RuleStatus::Dead)]
+        );
+    }
+
+    #[test]
+    fn test_owner_json_matches_schema() {
+        let codeowners = CodeownersFile::parse(OWNER_CONTENT);
+        let (owned_files, rules) =
+            owner_coverage(&codeowners, &owner_cache(), "@web", Semantics::GitHub).unwrap();
+        let report = OwnerCoverage {
+            codeowners: "CODEOWNERS".to_string(),
+            owner: "@web".to_string(),
+            total: 4,
+            owned: owned_files.len(),
+            owned_percent: 50.0,
+            owned_files,
+            rules,
+            live_rules: 1,
+            shadowed_rules: 1,
+            dead_rules: 2,
+        };
+        let json = serde_json::to_value(CoverageOutput::Owner(&report)).unwrap();
+        validate(&json_schema(), &json).unwrap();
+
+        // An owner report isn't whole-file coverage, or vice versa
+        let mut mixed = json.clone();
+        mixed["mode"] = "total".into();
+        assert!(validate(&json_schema(), &mixed).is_err());
+    }
+
+    /// Uneven coverage: docs fully owned, src partly, scripts not at all
+    const FILES: [&str; 9] = [
+        "Makefile",
+        "README.md",
+        "docs/guide.md",
+        "docs/api/index.md",
+        "scripts/release.sh",
+        "src/main.rs",
+        "src/lib.rs",
+        "src/cli/args.rs",
+        "src/cli/run.rs",
+    ];
+    const UNOWNED: [&str; 4] = [
+        "Makefile",
+        "scripts/release.sh",
+        "src/cli/args.rs",
+        "src/cli/run.rs",
+    ];
+
+    fn summary(dirs: &[DirectoryCoverage]) -> Vec<(&str, usize, usize)> {
+        dirs.iter()
+            .map(|d| (d.directory.as_str(), d.owned, d.total))
+            .collect()
+    }
+
+    #[test]
+    fn test_coverage_by_directory_worst_first() {
+        let dirs = coverage_by_directory(&FILES, &HashSet::from(UNOWNED), 1);
+        assert_eq!(
+            summary(&dirs),
+            vec![
+                ("scripts", 0, 1),
+                // Tied at 50%; more unowned files first
+                ("src", 2, 4),
+                ("/", 1, 2),
+                ("docs", 2, 2),
+            ]
+        );
+        assert_eq!(dirs[2].coverage_percent, 50.0);
+        assert_eq!(dirs[2].unowned, 1);
+    }
+
+    #[test]
+    fn test_coverage_by_directory_depth_two() {
+        let dirs = coverage_by_directory(&FILES, &HashSet::from(UNOWNED), 2);
+        assert_eq!(
+            summary(&dirs),
+            vec![
+                ("src/cli", 0, 2),
+                ("scripts", 0, 1),
+                ("/", 1, 2),
+                ("docs", 1, 1),
+                ("docs/api", 1, 1),
+                ("src", 2, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_coverage_by_directory_rounds_percentages() {
+        let dirs = coverage_by_directory(&["a/1", "a/2", "a/3"], &HashSet::from(["a/3"]), 1);
+        assert_eq!(dirs[0].coverage_percent, 66.7);
+    }
+
+    #[test]
+    fn test_tsv_rows() {
+        let mut out = Vec::new();
+        let unowned = HashSet::from(["docs/new\tdraft.md"]);
+        write_tsv(&mut out, &["docs/new\tdraft.md", "src/main.rs"], &unowned).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "docs/new\\tdraft.md\tunowned\nsrc/main.rs\towned\n"
+        );
+    }
+
+    #[test]
+    fn test_github_annotations() {
+        let annotations = github_annotations(
+            "CODEOWNERS",
+            "*.rs @rust\n/docs/ not-an-owner\n",
+            &["docs/a:b.md", "Makefile"],
+        );
+        assert_eq!(
+            annotations,
+            vec![
+                "::warning file=docs/a%3Ab.md,title=unowned-file::File has no CODEOWNERS owners",
+                "::warning file=Makefile,title=unowned-file::File has no CODEOWNERS owners",
+                "::error file=CODEOWNERS,line=2,col=8,title=invalid-owner::Invalid owner format 'not-an-owner': missing leading '@'. Expected @user, @org/team, or email@domain.com",
+            ]
+        );
+    }
 
     #[test]
     fn test_tree_node_count() {
@@ -328,4 +1321,44 @@ mod tests {
         assert!(lines.iter().any(|l| l.contains("symbols.rs")));
         assert!(lines.iter().any(|l| l.contains("settings.toml")));
     }
+
+    #[test]
+    fn test_baseline_subset_passes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("coverage-baseline.txt");
+        fs::write(&path, format_baseline(&["scripts/release.sh", "Makefile"])).unwrap();
+
+        let baseline = read_baseline(&path).unwrap();
+        assert_eq!(baseline.len(), 2, "comments aren't entries");
+        assert!(new_unowned(&["Makefile", "scripts/release.sh"], &baseline).is_empty());
+        // Files that gained an owner since are fine too
+        assert!(new_unowned(&["Makefile"], &baseline).is_empty());
+    }
+
+    #[test]
+    fn test_baseline_reports_new_unowned_files() {
+        let baseline = HashSet::from(["Makefile".to_string()]);
+        assert_eq!(
+            new_unowned(&["LICENSE", "Makefile", "src/new.rs"], &baseline),
+            vec!["LICENSE", "src/new.rs"]
+        );
+    }
+
+    #[test]
+    fn test_baseline_file_format() {
+        let content = format_baseline(&["src/b.rs", "Makefile", "src/a.rs"]);
+        let entries: Vec<&str> = content.lines().filter(|l| !l.starts_with('#')).collect();
+        assert_eq!(entries, ["Makefile", "src/a.rs", "src/b.rs"]);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("baseline.txt");
+        fs::write(&path, "# accepted\n\n./src/a.rs\n  Makefile  \n").unwrap();
+        assert_eq!(
+            read_baseline(&path).unwrap(),
+            HashSet::from(["src/a.rs".to_string(), "Makefile".to_string()])
+        );
+        assert!(read_baseline(&dir.path().join("missing.txt"))
+            .unwrap_err()
+            .contains("missing.txt"));
+    }
 }
@@ -0,0 +1,151 @@
+//! Exit codes, the same for every command so scripts can branch on the
+//! result without parsing output:
+//!
+//! | Code | Meaning                                                              |
+//! |------|----------------------------------------------------------------------|
+//! | 0    | Success: everything checked is owned and valid                       |
+//! | 1    | Findings: unowned files, failing lint checks, dead rules, ...        |
+//! | 2    | Usage or I/O error: no CODEOWNERS, unreadable input, bad flags       |
+//! | 3    | Invalid owners found (validate-owners, lint with GitHub checks, gha) |
+//!
+//! Owners GitHub couldn't check only exit 3 with `validate-owners
+//! --fail-on-unknown`. Clap already exits 2 for bad arguments. With
+//! `--quiet` stdout is silenced, leaving only the exit code (and errors on
+//! stderr).
+
+use std::process::ExitCode;
+
+/// Help text listing the exit codes
+pub const HELP: &str = "\
+Exit codes:
+  0  Success: everything checked is owned and valid
+  1  Findings: unowned files, failing lint checks, dead rules, ...
+  2  Usage or I/O error: no CODEOWNERS, unreadable input, bad flags
+  3  Invalid owners found (validate-owners, lint with GitHub checks, gha)";
+
+/// How a command finished
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exit {
+    Success = 0,
+    Findings = 1,
+    Error = 2,
+    InvalidOwners = 3,
+}
+
+impl From<Exit> for ExitCode {
+    fn from(exit: Exit) -> Self {
+        ExitCode::from(exit as u8)
+    }
+}
+
+/// Send everything written to stdout to `/dev/null`, for `--quiet`
+#[cfg(unix)]
+pub fn silence_stdout() {
+    use std::os::fd::AsRawFd;
+
+    let Ok(null) = std::fs::OpenOptions::new().write(true).open("/dev/null") else {
+        return;
+    };
+    // SAFETY: both descriptors are open for the call; dup2 replaces stdout's
+    // descriptor in place, and nothing has been written to stdout yet
+    unsafe {
+        libc::dup2(null.as_raw_fd(), libc::STDOUT_FILENO);
+    }
+}
+
+/// Stdout can't be swapped out in place here, so `--quiet` only warns
+#[cfg(not(unix))]
+pub fn silence_stdout() {
+    eprintln!("warning: --quiet is not supported on this platform; output is not silenced");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::commands::{check, fmt, lint, schema, CheckOptions, LintOptions};
+    use crate::parser::format_codeowners;
+
+    #[test]
+    fn test_exit_code_values() {
+        assert_eq!(ExitCode::from(Exit::Success), ExitCode::SUCCESS);
+        assert_eq!(ExitCode::from(Exit::Findings), ExitCode::from(1));
+        assert_eq!(ExitCode::from(Exit::Error), ExitCode::from(2));
+        assert_eq!(ExitCode::from(Exit::InvalidOwners), ExitCode::from(3));
+    }
+
+    fn check_file(codeowners: PathBuf, files: &[&str]) -> ExitCode {
+        check(CheckOptions {
+            codeowners: Some(codeowners),
+            files: Some(files.iter().map(|f| f.to_string()).collect()),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_check_exit_codes() {
+        let dir = tempfile::tempdir().unwrap();
+        let codeowners = dir.path().join("CODEOWNERS");
+        fs::write(&codeowners, "*.rs @rust\n").unwrap();
+
+        assert_eq!(
+            check_file(codeowners.clone(), &["src/main.rs"]),
+            Exit::Success.into()
+        );
+        assert_eq!(
+            check_file(codeowners.clone(), &["README.md"]),
+            Exit::Findings.into()
+        );
+        // Any unowned file is a finding, however many files are checked
+        assert_eq!(
+            check_file(codeowners.clone(), &["src/main.rs", "README.md"]),
+            Exit::Findings.into()
+        );
+        assert_eq!(
+            check_file(codeowners.clone(), &["src/main.rs", "src/lib.rs"]),
+            Exit::Success.into()
+        );
+        assert_eq!(
+            check_file(dir.path().join("missing"), &["src/main.rs"]),
+            Exit::Error.into()
+        );
+        // No files to check is a usage error
+        assert_eq!(check_file(codeowners, &[]), Exit::Error.into());
+    }
+
+    #[test]
+    fn test_fmt_exit_codes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("CODEOWNERS");
+        let messy = "*.rs    @rust\n\n\n/docs/ @docs\n";
+        assert_ne!(format_codeowners(messy), messy);
+
+        fs::write(&path, messy).unwrap();
+        assert_eq!(fmt(Some(path.clone()), false), Exit::Findings.into());
+        assert_eq!(fmt(Some(path.clone()), true), Exit::Success.into());
+        assert_eq!(fmt(Some(path), false), Exit::Success.into());
+        assert_eq!(
+            fmt(Some(dir.path().join("missing")), false),
+            Exit::Error.into()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lint_unreadable_codeowners_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = LintOptions {
+            path: Some(dir.path().join("missing")),
+            ..Default::default()
+        };
+        let code = lint(options, Default::default()).await;
+        assert_eq!(code, Exit::Error.into());
+    }
+
+    #[test]
+    fn test_schema_exit_codes() {
+        assert_eq!(schema("check"), Exit::Success.into());
+        assert_eq!(schema("nope"), Exit::Error.into());
+    }
+}
@@ -0,0 +1,444 @@
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::{env, fs};
+
+use colored::Colorize;
+use serde::Serialize;
+
+use super::exit::Exit;
+use super::profile;
+use crate::file_cache::FileCache;
+use crate::ownership::{find_all_codeowners, get_repo_root, CodeownersFile};
+use crate::parser::{CodeownersLine, ParsedLine};
+use crate::settings::load_settings_from_path;
+
+/// Output format for the export command
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Nested directory tree
+    #[default]
+    Json,
+    /// One row per file: path, owners, rule, line
+    Csv,
+    /// Each owner with the patterns it's listed on (no file scan)
+    Owners,
+}
+
+/// Options for the export command
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    /// Output format
+    pub format: OutputFormat,
+    /// Omit the contents of directories whose files all share the same owners
+    pub collapse: bool,
+    /// Only expand directories this many levels below the root
+    pub max_depth: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct DirJson {
+    name: String,
+    path: String,
+    file_count: usize,
+    /// Owners shared by every file below this directory, if they all agree
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owners: Option<Vec<String>>,
+    /// Contents omitted by `--collapse` or `--max-depth`
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    collapsed: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    directories: Vec<DirJson>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    files: Vec<FileJson>,
+}
+
+#[derive(Serialize)]
+struct FileJson {
+    name: String,
+    path: String,
+    owners: Vec<String>,
+}
+
+/// Directory tree of files and their winning owners
+#[derive(Default)]
+struct DirNode {
+    dirs: BTreeMap<String, DirNode>,
+    files: BTreeMap<String, Vec<String>>,
+}
+
+impl DirNode {
+    fn insert(&mut self, path: &str, owners: Vec<String>) {
+        match path.split_once('/') {
+            Some((dir, rest)) => self
+                .dirs
+                .entry(dir.to_string())
+                .or_default()
+                .insert(rest, owners),
+            None => {
+                self.files.insert(path.to_string(), owners);
+            }
+        }
+    }
+
+    fn file_count(&self) -> usize {
+        self.files.len() + self.dirs.values().map(DirNode::file_count).sum::<usize>()
+    }
+
+    /// Owners shared by every file below this node, or `None` if they differ
+    /// (or there are no files)
+    fn shared_owners(&self) -> Option<&Vec<String>> {
+        let mut owners = self
+            .files
+            .values()
+            .map(Some)
+            .chain(self.dirs.values().map(DirNode::shared_owners));
+        let first = owners.next()??;
+        owners.all(|o| o == Some(first)).then_some(first)
+    }
+
+    fn to_json(&self, name: &str, path: &str, depth: usize, options: &ExportOptions) -> DirJson {
+        let owners = self.shared_owners().cloned();
+        let collapsed = (options.collapse && owners.is_some())
+            || options.max_depth.is_some_and(|max| depth >= max);
+
+        let (directories, files) = if collapsed {
+            (vec![], vec![])
+        } else {
+            let directories = self
+                .dirs
+                .iter()
+                .map(|(child, node)| node.to_json(child, &join(path, child), depth + 1, options))
+                .collect();
+            let files = self
+                .files
+                .iter()
+                .map(|(child, owners)| FileJson {
+                    name: child.clone(),
+                    path: join(path, child),
+                    owners: owners.clone(),
+                })
+                .collect();
+            (directories, files)
+        };
+
+        DirJson {
+            name: name.to_string(),
+            path: path.to_string(),
+            file_count: self.file_count(),
+            owners,
+            collapsed,
+            directories,
+            files,
+        }
+    }
+}
+
+fn join(dir: &str, name: &str) -> String {
+    if dir.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", dir, name)
+    }
+}
+
+/// Build the JSON ownership tree for `files`
+fn ownership_tree(
+    codeowners: &CodeownersFile,
+    files: &[String],
+    options: &ExportOptions,
+) -> DirJson {
+    let mut root = DirNode::default();
+    for file in files {
+        let owners = codeowners
+            .owners_for(file)
+            .map(|r| r.owners)
+            .unwrap_or_default();
+        root.insert(file, owners);
+    }
+    root.to_json("", "", 0, options)
+}
+
+/// Invert rules to owner -> patterns, most patterns first (ties by name).
+/// A multi-owner rule's pattern is listed under each of its owners; a
+/// pattern repeated for the same owner is listed once.
+fn patterns_by_owner(lines: &[ParsedLine]) -> Vec<(&str, Vec<&str>)> {
+    let mut by_owner: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for line in lines {
+        if let CodeownersLine::Rule { pattern, owners } = &line.content {
+            for owner in owners {
+                let patterns = by_owner.entry(owner).or_default();
+                if !patterns.contains(&pattern.as_str()) {
+                    patterns.push(pattern);
+                }
+            }
+        }
+    }
+    let mut grouped: Vec<_> = by_owner.into_iter().collect();
+    // Stable sort keeps owners with equal counts in name order
+    grouped.sort_by_key(|(_, patterns)| std::cmp::Reverse(patterns.len()));
+    grouped
+}
+
+/// Write each owner and the patterns it owns, one owner per line
+fn write_owners<W: Write>(out: &mut W, lines: &[ParsedLine]) -> io::Result<()> {
+    for (owner, patterns) in patterns_by_owner(lines) {
+        writeln!(
+            out,
+            "{} owns {}: {}",
+            owner.cyan(),
+            format!(
+                "{} pattern{}",
+                patterns.len(),
+                if patterns.len() == 1 { "" } else { "s" }
+            )
+            .dimmed(),
+            patterns.join(", ")
+        )?;
+    }
+    Ok(())
+}
+
+/// Write one CSV row per file with its winning rule
+fn write_csv<W: Write>(
+    out: &mut W,
+    codeowners: &CodeownersFile,
+    files: &[String],
+) -> io::Result<()> {
+    let mut csv = csv::WriterBuilder::new()
+        .terminator(csv::Terminator::Any(b'\n'))
+        .from_writer(out);
+    csv.write_record(["file", "owners", "rule", "line"])?;
+    for file in files {
+        match codeowners.owners_for(file) {
+            Some(r) => csv.write_record([
+                file.as_str(),
+                &r.owners.join(" "),
+                &r.pattern,
+                &(r.line_number + 1).to_string(),
+            ])?,
+            None => csv.write_record([file.as_str(), "", "", ""])?,
+        }
+    }
+    csv.flush()
+}
+
+pub fn export(options: ExportOptions) -> ExitCode {
+    let cwd = env::current_dir().expect("Failed to get current directory");
+
+    let all_codeowners = find_all_codeowners(&cwd);
+    let codeowners_path = match all_codeowners.first() {
+        Some(p) => p.clone(),
+        None => {
+            eprintln!("No CODEOWNERS file found");
+            return Exit::Error.into();
+        }
+    };
+    for ignored in &all_codeowners[1..] {
+        eprintln!(
+            "{} ignoring {} ({} takes precedence)",
+            "warning:".yellow().bold(),
+            ignored.display(),
+            codeowners_path.display()
+        );
+    }
+
+    let content = match fs::read_to_string(&codeowners_path) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", codeowners_path.display(), e);
+            return Exit::Error.into();
+        }
+    };
+
+    let repo_root = get_repo_root(&codeowners_path, &cwd);
+    let pattern_options = load_settings_from_path(&repo_root).pattern_options();
+    let codeowners = profile::time(profile::PARSE, || {
+        CodeownersFile::parse_with_options(&content, pattern_options)
+    });
+    let result = match options.format {
+        // Only the rules are needed, so the repository isn't scanned
+        OutputFormat::Owners => write_owners(&mut io::stdout().lock(), codeowners.lines()),
+        OutputFormat::Json => {
+            let files = sorted_files(&repo_root);
+            let tree = profile::time(profile::MATCH, || {
+                ownership_tree(&codeowners, &files, &options)
+            });
+            writeln!(
+                io::stdout(),
+                "{}",
+                serde_json::to_string(&tree).expect("Failed to serialize JSON")
+            )
+        }
+        OutputFormat::Csv => write_csv(
+            &mut io::stdout().lock(),
+            &codeowners,
+            &sorted_files(&repo_root),
+        ),
+    };
+    finish(result)
+}
+
+/// Every file in the repository, sorted
+fn sorted_files(repo_root: &PathBuf) -> Vec<String> {
+    let file_cache = profile::time(profile::WALK, || FileCache::new(repo_root));
+    let mut files = file_cache.all_files().to_vec();
+    files.sort();
+    files
+}
+
+fn finish(result: io::Result<()>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        // Downstream closed the pipe (e.g. `| head`)
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Failed to write export: {}", e);
+            Exit::Error.into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const CONTENT: &str = "* @core\n/src/ @backend\n/src/web/ @frontend\n/vendor/\n";
+
+    fn fixture() -> Vec<String> {
+        [
+            "README.md",
+            "src/lib.rs",
+            "src/db/pool.rs",
+            "src/web/app.ts",
+            "src/web/ui/button.ts",
+            "vendor/dep.c",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+    }
+
+    fn tree(options: ExportOptions) -> serde_json::Value {
+        let codeowners = CodeownersFile::parse(CONTENT);
+        serde_json::to_value(ownership_tree(&codeowners, &fixture(), &options)).unwrap()
+    }
+
+    #[test]
+    fn test_tree_structure() {
+        let root = tree(ExportOptions::default());
+
+        assert_eq!(root["file_count"], 6);
+        assert!(root.get("owners").is_none(), "root has mixed owners");
+        assert_eq!(
+            root["files"],
+            json!([{ "name": "README.md", "path": "README.md", "owners": ["@core"] }])
+        );
+
+        let src = &root["directories"][0];
+        assert_eq!(src["path"], "src");
+        assert_eq!(src["file_count"], 4);
+        assert_eq!(src["directories"][0]["path"], "src/db");
+        assert_eq!(src["directories"][1]["path"], "src/web");
+        assert_eq!(
+            src["directories"][1]["directories"][0]["files"][0],
+            json!({ "name": "button.ts", "path": "src/web/ui/button.ts", "owners": ["@frontend"] })
+        );
+
+        // Explicitly unowned files share an empty owner list
+        let vendor = &root["directories"][1];
+        assert_eq!(vendor["owners"], json!([]));
+    }
+
+    #[test]
+    fn test_directory_level_owners() {
+        let root = tree(ExportOptions::default());
+        let src = &root["directories"][0];
+        assert!(src.get("owners").is_none(), "src/web overrides src/");
+        assert_eq!(src["directories"][0]["owners"], json!(["@backend"]));
+        assert_eq!(src["directories"][1]["owners"], json!(["@frontend"]));
+        assert!(src["directories"][1].get("collapsed").is_none());
+    }
+
+    #[test]
+    fn test_collapse_uniform_directories() {
+        let root = tree(ExportOptions {
+            collapse: true,
+            ..Default::default()
+        });
+        let src = &root["directories"][0];
+        assert!(
+            src.get("collapsed").is_none(),
+            "mixed directories stay open"
+        );
+
+        let web = &src["directories"][1];
+        assert_eq!(web["collapsed"], true);
+        assert_eq!(web["owners"], json!(["@frontend"]));
+        assert_eq!(web["file_count"], 2);
+        assert!(web.get("directories").is_none());
+        assert!(web.get("files").is_none());
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let root = tree(ExportOptions {
+            max_depth: Some(1),
+            ..Default::default()
+        });
+        let src = &root["directories"][0];
+        assert_eq!(src["collapsed"], true);
+        assert_eq!(src["file_count"], 4);
+        assert!(src.get("directories").is_none());
+        assert_eq!(root["files"][0]["path"], "README.md");
+    }
+
+    #[test]
+    fn test_patterns_by_owner_inverts_rules() {
+        let codeowners = CodeownersFile::parse(
+            "# Platform\n\
+             /infra/ @org/platform\n\
+             /ci/ @org/platform @alice\n\
+             *.tf @org/platform\n\
+             /docs/ @alice\n\
+             /ci/ @org/platform\n\
+             /vendor/\n\
+             *.md @bob\n",
+        );
+        assert_eq!(
+            patterns_by_owner(codeowners.lines()),
+            vec![
+                ("@org/platform", vec!["/infra/", "/ci/", "*.tf"]),
+                ("@alice", vec!["/ci/", "/docs/"]),
+                ("@bob", vec!["*.md"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_write_owners() {
+        let codeowners = CodeownersFile::parse("/infra/ @ops\n*.tf @ops @dev\n");
+        let mut out = Vec::new();
+        write_owners(&mut out, codeowners.lines()).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("@ops") && lines[0].contains("2 patterns"));
+        assert!(lines[0].ends_with(": /infra/, *.tf"));
+        assert!(lines[1].contains("@dev") && lines[1].contains("1 pattern"));
+        assert!(lines[1].ends_with(": *.tf"));
+    }
+
+    #[test]
+    fn test_csv_rows() {
+        let codeowners = CodeownersFile::parse(CONTENT);
+        let mut out = Vec::new();
+        write_csv(&mut out, &codeowners, &fixture()[..2]).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "file,owners,rule,line\nREADME.md,@core,*,1\nsrc/lib.rs,@backend,/src/,2\n"
+        );
+    }
+}
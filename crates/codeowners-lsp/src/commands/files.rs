@@ -1,19 +1,166 @@
-//! File collection utilities for CLI commands that accept file lists.
+//! File collection utilities for CLI commands that accept file lists,
+//! reading a CODEOWNERS file given on the command line (or via stdin), and
+//! rewriting paths for output (`--path-style`).
 
 use std::collections::HashSet;
-use std::fs;
-use std::io::{self, BufRead};
-use std::path::PathBuf;
+use std::io::{self, BufRead, Read};
+use std::path::{Component, Path, PathBuf};
+use std::{fmt, fs};
 
-/// Collect files from various sources (--files, --files-from, --stdin)
+use colored::Colorize;
+
+use crate::pattern::normalize_path;
+
+/// CODEOWNERS path argument meaning "read the content from stdin"
+const STDIN_ARG: &str = "-";
+
+/// Shown in place of a path when CODEOWNERS came from stdin
+const STDIN_LABEL: &str = "<stdin>";
+
+/// Why a CODEOWNERS file or file list given on the command line couldn't
+/// be read
+#[derive(Debug)]
+pub enum FileCollectError {
+    /// The CODEOWNERS path doesn't exist
+    CodeownersNotFound { path: PathBuf, source: io::Error },
+    /// The CODEOWNERS path exists but couldn't be read (permissions, a
+    /// directory, invalid UTF-8, ...)
+    CodeownersUnreadable { path: PathBuf, source: io::Error },
+    /// CODEOWNERS content couldn't be read from stdin
+    CodeownersStdin(io::Error),
+    /// The `--files-from` list doesn't exist
+    FileListNotFound { path: PathBuf, source: io::Error },
+    /// The `--files-from` list exists but couldn't be read, or has an
+    /// entry that isn't valid UTF-8
+    FileListUnreadable { path: PathBuf, source: io::Error },
+    /// The `--stdin` file list couldn't be read
+    FileListStdin(io::Error),
+}
+
+impl FileCollectError {
+    fn codeowners(path: &Path, source: io::Error) -> Self {
+        let path = path.to_path_buf();
+        if source.kind() == io::ErrorKind::NotFound {
+            Self::CodeownersNotFound { path, source }
+        } else {
+            Self::CodeownersUnreadable { path, source }
+        }
+    }
+
+    fn file_list(path: &Path, source: io::Error) -> Self {
+        let path = path.to_path_buf();
+        if source.kind() == io::ErrorKind::NotFound {
+            Self::FileListNotFound { path, source }
+        } else {
+            Self::FileListUnreadable { path, source }
+        }
+    }
+}
+
+impl fmt::Display for FileCollectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CodeownersNotFound { path, .. } => {
+                write!(f, "File not found: {}", path.display())
+            }
+            Self::CodeownersUnreadable { path, source } => {
+                write!(f, "Failed to read {}: {}", path.display(), source)
+            }
+            Self::CodeownersStdin(source) => {
+                write!(f, "Failed to read CODEOWNERS from stdin: {}", source)
+            }
+            Self::FileListNotFound { path, source } | Self::FileListUnreadable { path, source } => {
+                write!(f, "Failed to read '{}': {}", path.display(), source)
+            }
+            Self::FileListStdin(source) => {
+                write!(f, "Failed to read file list from stdin: {}", source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FileCollectError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::CodeownersNotFound { source, .. }
+            | Self::CodeownersUnreadable { source, .. }
+            | Self::CodeownersStdin(source)
+            | Self::FileListNotFound { source, .. }
+            | Self::FileListUnreadable { source, .. }
+            | Self::FileListStdin(source) => Some(source),
+        }
+    }
+}
+
+/// Whether a CODEOWNERS path argument is `-` (stdin)
+pub fn is_stdin_arg(path: &Path) -> bool {
+    path.as_os_str() == STDIN_ARG
+}
+
+/// Read the CODEOWNERS named on the command line, where `-` reads all of
+/// `stdin`. Returns the path to show in output (`<stdin>` for stdin) and the
+/// content.
+pub fn read_codeowners_arg<R: Read>(
+    path: &Path,
+    mut stdin: R,
+) -> Result<(PathBuf, String), FileCollectError> {
+    if is_stdin_arg(path) {
+        let mut content = String::new();
+        stdin
+            .read_to_string(&mut content)
+            .map_err(FileCollectError::CodeownersStdin)?;
+        return Ok((PathBuf::from(STDIN_LABEL), content));
+    }
+
+    let content = fs::read_to_string(path).map_err(|e| FileCollectError::codeowners(path, e))?;
+    Ok((path.to_path_buf(), content))
+}
+
+/// Paths listed in `reader`, one per line, or NUL-separated with `null`
+/// (`git diff -z`, `find -print0`). Lines are trimmed; NUL-separated
+/// entries are kept exactly, so paths may contain spaces or newlines.
+/// Empty entries are skipped.
+pub fn read_file_list<'a, R: BufRead + 'a>(
+    reader: R,
+    null: bool,
+) -> Box<dyn Iterator<Item = io::Result<String>> + 'a> {
+    let entries: Box<dyn Iterator<Item = io::Result<String>>> = if null {
+        Box::new(reader.split(b'\0').map(|entry| {
+            String::from_utf8(entry?).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        }))
+    } else {
+        Box::new(
+            reader
+                .lines()
+                .map(|line| line.map(|l| l.trim().to_string())),
+        )
+    };
+    Box::new(entries.filter(|entry| !matches!(entry, Ok(path) if path.is_empty())))
+}
+
+/// Collect files from various sources (--files, --files-from, --stdin),
+/// splitting lists on NUL instead of newlines with `null`.
 /// Returns Ok(None) if no filtering requested, Ok(Some(set)) if files specified,
-/// Err if --files-from path doesn't exist
+/// Err if --files-from or stdin can't be read in full
 pub fn collect_files(
     files: Option<Vec<String>>,
     files_from: Option<PathBuf>,
     stdin: bool,
-) -> Result<Option<HashSet<String>>, String> {
-    let mut result = HashSet::new();
+    null: bool,
+) -> Result<Option<HashSet<String>>, FileCollectError> {
+    collect_files_ordered(files, files_from, stdin, null)
+        .map(|f| f.map(|v| v.into_iter().collect()))
+}
+
+/// Like [`collect_files`], but keeps input order (--files, then --files-from,
+/// then stdin). Duplicates are dropped, keeping the first occurrence.
+pub fn collect_files_ordered(
+    files: Option<Vec<String>>,
+    files_from: Option<PathBuf>,
+    stdin: bool,
+    null: bool,
+) -> Result<Option<Vec<String>>, FileCollectError> {
+    let mut result = OrderedFiles::default();
 
     // From --files argument
     if let Some(f) = files {
@@ -24,31 +171,147 @@ pub fn collect_files(
 
     // From --files-from file
     if let Some(path) = files_from {
-        let content = fs::read_to_string(&path)
-            .map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
-        for line in content.lines() {
-            let line = line.trim();
-            if !line.is_empty() {
-                result.insert(line.to_string());
-            }
+        let content =
+            fs::read_to_string(&path).map_err(|e| FileCollectError::file_list(&path, e))?;
+        for file in read_file_list(content.as_bytes(), null) {
+            result.insert(file.map_err(|e| FileCollectError::file_list(&path, e))?);
         }
     }
 
     // From stdin
     if stdin {
         let stdin_handle = io::stdin();
-        for line in stdin_handle.lock().lines().map_while(Result::ok) {
-            let line = line.trim();
-            if !line.is_empty() {
-                result.insert(line.to_string());
-            }
+        for file in read_file_list(stdin_handle.lock(), null) {
+            result.insert(file.map_err(FileCollectError::FileListStdin)?);
         }
     }
 
-    if result.is_empty() {
+    if result.files.is_empty() {
         Ok(None) // No file filtering
     } else {
-        Ok(Some(result))
+        Ok(Some(result.files))
+    }
+}
+
+/// How paths given on the command line appear in output (`--path-style`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PathStyle {
+    /// Exactly as given: absolute, `./`-prefixed or repo-relative
+    #[default]
+    AsIs,
+    /// Relative to the repository root
+    Relative,
+}
+
+/// Rewrites paths for matching and printing according to a [`PathStyle`]
+#[derive(Debug, Clone, Default)]
+pub struct OutputPaths {
+    style: PathStyle,
+    cwd: PathBuf,
+    repo_root: PathBuf,
+}
+
+impl OutputPaths {
+    /// Paths given relative to `cwd`, for a repository at `repo_root`
+    pub fn new(style: PathStyle, cwd: &Path, repo_root: &Path) -> Self {
+        Self {
+            style,
+            cwd: cwd.to_path_buf(),
+            repo_root: repo_root.to_path_buf(),
+        }
+    }
+
+    /// An input path as it should be matched and printed. In relative mode
+    /// a path outside the repository is kept as given, with a note.
+    pub fn input(&self, path: String) -> String {
+        if self.style == PathStyle::AsIs {
+            return path;
+        }
+        match repo_relative(&path, &self.cwd, &self.repo_root) {
+            Some(relative) => relative,
+            None => {
+                eprintln!(
+                    "{} {} is outside the repository; reporting it as given",
+                    "Note:".yellow().bold(),
+                    path
+                );
+                path
+            }
+        }
+    }
+
+    /// A file on disk (such as CODEOWNERS itself) for display
+    pub fn display(&self, path: &Path) -> String {
+        let shown = path.display().to_string();
+        match self.style {
+            PathStyle::AsIs => shown,
+            PathStyle::Relative => {
+                repo_relative(&shown, &self.cwd, &self.repo_root).unwrap_or(shown)
+            }
+        }
+    }
+}
+
+/// Resolve `.` and `..` without touching the filesystem
+fn lexical_clean(path: &Path) -> PathBuf {
+    let mut clean = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                clean.pop();
+            }
+            other => clean.push(other),
+        }
+    }
+    clean
+}
+
+/// `path` (absolute, or relative to `cwd`) relative to `repo_root`, with
+/// `/` separators and a trailing `/` kept. `None` if it lies outside the
+/// repository. The root itself is `.`.
+pub fn repo_relative(path: &str, cwd: &Path, repo_root: &Path) -> Option<String> {
+    let normalized = normalize_path(path);
+    let full = lexical_clean(&cwd.join(normalized.as_ref()));
+    let root = lexical_clean(&cwd.join(repo_root));
+    // Symlinked prefixes (macOS /tmp is /private/tmp) differ only once
+    // canonicalized, which needs the path to exist
+    let relative = match full.strip_prefix(&root) {
+        Ok(relative) => relative.to_path_buf(),
+        Err(_) => {
+            let full = full.canonicalize().ok()?;
+            full.strip_prefix(root.canonicalize().ok()?)
+                .ok()?
+                .to_path_buf()
+        }
+    };
+
+    let mut relative = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    if relative.is_empty() {
+        relative.push('.');
+    }
+    if normalized.ends_with('/') && !relative.ends_with('/') {
+        relative.push('/');
+    }
+    Some(relative)
+}
+
+/// Insertion-ordered set of file paths
+#[derive(Default)]
+struct OrderedFiles {
+    seen: HashSet<String>,
+    files: Vec<String>,
+}
+
+impl OrderedFiles {
+    fn insert(&mut self, file: String) {
+        if self.seen.insert(file.clone()) {
+            self.files.push(file);
+        }
     }
 }
 
@@ -58,16 +321,72 @@ mod tests {
     use std::io::Write;
     use tempfile::NamedTempFile;
 
+    #[test]
+    fn test_repo_relative_absolute_and_relative_inputs() {
+        let root = Path::new("/work/repo");
+        let sub = Path::new("/work/repo/src");
+        for (path, cwd, expected) in [
+            ("/work/repo/src/main.rs", root, "src/main.rs"),
+            ("/work/repo/src/../docs/", root, "docs/"),
+            ("./src/main.rs", root, "src/main.rs"),
+            ("src\\lib.rs", root, "src/lib.rs"),
+            ("main.rs", sub, "src/main.rs"),
+            ("../README.md", sub, "README.md"),
+            ("..", sub, "."),
+        ] {
+            assert_eq!(
+                repo_relative(path, cwd, root).as_deref(),
+                Some(expected),
+                "{path} from {}",
+                cwd.display()
+            );
+        }
+    }
+
+    #[test]
+    fn test_repo_relative_outside_repo() {
+        let root = Path::new("/work/repo");
+        assert_eq!(repo_relative("/etc/passwd", root, root), None);
+        assert_eq!(repo_relative("../other/x.rs", root, root), None);
+        // A sibling sharing the root's name as a prefix is still outside
+        assert_eq!(repo_relative("/work/repo2/x.rs", root, root), None);
+    }
+
+    #[test]
+    fn test_output_paths_styles() {
+        let root = Path::new("/work/repo");
+        let as_is = OutputPaths::new(PathStyle::AsIs, root, root);
+        assert_eq!(
+            as_is.input("/work/repo/a.rs".to_string()),
+            "/work/repo/a.rs"
+        );
+        assert_eq!(
+            as_is.display(Path::new("/work/repo/CODEOWNERS")),
+            "/work/repo/CODEOWNERS"
+        );
+
+        let relative = OutputPaths::new(PathStyle::Relative, root, root);
+        assert_eq!(relative.input("/work/repo/a.rs".to_string()), "a.rs");
+        assert_eq!(
+            relative.input("/elsewhere/b.rs".to_string()),
+            "/elsewhere/b.rs"
+        );
+        assert_eq!(
+            relative.display(Path::new("/work/repo/.github/CODEOWNERS")),
+            ".github/CODEOWNERS"
+        );
+    }
+
     #[test]
     fn test_collect_files_none_when_empty() {
-        let result = collect_files(None, None, false).unwrap();
+        let result = collect_files(None, None, false, false).unwrap();
         assert!(result.is_none());
     }
 
     #[test]
     fn test_collect_files_from_args() {
         let files = vec!["src/main.rs".to_string(), "src/lib.rs".to_string()];
-        let result = collect_files(Some(files), None, false).unwrap();
+        let result = collect_files(Some(files), None, false, false).unwrap();
         assert!(result.is_some());
         let set = result.unwrap();
         assert_eq!(set.len(), 2);
@@ -84,7 +403,7 @@ mod tests {
         writeln!(temp).unwrap(); // empty line
         temp.flush().unwrap();
 
-        let result = collect_files(None, Some(temp.path().to_path_buf()), false).unwrap();
+        let result = collect_files(None, Some(temp.path().to_path_buf()), false, false).unwrap();
         assert!(result.is_some());
         let set = result.unwrap();
         assert_eq!(set.len(), 3);
@@ -95,9 +414,32 @@ mod tests {
 
     #[test]
     fn test_collect_files_from_nonexistent_file() {
-        let result = collect_files(None, Some(PathBuf::from("/nonexistent/path.txt")), false);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Failed to read"));
+        let result = collect_files(
+            None,
+            Some(PathBuf::from("/nonexistent/path.txt")),
+            false,
+            false,
+        );
+        let err = result.unwrap_err();
+        assert!(matches!(
+            &err,
+            FileCollectError::FileListNotFound { path, .. } if path == Path::new("/nonexistent/path.txt")
+        ));
+        assert!(err
+            .to_string()
+            .starts_with("Failed to read '/nonexistent/path.txt': "));
+    }
+
+    #[test]
+    fn test_collect_files_from_unreadable_file() {
+        // A directory exists but can't be read as a list (works even as root,
+        // unlike permission bits)
+        let dir = tempfile::tempdir().unwrap();
+        let err = collect_files(None, Some(dir.path().to_path_buf()), false, false).unwrap_err();
+        assert!(matches!(err, FileCollectError::FileListUnreadable { .. }));
+        assert!(err
+            .to_string()
+            .starts_with(&format!("Failed to read '{}': ", dir.path().display())));
     }
 
     #[test]
@@ -107,7 +449,8 @@ mod tests {
         temp.flush().unwrap();
 
         let files = vec!["from_args.rs".to_string()];
-        let result = collect_files(Some(files), Some(temp.path().to_path_buf()), false).unwrap();
+        let result =
+            collect_files(Some(files), Some(temp.path().to_path_buf()), false, false).unwrap();
         assert!(result.is_some());
         let set = result.unwrap();
         assert_eq!(set.len(), 2);
@@ -115,6 +458,143 @@ mod tests {
         assert!(set.contains("from_file.rs"));
     }
 
+    #[test]
+    fn test_collect_files_ordered_keeps_input_order() {
+        let mut temp = NamedTempFile::new().unwrap();
+        writeln!(temp, "z.rs").unwrap();
+        writeln!(temp, "a.rs").unwrap();
+        temp.flush().unwrap();
+
+        let files = vec!["m.rs".to_string(), "a.rs".to_string(), "m.rs".to_string()];
+        let result =
+            collect_files_ordered(Some(files), Some(temp.path().to_path_buf()), false, false)
+                .unwrap();
+        assert_eq!(result.unwrap(), vec!["m.rs", "a.rs", "z.rs"]);
+    }
+
+    #[test]
+    fn test_collect_files_is_the_ordered_list_as_a_set() {
+        // coverage/validate (set) and check (ordered) share one implementation
+        let mut temp = NamedTempFile::new().unwrap();
+        writeln!(temp, "b.rs").unwrap();
+        writeln!(temp, "a.rs").unwrap();
+        temp.flush().unwrap();
+
+        let files = || Some(vec!["c.rs".to_string(), "b.rs".to_string()]);
+        let from = || Some(temp.path().to_path_buf());
+        let ordered = collect_files_ordered(files(), from(), false, false)
+            .unwrap()
+            .unwrap();
+        let set = collect_files(files(), from(), false, false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(ordered, vec!["c.rs", "b.rs", "a.rs"]);
+        assert_eq!(set, ordered.into_iter().collect::<HashSet<_>>());
+    }
+
+    fn file_list(input: &[u8], null: bool) -> Vec<String> {
+        read_file_list(input, null)
+            .collect::<io::Result<_>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_read_file_list_null_delimited() {
+        let input = b"src/my file.rs\0docs/new\nline.md\0\0 padded.rs \0last.rs";
+        assert_eq!(
+            file_list(input, true),
+            [
+                "src/my file.rs",
+                "docs/new\nline.md",
+                " padded.rs ",
+                "last.rs"
+            ]
+        );
+        // Newlines stay the default, trimmed
+        assert_eq!(
+            file_list(b"src/my file.rs\n  b.rs  \r\n\nc.rs", false),
+            ["src/my file.rs", "b.rs", "c.rs"]
+        );
+        assert_eq!(file_list(b"a.rs\0b.rs\0", false), ["a.rs\0b.rs\0"]);
+    }
+
+    #[test]
+    fn test_read_file_list_null_delimited_invalid_utf8() {
+        let mut entries = read_file_list(&b"ok.rs\0bad\xff.rs\0"[..], true);
+        assert_eq!(entries.next().unwrap().unwrap(), "ok.rs");
+        let err = entries.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_collect_files_fails_on_unreadable_entry() {
+        let mut temp = NamedTempFile::new().unwrap();
+        temp.write_all(b"ok.rs\0bad\xff.rs\0after.rs\0").unwrap();
+        temp.flush().unwrap();
+
+        // The rest of the list isn't silently dropped
+        let err =
+            collect_files_ordered(None, Some(temp.path().to_path_buf()), false, true).unwrap_err();
+        assert!(
+            matches!(&err, FileCollectError::FileListUnreadable { path, source }
+                if path == temp.path() && source.kind() == io::ErrorKind::InvalidData),
+            "{:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_collect_files_from_null_delimited_file() {
+        let mut temp = NamedTempFile::new().unwrap();
+        temp.write_all(b"src/my file.rs\0src/b.rs\0").unwrap();
+        temp.flush().unwrap();
+
+        let from = || Some(temp.path().to_path_buf());
+        let files = collect_files_ordered(None, from(), false, true).unwrap();
+        assert_eq!(files.unwrap(), ["src/my file.rs", "src/b.rs"]);
+        // Without --null the whole file is one (odd) path
+        let files = collect_files_ordered(None, from(), false, false).unwrap();
+        assert_eq!(files.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_read_codeowners_arg_from_stdin() {
+        let stdin = io::Cursor::new("*.rs @rust\n");
+        let (path, content) = read_codeowners_arg(Path::new("-"), stdin).unwrap();
+        assert_eq!(path, PathBuf::from("<stdin>"));
+        assert_eq!(content, "*.rs @rust\n");
+    }
+
+    #[test]
+    fn test_read_codeowners_arg_from_file_ignores_stdin() {
+        let mut temp = NamedTempFile::new().unwrap();
+        writeln!(temp, "* @default").unwrap();
+
+        let stdin = io::Cursor::new("*.rs @rust\n");
+        let (path, content) = read_codeowners_arg(temp.path(), stdin).unwrap();
+        assert_eq!(path, temp.path());
+        assert_eq!(content, "* @default\n");
+
+        let missing = read_codeowners_arg(Path::new("/nonexistent/CODEOWNERS"), io::empty());
+        let err = missing.unwrap_err();
+        assert!(matches!(err, FileCollectError::CodeownersNotFound { .. }));
+        assert_eq!(err.to_string(), "File not found: /nonexistent/CODEOWNERS");
+    }
+
+    #[test]
+    fn test_read_codeowners_arg_unreadable() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = read_codeowners_arg(dir.path(), io::empty()).unwrap_err();
+        assert!(matches!(
+            &err,
+            FileCollectError::CodeownersUnreadable { path, .. } if path == dir.path()
+        ));
+        assert!(err
+            .to_string()
+            .starts_with(&format!("Failed to read {}: ", dir.path().display())));
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
     #[test]
     fn test_collect_files_dedupes() {
         let files = vec![
@@ -122,7 +602,7 @@ mod tests {
             "same.rs".to_string(),
             "different.rs".to_string(),
         ];
-        let result = collect_files(Some(files), None, false).unwrap();
+        let result = collect_files(Some(files), None, false, false).unwrap();
         assert!(result.is_some());
         let set = result.unwrap();
         assert_eq!(set.len(), 2);
@@ -4,6 +4,7 @@ use std::{env, fs};
 
 use colored::Colorize;
 
+use super::exit::Exit;
 use crate::ownership::find_codeowners;
 use crate::parser::format_codeowners;
 
@@ -13,20 +14,20 @@ pub fn fmt(path: Option<PathBuf>, write: bool) -> ExitCode {
     let codeowners_path = path.unwrap_or_else(|| {
         find_codeowners(&cwd).unwrap_or_else(|| {
             eprintln!("No CODEOWNERS file found");
-            std::process::exit(1);
+            std::process::exit(Exit::Error as i32);
         })
     });
 
     if !codeowners_path.exists() {
         eprintln!("File not found: {}", codeowners_path.display());
-        return ExitCode::from(1);
+        return Exit::Error.into();
     }
 
     let content = match fs::read_to_string(&codeowners_path) {
         Ok(c) => c,
         Err(e) => {
             eprintln!("Failed to read {}: {}", codeowners_path.display(), e);
-            return ExitCode::from(1);
+            return Exit::Error.into();
         }
     };
 
@@ -54,7 +55,7 @@ pub fn fmt(path: Option<PathBuf>, write: bool) -> ExitCode {
                     codeowners_path.display(),
                     e
                 );
-                ExitCode::from(1)
+                Exit::Error.into()
             }
         }
     } else {
@@ -89,6 +90,6 @@ pub fn fmt(path: Option<PathBuf>, write: bool) -> ExitCode {
             "--write".cyan(),
             "-w".cyan()
         );
-        ExitCode::from(1)
+        Exit::Findings.into()
     }
 }
@@ -7,19 +7,18 @@ use std::sync::Arc;
 use std::{env, fs};
 
 use colored::Colorize;
-use futures::stream::{self, StreamExt};
 use serde::Serialize;
 use tower_lsp::lsp_types::{DiagnosticSeverity, NumberOrString};
 
+use super::exit::Exit;
 use crate::diagnostics;
 use crate::file_cache::FileCache;
 use crate::github::{GitHubClient, PersistentCache};
 use crate::ownership::{find_codeowners, get_repo_root};
 use crate::parser::{parse_codeowners_file_with_positions, CodeownersLine};
-use crate::pattern::pattern_matches;
+use crate::pattern::{normalize_path, CompiledPattern};
 use crate::settings::load_settings_from_path;
-
-const CONCURRENCY: usize = 5;
+use crate::skip_owners::SkipOwners;
 
 /// Options for the gha command
 pub struct GhaOptions {
@@ -35,6 +34,8 @@ pub struct GhaOptions {
     pub output_annotations: bool,
     pub output_summary: bool,
     pub output_vars: bool,
+    /// Client for owner validation; share one to reuse its cache
+    pub github: Arc<GitHubClient>,
 }
 
 /// Results from all checks
@@ -95,7 +96,7 @@ pub async fn gha(opts: GhaOptions) -> ExitCode {
         Some(p) => p,
         None => {
             eprintln!("::error::No CODEOWNERS file found");
-            return ExitCode::from(1);
+            return Exit::Error.into();
         }
     };
 
@@ -107,16 +108,19 @@ pub async fn gha(opts: GhaOptions) -> ExitCode {
                 codeowners_path.display(),
                 e
             );
-            return ExitCode::from(1);
+            return Exit::Error.into();
         }
     };
 
     let repo_root = get_repo_root(&codeowners_path, &cwd);
-    let file_cache = FileCache::new(&repo_root);
+    let settings = load_settings_from_path(&repo_root);
+    let pattern_options = settings.pattern_options();
+    let file_cache = FileCache::new(&repo_root).with_pattern_options(pattern_options);
     let lines = parse_codeowners_file_with_positions(&content);
 
     let mut results = GhaResults::default();
     let mut failed = false;
+    let mut invalid_owners = false;
 
     // === Coverage checks ===
     if opts.check_coverage_changed || opts.check_coverage_all {
@@ -189,12 +193,13 @@ pub async fn gha(opts: GhaOptions) -> ExitCode {
 
     // === Owner validation ===
     if opts.check_owners_changed || opts.check_owners_all {
-        let client = Arc::new(GitHubClient::new());
+        let client = &opts.github;
+        let skip = settings.skip_owners();
 
         // Load persistent cache
         let persistent = PersistentCache::load(&repo_root);
         if !persistent.is_stale() {
-            client.load_from_persistent(&persistent);
+            client.load_from_persistent(&persistent, Some(&opts.token));
         }
 
         // Collect all owners
@@ -212,7 +217,8 @@ pub async fn gha(opts: GhaOptions) -> ExitCode {
 
                     // Check if this rule matches any changed file
                     if let Some(ref changed) = opts.changed_files {
-                        if changed.iter().any(|f| pattern_matches(pattern, f)) {
+                        let compiled = CompiledPattern::with_options(pattern, pattern_options);
+                        if changed.iter().any(|f| compiled.matches(&normalize_path(f))) {
                             changed_owners.insert(owner.clone());
                         }
                     }
@@ -220,34 +226,28 @@ pub async fn gha(opts: GhaOptions) -> ExitCode {
             }
         }
 
-        // Validate all unique owners
+        // Validate all unique owners but skipped ones
         let owners_to_validate: Vec<_> = all_owners.iter().cloned().collect();
         let uncached: Vec<_> = owners_to_validate
             .iter()
-            .filter(|o| !client.is_cached(o))
+            .filter(|o| !skip.matches(o) && !client.is_cached(o))
             .cloned()
             .collect();
 
         if !uncached.is_empty() {
-            let token = opts.token.clone();
-            let _: Vec<_> = stream::iter(uncached)
-                .map(|owner| {
-                    let client = Arc::clone(&client);
-                    let token = token.clone();
-                    async move {
-                        let _ = client.validate_owner(&owner, &token).await;
-                    }
-                })
-                .buffer_unordered(CONCURRENCY)
-                .collect()
+            let concurrency = settings.validation_concurrency();
+            client
+                .validate_owners(uncached, &opts.token, concurrency)
                 .await;
 
             // Save updated cache
-            let _ = client.export_to_persistent().save(&repo_root);
+            let _ = client
+                .export_to_persistent(Some(&opts.token))
+                .save(&repo_root);
         }
 
         let build_owners_result =
-            |owners: &HashSet<String>| -> OwnersResult { classify_owners(owners, &client) };
+            |owners: &HashSet<String>| -> OwnersResult { classify_owners(owners, client, &skip) };
 
         // Changed files owners (enforced)
         if opts.check_owners_changed && opts.changed_files.is_some() {
@@ -257,6 +257,7 @@ pub async fn gha(opts: GhaOptions) -> ExitCode {
                     eprintln!("::error::Invalid teams found in CODEOWNERS for changed files");
                 }
                 failed = true;
+                invalid_owners = true;
             } else if !result.unknown.is_empty() && opts.output_annotations {
                 eprintln!(
                     "::warning::Could not verify {} team(s) in CODEOWNERS for changed files - check token permissions",
@@ -286,7 +287,6 @@ pub async fn gha(opts: GhaOptions) -> ExitCode {
 
     // === Lint check ===
     if opts.check_lint {
-        let settings = load_settings_from_path(&repo_root);
         let diag_config = settings.diagnostic_config();
         let (mut diagnostics, _) =
             diagnostics::compute_diagnostics_sync(&content, Some(&file_cache), &diag_config);
@@ -392,8 +392,11 @@ pub async fn gha(opts: GhaOptions) -> ExitCode {
     // === Output results ===
     output_results(&results, &opts, failed);
 
-    if failed {
-        ExitCode::from(1)
+    // Invalid owners take precedence over other findings
+    if invalid_owners {
+        Exit::InvalidOwners.into()
+    } else if failed {
+        Exit::Findings.into()
     } else {
         ExitCode::SUCCESS
     }
@@ -734,16 +737,30 @@ fn build_step_summary(results: &GhaResults, failed: bool) -> String {
 }
 
 /// Classify a set of owners into valid/invalid/unknown based on cached GitHub info.
-fn classify_owners(owners: &HashSet<String>, client: &GitHubClient) -> OwnersResult {
+/// Skipped owners are left out.
+fn classify_owners(
+    owners: &HashSet<String>,
+    client: &GitHubClient,
+    skip: &SkipOwners,
+) -> OwnersResult {
     let mut valid = Vec::new();
     let mut invalid = Vec::new();
     let mut unknown = Vec::new();
 
     for owner in owners {
+        if skip.matches(owner) {
+            continue;
+        }
         match client.get_owner_info(owner) {
-            Some(crate::github::OwnerInfo::User(_)) | Some(crate::github::OwnerInfo::Team(_)) => {
-                valid.push(owner.clone())
-            }
+            Some(
+                crate::github::OwnerInfo::User(_)
+                | crate::github::OwnerInfo::Team(_)
+                | crate::github::OwnerInfo::Org(_),
+            ) => valid.push(owner.clone()),
+            Some(crate::github::OwnerInfo::Suspended(_)) => invalid.push(InvalidOwner {
+                owner: owner.clone(),
+                reason: "account suspended".to_string(),
+            }),
             Some(crate::github::OwnerInfo::Invalid) => invalid.push(InvalidOwner {
                 owner: owner.clone(),
                 reason: "not found on GitHub".to_string(),
@@ -819,7 +836,7 @@ mod tests {
     fn test_classify_valid_user() {
         let client = make_client_with_cache(vec![("@alice", user_info("alice"))]);
         let owners: HashSet<String> = ["@alice".to_string()].into();
-        let result = classify_owners(&owners, &client);
+        let result = classify_owners(&owners, &client, &SkipOwners::default());
 
         assert_eq!(result.valid, vec!["@alice"]);
         assert!(result.invalid.is_empty());
@@ -830,7 +847,7 @@ mod tests {
     fn test_classify_valid_team() {
         let client = make_client_with_cache(vec![("@myorg/myteam", team_info("myorg", "myteam"))]);
         let owners: HashSet<String> = ["@myorg/myteam".to_string()].into();
-        let result = classify_owners(&owners, &client);
+        let result = classify_owners(&owners, &client, &SkipOwners::default());
 
         assert_eq!(result.valid, vec!["@myorg/myteam"]);
         assert!(result.invalid.is_empty());
@@ -841,7 +858,7 @@ mod tests {
     fn test_classify_invalid_user() {
         let client = make_client_with_cache(vec![("@ghost", OwnerInfo::Invalid)]);
         let owners: HashSet<String> = ["@ghost".to_string()].into();
-        let result = classify_owners(&owners, &client);
+        let result = classify_owners(&owners, &client, &SkipOwners::default());
 
         assert!(result.valid.is_empty());
         assert_eq!(result.invalid.len(), 1);
@@ -869,7 +886,7 @@ mod tests {
             "@surgeventures/tribe-growth".to_string(),
         ]
         .into();
-        let result = classify_owners(&owners, &client);
+        let result = classify_owners(&owners, &client, &SkipOwners::default());
 
         // MUST be in unknown, NOT invalid
         assert!(result.valid.is_empty());
@@ -887,7 +904,7 @@ mod tests {
     fn test_classify_uncached_owner_goes_to_unknown() {
         let client = GitHubClient::new(); // empty cache
         let owners: HashSet<String> = ["@org/uncached-team".to_string()].into();
-        let result = classify_owners(&owners, &client);
+        let result = classify_owners(&owners, &client, &SkipOwners::default());
 
         assert!(result.valid.is_empty());
         assert!(result.invalid.is_empty());
@@ -900,7 +917,7 @@ mod tests {
     fn test_classify_email_gets_email_reason() {
         let client = GitHubClient::new(); // emails can't be validated
         let owners: HashSet<String> = ["user@example.com".to_string()].into();
-        let result = classify_owners(&owners, &client);
+        let result = classify_owners(&owners, &client, &SkipOwners::default());
 
         assert!(result.valid.is_empty());
         assert!(result.invalid.is_empty());
@@ -927,7 +944,7 @@ mod tests {
             "uncached@email.com".to_string(),
         ]
         .into();
-        let result = classify_owners(&owners, &client);
+        let result = classify_owners(&owners, &client, &SkipOwners::default());
 
         assert_eq!(result.valid.len(), 2);
         assert!(result.valid.contains(&"@valid-user".to_string()));
@@ -957,7 +974,7 @@ mod tests {
             ),
         ]);
         let owners: HashSet<String> = ["@org/team-a".to_string(), "@org/team-b".to_string()].into();
-        let result = classify_owners(&owners, &client);
+        let result = classify_owners(&owners, &client, &SkipOwners::default());
         assert!(
             result.invalid.is_empty(),
             "No invalid owners means no CI failure"
@@ -973,7 +990,7 @@ mod tests {
         ]);
         let owners: HashSet<String> =
             ["@org/team-a".to_string(), "@definitely-fake".to_string()].into();
-        let result = classify_owners(&owners, &client);
+        let result = classify_owners(&owners, &client, &SkipOwners::default());
         assert_eq!(
             result.invalid.len(),
             1,
@@ -981,4 +998,22 @@ mod tests {
         );
         assert_eq!(result.unknown.len(), 1, "Unknown should be separate");
     }
+
+    #[test]
+    fn test_classify_leaves_out_skipped_owners() {
+        // Stale results from before the owner was skipped stay hidden
+        let client = make_client_with_cache(vec![
+            ("@acme/old-bot", OwnerInfo::Invalid),
+            ("@alice", user_info("alice")),
+        ]);
+        let owners: HashSet<String> = ["@acme/old-bot".to_string(), "@alice".to_string()].into();
+        let result = classify_owners(&owners, &client, &SkipOwners::new(["@acme/*-bot"]));
+        assert_eq!(result.valid, vec!["@alice"]);
+        assert!(result.invalid.is_empty());
+        assert!(result.unknown.is_empty());
+
+        // The skip list belongs to the run, not the shared client
+        let result = classify_owners(&owners, &client, &SkipOwners::default());
+        assert_eq!(result.invalid.len(), 1);
+    }
 }
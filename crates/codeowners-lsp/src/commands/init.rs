@@ -0,0 +1,446 @@
+//! Init command - drafts a CODEOWNERS for a repository from git history.
+//!
+//! Every file is treated as unowned and run through the same blame engine
+//! as `suggest`: directories whose history points clearly at one owner
+//! become directory rules, and the files of other directories get rules of
+//! their own. Contributors are resolved to owners the same way as in
+//! `suggest` (identities file, then `lookup_cmd`).
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::{env, fs};
+
+use colored::Colorize;
+
+use super::exit::Exit;
+use super::profile;
+use super::progress;
+use super::suggest::{owner_sources, pick_owners};
+use crate::blame::{
+    ensure_repo, is_shallow, repo_toplevel, safe_directory_command, suggest_owners_with_fallback,
+    AnalysisOptions, AuthorActivity, BlameError, ConfidenceWeights, OwnerSuggestion,
+    SHALLOW_WARNING,
+};
+use crate::file_cache::{FileCache, FileCacheOptions};
+use crate::identities::Identities;
+use crate::lookup::OwnerLookup;
+use crate::ownership::find_codeowners;
+use crate::pattern::{CompiledPattern, PatternOptions};
+use crate::settings::load_settings_from_path;
+
+/// Uncovered files listed by name at the end of the draft
+const MAX_LISTED_UNCOVERED: usize = 50;
+
+/// Options for the init command
+#[derive(Debug, Clone)]
+pub struct InitOptions {
+    /// Minimum confidence (0-100) for a whole directory to get one rule;
+    /// below it, its files get individual rules
+    pub min_confidence: f64,
+    /// Group files by their first N directory segments instead of parent
+    pub depth: Option<usize>,
+    /// Write the draft here instead of stdout
+    pub output: Option<PathBuf>,
+    /// Overwrite `output` if it exists
+    pub force: bool,
+    /// Down-weight contributors with no commits in this many months
+    pub stale_after_months: Option<u32>,
+    /// Leave out files matching any of these patterns
+    pub exclude: Vec<String>,
+    /// How confidence is computed; None uses the `[suggest]` config
+    pub weights: Option<ConfidenceWeights>,
+}
+
+impl Default for InitOptions {
+    fn default() -> Self {
+        Self {
+            min_confidence: 50.0,
+            depth: None,
+            output: None,
+            force: false,
+            stale_after_months: None,
+            exclude: Vec::new(),
+            weights: None,
+        }
+    }
+}
+
+pub fn init(mut options: InitOptions) -> ExitCode {
+    let cwd = env::current_dir().expect("Failed to get current directory");
+
+    if let Some(ref output) = options.output {
+        if output.exists() && !options.force {
+            eprintln!(
+                "{} {} already exists (use --force to overwrite)",
+                "Error:".red().bold(),
+                output.display()
+            );
+            return Exit::Error.into();
+        }
+    }
+
+    let repo_root = match ensure_repo(&cwd).and_then(|_| repo_toplevel(&cwd)) {
+        Ok(root) => root,
+        Err(e) => return report_blame_error(e, &cwd),
+    };
+    if is_shallow(&repo_root) {
+        eprintln!("{} {}", "Warning:".yellow().bold(), SHALLOW_WARNING);
+    }
+    if let Some(existing) = find_codeowners(&cwd) {
+        eprintln!(
+            "{} {} already exists; the draft ignores it",
+            "Note:".yellow().bold(),
+            existing.display()
+        );
+    }
+
+    let (identities, lookup_cmd) = match owner_sources(&repo_root, "init") {
+        Ok(sources) => sources,
+        Err(code) => return code,
+    };
+    let mut lookup =
+        lookup_cmd.map(|cmd| OwnerLookup::new(&cmd, Vec::new()).with_progress(progress::enabled()));
+    let weights = options
+        .weights
+        .unwrap_or_else(|| load_settings_from_path(&repo_root).confidence_weights());
+    if let Some(warning) = weights.warning() {
+        eprintln!("{} {}", "Warning:".yellow().bold(), warning);
+    }
+    options.weights = Some(weights);
+
+    let file_cache = profile::time(profile::WALK, || {
+        FileCache::with_options(
+            &repo_root,
+            FileCacheOptions {
+                exclude: options.exclude.clone(),
+                ..Default::default()
+            },
+        )
+    });
+    let files: Vec<String> = file_cache.all_files().to_vec();
+    eprintln!(
+        "{} Analyzing git history for {} files...",
+        "→".blue(),
+        files.len()
+    );
+
+    let draft = match draft(&repo_root, &files, &options, &identities, lookup.as_mut()) {
+        Ok(draft) => draft,
+        Err(e) => return report_blame_error(e, &repo_root),
+    };
+
+    match options.output {
+        Some(path) => {
+            if let Err(e) = fs::write(&path, &draft) {
+                eprintln!(
+                    "{} Failed to write {}: {}",
+                    "Error:".red().bold(),
+                    path.display(),
+                    e
+                );
+                return Exit::Error.into();
+            }
+            eprintln!(
+                "{} Wrote draft to {} - review it before committing",
+                "✓".green(),
+                path.display()
+            );
+        }
+        None => print!("{}", draft),
+    }
+    ExitCode::SUCCESS
+}
+
+/// Draft CODEOWNERS content for `files`, from their git history
+fn draft(
+    repo_root: &Path,
+    files: &[String],
+    options: &InitOptions,
+    identities: &Identities,
+    lookup: Option<&mut OwnerLookup>,
+) -> Result<String, BlameError> {
+    let mut activity = options
+        .stale_after_months
+        .map(|months| AuthorActivity::new(repo_root, months));
+    // Files in unclear directories get a rule whatever their confidence:
+    // the draft is only useful if it covers the tree
+    let suggestions = profile::time(profile::BLAME, || {
+        suggest_owners_with_fallback(
+            repo_root,
+            files,
+            options.min_confidence,
+            0.0,
+            &AnalysisOptions {
+                depth: options.depth,
+                weights: options.weights.unwrap_or_default(),
+                ..Default::default()
+            },
+            activity.as_mut(),
+        )
+    })?;
+    let suggestions = pick_owners(suggestions, identities, lookup);
+    let pattern_options = load_settings_from_path(repo_root).pattern_options();
+    Ok(render_draft(&suggestions, files, pattern_options))
+}
+
+/// CODEOWNERS pattern for a suggestion path: anchored, except the `*`
+/// catch-all for the repository root. Whitespace and `#` are escaped so
+/// they don't end the pattern or start a comment.
+fn draft_pattern(path: &str) -> String {
+    if path == "*" {
+        return path.to_string();
+    }
+    let mut pattern = String::with_capacity(path.len() + 1);
+    if !path.starts_with('/') {
+        pattern.push('/');
+    }
+    for c in path.chars() {
+        if c.is_whitespace() || c == '#' || c == '\\' {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern
+}
+
+/// Draft file: a header, then one commented rule per suggestion, broadest
+/// first so more specific rules (later) win. Files no rule covers are
+/// listed at the end.
+fn render_draft(
+    suggestions: &[OwnerSuggestion],
+    files: &[String],
+    pattern_options: PatternOptions,
+) -> String {
+    let mut rules: Vec<(String, &OwnerSuggestion)> = suggestions
+        .iter()
+        .map(|s| (draft_pattern(&s.path), s))
+        .collect();
+    // `*` sorts before `/`, so the catch-all comes first
+    rules.sort_by(|(a, _), (b, _)| a.cmp(b));
+    rules.dedup_by(|(a, _), (b, _)| a == b);
+
+    let mut out = String::from(
+        "# CODEOWNERS draft generated by `codeowners-cli init` from git history.\n\
+         # Confidence weighs the top contributor's share of commits with how many\n\
+         # commits there are.\n\
+         # Review every rule before committing.\n",
+    );
+    for (pattern, suggestion) in &rules {
+        out.push_str(&format!(
+            "\n# {:.0}% confidence ({} commit{})\n{} {}\n",
+            suggestion.confidence,
+            suggestion.total_commits,
+            if suggestion.total_commits == 1 {
+                ""
+            } else {
+                "s"
+            },
+            pattern,
+            suggestion.suggested_owner
+        ));
+    }
+
+    let compiled: Vec<CompiledPattern> = rules
+        .iter()
+        .map(|(pattern, _)| CompiledPattern::with_options(pattern, pattern_options))
+        .collect();
+    let uncovered: Vec<&String> = files
+        .iter()
+        .filter(|file| !compiled.iter().any(|p| p.matches(file)))
+        .collect();
+    if !uncovered.is_empty() {
+        out.push_str(&format!(
+            "\n# No owner found for {} file{} (no git history, or contributors not mapped to owners):\n",
+            uncovered.len(),
+            if uncovered.len() == 1 { "" } else { "s" }
+        ));
+        for file in uncovered.iter().take(MAX_LISTED_UNCOVERED) {
+            out.push_str(&format!("#   {}\n", file));
+        }
+        if uncovered.len() > MAX_LISTED_UNCOVERED {
+            out.push_str(&format!(
+                "#   ... and {} more\n",
+                uncovered.len() - MAX_LISTED_UNCOVERED
+            ));
+        }
+    }
+    out
+}
+
+/// Explain why git history couldn't be used
+fn report_blame_error(err: BlameError, repo_root: &Path) -> ExitCode {
+    match err {
+        BlameError::GitMissing => eprintln!(
+            "{} git is not installed or not on PATH; init reads commit history.",
+            "Error:".red().bold()
+        ),
+        BlameError::NotARepo => eprintln!(
+            "{} Not inside a git repository; run init from a git checkout.",
+            "Error:".red().bold()
+        ),
+        BlameError::NoHistory => eprintln!(
+            "{} No commit history yet; commit some files first.",
+            "Error:".red().bold()
+        ),
+        BlameError::DubiousOwnership => eprintln!(
+            "{} git refuses this repository because another user owns it; trust it with `{}`.",
+            "Error:".red().bold(),
+            safe_directory_command(repo_root)
+        ),
+    }
+    Exit::Error.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::{compute_diagnostics_sync, DiagnosticConfig};
+    use crate::ownership::check_file_ownership_parsed;
+    use crate::parser::parse_codeowners_file_with_positions;
+    use crate::test_git::{commit_as, git};
+    use tower_lsp::lsp_types::DiagnosticSeverity;
+
+    /// Write `files` and commit them as `author`
+    fn commit_files(dir: &Path, author: &str, files: &[&str]) {
+        for file in files {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, format!("{} by {}\n", file, author)).unwrap();
+        }
+        commit_as(dir, author, "work");
+    }
+
+    const FIXTURE_FILES: [&str; 6] = [
+        "README.md",
+        "docs/guide.md",
+        "docs/api.md",
+        "src/main.rs",
+        "src/lib.rs",
+        "tools/build.sh",
+    ];
+
+    /// Alice writes src/, Bob writes docs/, tools/ is shared
+    fn fixture_repo() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        git(root, &["init", "-q"]);
+        commit_files(root, "Alice", &["README.md", "src/main.rs", "src/lib.rs"]);
+        commit_files(root, "Bob", &["docs/guide.md", "docs/api.md"]);
+        commit_files(root, "Alice", &["tools/build.sh"]);
+        commit_files(root, "Bob", &["tools/build.sh"]);
+        dir
+    }
+
+    #[test]
+    fn test_draft_parses_cleanly_and_covers_the_tree() {
+        let repo = fixture_repo();
+        let identities = Identities::parse(
+            r#"
+            [[identity]]
+            owner = "@org/core"
+            emails = ["alice@test.com"]
+
+            [[identity]]
+            owner = "@org/docs"
+            emails = ["bob@test.com"]
+            "#,
+        )
+        .unwrap();
+        let files: Vec<String> = FIXTURE_FILES.iter().map(|f| f.to_string()).collect();
+
+        let content = draft(
+            repo.path(),
+            &files,
+            &InitOptions::default(),
+            &identities,
+            None,
+        )
+        .unwrap();
+
+        let cache = FileCache::from_files(files.clone());
+        let (diagnostics, _) =
+            compute_diagnostics_sync(&content, Some(&cache), &DiagnosticConfig::default());
+        let errors: Vec<_> = diagnostics
+            .iter()
+            .filter(|d| d.severity == Some(DiagnosticSeverity::ERROR))
+            .collect();
+        assert!(errors.is_empty(), "{:?}\n{}", errors, content);
+        assert!(!content.contains("No owner found"), "{}", content);
+
+        let parsed = parse_codeowners_file_with_positions(&content);
+        let owner_of = |file: &str| check_file_ownership_parsed(&parsed, file).map(|r| r.owners);
+        for file in FIXTURE_FILES {
+            assert!(
+                owner_of(file).is_some(),
+                "{} is not covered:\n{}",
+                file,
+                content
+            );
+        }
+        assert_eq!(owner_of("src/main.rs").unwrap(), ["@org/core"]);
+        assert_eq!(owner_of("docs/api.md").unwrap(), ["@org/docs"]);
+        // Single-owner directories become directory rules
+        assert!(content.contains("\n/src/ @org/core\n"), "{}", content);
+        assert!(content.contains("\n/docs/ @org/docs\n"), "{}", content);
+        assert!(
+            content.contains("% confidence (1 commit)\n/src/"),
+            "{}",
+            content
+        );
+    }
+
+    #[test]
+    fn test_render_draft_orders_rules_and_lists_uncovered_files() {
+        let suggestion = |path: &str, owner: &str| OwnerSuggestion {
+            path: path.to_string(),
+            suggested_owner: owner.to_string(),
+            confidence: 75.0,
+            contributors: Vec::new(),
+            total_commits: 4,
+        };
+        let files: Vec<String> = ["src/a.rs", "src/api/b.rs", "vendor/x.c"]
+            .iter()
+            .map(|f| f.to_string())
+            .collect();
+        let content = render_draft(
+            &[suggestion("src/api/", "@api"), suggestion("src/", "@core")],
+            &files,
+            PatternOptions::default(),
+        );
+
+        let rules: Vec<&str> = content
+            .lines()
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .collect();
+        assert_eq!(rules, ["/src/ @core", "/src/api/ @api"]);
+        assert!(content.contains("# 75% confidence (4 commits)\n/src/ @core"));
+        assert!(content.contains("# No owner found for 1 file "));
+        assert!(content.contains("#   vendor/x.c\n"));
+    }
+
+    #[test]
+    fn test_draft_pattern_escapes_spaces_and_hashes() {
+        assert_eq!(draft_pattern("*"), "*");
+        assert_eq!(draft_pattern("src/"), "/src/");
+        assert_eq!(draft_pattern("my docs/#1.md"), r"/my\ docs/\#1.md");
+
+        let content = render_draft(
+            &[OwnerSuggestion {
+                path: "my docs/".to_string(),
+                suggested_owner: "@docs".to_string(),
+                confidence: 90.0,
+                contributors: Vec::new(),
+                total_commits: 1,
+            }],
+            &["my docs/#1.md".to_string()],
+            PatternOptions::default(),
+        );
+        assert!(
+            content.contains("# 90% confidence (1 commit)\n/my\\ docs/ @docs\n"),
+            "{}",
+            content
+        );
+        // The escaped rule covers the file
+        assert!(!content.contains("No owner found"), "{}", content);
+    }
+}
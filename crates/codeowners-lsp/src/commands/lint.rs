@@ -4,52 +4,98 @@ use std::sync::Arc;
 use std::{env, fs};
 
 use colored::Colorize;
-use futures::stream::{self, StreamExt};
 use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range};
 
-use crate::diagnostics;
+use super::annotations::diagnostic_annotation;
+use super::exit::Exit;
+use super::files::{is_stdin_arg, read_codeowners_arg};
+use super::profile;
+use crate::diagnostics::{self, codes, DiagnosticConfig};
 use crate::file_cache::FileCache;
-use crate::github::{GitHubClient, PersistentCache};
-use crate::ownership::{apply_safe_fixes, find_codeowners, get_repo_root};
+use crate::github::{GitHubClient, PersistentCache, ME_ALIAS, ME_IN_CODEOWNERS};
+use crate::ownership::{
+    apply_safe_fixes, find_all_codeowners, find_codeowners, get_repo_root, Semantics,
+};
 use crate::parser::{parse_codeowners_file_with_positions, CodeownersLine};
 use crate::settings::load_settings_from_path;
+use crate::skip_owners::SkipOwners;
+use crate::validation::merge_conflict_markers;
+
+/// Options for the lint command
+#[derive(Debug, Clone, Default)]
+pub struct LintOptions {
+    /// CODEOWNERS file to lint (default: auto-detect; - reads stdin)
+    pub path: Option<PathBuf>,
+    /// Output as JSON
+    pub json: bool,
+    /// Auto-fix safe issues
+    pub fix: bool,
+    /// Exit non-zero on warnings (not just errors)
+    pub strict: bool,
+    /// Output GitHub Actions annotations
+    pub github_actions: bool,
+    /// Flag owners that are not @org/team
+    pub require_teams: bool,
+    /// How the rules owning the CODEOWNERS file itself are resolved
+    pub semantics: Semantics,
+}
 
-const CONCURRENCY: usize = 5;
-
-pub async fn lint(
-    path: Option<PathBuf>,
-    json_output: bool,
-    fix: bool,
-    strict: bool,
-    github_actions: bool,
-) -> ExitCode {
+pub async fn lint(options: LintOptions, github: Arc<GitHubClient>) -> ExitCode {
+    let LintOptions {
+        path,
+        json: json_output,
+        fix,
+        strict,
+        github_actions,
+        require_teams,
+        semantics,
+    } = options;
     let cwd = env::current_dir().expect("Failed to get current directory");
 
-    let codeowners_path = path.unwrap_or_else(|| {
+    let path = path.unwrap_or_else(|| {
         find_codeowners(&cwd).unwrap_or_else(|| {
             eprintln!("No CODEOWNERS file found");
-            std::process::exit(1);
+            std::process::exit(Exit::Error as i32);
         })
     });
 
-    if !codeowners_path.exists() {
-        eprintln!("File not found: {}", codeowners_path.display());
-        return ExitCode::from(1);
+    let from_stdin = is_stdin_arg(&path);
+    if from_stdin && fix {
+        eprintln!("--fix needs a CODEOWNERS file to write to; it can't be used with stdin");
+        return Exit::Error.into();
     }
 
-    let content = match fs::read_to_string(&codeowners_path) {
-        Ok(c) => c,
+    let (codeowners_path, content) = match read_codeowners_arg(&path, std::io::stdin().lock()) {
+        Ok(loaded) => loaded,
         Err(e) => {
-            eprintln!("Failed to read {}: {}", codeowners_path.display(), e);
-            return ExitCode::from(1);
+            eprintln!("{}", e);
+            return Exit::Error.into();
         }
     };
 
-    let repo_root = get_repo_root(&codeowners_path, &cwd);
-    let file_cache = FileCache::new(&repo_root);
+    // Content from stdin isn't on disk, so resolve files from the cwd
+    let repo_root = if from_stdin {
+        cwd.clone()
+    } else {
+        get_repo_root(&codeowners_path, &cwd)
+    };
+    let settings = load_settings_from_path(&repo_root);
+    let file_cache = profile::time(profile::WALK, || {
+        FileCache::new(&repo_root).with_pattern_options(settings.pattern_options())
+    });
 
     // If --fix, apply safe fixes and write
     if fix {
+        // Fixing either side of a conflict would only make it harder to resolve
+        if let Some(line) = merge_conflict_markers(&content).first() {
+            eprintln!(
+                "{} unresolved merge conflict at line {} of {}; resolve it before using --fix",
+                "Error:".red().bold(),
+                line + 1,
+                codeowners_path.display()
+            );
+            return Exit::Findings.into();
+        }
         let fix_result = apply_safe_fixes(&content, Some(&file_cache));
         if fix_result.fixes.is_empty() {
             println!(
@@ -80,50 +126,77 @@ pub async fn lint(
                     codeowners_path.display(),
                     e
                 );
-                return ExitCode::from(1);
+                return Exit::Error.into();
             }
         }
     }
 
-    // Load config from file
-    let settings = load_settings_from_path(&repo_root);
-    let diag_config = settings.diagnostic_config();
-    let (mut diagnostics, _) =
-        diagnostics::compute_diagnostics_sync(&content, Some(&file_cache), &diag_config);
+    let mut diag_config = settings.diagnostic_config();
+    if require_teams {
+        diag_config.enable(
+            diagnostics::codes::NON_TEAM_OWNER,
+            DiagnosticSeverity::ERROR,
+        );
+    }
+    let (mut diagnostics, _) = profile::time(profile::MATCH, || {
+        diagnostics::compute_diagnostics_sync(&content, Some(&file_cache), &diag_config)
+    });
+    // Conflict markers are all that's reported until they're resolved
+    let conflicted = diagnostics.iter().any(
+        |d| matches!(&d.code, Some(NumberOrString::String(code)) if code == codes::MERGE_CONFLICT),
+    );
+
+    // Warn about stray CODEOWNERS copies that GitHub will ignore.
+    // Canonicalize so `./CODEOWNERS` and absolute paths compare equal.
+    if !from_stdin && !conflicted {
+        let canonical_path = fs::canonicalize(&codeowners_path).unwrap_or(codeowners_path.clone());
+        let canonical_root = get_repo_root(&canonical_path, &cwd);
+        let all_codeowners: Vec<PathBuf> = find_all_codeowners(&canonical_root)
+            .into_iter()
+            .map(|p| fs::canonicalize(&p).unwrap_or(p))
+            .collect();
+        diagnostics.extend(diagnostics::multiple_codeowners_diagnostic(
+            &canonical_path,
+            &all_codeowners,
+            &canonical_root,
+            &diag_config,
+        ));
+        if let Ok(relative) = canonical_path.strip_prefix(&canonical_root) {
+            diagnostics.extend(diagnostics::codeowners_file_owner_diagnostic(
+                &content,
+                &relative.to_string_lossy().replace('\\', "/"),
+                semantics,
+                &diag_config,
+            ));
+        }
+    }
 
     // Check if validation is enabled
-    if settings.validate_owners {
+    if settings.validate_owners && !conflicted {
         if let Some(token) = settings.resolve_token() {
-            let validation_diags = validate_owners_for_lint(&content, &repo_root, &token).await;
+            let validation_diags = validate_owners_for_lint(
+                &github,
+                &content,
+                &repo_root,
+                &token,
+                settings.validation_concurrency(),
+                &settings.skip_owners(),
+                &diag_config,
+            )
+            .await;
             diagnostics.extend(validation_diags);
         }
     }
+    diagnostics::remove_suppressed(&content, &mut diagnostics);
 
     // Sort diagnostics by line number
     diagnostics.sort_by_key(|d| d.range.start.line);
 
     if github_actions {
         // GitHub Actions annotations only (no human output)
-        let file_path = codeowners_path.display();
+        let file_path = codeowners_path.display().to_string();
         for d in &diagnostics {
-            let level = match d.severity {
-                Some(DiagnosticSeverity::ERROR) => "error",
-                Some(DiagnosticSeverity::WARNING) => "warning",
-                _ => "notice", // hints and info become notices
-            };
-            let line = d.range.start.line + 1;
-            let col = d.range.start.character + 1;
-            let title = d
-                .code
-                .as_ref()
-                .map(|c| match c {
-                    NumberOrString::String(s) => s.clone(),
-                    NumberOrString::Number(n) => n.to_string(),
-                })
-                .unwrap_or_default();
-            // Escape message for GitHub Actions (newlines become %0A)
-            let message = d.message.replace('\n', "%0A").replace('\r', "%0D");
-            println!("::{level} file={file_path},line={line},col={col},title={title}::{message}");
+            println!("{}", diagnostic_annotation(d, &file_path));
         }
     } else if json_output {
         // Parse content to get pattern/owners for each line
@@ -179,62 +252,116 @@ pub async fn lint(
             return ExitCode::SUCCESS;
         }
 
+        // Group by severity, most severe first; line order within a group
         println!("{}:", codeowners_path.display().to_string().bold());
-        for d in &diagnostics {
-            let (severity_label, severity_color) = match d.severity {
-                Some(DiagnosticSeverity::ERROR) => ("error", "red"),
-                Some(DiagnosticSeverity::WARNING) => ("warning", "yellow"),
-                Some(DiagnosticSeverity::HINT) => ("hint", "cyan"),
-                Some(DiagnosticSeverity::INFORMATION) => ("info", "blue"),
-                _ => ("unknown", "white"),
-            };
-            let code = d
-                .code
-                .as_ref()
-                .map(|c| match c {
-                    NumberOrString::String(s) => s.clone(),
-                    NumberOrString::Number(n) => n.to_string(),
-                })
-                .unwrap_or_default();
+        let mut summary = Vec::new();
+        for (severity, singular, plural, color) in SEVERITY_GROUPS {
+            let group: Vec<&Diagnostic> = diagnostics
+                .iter()
+                .filter(|d| d.severity == Some(severity))
+                .collect();
+            if group.is_empty() {
+                continue;
+            }
+            let label = if group.len() == 1 { singular } else { plural };
+            summary.push(
+                format!("{} {}", group.len(), label)
+                    .color(color)
+                    .to_string(),
+            );
+
             println!(
-                "  {} {} {} {}",
-                format!("line {}:", d.range.start.line + 1).dimmed(),
-                format!("[{}]", severity_label).color(severity_color),
-                code.bold(),
-                d.message
+                "  {}",
+                format!("{} ({})", plural, group.len()).color(color).bold()
             );
+            for d in group {
+                let code = d
+                    .code
+                    .as_ref()
+                    .map(|c| match c {
+                        NumberOrString::String(s) => s.clone(),
+                        NumberOrString::Number(n) => n.to_string(),
+                    })
+                    .unwrap_or_default();
+                println!(
+                    "    {} {} {}",
+                    format!("line {}:", d.range.start.line + 1).dimmed(),
+                    code.bold(),
+                    d.message
+                );
+            }
         }
+        println!("{}", summary.join(", "));
     }
 
-    if should_fail(&diagnostics, strict) {
-        ExitCode::from(1)
-    } else {
-        ExitCode::SUCCESS
-    }
+    exit_status(&diagnostics, strict).into()
 }
 
-/// Validate owners and return diagnostics for invalid ones
+/// Severity groups for the human report, most severe first
+/// as (severity, singular, plural, color)
+const SEVERITY_GROUPS: [(DiagnosticSeverity, &str, &str, &str); 4] = [
+    (DiagnosticSeverity::ERROR, "error", "errors", "red"),
+    (DiagnosticSeverity::WARNING, "warning", "warnings", "yellow"),
+    (DiagnosticSeverity::INFORMATION, "info", "info", "blue"),
+    (DiagnosticSeverity::HINT, "hint", "hints", "cyan"),
+];
+
+/// Validate owners and return diagnostics for invalid ones. Owners matching
+/// `skip` are neither looked up nor reported.
 async fn validate_owners_for_lint(
+    client: &GitHubClient,
     content: &str,
     repo_root: &std::path::Path,
     token: &str,
+    concurrency: usize,
+    skip: &SkipOwners,
+    config: &DiagnosticConfig,
 ) -> Vec<Diagnostic> {
-    let lines = parse_codeowners_file_with_positions(content);
-    let client = Arc::new(GitHubClient::new());
-
     // Load persistent cache and check staleness
     let persistent = PersistentCache::load(repo_root);
     let cache_was_stale = persistent.is_stale();
 
     if !cache_was_stale {
         // Load cached results into client
-        client.load_from_persistent(&persistent);
+        client.load_from_persistent(&persistent, Some(token));
+    }
+
+    let mut owners_to_check = owner_positions(content);
+    owners_to_check.retain(|(owner, _, _, _)| !skip.matches(owner));
+
+    // Dedupe owners for validation (but keep all positions for diagnostics)
+    let unique_owners: std::collections::HashSet<_> = owners_to_check
+        .iter()
+        .map(|(o, _, _, _)| o.clone())
+        .collect();
+
+    // Validate uncached owners in parallel; `@me` isn't expanded in
+    // CODEOWNERS, so it's reported without looking up the user `me`
+    let uncached: Vec<_> = unique_owners
+        .iter()
+        .filter(|o| !client.is_cached(o) && !o.eq_ignore_ascii_case(ME_ALIAS))
+        .cloned()
+        .collect();
+
+    if !uncached.is_empty() {
+        profile::time_async(
+            profile::VALIDATE,
+            client.validate_owners(uncached, token, concurrency),
+        )
+        .await;
+
+        // Save updated cache
+        let _ = client.export_to_persistent(Some(token)).save(repo_root);
     }
 
-    // Collect owners with their line positions
-    let mut owners_to_check: Vec<(String, u32, u32, u32)> = Vec::new(); // (owner, line, start, len)
+    owner_diagnostics_from_cache(&owners_to_check, client, config)
+}
 
-    for line in &lines {
+/// Every owner on a rule line as (owner, line, start, len), with byte
+/// offsets into the raw line
+fn owner_positions(content: &str) -> Vec<(String, u32, u32, u32)> {
+    let mut owners_to_check = Vec::new();
+    for line in &parse_codeowners_file_with_positions(content) {
         if let CodeownersLine::Rule { owners, .. } = &line.content {
             let line_text = content.lines().nth(line.line_number as usize).unwrap_or("");
             let mut search_start = line.owners_start as usize;
@@ -254,69 +381,57 @@ async fn validate_owners_for_lint(
         }
     }
 
-    // Dedupe owners for validation (but keep all positions for diagnostics)
-    let unique_owners: std::collections::HashSet<_> = owners_to_check
-        .iter()
-        .map(|(o, _, _, _)| o.clone())
-        .collect();
-
-    // Validate uncached owners in parallel
-    let uncached: Vec<_> = unique_owners
-        .iter()
-        .filter(|o| !client.is_cached(o))
-        .cloned()
-        .collect();
-
-    if !uncached.is_empty() {
-        let _: Vec<_> = stream::iter(uncached)
-            .map(|owner| {
-                let client = Arc::clone(&client);
-                let token = token.to_string();
-                async move {
-                    let _ = client.validate_owner_with_info(&owner, &token).await;
-                }
-            })
-            .buffer_unordered(CONCURRENCY)
-            .collect()
-            .await;
-
-        // Save updated cache
-        let _ = client.export_to_persistent().save(repo_root);
-    }
-
-    owner_diagnostics_from_cache(&owners_to_check, &client)
+    owners_to_check
 }
 
-/// Generate diagnostics for owners that are definitively Invalid (not Unknown).
+/// Generate diagnostics for owners that are definitively Invalid or
+/// Suspended (not Unknown), at their configured severities.
 fn owner_diagnostics_from_cache(
     owners_to_check: &[(String, u32, u32, u32)],
     client: &GitHubClient,
+    config: &DiagnosticConfig,
 ) -> Vec<Diagnostic> {
     let mut diagnostics = Vec::new();
 
     for (owner, line_num, char_start, owner_len) in owners_to_check {
-        if matches!(
-            client.get_owner_info(owner),
-            Some(crate::github::OwnerInfo::Invalid)
-        ) {
-            diagnostics.push(Diagnostic {
-                range: Range {
-                    start: Position {
-                        line: *line_num,
-                        character: *char_start,
-                    },
-                    end: Position {
-                        line: *line_num,
-                        character: char_start + owner_len,
-                    },
+        let (code, message) = match client.get_owner_info(owner) {
+            _ if owner.eq_ignore_ascii_case(ME_ALIAS) => (
+                codes::GITHUB_OWNER_NOT_FOUND,
+                format!("{}; write your login instead", ME_IN_CODEOWNERS),
+            ),
+            Some(crate::github::OwnerInfo::Invalid) => (
+                codes::GITHUB_OWNER_NOT_FOUND,
+                format!("Owner '{}' not found on GitHub", owner),
+            ),
+            Some(crate::github::OwnerInfo::Suspended(_)) => (
+                codes::GITHUB_OWNER_SUSPENDED,
+                format!(
+                    "Owner '{}' is a suspended GitHub account and can't review",
+                    owner
+                ),
+            ),
+            _ => continue,
+        };
+        let Some(severity) = config.get(code, DiagnosticSeverity::WARNING) else {
+            continue;
+        };
+        diagnostics.push(Diagnostic {
+            range: Range {
+                start: Position {
+                    line: *line_num,
+                    character: *char_start,
                 },
-                severity: Some(DiagnosticSeverity::WARNING),
-                code: Some(NumberOrString::String("github-owner-not-found".to_string())),
-                source: Some("codeowners".to_string()),
-                message: format!("Owner '{}' not found on GitHub", owner),
-                ..Default::default()
-            });
-        }
+                end: Position {
+                    line: *line_num,
+                    character: char_start + owner_len,
+                },
+            },
+            severity: Some(severity),
+            code: Some(NumberOrString::String(code.to_string())),
+            source: Some("codeowners".to_string()),
+            message,
+            ..Default::default()
+        });
     }
 
     diagnostics
@@ -335,6 +450,27 @@ fn should_fail(diagnostics: &[Diagnostic], strict: bool) -> bool {
     has_errors || (strict && has_warnings)
 }
 
+/// Failing lint is a finding, or invalid owners if GitHub rejected any
+/// owner at a failing severity
+fn exit_status(diagnostics: &[Diagnostic], strict: bool) -> Exit {
+    if !should_fail(diagnostics, strict) {
+        return Exit::Success;
+    }
+    let failing = |d: &&Diagnostic| {
+        d.severity == Some(DiagnosticSeverity::ERROR)
+            || (strict && d.severity == Some(DiagnosticSeverity::WARNING))
+    };
+    let rejected_owner = diagnostics.iter().filter(failing).any(|d| {
+        matches!(&d.code, Some(NumberOrString::String(code))
+            if code == codes::GITHUB_OWNER_NOT_FOUND || code == codes::GITHUB_OWNER_SUSPENDED)
+    });
+    if rejected_owner {
+        Exit::InvalidOwners
+    } else {
+        Exit::Findings
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -399,6 +535,36 @@ mod tests {
         assert!(should_fail(&diags, true));
     }
 
+    #[test]
+    fn test_exit_status_distinguishes_invalid_owners() {
+        use std::slice;
+
+        let with_code = |severity, code: &str| Diagnostic {
+            code: Some(NumberOrString::String(code.to_string())),
+            ..make_diag(severity)
+        };
+        let syntax = with_code(DiagnosticSeverity::ERROR, codes::INVALID_PATTERN);
+        let not_found = with_code(DiagnosticSeverity::ERROR, codes::GITHUB_OWNER_NOT_FOUND);
+        let suspended = with_code(DiagnosticSeverity::WARNING, codes::GITHUB_OWNER_SUSPENDED);
+
+        assert_eq!(exit_status(&[], false), Exit::Success);
+        assert_eq!(exit_status(slice::from_ref(&syntax), false), Exit::Findings);
+        assert_eq!(
+            exit_status(slice::from_ref(&not_found), false),
+            Exit::InvalidOwners
+        );
+        assert_eq!(
+            exit_status(&[syntax, not_found], false),
+            Exit::InvalidOwners
+        );
+        // A warning only counts with --strict
+        assert_eq!(
+            exit_status(slice::from_ref(&suspended), false),
+            Exit::Success
+        );
+        assert_eq!(exit_status(&[suspended], true), Exit::InvalidOwners);
+    }
+
     // =========================================================================
     // owner_diagnostics_from_cache tests
     // =========================================================================
@@ -413,11 +579,29 @@ mod tests {
         client
     }
 
+    #[test]
+    fn test_owner_positions_after_byte_order_mark() {
+        let content = "\u{feff}* @ghost @x\n/docs/ @ghost\n";
+        let owners = owner_positions(content);
+        assert_eq!(
+            owners,
+            vec![
+                ("@ghost".to_string(), 0, 5, 6),
+                ("@x".to_string(), 0, 12, 2),
+                ("@ghost".to_string(), 1, 7, 6),
+            ]
+        );
+        for (owner, line, start, len) in &owners {
+            let text = content.lines().nth(*line as usize).unwrap();
+            assert_eq!(&text[*start as usize..(start + len) as usize], owner);
+        }
+    }
+
     #[test]
     fn test_invalid_owner_generates_diagnostic() {
         let client = make_client_with_cache(vec![("@ghost", OwnerInfo::Invalid)]);
         let owners = vec![("@ghost".to_string(), 5, 10, 6)];
-        let diags = owner_diagnostics_from_cache(&owners, &client);
+        let diags = owner_diagnostics_from_cache(&owners, &client, &DiagnosticConfig::default());
 
         assert_eq!(diags.len(), 1);
         assert_eq!(diags[0].message, "Owner '@ghost' not found on GitHub");
@@ -429,6 +613,30 @@ mod tests {
         assert_eq!(diags[0].range.start.character, 10);
     }
 
+    #[test]
+    fn test_suspended_owner_generates_diagnostic() {
+        let client = make_client_with_cache(vec![(
+            "@frozen",
+            OwnerInfo::Suspended(UserInfo {
+                login: "frozen".to_string(),
+                name: None,
+                html_url: "https://github.com/frozen".to_string(),
+                avatar_url: None,
+                bio: None,
+                company: None,
+            }),
+        )]);
+        let owners = vec![("@frozen".to_string(), 2, 4, 7)];
+        let diags = owner_diagnostics_from_cache(&owners, &client, &DiagnosticConfig::default());
+
+        assert_eq!(diags.len(), 1);
+        assert_eq!(
+            diags[0].code,
+            Some(NumberOrString::String("github-owner-suspended".to_string()))
+        );
+        assert!(diags[0].message.contains("suspended"));
+    }
+
     /// Critical regression test: Unknown teams (404 ambiguous) must NOT
     /// generate "not found" diagnostics.
     #[test]
@@ -438,7 +646,7 @@ mod tests {
             OwnerInfo::Unknown("team not found or token lacks read:org scope".into()),
         )]);
         let owners = vec![("@org/invisible-team".to_string(), 3, 8, 19)];
-        let diags = owner_diagnostics_from_cache(&owners, &client);
+        let diags = owner_diagnostics_from_cache(&owners, &client, &DiagnosticConfig::default());
 
         assert!(
             diags.is_empty(),
@@ -460,16 +668,32 @@ mod tests {
             }),
         )]);
         let owners = vec![("@alice".to_string(), 1, 5, 6)];
-        let diags = owner_diagnostics_from_cache(&owners, &client);
+        let diags = owner_diagnostics_from_cache(&owners, &client, &DiagnosticConfig::default());
 
         assert!(diags.is_empty());
     }
 
+    #[test]
+    fn test_me_in_codeowners_is_not_found() {
+        let client = GitHubClient::new();
+        let owners = vec![("@me".to_string(), 0, 2, 3)];
+        let diags = owner_diagnostics_from_cache(&owners, &client, &DiagnosticConfig::default());
+
+        assert_eq!(diags.len(), 1);
+        assert_eq!(
+            diags[0].code,
+            Some(NumberOrString::String("github-owner-not-found".to_string()))
+        );
+        assert!(diags[0]
+            .message
+            .contains("only means you on the command line"));
+    }
+
     #[test]
     fn test_uncached_owner_does_not_generate_diagnostic() {
         let client = GitHubClient::new(); // empty cache
         let owners = vec![("@org/not-yet-checked".to_string(), 2, 10, 20)];
-        let diags = owner_diagnostics_from_cache(&owners, &client);
+        let diags = owner_diagnostics_from_cache(&owners, &client, &DiagnosticConfig::default());
 
         assert!(
             diags.is_empty(),
@@ -517,7 +741,7 @@ mod tests {
             ("@truly-gone".to_string(), 4, 10, 11),
             ("@not-cached".to_string(), 5, 10, 11),
         ];
-        let diags = owner_diagnostics_from_cache(&owners, &client);
+        let diags = owner_diagnostics_from_cache(&owners, &client, &DiagnosticConfig::default());
 
         assert_eq!(
             diags.len(),
@@ -527,4 +751,59 @@ mod tests {
         assert_eq!(diags[0].message, "Owner '@truly-gone' not found on GitHub");
         assert_eq!(diags[0].range.start.line, 4);
     }
+
+    fn config(overrides: &[(&str, &str)]) -> DiagnosticConfig {
+        DiagnosticConfig::from_map(
+            &overrides
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_owner_severity_config_controls_exit_code() {
+        let client = make_client_with_cache(vec![("@ghost", OwnerInfo::Invalid)]);
+        let owners = vec![("@ghost".to_string(), 0, 4, 6)];
+
+        // Default warning doesn't fail
+        let diags = owner_diagnostics_from_cache(&owners, &client, &config(&[]));
+        assert!(!should_fail(&diags, false));
+
+        // Promoted to error, it does
+        let promoted = config(&[(codes::GITHUB_OWNER_NOT_FOUND, "error")]);
+        let diags = owner_diagnostics_from_cache(&owners, &client, &promoted);
+        assert_eq!(diags[0].severity, Some(DiagnosticSeverity::ERROR));
+        assert!(should_fail(&diags, false));
+
+        // Turned off, nothing is reported even in strict mode
+        let off = config(&[(codes::GITHUB_OWNER_NOT_FOUND, "off")]);
+        let diags = owner_diagnostics_from_cache(&owners, &client, &off);
+        assert!(diags.is_empty());
+        assert!(!should_fail(&diags, true));
+    }
+
+    #[test]
+    fn test_rule_severity_config_controls_exit_code() {
+        // An invalid owner is an error by default
+        let content = "*.rs not-an-owner\n";
+        let lint = |config: &DiagnosticConfig| {
+            let (diags, _) = diagnostics::compute_diagnostics_sync(content, None, config);
+            should_fail(&diags, false)
+        };
+        assert!(lint(&config(&[])));
+        assert!(!lint(&config(&[(codes::INVALID_OWNER, "warning")])));
+        assert!(!lint(&config(&[(codes::INVALID_OWNER, "off")])));
+
+        // A warning promoted to error fails the run
+        let content = "*.rs @a @a\n";
+        let (diags, _) = diagnostics::compute_diagnostics_sync(content, None, &config(&[]));
+        assert!(!should_fail(&diags, false));
+        let (diags, _) = diagnostics::compute_diagnostics_sync(
+            content,
+            None,
+            &config(&[(codes::DUPLICATE_OWNER, "error")]),
+        );
+        assert!(should_fail(&diags, false));
+    }
 }
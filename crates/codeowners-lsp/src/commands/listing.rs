@@ -0,0 +1,88 @@
+//! `--top` and `--sort` handling shared by commands that print long lists.
+//!
+//! Both only shape human output; `--json` always carries the full list.
+
+use std::cmp::Reverse;
+use std::fs;
+use std::path::Path;
+
+use colored::Colorize;
+
+/// Order for a printed list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortBy {
+    /// Alphabetical
+    #[default]
+    Name,
+    /// Largest file on disk first
+    Size,
+    /// Most files first
+    Count,
+}
+
+/// The first `top` items (all of them if `top` is 0) and how many are left out
+pub(super) fn top_n<T>(items: &[T], top: usize) -> (&[T], usize) {
+    if top == 0 || items.len() <= top {
+        (items, 0)
+    } else {
+        (&items[..top], items.len() - top)
+    }
+}
+
+/// Trailing line for items hidden by `--top`, if any
+pub(super) fn hidden_note(hidden: usize) -> Option<String> {
+    (hidden > 0).then(|| {
+        format!(
+            "    {} {}",
+            format!("… and {} more", hidden).dimmed(),
+            "(--top 0 shows all)".dimmed()
+        )
+    })
+}
+
+/// Sort repo-relative `files` by `sort`. Sizes are read from disk under
+/// `root`; files that can't be read count as empty. Ties keep name order.
+pub(super) fn sort_files(files: &mut [&str], sort: SortBy, root: &Path) {
+    files.sort_unstable();
+    if sort == SortBy::Size {
+        files.sort_by_cached_key(|file| {
+            Reverse(fs::metadata(root.join(file)).map_or(0, |m| m.len()))
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_n() {
+        let items = [1, 2, 3, 4, 5];
+        assert_eq!(top_n(&items, 0), (&items[..], 0));
+        assert_eq!(top_n(&items, 2), (&items[..2], 3));
+        assert_eq!(top_n(&items, 5), (&items[..], 0));
+        assert_eq!(top_n(&items, 10), (&items[..], 0));
+        assert_eq!(top_n::<i32>(&[], 3), (&[][..], 0));
+    }
+
+    #[test]
+    fn test_hidden_note() {
+        assert!(hidden_note(0).is_none());
+        assert!(hidden_note(3).unwrap().contains("3 more"));
+    }
+
+    #[test]
+    fn test_sort_files_by_name_and_size() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("small.txt"), "a").unwrap();
+        fs::write(dir.path().join("big.txt"), "a".repeat(100)).unwrap();
+        fs::write(dir.path().join("mid.txt"), "a".repeat(10)).unwrap();
+
+        let mut files = vec!["small.txt", "missing.txt", "big.txt", "mid.txt"];
+        sort_files(&mut files, SortBy::Name, dir.path());
+        assert_eq!(files, ["big.txt", "mid.txt", "missing.txt", "small.txt"]);
+
+        sort_files(&mut files, SortBy::Size, dir.path());
+        assert_eq!(files, ["big.txt", "mid.txt", "small.txt", "missing.txt"]);
+    }
+}
@@ -0,0 +1,309 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::{env, fs};
+
+use colored::Colorize;
+
+use super::exit::Exit;
+use crate::ownership::{find_codeowners, replace_owners};
+use crate::parser::{parse_codeowners_file_with_positions, CodeownersLine};
+use crate::validation::{canonical_owner, classify_owner, OwnerKind};
+
+/// Options for the migrate-teams command
+#[derive(Debug, Clone, Default)]
+pub struct MigrateTeamsOptions {
+    /// JSON or TOML file mapping individual owners to teams
+    pub mapping: PathBuf,
+    /// CODEOWNERS file (default: auto-detected)
+    pub path: Option<PathBuf>,
+    /// Apply the changes instead of showing a diff
+    pub write: bool,
+}
+
+/// Result of rewriting CODEOWNERS content with a user → team mapping
+#[derive(Debug, Default)]
+struct Migration {
+    content: String,
+    /// Number of owner tokens replaced by a team
+    replaced: usize,
+    /// Individual owners with no mapping -> 1-based lines they appear on
+    unmapped: BTreeMap<String, Vec<u32>>,
+}
+
+/// Load a flat `{ "@user": "@org/team" }` mapping from JSON, or TOML when the
+/// file ends in `.toml`. Keys may omit the `@` and are matched
+/// case-insensitively; values must be `@org/team` owners.
+fn load_mapping(path: &Path) -> Result<HashMap<String, String>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let raw: HashMap<String, String> = if path.extension().is_some_and(|ext| ext == "toml") {
+        toml::from_str(&content)
+            .map_err(|e| format!("Invalid TOML in {}: {}", path.display(), e))?
+    } else {
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e))?
+    };
+
+    raw.into_iter()
+        .map(|(user, team)| {
+            let user = with_at(&user);
+            let team = with_at(&team);
+            if classify_owner(&team) != Some(OwnerKind::Team) {
+                return Err(format!(
+                    "Mapping for '{}' must be an @org/team owner, got '{}'",
+                    user, team
+                ));
+            }
+            Ok((canonical_owner(&user).into_owned(), team))
+        })
+        .collect()
+}
+
+/// Emails are used as-is; bare handles get a leading `@`
+fn with_at(owner: &str) -> String {
+    if owner.starts_with('@') || owner.contains('@') {
+        owner.to_string()
+    } else {
+        format!("@{}", owner)
+    }
+}
+
+/// Replace mapped individual owners with their team, deduping the owners of
+/// each rewritten line. Only the owner tokens are touched: patterns, spacing
+/// around them, inline comments and other lines are left as written.
+fn migrate(content: &str, mapping: &HashMap<String, String>) -> Migration {
+    let mut migration = Migration::default();
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+
+    for parsed in parse_codeowners_file_with_positions(content) {
+        let CodeownersLine::Rule { owners, .. } = &parsed.content else {
+            continue;
+        };
+
+        let mut changed = false;
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut rewritten: Vec<&str> = Vec::new();
+        for owner in owners {
+            let owner = match classify_owner(owner) {
+                Some(OwnerKind::User | OwnerKind::Email) => {
+                    match mapping.get(canonical_owner(owner).as_ref()) {
+                        Some(team) => {
+                            migration.replaced += 1;
+                            changed = true;
+                            team.as_str()
+                        }
+                        None => {
+                            migration
+                                .unmapped
+                                .entry(owner.clone())
+                                .or_default()
+                                .push(parsed.line_number + 1);
+                            owner.as_str()
+                        }
+                    }
+                }
+                _ => owner.as_str(),
+            };
+            if seen.insert(canonical_owner(owner).into_owned()) {
+                rewritten.push(owner);
+            }
+        }
+        if !changed {
+            continue;
+        }
+
+        let line = &mut lines[parsed.line_number as usize];
+        *line = replace_owners(line, parsed.pattern_end as usize, &rewritten);
+    }
+
+    migration.content = lines.join("\n");
+    if content.ends_with('\n') {
+        migration.content.push('\n');
+    }
+    migration
+}
+
+pub fn migrate_teams(options: MigrateTeamsOptions) -> ExitCode {
+    let cwd = env::current_dir().expect("Failed to get current directory");
+
+    let codeowners_path = match options.path.or_else(|| find_codeowners(&cwd)) {
+        Some(p) => p,
+        None => {
+            eprintln!("No CODEOWNERS file found");
+            return Exit::Error.into();
+        }
+    };
+
+    let content = match fs::read_to_string(&codeowners_path) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", codeowners_path.display(), e);
+            return Exit::Error.into();
+        }
+    };
+
+    let mapping = match load_mapping(&options.mapping) {
+        Ok(m) => m,
+        Err(e) => {
+            eprintln!("{}", e);
+            return Exit::Error.into();
+        }
+    };
+
+    let migration = migrate(&content, &mapping);
+
+    if migration.content == content {
+        println!(
+            "{} No mapped owners found in {}",
+            "✓".green(),
+            codeowners_path.display()
+        );
+    } else if options.write {
+        if let Err(e) = fs::write(&codeowners_path, &migration.content) {
+            eprintln!(
+                "{} Failed to write {}: {}",
+                "✗".red(),
+                codeowners_path.display(),
+                e
+            );
+            return Exit::Error.into();
+        }
+        println!(
+            "{} Replaced {} owner{} with teams in {}",
+            "✓".green(),
+            migration.replaced,
+            if migration.replaced == 1 { "" } else { "s" },
+            codeowners_path.display()
+        );
+    } else {
+        println!(
+            "Would migrate {}:\n",
+            codeowners_path.display().to_string().bold()
+        );
+        println!("{}", "--- original".red());
+        println!("{}\n", "+++ migrated".green());
+
+        for (old, new) in content.lines().zip(migration.content.lines()) {
+            if old != new {
+                println!("{}", format!("-{}", old).red());
+                println!("{}", format!("+{}", new).green());
+            }
+        }
+
+        println!(
+            "\nRun with {} or {} to apply changes",
+            "--write".cyan(),
+            "-w".cyan()
+        );
+    }
+
+    if !migration.unmapped.is_empty() {
+        println!("\n{} (left unchanged):", "Unmapped owners".yellow().bold());
+        for (owner, lines) in &migration.unmapped {
+            let lines: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+            println!(
+                "  {} {}",
+                owner,
+                format!("(line {})", lines.join(", ")).dimmed()
+            );
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(user, team)| (user.to_string(), team.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_maps_users_to_teams() {
+        let content = "# Owners\n*.rs @alice @org/core\n/docs/  @bob   # docs team\n";
+        let migration = migrate(
+            content,
+            &mapping(&[("@alice", "@org/rust"), ("@bob", "@org/docs")]),
+        );
+        assert_eq!(
+            migration.content,
+            "# Owners\n*.rs @org/rust @org/core\n/docs/  @org/docs   # docs team\n"
+        );
+        assert_eq!(migration.replaced, 2);
+        assert!(migration.unmapped.is_empty());
+    }
+
+    #[test]
+    fn test_keeps_byte_order_mark() {
+        let content = "\u{feff}* @x # all\n/docs/ @x\n";
+        let migration = migrate(content, &mapping(&[("@x", "@org/y")]));
+        assert_eq!(migration.content, "\u{feff}* @org/y # all\n/docs/ @org/y\n");
+        assert_eq!(migration.replaced, 2);
+    }
+
+    #[test]
+    fn test_dedupes_owners_on_rewritten_lines() {
+        let content = "*.rs @alice @bob @org/rust\n*.md @org/docs @org/docs\n";
+        let migration = migrate(
+            content,
+            &mapping(&[("@alice", "@org/rust"), ("@bob", "@org/rust")]),
+        );
+        // Untouched lines keep their duplicates; that's `lint --fix`'s job
+        assert_eq!(
+            migration.content,
+            "*.rs @org/rust\n*.md @org/docs @org/docs\n"
+        );
+        assert_eq!(migration.replaced, 2);
+    }
+
+    #[test]
+    fn test_reports_unmapped_individuals() {
+        let content = "*.rs @alice @carol\n*.md @Carol dev@example.com @org/docs\n";
+        let migration = migrate(content, &mapping(&[("@alice", "@org/rust")]));
+        assert_eq!(
+            migration.content,
+            "*.rs @org/rust @carol\n*.md @Carol dev@example.com @org/docs\n"
+        );
+        assert_eq!(
+            migration.unmapped.into_iter().collect::<Vec<_>>(),
+            vec![
+                ("@Carol".to_string(), vec![2]),
+                ("@carol".to_string(), vec![1]),
+                ("dev@example.com".to_string(), vec![2]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mapping_is_case_insensitive() {
+        let migration = migrate("* @Alice\n", &mapping(&[("@alice", "@org/team")]));
+        assert_eq!(migration.content, "* @org/team\n");
+    }
+
+    #[test]
+    fn test_load_mapping_json_and_toml() {
+        let dir = tempfile::tempdir().unwrap();
+        let json = dir.path().join("teams.json");
+        fs::write(
+            &json,
+            r#"{ "Alice": "@org/rust", "dev@example.com": "org/docs" }"#,
+        )
+        .unwrap();
+        let loaded = load_mapping(&json).unwrap();
+        assert_eq!(loaded["@alice"], "@org/rust");
+        assert_eq!(loaded["dev@example.com"], "@org/docs");
+
+        let toml = dir.path().join("teams.toml");
+        fs::write(&toml, "\"@bob\" = \"@org/web\"\n").unwrap();
+        assert_eq!(load_mapping(&toml).unwrap()["@bob"], "@org/web");
+
+        fs::write(&toml, "bob = \"@carol\"\n").unwrap();
+        assert!(load_mapping(&toml).unwrap_err().contains("@org/team"));
+    }
+}
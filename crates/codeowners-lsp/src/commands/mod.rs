@@ -1,22 +1,45 @@
+mod annotations;
 mod check;
+pub mod color;
 mod config;
 mod coverage;
+pub mod exit;
+mod export;
 pub mod files;
 mod fmt;
 mod gha;
+mod init;
 mod lint;
+mod listing;
+mod migrate_teams;
 mod optimize;
+mod overlaps;
+pub mod profile;
+pub mod progress;
+mod prune_cache;
+pub mod repo_root;
+mod schema;
+mod stats;
 mod suggest;
 mod tree;
+mod tsv;
 mod validate_owners;
 
-pub use check::check;
+pub use check::{check, CheckOptions, OutputFormat as CheckFormat};
 pub use config::config;
-pub use coverage::coverage;
+pub use coverage::{coverage, CoverageOptions, OutputFormat as CoverageFormat};
+pub use export::{export, ExportOptions, OutputFormat as ExportFormat};
 pub use fmt::fmt;
 pub use gha::{gha, GhaOptions};
-pub use lint::lint;
+pub use init::{init, InitOptions};
+pub use lint::{lint, LintOptions};
+pub use listing::SortBy;
+pub use migrate_teams::{migrate_teams, MigrateTeamsOptions};
 pub use optimize::{optimize, OptimizeOptions, OutputFormat as OptimizeFormat};
+pub use overlaps::{overlaps, OverlapsOptions};
+pub use prune_cache::{prune_cache, PruneCacheOptions};
+pub use schema::{schema, SCHEMA_COMMANDS};
+pub use stats::{stats, StatsOptions};
 pub use suggest::{suggest, OutputFormat as SuggestFormat, SuggestOptions};
 pub use tree::tree;
-pub use validate_owners::validate_owners;
+pub use validate_owners::{validate_owners, ValidateOwnersOptions};
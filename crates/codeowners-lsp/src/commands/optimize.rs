@@ -5,16 +5,18 @@
 //! - Removing redundant/shadowed rules
 //! - Using more specific globs instead of listing files
 
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::process::ExitCode;
 use std::{env, fs};
 
 use colored::Colorize;
 
+use super::exit::Exit;
 use crate::file_cache::FileCache;
 use crate::ownership::{find_codeowners, get_repo_root};
 use crate::parser::{self, CodeownersLine, ParsedLine};
+use crate::settings::load_settings_from_path;
 
 /// A suggested optimization
 #[derive(Debug, Clone)]
@@ -80,7 +82,7 @@ pub fn optimize(options: OptimizeOptions) -> ExitCode {
         Some(p) => p,
         None => {
             eprintln!("{} No CODEOWNERS file found", "Error:".red().bold());
-            return ExitCode::from(1);
+            return Exit::Error.into();
         }
     };
 
@@ -93,12 +95,13 @@ pub fn optimize(options: OptimizeOptions) -> ExitCode {
                 codeowners_path.display(),
                 e
             );
-            return ExitCode::from(1);
+            return Exit::Error.into();
         }
     };
 
     let repo_root = get_repo_root(&codeowners_path, &cwd);
-    let file_cache = FileCache::new(&repo_root);
+    let pattern_options = load_settings_from_path(&repo_root).pattern_options();
+    let file_cache = FileCache::new(&repo_root).with_pattern_options(pattern_options);
     let lines = parser::parse_codeowners_file_with_positions(&content);
 
     // Find optimizations
@@ -132,7 +135,7 @@ pub fn optimize(options: OptimizeOptions) -> ExitCode {
                 codeowners_path.display(),
                 e
             );
-            return ExitCode::from(1);
+            return Exit::Error.into();
         }
         println!("\n{} Written to {}", "✓".green(), codeowners_path.display());
     }
@@ -197,7 +200,7 @@ fn find_directory_consolidations(
     file_cache: &FileCache,
     options: &OptimizeOptions,
 ) -> Vec<Optimization> {
-    use crate::pattern::{pattern_matches, pattern_subsumes};
+    use crate::pattern::pattern_subsumes;
 
     let mut optimizations = Vec::new();
 
@@ -236,7 +239,8 @@ fn find_directory_consolidations(
         .collect();
 
     // Group by parent directory
-    let mut dir_to_rules: HashMap<String, Vec<(u32, &str, &[String], bool)>> = HashMap::new();
+    // Sorted, so suggestions come out in the same order every run
+    let mut dir_to_rules: BTreeMap<String, Vec<(u32, &str, &[String], bool)>> = BTreeMap::new();
 
     for (line_num, pattern, owners, anchored) in &file_rules {
         let clean_pattern = pattern.trim_start_matches('/');
@@ -276,12 +280,13 @@ fn find_directory_consolidations(
         }
 
         // Check each file is matched by exactly one of our rules
-        let rule_patterns: Vec<&str> = rules.iter().map(|(_, p, _, _)| *p).collect();
-        let all_covered = all_files_in_dir.iter().all(|file| {
-            rule_patterns
-                .iter()
-                .any(|pattern| pattern_matches(pattern, file))
-        });
+        let rule_patterns: Vec<_> = rules
+            .iter()
+            .map(|(_, p, _, _)| file_cache.compile(p))
+            .collect();
+        let all_covered = all_files_in_dir
+            .iter()
+            .all(|file| rule_patterns.iter().any(|pattern| pattern.matches(file)));
 
         if !all_covered {
             continue;
@@ -0,0 +1,353 @@
+use std::collections::BTreeMap;
+use std::process::ExitCode;
+use std::{env, fs};
+
+use colored::Colorize;
+use serde::Serialize;
+
+use super::exit::Exit;
+use crate::file_cache::FileCache;
+use crate::ownership::{find_all_codeowners, get_repo_root, CodeownersFile};
+use crate::parser::{CodeownersLine, ParsedLine};
+use crate::settings::load_settings_from_path;
+
+/// Maximum number of example files shown per conflict in human output
+const MAX_EXAMPLES: usize = 5;
+
+#[derive(Serialize)]
+struct OverlapsJson<'a> {
+    codeowners: String,
+    total_files: usize,
+    contested_files: usize,
+    conflicts: Vec<ConflictJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct ConflictJson<'a> {
+    winner: RuleJson<'a>,
+    overridden: RuleJson<'a>,
+    count: usize,
+    files: &'a [&'a str],
+}
+
+#[derive(Serialize)]
+struct RuleJson<'a> {
+    rule: &'a str,
+    line: u32,
+    owners: &'a [String],
+}
+
+impl<'a> From<&'a ParsedLine> for RuleJson<'a> {
+    fn from(line: &'a ParsedLine) -> Self {
+        let (pattern, owners) = rule_parts(line);
+        RuleJson {
+            rule: pattern,
+            line: line.line_number + 1,
+            owners,
+        }
+    }
+}
+
+fn rule_parts(line: &ParsedLine) -> (&str, &[String]) {
+    match &line.content {
+        CodeownersLine::Rule { pattern, owners } => (pattern, owners),
+        _ => ("", &[]),
+    }
+}
+
+/// Owner sets share nobody (GitHub handles are case-insensitive)
+fn disjoint(a: &[String], b: &[String]) -> bool {
+    !a.iter()
+        .any(|x| b.iter().any(|y| x.eq_ignore_ascii_case(y)))
+}
+
+/// Files whose winning rule silently overrides an earlier matching rule
+/// owned by entirely different people
+struct Overlaps<'a> {
+    contested_files: usize,
+    /// (winning rule, overridden rule) -> files, ordered by line numbers
+    conflicts: BTreeMap<(u32, u32), (&'a ParsedLine, &'a ParsedLine, Vec<&'a str>)>,
+}
+
+/// Default-owner rules that every other rule is expected to override
+fn is_catch_all(pattern: &str) -> bool {
+    matches!(pattern, "*" | "**" | "/**")
+}
+
+/// A file is contested when an earlier matching rule's owners are disjoint
+/// from the winning rule's. Rules without owners are ignored: explicitly
+/// unowning a path (e.g. `/vendor/`) is deliberate, not a conflict. So are
+/// catch-all defaults like `*`, which exist to be overridden.
+fn find_overlaps<'a>(codeowners: &'a CodeownersFile, files: &'a [String]) -> Overlaps<'a> {
+    let mut overlaps = Overlaps {
+        contested_files: 0,
+        conflicts: BTreeMap::new(),
+    };
+
+    for file in files {
+        let matches = codeowners.matching_rules(file);
+        let Some((winner, earlier)) = matches.split_last() else {
+            continue;
+        };
+        let winner_owners = rule_parts(winner).1;
+        if winner_owners.is_empty() {
+            continue;
+        }
+
+        let mut contested = false;
+        for rule in earlier {
+            let (pattern, owners) = rule_parts(rule);
+            if owners.is_empty() || is_catch_all(pattern) || !disjoint(winner_owners, owners) {
+                continue;
+            }
+            contested = true;
+            overlaps
+                .conflicts
+                .entry((winner.line_number, rule.line_number))
+                .or_insert_with(|| (*winner, *rule, Vec::new()))
+                .2
+                .push(file);
+        }
+        if contested {
+            overlaps.contested_files += 1;
+        }
+    }
+
+    overlaps
+}
+
+/// Options for the overlaps command
+#[derive(Debug, Clone, Default)]
+pub struct OverlapsOptions {
+    /// Output as JSON
+    pub json: bool,
+}
+
+pub fn overlaps(options: OverlapsOptions) -> ExitCode {
+    let cwd = env::current_dir().expect("Failed to get current directory");
+
+    let all_codeowners = find_all_codeowners(&cwd);
+    let codeowners_path = match all_codeowners.first() {
+        Some(p) => p.clone(),
+        None => {
+            eprintln!("No CODEOWNERS file found");
+            return Exit::Error.into();
+        }
+    };
+    for ignored in &all_codeowners[1..] {
+        eprintln!(
+            "{} ignoring {} ({} takes precedence)",
+            "warning:".yellow().bold(),
+            ignored.display(),
+            codeowners_path.display()
+        );
+    }
+
+    let content = match fs::read_to_string(&codeowners_path) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", codeowners_path.display(), e);
+            return Exit::Error.into();
+        }
+    };
+
+    let repo_root = get_repo_root(&codeowners_path, &cwd);
+    let file_cache = FileCache::new(&repo_root);
+    let pattern_options = load_settings_from_path(&repo_root).pattern_options();
+    let codeowners = CodeownersFile::parse_with_options(&content, pattern_options);
+
+    let mut files = file_cache.all_files().to_vec();
+    files.sort();
+
+    let overlaps = find_overlaps(&codeowners, &files);
+
+    if options.json {
+        let output = OverlapsJson {
+            codeowners: codeowners_path.display().to_string(),
+            total_files: files.len(),
+            contested_files: overlaps.contested_files,
+            conflicts: overlaps
+                .conflicts
+                .values()
+                .map(|(winner, overridden, files)| ConflictJson {
+                    winner: RuleJson::from(*winner),
+                    overridden: RuleJson::from(*overridden),
+                    count: files.len(),
+                    files,
+                })
+                .collect(),
+        };
+        println!(
+            "{}",
+            serde_json::to_string(&output).expect("Failed to serialize JSON")
+        );
+    } else {
+        output_human(&overlaps, files.len());
+    }
+
+    if overlaps.contested_files == 0 {
+        ExitCode::SUCCESS
+    } else {
+        Exit::Findings.into()
+    }
+}
+
+fn format_rule(line: &ParsedLine) -> String {
+    let (pattern, owners) = rule_parts(line);
+    format!(
+        "{} {} {}",
+        pattern.cyan(),
+        owners.join(" "),
+        format!("(line {})", line.line_number + 1).dimmed()
+    )
+}
+
+fn output_human(overlaps: &Overlaps, total_files: usize) {
+    println!();
+    if overlaps.conflicts.is_empty() {
+        println!(
+            "  {} No contested files: every override keeps an owner in common",
+            "✓".green()
+        );
+        println!();
+        return;
+    }
+
+    for (winner, overridden, files) in overlaps.conflicts.values() {
+        println!("  {}", format_rule(winner));
+        println!(
+            "    {} {} {}",
+            "overrides".yellow(),
+            format_rule(overridden),
+            format!(
+                "- {} file{}",
+                files.len(),
+                if files.len() == 1 { "" } else { "s" }
+            )
+            .bold()
+        );
+        for file in files.iter().take(MAX_EXAMPLES) {
+            println!("      {} {}", "•".dimmed(), file);
+        }
+        if files.len() > MAX_EXAMPLES {
+            println!(
+                "      {}",
+                format!("… and {} more", files.len() - MAX_EXAMPLES).dimmed()
+            );
+        }
+        println!();
+    }
+
+    println!(
+        "  {} {} of {} files contested by rules with different owners",
+        "✗".red(),
+        overlaps.contested_files.to_string().red().bold(),
+        total_files
+    );
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn conflicts(content: &str, list: &[&str]) -> (usize, Vec<(u32, u32, Vec<String>)>) {
+        let codeowners = CodeownersFile::parse(content);
+        let files = files(list);
+        let overlaps = find_overlaps(&codeowners, &files);
+        (
+            overlaps.contested_files,
+            overlaps
+                .conflicts
+                .values()
+                .map(|(w, o, f)| {
+                    (
+                        w.line_number + 1,
+                        o.line_number + 1,
+                        f.iter().map(|s| s.to_string()).collect(),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_overlapping_rules_different_teams() {
+        let (contested, found) = conflicts(
+            "/src/ @org/backend\n/src/web/ @org/frontend\n",
+            &["src/lib.rs", "src/web/app.ts", "src/web/ui.ts"],
+        );
+        assert_eq!(contested, 2);
+        assert_eq!(
+            found,
+            vec![(
+                2,
+                1,
+                vec!["src/web/app.ts".to_string(), "src/web/ui.ts".to_string()]
+            )]
+        );
+    }
+
+    #[test]
+    fn test_shared_owner_is_not_contested() {
+        let (contested, found) = conflicts(
+            "/src/ @org/backend\n/src/web/ @org/frontend @ORG/Backend\n",
+            &["src/web/app.ts"],
+        );
+        assert_eq!(contested, 0);
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn test_unowned_and_catch_all_rules_ignored() {
+        let (contested, _) = conflicts(
+            "* @org/core\n/vendor/\n/docs/\n/docs/api/ @org/docs\n",
+            &["vendor/dep.c", "docs/api/index.md", "README.md"],
+        );
+        assert_eq!(contested, 0);
+    }
+
+    #[test]
+    fn test_file_counted_once_across_conflicts() {
+        let (contested, found) = conflicts(
+            "/src/ @org/core\n*.ts @org/web\n/src/web/ @org/frontend\n",
+            &["src/web/app.ts", "README.md"],
+        );
+        assert_eq!(contested, 1);
+        assert_eq!(
+            found,
+            vec![
+                (3, 1, vec!["src/web/app.ts".to_string()]),
+                (3, 2, vec!["src/web/app.ts".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_shape() {
+        let codeowners = CodeownersFile::parse("/src/ @a\n/src/web/ @b\n");
+        let files = files(&["src/web/x.ts"]);
+        let overlaps = find_overlaps(&codeowners, &files);
+        let (winner, overridden, list) = overlaps.conflicts.values().next().unwrap();
+        let json = serde_json::to_value(ConflictJson {
+            winner: RuleJson::from(*winner),
+            overridden: RuleJson::from(*overridden),
+            count: list.len(),
+            files: list,
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "winner": { "rule": "/src/web/", "line": 2, "owners": ["@b"] },
+                "overridden": { "rule": "/src/", "line": 1, "owners": ["@a"] },
+                "count": 1,
+                "files": ["src/web/x.ts"],
+            })
+        );
+    }
+}
@@ -0,0 +1,153 @@
+//! `--profile`: wall-clock time per phase, printed to stderr on exit.
+//!
+//! Commands wrap their expensive steps in [`time`] or [`time_async`] under a
+//! fixed set of phase names (walk, parse, match, validate, blame). With
+//! profiling off, each wrapper costs one atomic load.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Listing repository files (`FileCache`)
+pub(super) const WALK: &str = "walk";
+/// Parsing CODEOWNERS
+pub(super) const PARSE: &str = "parse";
+/// Matching files against rules
+pub(super) const MATCH: &str = "match";
+/// GitHub API requests
+pub(super) const VALIDATE: &str = "validate";
+/// git history analysis
+pub(super) const BLAME: &str = "blame";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static PHASES: Mutex<Vec<(&str, Duration)>> = Mutex::new(Vec::new());
+
+/// Start recording phase timings
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+fn record(phase: &'static str, elapsed: Duration) {
+    PHASES.lock().unwrap().push((phase, elapsed));
+}
+
+/// Run `f`, recording its duration under `phase` when profiling
+pub(super) fn time<T>(phase: &'static str, f: impl FnOnce() -> T) -> T {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return f();
+    }
+    let start = Instant::now();
+    let result = f();
+    record(phase, start.elapsed());
+    result
+}
+
+/// Await `future`, recording its duration under `phase` when profiling
+pub(super) async fn time_async<T>(phase: &'static str, future: impl Future<Output = T>) -> T {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return future.await;
+    }
+    let start = Instant::now();
+    let result = future.await;
+    record(phase, start.elapsed());
+    result
+}
+
+/// Print the recorded timings to stderr, if profiling
+pub fn print_report(total: Duration) {
+    if ENABLED.load(Ordering::Relaxed) {
+        eprint!("{}", format_report(&PHASES.lock().unwrap(), total));
+    }
+}
+
+/// Timings summed per phase in first-seen order, then the whole run
+fn format_report(phases: &[(&str, Duration)], total: Duration) -> String {
+    let mut summed: Vec<(&str, Duration, usize)> = Vec::new();
+    for &(phase, elapsed) in phases {
+        match summed.iter_mut().find(|(name, _, _)| *name == phase) {
+            Some((_, sum, count)) => {
+                *sum += elapsed;
+                *count += 1;
+            }
+            None => summed.push((phase, elapsed, 1)),
+        }
+    }
+
+    let mut report = String::from("Profile:\n");
+    for (phase, elapsed, count) in summed {
+        let calls = if count > 1 {
+            format!(" ({}×)", count)
+        } else {
+            String::new()
+        };
+        report.push_str(&format!(
+            "  {:<9} {:>10}{}\n",
+            phase,
+            millis(elapsed),
+            calls
+        ));
+    }
+    report.push_str(&format!("  {:<9} {:>10}\n", "total", millis(total)));
+    report
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.1}ms", duration.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_lists_phases_in_order_with_totals() {
+        let ms = Duration::from_millis;
+        let report = format_report(
+            &[
+                (WALK, ms(12)),
+                (PARSE, ms(1)),
+                (MATCH, ms(20)),
+                (MATCH, ms(10)),
+                (VALIDATE, ms(300)),
+                (BLAME, ms(40)),
+            ],
+            ms(400),
+        );
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines[0], "Profile:");
+        let labels: Vec<&str> = lines[1..]
+            .iter()
+            .map(|l| l.split_whitespace().next().unwrap())
+            .collect();
+        assert_eq!(
+            labels,
+            ["walk", "parse", "match", "validate", "blame", "total"]
+        );
+        assert!(lines[3].contains("30.0ms (2×)"), "{}", lines[3]);
+        assert!(lines[6].contains("400.0ms"), "{}", lines[6]);
+    }
+
+    /// Switches profiling back off when dropped, even if the test fails,
+    /// so other tests don't record timings
+    struct Disable;
+
+    impl Drop for Disable {
+        fn drop(&mut self) {
+            ENABLED.store(false, Ordering::Relaxed);
+            if let Ok(mut phases) = PHASES.lock() {
+                phases.clear();
+            }
+        }
+    }
+
+    #[test]
+    fn test_time_records_when_enabled() {
+        assert_eq!(time(PARSE, || 42), 42);
+        let _disable = Disable;
+        enable();
+        time(WALK, || ());
+        let recorded = PHASES.lock().unwrap().clone();
+        assert!(recorded.iter().any(|(phase, _)| *phase == WALK));
+    }
+}
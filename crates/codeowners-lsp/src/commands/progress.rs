@@ -0,0 +1,98 @@
+//! Progress bars for long runs (owner validation, history analysis).
+//!
+//! Bars go to stderr, and only when it is a terminal: with `--quiet`,
+//! machine-readable output or redirected stderr nothing is drawn, so
+//! scripts and JSON consumers never see them.
+
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use indicatif::{ProgressBar, ProgressStyle};
+
+use crate::blame::AnalysisProgress;
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Allow progress bars, decided once before any command runs
+pub fn init(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether bars should be drawn
+fn visible(enabled: bool, stderr_is_terminal: bool) -> bool {
+    enabled && stderr_is_terminal
+}
+
+/// Whether this run draws progress bars
+pub(super) fn enabled() -> bool {
+    visible(ENABLED.load(Ordering::Relaxed), io::stderr().is_terminal())
+}
+
+/// A bar counting `len` items with an ETA, or a hidden one when progress
+/// is disabled
+pub(super) fn bar(len: u64, message: &'static str) -> ProgressBar {
+    if !enabled() {
+        return ProgressBar::hidden();
+    }
+    let bar = ProgressBar::new(len);
+    bar.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} ({eta}) {msg}")
+            .unwrap()
+            .progress_chars("━╸─"),
+    );
+    bar.set_message(message);
+    bar
+}
+
+/// Advance `bar` through a history analysis, restarting it for each phase
+pub(super) fn track_analysis(bar: &ProgressBar, event: AnalysisProgress) {
+    match event {
+        AnalysisProgress::Directories(total) => restart(bar, total, "Analyzing directories"),
+        AnalysisProgress::Files(total) => restart(bar, total, "Analyzing files"),
+        AnalysisProgress::Analyzed => bar.inc(1),
+    }
+}
+
+fn restart(bar: &ProgressBar, total: usize, message: &'static str) {
+    bar.set_length(total as u64);
+    bar.set_position(0);
+    bar.reset_eta();
+    bar.set_message(message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visible_only_on_a_terminal_when_enabled() {
+        assert!(visible(true, true));
+        // Redirected stderr (CI logs, pipes)
+        assert!(!visible(true, false));
+        // --quiet or JSON/CSV output
+        assert!(!visible(false, true));
+        assert!(!visible(false, false));
+    }
+
+    #[test]
+    fn test_disabled_bars_are_hidden() {
+        let previous = ENABLED.load(Ordering::Relaxed);
+        init(false);
+        let (enabled, hidden) = (enabled(), bar(10, "Validating owners").is_hidden());
+        init(previous);
+        assert!(!enabled);
+        assert!(hidden);
+    }
+
+    #[test]
+    fn test_track_analysis_phases() {
+        let bar = ProgressBar::hidden();
+        track_analysis(&bar, AnalysisProgress::Directories(3));
+        track_analysis(&bar, AnalysisProgress::Analyzed);
+        assert_eq!((bar.position(), bar.length()), (1, Some(3)));
+        track_analysis(&bar, AnalysisProgress::Files(5));
+        assert_eq!((bar.position(), bar.length()), (0, Some(5)));
+        assert_eq!(bar.message(), "Analyzing files");
+    }
+}
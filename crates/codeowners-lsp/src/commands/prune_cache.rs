@@ -0,0 +1,185 @@
+use std::collections::HashSet;
+use std::process::ExitCode;
+use std::{env, fs};
+
+use colored::Colorize;
+
+use super::exit::Exit;
+use crate::github::PersistentCache;
+use crate::ownership::{find_codeowners, get_repo_root};
+use crate::parser::{parse_codeowners_file_with_positions, CodeownersLine};
+
+/// Options for the prune-cache command
+#[derive(Debug, Clone, Default)]
+pub struct PruneCacheOptions {
+    /// Report what would be removed without saving
+    pub dry_run: bool,
+}
+
+/// Every owner named by a rule in the CODEOWNERS content
+fn referenced_owners(content: &str) -> HashSet<String> {
+    parse_codeowners_file_with_positions(content)
+        .into_iter()
+        .filter_map(|line| match line.content {
+            CodeownersLine::Rule { owners, .. } => Some(owners),
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
+/// Drop cache entries for owners no longer referenced, in every scope,
+/// returning the removed owners in sorted order. Referenced entries are kept
+/// whatever their result, including `Unknown`.
+fn prune(cache: &mut PersistentCache, referenced: &HashSet<String>) -> Vec<String> {
+    let mut removed = Vec::new();
+    cache.retain(|owner, _| {
+        let keep = referenced.contains(owner);
+        if !keep {
+            removed.push(owner.to_string());
+        }
+        keep
+    });
+    removed.sort();
+    removed.dedup();
+    removed
+}
+
+pub fn prune_cache(options: PruneCacheOptions) -> ExitCode {
+    let cwd = env::current_dir().expect("Failed to get current directory");
+
+    let codeowners_path = match find_codeowners(&cwd) {
+        Some(p) => p,
+        None => {
+            eprintln!("No CODEOWNERS file found");
+            return Exit::Error.into();
+        }
+    };
+
+    let content = match fs::read_to_string(&codeowners_path) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", codeowners_path.display(), e);
+            return Exit::Error.into();
+        }
+    };
+
+    let repo_root = get_repo_root(&codeowners_path, &cwd);
+    if !PersistentCache::path(&repo_root).exists() {
+        println!("{}", "No validation cache to prune".dimmed());
+        return ExitCode::SUCCESS;
+    }
+
+    let mut cache = PersistentCache::load(&repo_root);
+    let total = cache.owner_count();
+    let removed = prune(&mut cache, &referenced_owners(&content));
+
+    for owner in &removed {
+        println!("  {} {}", "-".red(), owner);
+    }
+
+    if removed.is_empty() {
+        println!(
+            "{} All {} cached owners are still referenced",
+            "✓".green(),
+            total
+        );
+        return ExitCode::SUCCESS;
+    }
+
+    if options.dry_run {
+        println!(
+            "\nWould remove {} of {} cached owners",
+            removed.len().to_string().yellow(),
+            total
+        );
+        return ExitCode::SUCCESS;
+    }
+
+    if let Err(e) = cache.overwrite(&repo_root) {
+        eprintln!("Failed to save cache: {}", e);
+        return Exit::Error.into();
+    }
+    println!(
+        "\n{} Removed {} of {} cached owners",
+        "✓".green(),
+        removed.len().to_string().cyan(),
+        total
+    );
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::{cache_scope, OwnerInfo};
+
+    const HOST: &str = "api.github.com";
+
+    fn cache(entries: &[(&str, OwnerInfo)]) -> PersistentCache {
+        let mut cache = PersistentCache {
+            last_updated: 42,
+            ..Default::default()
+        };
+        for (owner, info) in entries {
+            cache.insert(HOST, owner, info.clone());
+        }
+        cache
+    }
+
+    #[test]
+    fn test_referenced_owners() {
+        let owners = referenced_owners("# @commented\n*.rs @a @org/b\n/docs/\n*.md @a dev@x.com\n");
+        let mut owners: Vec<_> = owners.into_iter().collect();
+        owners.sort();
+        assert_eq!(owners, vec!["@a", "@org/b", "dev@x.com"]);
+    }
+
+    #[test]
+    fn test_prune_keeps_referenced_and_drops_orphans() {
+        let mut cache = cache(&[
+            ("@a", OwnerInfo::Invalid),
+            ("@org/b", OwnerInfo::Unknown("team not found".into())),
+            ("@gone", OwnerInfo::Invalid),
+            ("@org/old", OwnerInfo::Unknown("rate limit exceeded".into())),
+        ]);
+
+        // The same owner cached for another token is pruned too, and
+        // reported once
+        let token_scope = cache_scope(HOST, Some("ghp_other"));
+        cache.insert(&token_scope, "@org/old", OwnerInfo::Invalid);
+        cache.insert(&token_scope, "@org/b", OwnerInfo::Invalid);
+
+        let removed = prune(&mut cache, &referenced_owners("*.rs @a @org/b\n"));
+
+        assert_eq!(removed, vec!["@gone", "@org/old"]);
+        assert_eq!(cache.owner_count(), 2);
+        assert!(cache.get(HOST, "@a").is_some());
+        // Still referenced, so kept even though unverified
+        assert!(matches!(
+            cache.get(HOST, "@org/b"),
+            Some(OwnerInfo::Unknown(_))
+        ));
+        assert!(cache.get(&token_scope, "@org/b").is_some());
+        // Pruning isn't a revalidation
+        assert_eq!(cache.last_updated, 42);
+    }
+
+    #[test]
+    fn test_prune_round_trips_through_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        cache(&[("@a", OwnerInfo::Invalid), ("@gone", OwnerInfo::Invalid)])
+            .save(dir.path())
+            .unwrap();
+
+        let mut loaded = PersistentCache::load(dir.path());
+        prune(&mut loaded, &referenced_owners("* @a\n"));
+        loaded.overwrite(dir.path()).unwrap();
+
+        let reloaded = PersistentCache::load(dir.path());
+        assert_eq!(
+            reloaded.scopes[HOST].keys().collect::<Vec<_>>(),
+            vec![&"@a".to_string()]
+        );
+    }
+}
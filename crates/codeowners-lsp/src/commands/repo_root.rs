@@ -0,0 +1,75 @@
+//! `--repo-root`: run as if started in another directory, like `git -C`.
+//!
+//! Commands discover CODEOWNERS, load settings and list files relative to
+//! the working directory, so the override simply moves the process there
+//! before any command runs. Relative paths given on the command line are
+//! then read from the root too.
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+use crate::ownership::CODEOWNERS_LOCATIONS;
+
+/// Check that `path` is a directory holding a CODEOWNERS file in one of the
+/// locations GitHub reads (unless `require_codeowners` is off, for `init`),
+/// returning it canonicalized
+pub fn resolve(path: &Path, require_codeowners: bool) -> Result<PathBuf, String> {
+    let root = path
+        .canonicalize()
+        .map_err(|e| format!("--repo-root {}: {}", path.display(), e))?;
+    if !root.is_dir() {
+        return Err(format!("--repo-root {} is not a directory", path.display()));
+    }
+    if require_codeowners && !CODEOWNERS_LOCATIONS.iter().any(|l| root.join(l).is_file()) {
+        return Err(format!(
+            "--repo-root {} has no CODEOWNERS (looked for {})",
+            path.display(),
+            CODEOWNERS_LOCATIONS.join(", ")
+        ));
+    }
+    Ok(root)
+}
+
+/// Validate `path` and make it the working directory
+pub fn enter(path: &Path, require_codeowners: bool) -> Result<(), String> {
+    let root = resolve(path, require_codeowners)?;
+    env::set_current_dir(&root).map_err(|e| format!("--repo-root {}: {}", root.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_resolve_finds_codeowners_locations() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join(".github")).unwrap();
+        fs::write(dir.path().join(".github/CODEOWNERS"), "* @core\n").unwrap();
+
+        let root = resolve(dir.path(), true).unwrap();
+        assert_eq!(root, dir.path().canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_resolve_errors() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let err = resolve(&dir.path().join("missing"), true).unwrap_err();
+        assert!(err.contains("missing"), "{err}");
+
+        let file = dir.path().join("file");
+        fs::write(&file, "").unwrap();
+        assert!(resolve(&file, true)
+            .unwrap_err()
+            .contains("is not a directory"));
+
+        let err = resolve(dir.path(), true).unwrap_err();
+        assert!(err.contains("has no CODEOWNERS"), "{err}");
+        assert!(err.contains(".github/CODEOWNERS"), "{err}");
+
+        // `init` creates the file, so only the directory has to exist
+        assert!(resolve(dir.path(), false).is_ok());
+    }
+}
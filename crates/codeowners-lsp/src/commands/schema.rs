@@ -0,0 +1,111 @@
+//! Schema command - prints JSON Schema documents for `--json` output.
+//!
+//! The schemas are derived from the serialized types in each command module,
+//! so they can't drift; tests there validate real output against them.
+
+use std::process::ExitCode;
+
+use schemars::generate::SchemaSettings;
+use schemars::JsonSchema;
+use serde_json::Value;
+
+use super::exit::Exit;
+use super::{check, coverage, suggest};
+
+/// Commands whose JSON output has a published schema
+pub const SCHEMA_COMMANDS: [&str; 3] = ["check", "coverage", "suggest"];
+
+/// JSON Schema (draft 2020-12) for a command's `--json` output
+fn schema_for(command: &str) -> Option<Value> {
+    match command {
+        "check" => Some(check::json_schema()),
+        "coverage" => Some(coverage::json_schema()),
+        "suggest" => Some(suggest::json_schema()),
+        _ => None,
+    }
+}
+
+pub fn schema(command: &str) -> ExitCode {
+    match schema_for(command) {
+        Some(schema) => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&schema).expect("Failed to serialize JSON")
+            );
+            ExitCode::SUCCESS
+        }
+        None => {
+            eprintln!(
+                "No schema for '{}' (expected one of: {})",
+                command,
+                SCHEMA_COMMANDS.join(", ")
+            );
+            Exit::Error.into()
+        }
+    }
+}
+
+/// JSON Schema (draft 2020-12) for `T` as it serializes, with the
+/// document's title and description
+pub(super) fn document<T: JsonSchema>(title: &str, description: &str) -> Value {
+    let mut schema = SchemaSettings::draft2020_12()
+        .for_serialize()
+        .into_generator()
+        .into_root_schema_for::<T>();
+    schema.insert("title".to_string(), title.into());
+    if !description.is_empty() {
+        schema.insert("description".to_string(), description.into());
+    }
+    schema.to_value()
+}
+
+/// Check `value` against `schema`, reporting the first violation
+#[cfg(test)]
+pub(super) fn validate(schema: &Value, value: &Value) -> Result<(), String> {
+    jsonschema::validate(schema, value).map_err(|e| format!("{}: {}", e.instance_path(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_schema_for_known_commands() {
+        for command in SCHEMA_COMMANDS {
+            let schema = schema_for(command).unwrap();
+            assert_eq!(
+                schema["$schema"],
+                "https://json-schema.org/draft/2020-12/schema"
+            );
+            assert!(schema["title"].as_str().unwrap().contains(command));
+        }
+        assert!(schema_for("lint").is_none());
+    }
+
+    #[test]
+    fn test_validate_rejects_contract_breaks() {
+        let schema = json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "required": ["owned"],
+            "properties": {
+                "owned": { "type": "boolean" },
+                "line": { "type": ["integer", "null"] },
+                "owners": { "type": "array", "items": { "$ref": "#/$defs/Owner" } }
+            },
+            "additionalProperties": false,
+            "$defs": { "Owner": { "type": "string" } }
+        });
+
+        assert!(validate(&schema, &json!({ "owned": true, "line": null })).is_ok());
+        assert!(validate(&schema, &json!({ "owned": true, "owners": ["@a"] })).is_ok());
+
+        let missing = validate(&schema, &json!({ "line": 3 })).unwrap_err();
+        assert!(missing.contains("\"owned\""), "{}", missing);
+        assert!(validate(&schema, &json!({ "owned": true, "new": 1 })).is_err());
+        assert!(validate(&schema, &json!({ "owned": true, "line": 1.5 })).is_err());
+        let item = validate(&schema, &json!({ "owned": true, "owners": [1] })).unwrap_err();
+        assert!(item.contains("/owners/0"), "{}", item);
+    }
+}
@@ -0,0 +1,1617 @@
+//! Stats command - summarizes how the ownership surface is distributed.
+//!
+//! Every file in the repository is assigned to the owners of its winning
+//! rule (last match wins). A file matched by a multi-owner rule counts
+//! toward each listed owner, so percentages can sum to more than 100%.
+//!
+//! With `--semantics gitlab`, a file's owners are the union of the winning
+//! rule in each GitLab section instead.
+//!
+//! The `--bus-factor` mode groups owned files by directory and flags
+//! directories whose only owner is a single person.
+//!
+//! The `--idle-owners` mode lists owners named on rules who end up owning no
+//! files, either because their rules match nothing or because later rules
+//! take every match.
+//!
+//! The `--members` mode expands teams into their GitHub members and splits
+//! each file between the people behind its owners, for review-load planning.
+//!
+//! The `--last-review` mode looks up the pull requests behind the latest
+//! commits to each directory and reports when each of its owners (or, for a
+//! team, any member) last reviewed one, to spot owners who no longer review
+//! the code they own.
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
+use std::process::{Command, ExitCode};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, fs};
+
+use colored::Colorize;
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+
+use super::exit::Exit;
+use super::listing::{hidden_note, top_n, SortBy};
+use super::profile;
+use crate::file_cache::FileCache;
+use crate::github::{GitHubClient, PersistentCache, PullReview};
+use crate::owner_index::{winning_owners, OwnerIndex};
+use crate::ownership::{find_codeowners, get_repo_root, CodeownersFile, Semantics};
+use crate::parser::CodeownersLine;
+use crate::settings::load_settings_from_path;
+use crate::validation::{classify_owner, OwnerKind};
+
+/// Ownership totals across a set of files
+#[derive(Debug, Default)]
+struct OwnershipStats {
+    total_files: usize,
+    unowned_files: usize,
+    /// (owner, file count), sorted by count descending then owner name
+    owners: Vec<(String, usize)>,
+}
+
+impl OwnershipStats {
+    fn owned_files(&self) -> usize {
+        self.total_files - self.unowned_files
+    }
+
+    fn percent(&self, count: usize) -> f64 {
+        if self.total_files == 0 {
+            0.0
+        } else {
+            (count as f64 / self.total_files as f64) * 100.0
+        }
+    }
+
+    /// The owner responsible for the most files (ties broken alphabetically)
+    fn largest(&self) -> Option<&(String, usize)> {
+        self.owners.first()
+    }
+}
+
+#[derive(Serialize)]
+struct OwnerStatsJson {
+    owner: String,
+    files: usize,
+    percent: f64,
+}
+
+#[derive(Serialize)]
+struct StatsJson {
+    total_files: usize,
+    owned_files: usize,
+    unowned_files: usize,
+    largest_owner: Option<OwnerStatsJson>,
+    owners: Vec<OwnerStatsJson>,
+}
+
+/// Options for the stats command
+#[derive(Debug, Clone)]
+pub struct StatsOptions {
+    /// Output as JSON
+    pub json: bool,
+    /// Report single-owner directories instead of per-owner totals
+    pub bus_factor: bool,
+    /// Directory depth to group files by in bus-factor mode
+    pub depth: usize,
+    /// Also flag directories owned by a single team
+    pub flag_teams: bool,
+    /// Report owners who own no files instead of per-owner totals
+    pub idle_owners: bool,
+    /// Expand teams to their members and report each person's share
+    pub members: bool,
+    /// Report when each directory's owners last reviewed a PR touching it
+    pub last_review: bool,
+    /// Recent PRs to look at per directory in last-review mode
+    pub prs: usize,
+    /// `owner/name` of the GitHub repository (defaults to the `origin` remote)
+    pub repo: Option<String>,
+    /// GitHub token for listing team members and reviews (falls back to config)
+    pub token: Option<String>,
+    /// Print at most this many rows (0 = all)
+    pub top: usize,
+    /// Order of owner and directory rows
+    pub sort: SortBy,
+    /// How owners are resolved when several rules match
+    pub semantics: Semantics,
+    /// Client for listing team members; share one to reuse its cache
+    pub github: Arc<GitHubClient>,
+}
+
+impl Default for StatsOptions {
+    fn default() -> Self {
+        Self {
+            json: false,
+            bus_factor: false,
+            depth: 1,
+            flag_teams: false,
+            idle_owners: false,
+            members: false,
+            last_review: false,
+            prs: 20,
+            repo: None,
+            token: None,
+            top: 0,
+            sort: SortBy::Count,
+            semantics: Semantics::GitHub,
+            github: Arc::default(),
+        }
+    }
+}
+
+pub async fn stats(options: StatsOptions) -> ExitCode {
+    let cwd = env::current_dir().expect("Failed to get current directory");
+
+    let codeowners_path = match find_codeowners(&cwd) {
+        Some(p) => p,
+        None => {
+            eprintln!("No CODEOWNERS file found");
+            return Exit::Error.into();
+        }
+    };
+
+    let content = match fs::read_to_string(&codeowners_path) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", codeowners_path.display(), e);
+            return Exit::Error.into();
+        }
+    };
+
+    let repo_root = get_repo_root(&codeowners_path, &cwd);
+    let pattern_options = load_settings_from_path(&repo_root).pattern_options();
+    let file_cache = profile::time(profile::WALK, || {
+        FileCache::new(&repo_root).with_pattern_options(pattern_options)
+    });
+    let codeowners = profile::time(profile::PARSE, || {
+        CodeownersFile::parse_with_options(&content, pattern_options)
+    });
+
+    if options.bus_factor {
+        let mut dirs = compute_bus_factor(
+            &codeowners,
+            file_cache.all_files(),
+            options.depth,
+            options.semantics,
+        );
+        if options.sort == SortBy::Name {
+            dirs.sort_by(|a, b| a.directory.cmp(&b.directory));
+        }
+        if options.json {
+            output_bus_factor_json(&dirs, &options);
+        } else {
+            output_bus_factor_human(&dirs, &options);
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    if options.idle_owners {
+        let (listed, idle) = compute_idle_owners(&codeowners, &file_cache, options.semantics);
+        if options.json {
+            output_idle_owners_json(listed, &idle);
+        } else {
+            output_idle_owners_human(listed, &idle, options.top);
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    if options.last_review {
+        return last_review(&codeowners, &file_cache, &repo_root, &options).await;
+    }
+
+    if options.members {
+        let settings = load_settings_from_path(&repo_root);
+        let Some(token) = options.token.clone().or_else(|| settings.resolve_token()) else {
+            eprintln!(
+                "{} --members needs a GitHub token to list team members (--token or GITHUB_TOKEN)",
+                "Error:".red().bold()
+            );
+            return Exit::Error.into();
+        };
+
+        let winners = winning_owners(&codeowners, file_cache.all_files(), options.semantics);
+        let teams: BTreeSet<&str> = winners
+            .iter()
+            .flat_map(|owners| owners.iter())
+            .map(String::as_str)
+            .filter(|owner| classify_owner(owner) == Some(OwnerKind::Team))
+            .collect();
+        let members = profile::time_async(
+            profile::VALIDATE,
+            fetch_team_members(
+                &options.github,
+                &teams,
+                &token,
+                settings.validation_concurrency(),
+            ),
+        )
+        .await;
+
+        let mut load = compute_member_load(&winners, &members);
+        if options.sort == SortBy::Name {
+            load.sort_by(|a, b| a.member.cmp(&b.member));
+        }
+        let owned = winners.iter().filter(|owners| !owners.is_empty()).count();
+        if options.json {
+            output_member_load_json(&load, owned, members.len());
+        } else {
+            output_member_load_human(&load, owned, members.len(), options.top);
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    let stats = profile::time(profile::MATCH, || {
+        compute_stats(&codeowners, &file_cache, options.semantics)
+    });
+
+    if options.json {
+        output_json(&stats, options.sort);
+    } else {
+        output_human(&stats, &options);
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Assign each file to the owners of its winning rule and tally per owner
+fn compute_stats(
+    codeowners: &CodeownersFile,
+    file_cache: &FileCache,
+    semantics: Semantics,
+) -> OwnershipStats {
+    let index = OwnerIndex::new(codeowners, file_cache, semantics);
+
+    let mut owners: Vec<(String, usize)> = index
+        .owners()
+        .map(|(owner, files)| (owner.to_string(), files.len()))
+        .collect();
+    owners.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    OwnershipStats {
+        total_files: index.total_files(),
+        unowned_files: index.unowned_files().len(),
+        owners,
+    }
+}
+
+/// Owner rows in `sort` order (computed stats are sorted by count)
+fn sorted_owners(stats: &OwnershipStats, sort: SortBy) -> Vec<&(String, usize)> {
+    let mut owners: Vec<&(String, usize)> = stats.owners.iter().collect();
+    if sort == SortBy::Name {
+        owners.sort_by(|a, b| a.0.cmp(&b.0));
+    }
+    owners
+}
+
+fn output_human(stats: &OwnershipStats, options: &StatsOptions) {
+    println!();
+    println!(
+        "  {} {}",
+        "Ownership Stats".bold(),
+        format!("({} files)", stats.total_files).dimmed()
+    );
+    println!();
+
+    if stats.owners.is_empty() {
+        println!("  {}", "No files have owners".yellow());
+    } else {
+        let owners = sorted_owners(stats, options.sort);
+        let (shown, hidden) = top_n(&owners, options.top);
+        let width = shown.iter().map(|(o, _)| o.len()).max().unwrap_or(0);
+        for (owner, count) in shown {
+            println!(
+                "  {:<width$}  {:>7}  {}",
+                owner.cyan(),
+                count.to_string().bold(),
+                format!("{:5.1}%", stats.percent(*count)).dimmed(),
+                width = width
+            );
+        }
+        if let Some(note) = hidden_note(hidden) {
+            println!("{}", note);
+        }
+    }
+
+    println!();
+    println!(
+        "  {}  {} owned",
+        "✓".green(),
+        stats.owned_files().to_string().green().bold()
+    );
+    println!(
+        "  {}  {} unowned",
+        "✗".red(),
+        stats.unowned_files.to_string().red().bold()
+    );
+    if let Some((owner, count)) = stats.largest() {
+        println!(
+            "  {}  largest surface: {} ({} files, {:.1}%)",
+            "•".dimmed(),
+            owner.cyan().bold(),
+            count,
+            stats.percent(*count)
+        );
+    }
+    println!();
+}
+
+fn output_json(stats: &OwnershipStats, sort: SortBy) {
+    let to_json = |(owner, count): &(String, usize)| OwnerStatsJson {
+        owner: owner.clone(),
+        files: *count,
+        percent: (stats.percent(*count) * 10.0).round() / 10.0, // 1 decimal place
+    };
+
+    let output = StatsJson {
+        total_files: stats.total_files,
+        owned_files: stats.owned_files(),
+        unowned_files: stats.unowned_files,
+        largest_owner: stats.largest().map(to_json),
+        owners: sorted_owners(stats, sort)
+            .into_iter()
+            .map(to_json)
+            .collect(),
+    };
+
+    println!(
+        "{}",
+        serde_json::to_string(&output).expect("Failed to serialize JSON")
+    );
+}
+
+/// Review load attributed to one person (or to a team that couldn't be
+/// expanded)
+#[derive(Debug, PartialEq)]
+struct MemberLoad {
+    member: String,
+    /// Files' worth of ownership: each file is split evenly between its
+    /// owners, and a team's part evenly between its members
+    share: f64,
+    /// Files this person has any part in
+    files: usize,
+}
+
+/// List the members of each team as `@login`s. Teams that can't be listed
+/// are left out (with a warning) and keep their share as a single owner.
+async fn fetch_team_members(
+    client: &GitHubClient,
+    teams: &BTreeSet<&str>,
+    token: &str,
+    concurrency: usize,
+) -> HashMap<String, Vec<String>> {
+    let results: Vec<(&str, Result<Vec<String>, String>)> = stream::iter(teams.iter().copied())
+        .map(|team| async move {
+            let (org, slug) = team[1..].split_once('/').unwrap_or_default();
+            let members = client
+                .list_team_members(org, slug, token)
+                .await
+                .map(|users| {
+                    users
+                        .into_iter()
+                        .map(|user| format!("@{}", user.login))
+                        .collect()
+                });
+            (team, members)
+        })
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await;
+
+    let mut members = HashMap::new();
+    for (team, result) in results {
+        match result {
+            Ok(list) => {
+                members.insert(team.to_string(), list);
+            }
+            Err(e) => eprintln!(
+                "{} couldn't list members of {} ({}); counting it as one owner",
+                "Warning:".yellow().bold(),
+                team,
+                e
+            ),
+        }
+    }
+    members
+}
+
+/// Split every owned file between the people behind its owners. Owners
+/// missing from `members` (users, emails, unexpanded teams) or teams with
+/// no members keep their part themselves. Sorted by share, largest first.
+fn compute_member_load(
+    winners: &[Cow<'_, [String]>],
+    members: &HashMap<String, Vec<String>>,
+) -> Vec<MemberLoad> {
+    let mut load: HashMap<&str, (f64, usize)> = HashMap::new();
+    for owners in winners {
+        let owners: BTreeSet<&str> = owners.iter().map(String::as_str).collect();
+        if owners.is_empty() {
+            continue;
+        }
+        let per_owner = 1.0 / owners.len() as f64;
+
+        // Someone in two of the owning teams still only touches the file once
+        let mut touched = HashSet::new();
+        for owner in owners {
+            match members.get(owner).filter(|people| !people.is_empty()) {
+                Some(people) => {
+                    let each = per_owner / people.len() as f64;
+                    for person in people {
+                        load.entry(person).or_default().0 += each;
+                        touched.insert(person.as_str());
+                    }
+                }
+                None => {
+                    load.entry(owner).or_default().0 += per_owner;
+                    touched.insert(owner);
+                }
+            }
+        }
+        for person in touched {
+            load.entry(person).or_default().1 += 1;
+        }
+    }
+
+    let mut load: Vec<MemberLoad> = load
+        .into_iter()
+        .map(|(member, (share, files))| MemberLoad {
+            member: member.to_string(),
+            share,
+            files,
+        })
+        .collect();
+    load.sort_by(|a, b| {
+        b.share
+            .total_cmp(&a.share)
+            .then_with(|| a.member.cmp(&b.member))
+    });
+    load
+}
+
+fn output_member_load_human(load: &[MemberLoad], owned: usize, teams: usize, top: usize) {
+    println!();
+    println!(
+        "  {} {}",
+        "Review Load".bold(),
+        format!("({} owned files, {} teams expanded)", owned, teams).dimmed()
+    );
+    println!();
+
+    if load.is_empty() {
+        println!("  {}", "No files have owners".yellow());
+        println!();
+        return;
+    }
+
+    let (shown, hidden) = top_n(load, top);
+    let width = shown.iter().map(|m| m.member.len()).max().unwrap_or(0);
+    for member in shown {
+        println!(
+            "  {:<width$}  {:>8.1}  {}",
+            member.member.cyan(),
+            member.share,
+            format!(
+                "{:5.1}%  ({} files)",
+                percent(member.share, owned),
+                member.files
+            )
+            .dimmed(),
+            width = width
+        );
+    }
+    if let Some(note) = hidden_note(hidden) {
+        println!("{}", note);
+    }
+    println!();
+}
+
+fn output_member_load_json(load: &[MemberLoad], owned: usize, teams: usize) {
+    let members: Vec<serde_json::Value> = load
+        .iter()
+        .map(|m| {
+            serde_json::json!({
+                "member": m.member,
+                "share": (m.share * 100.0).round() / 100.0,
+                "files": m.files,
+                "percent": (percent(m.share, owned) * 10.0).round() / 10.0,
+            })
+        })
+        .collect();
+
+    let output = serde_json::json!({
+        "owned_files": owned,
+        "teams_expanded": teams,
+        "members": members,
+    });
+
+    println!(
+        "{}",
+        serde_json::to_string(&output).expect("Failed to serialize JSON")
+    );
+}
+
+fn percent(share: f64, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        share / total as f64 * 100.0
+    }
+}
+
+/// When one owner of a directory last reviewed a PR touching it
+#[derive(Debug, PartialEq)]
+struct OwnerReview {
+    owner: String,
+    /// Unix time of their latest review, if any of the PRs had one
+    last_review: Option<u64>,
+}
+
+/// Review activity of a directory's owners
+#[derive(Debug)]
+struct DirectoryReviews {
+    directory: String,
+    files: usize,
+    /// PRs looked at
+    pulls: usize,
+    owners: Vec<OwnerReview>,
+}
+
+async fn last_review(
+    codeowners: &CodeownersFile,
+    file_cache: &FileCache,
+    repo_root: &Path,
+    options: &StatsOptions,
+) -> ExitCode {
+    let settings = load_settings_from_path(repo_root);
+    let Some(token) = options.token.clone().or_else(|| settings.resolve_token()) else {
+        eprintln!(
+            "{} --last-review needs a GitHub token to read pull requests (--token or GITHUB_TOKEN)",
+            "Error:".red().bold()
+        );
+        return Exit::Error.into();
+    };
+    let Some(repo) = options
+        .repo
+        .clone()
+        .or_else(|| origin_repository(repo_root))
+    else {
+        eprintln!(
+            "{} couldn't tell the GitHub repository from the origin remote; pass --repo OWNER/NAME",
+            "Error:".red().bold()
+        );
+        return Exit::Error.into();
+    };
+
+    let mut dirs = compute_bus_factor(
+        codeowners,
+        file_cache.all_files(),
+        options.depth,
+        options.semantics,
+    );
+    if options.sort == SortBy::Name {
+        dirs.sort_by(|a, b| a.directory.cmp(&b.directory));
+    }
+
+    let client = &options.github;
+    client.load_pull_history(&PersistentCache::load(repo_root), &token);
+    let concurrency = settings.validation_concurrency().max(1);
+    let teams: BTreeSet<&str> = dirs
+        .iter()
+        .flat_map(|dir| dir.owners.iter())
+        .map(String::as_str)
+        .filter(|owner| classify_owner(owner) == Some(OwnerKind::Team))
+        .collect();
+    let (members, results) = profile::time_async(profile::VALIDATE, async {
+        let members = fetch_team_members(client, &teams, &token, concurrency).await;
+        let results: Vec<Result<(usize, Vec<PullReview>), String>> = stream::iter(&dirs)
+            .map(|dir| directory_reviews(client, &repo, &dir.directory, options.prs, &token))
+            .buffered(concurrency)
+            .collect()
+            .await;
+        (members, results)
+    })
+    .await;
+    let _ = client.export_pull_history(&token).save(repo_root);
+
+    let mut reviews = Vec::new();
+    for (dir, result) in dirs.iter().zip(results) {
+        match result {
+            Ok((pulls, list)) => reviews.push(DirectoryReviews {
+                directory: dir.directory.clone(),
+                files: dir.files,
+                pulls,
+                owners: last_reviews(&dir.owners, &list, &members),
+            }),
+            Err(e) => eprintln!(
+                "{} couldn't read pull requests for {} ({})",
+                "Warning:".yellow().bold(),
+                dir.directory,
+                e
+            ),
+        }
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    if options.json {
+        output_last_review_json(&reviews, &repo, options, now);
+    } else {
+        output_last_review_human(&reviews, &repo, options, now);
+    }
+    ExitCode::SUCCESS
+}
+
+/// Reviews on the latest `prs` PRs touching `directory`, and how many PRs
+/// there were. A PR whose reviews can't be read is skipped with a warning.
+async fn directory_reviews(
+    client: &GitHubClient,
+    repo: &str,
+    directory: &str,
+    prs: usize,
+    token: &str,
+) -> Result<(usize, Vec<PullReview>), String> {
+    let pulls = client.recent_pulls(repo, directory, prs, token).await?;
+    let mut reviews = Vec::new();
+    for &pull in &pulls {
+        match client.pull_reviews(repo, pull, token).await {
+            Ok(list) => reviews.extend(list),
+            Err(e) => eprintln!(
+                "{} couldn't read reviews on #{} ({})",
+                "Warning:".yellow().bold(),
+                pull,
+                e
+            ),
+        }
+    }
+    Ok((pulls.len(), reviews))
+}
+
+/// Latest review in `reviews` by each owner: a user's own, or any member's
+/// for a team in `members`. Emails and unexpanded teams never match.
+fn last_reviews(
+    owners: &BTreeSet<String>,
+    reviews: &[PullReview],
+    members: &HashMap<String, Vec<String>>,
+) -> Vec<OwnerReview> {
+    owners
+        .iter()
+        .map(|owner| {
+            let people: Vec<&str> = match members.get(owner) {
+                Some(people) => people.iter().map(String::as_str).collect(),
+                None if classify_owner(owner) == Some(OwnerKind::User) => vec![owner.as_str()],
+                None => Vec::new(),
+            };
+            let last_review = reviews
+                .iter()
+                .filter(|review| {
+                    people
+                        .iter()
+                        .any(|person| person[1..].eq_ignore_ascii_case(&review.login))
+                })
+                .map(|review| review.submitted_at)
+                .max();
+            OwnerReview {
+                owner: owner.clone(),
+                last_review,
+            }
+        })
+        .collect()
+}
+
+/// `owner/name` of the GitHub repository the `origin` remote points to
+fn origin_repository(repo_root: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["remote", "get-url", "origin"])
+        .current_dir(repo_root)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    parse_github_remote(String::from_utf8_lossy(&output.stdout).trim())
+}
+
+/// `owner/name` from a GitHub remote URL, in HTTPS, SSH or scp-like form
+fn parse_github_remote(url: &str) -> Option<String> {
+    let (_, path) = url
+        .split_once("github.com:")
+        .or_else(|| url.split_once("github.com/"))?;
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let (owner, name) = path.split_once('/')?;
+    (!owner.is_empty() && !name.is_empty() && !name.contains('/'))
+        .then(|| format!("{}/{}", owner, name))
+}
+
+/// "today", "1 day ago", "N days ago"
+fn days_ago(timestamp: u64, now: u64) -> String {
+    match now.saturating_sub(timestamp) / 86_400 {
+        0 => "today".to_string(),
+        1 => "1 day ago".to_string(),
+        days => format!("{} days ago", days),
+    }
+}
+
+fn output_last_review_human(
+    dirs: &[DirectoryReviews],
+    repo: &str,
+    options: &StatsOptions,
+    now: u64,
+) {
+    println!();
+    println!(
+        "  {} {}",
+        "Last Review".bold(),
+        format!(
+            "({}, depth {}, last {} PRs per directory)",
+            repo, options.depth, options.prs
+        )
+        .dimmed()
+    );
+    println!();
+
+    if dirs.is_empty() {
+        println!("  {}", "No files have owners".yellow());
+        println!();
+        return;
+    }
+
+    let (shown, hidden) = top_n(dirs, options.top);
+    let dir_width = shown.iter().map(|d| d.directory.len()).max().unwrap_or(0);
+    let owner_width = shown
+        .iter()
+        .flat_map(|d| d.owners.iter())
+        .map(|o| o.owner.len())
+        .max()
+        .unwrap_or(0);
+    let mut silent = 0;
+    for dir in shown {
+        for (i, owner) in dir.owners.iter().enumerate() {
+            let directory = if i == 0 { dir.directory.as_str() } else { "" };
+            let activity = match owner.last_review {
+                Some(at) => format!("last active review: {}", days_ago(at, now)).normal(),
+                None => {
+                    silent += 1;
+                    format!("no reviews in the last {} PRs", dir.pulls).yellow()
+                }
+            };
+            println!(
+                "  {:<dir_width$}  {:<owner_width$}  {}",
+                directory,
+                owner.owner.cyan(),
+                activity,
+                dir_width = dir_width,
+                owner_width = owner_width
+            );
+        }
+    }
+    if let Some(note) = hidden_note(hidden) {
+        println!("{}", note);
+    }
+
+    println!();
+    println!(
+        "  {} owner entries with no recent reviews",
+        silent.to_string().yellow().bold()
+    );
+    println!();
+}
+
+fn output_last_review_json(
+    dirs: &[DirectoryReviews],
+    repo: &str,
+    options: &StatsOptions,
+    now: u64,
+) {
+    let directories: Vec<serde_json::Value> = dirs
+        .iter()
+        .map(|d| {
+            let owners: Vec<serde_json::Value> = d
+                .owners
+                .iter()
+                .map(|o| {
+                    serde_json::json!({
+                        "owner": o.owner,
+                        "last_review": o.last_review,
+                        "days_ago": o.last_review.map(|at| now.saturating_sub(at) / 86_400),
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "directory": d.directory,
+                "files": d.files,
+                "pulls": d.pulls,
+                "owners": owners,
+            })
+        })
+        .collect();
+
+    let output = serde_json::json!({
+        "repository": repo,
+        "depth": options.depth,
+        "prs": options.prs,
+        "directories": directories,
+    });
+
+    println!(
+        "{}",
+        serde_json::to_string(&output).expect("Failed to serialize JSON")
+    );
+}
+
+/// Why an owner named in CODEOWNERS owns no files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IdleReason {
+    /// None of the owner's rules match any file
+    NoMatches,
+    /// Their rules match files, but a later rule wins every one of them
+    Shadowed,
+}
+
+impl IdleReason {
+    fn label(self) -> &'static str {
+        match self {
+            IdleReason::NoMatches => "no-matches",
+            IdleReason::Shadowed => "shadowed",
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            IdleReason::NoMatches => "rules match no files",
+            IdleReason::Shadowed => "every match is overridden by a later rule",
+        }
+    }
+}
+
+/// An owner listed on at least one rule who wins no files
+#[derive(Debug)]
+struct IdleOwner {
+    owner: String,
+    reason: IdleReason,
+    /// 1-based lines of the rules naming this owner
+    lines: Vec<u32>,
+}
+
+/// Find owners who are listed on rules but own no files. Returns the number
+/// of distinct owners listed and the idle ones, sorted by owner.
+fn compute_idle_owners(
+    codeowners: &CodeownersFile,
+    file_cache: &FileCache,
+    semantics: Semantics,
+) -> (usize, Vec<IdleOwner>) {
+    let index = OwnerIndex::new(codeowners, file_cache, semantics);
+
+    // Owner -> (rule lines, whether any of those rules matches a file)
+    let mut listed: BTreeMap<&str, (Vec<u32>, bool)> = BTreeMap::new();
+    for line in codeowners.lines() {
+        let CodeownersLine::Rule { pattern, owners } = &line.content else {
+            continue;
+        };
+        let matches = file_cache.has_matches(pattern);
+        for owner in owners {
+            let (lines, any_matches) = listed.entry(owner.as_str()).or_default();
+            // A rule can list the same owner twice
+            if lines.last() != Some(&(line.line_number + 1)) {
+                lines.push(line.line_number + 1);
+            }
+            *any_matches |= matches;
+        }
+    }
+
+    let total = listed.len();
+    let idle = listed
+        .into_iter()
+        .filter(|(owner, _)| index.files_for_owner(owner).is_empty())
+        .map(|(owner, (lines, any_matches))| IdleOwner {
+            owner: owner.to_string(),
+            reason: if any_matches {
+                IdleReason::Shadowed
+            } else {
+                IdleReason::NoMatches
+            },
+            lines,
+        })
+        .collect();
+    (total, idle)
+}
+
+fn output_idle_owners_human(listed: usize, idle: &[IdleOwner], top: usize) {
+    println!();
+    println!(
+        "  {} {}",
+        "Idle Owners".bold(),
+        format!("({} owners listed)", listed).dimmed()
+    );
+    println!();
+
+    if idle.is_empty() {
+        println!(
+            "  {} Every listed owner owns at least one file",
+            "✓".green()
+        );
+        println!();
+        return;
+    }
+
+    let (shown, hidden) = top_n(idle, top);
+    let width = shown.iter().map(|o| o.owner.len()).max().unwrap_or(0);
+    for owner in shown {
+        let lines: Vec<String> = owner.lines.iter().map(|l| l.to_string()).collect();
+        let label = if lines.len() == 1 { "line" } else { "lines" };
+        println!(
+            "  {} {:<width$}  {} {}",
+            "⚠".yellow(),
+            owner.owner.cyan(),
+            owner.reason.describe(),
+            format!("({} {})", label, lines.join(", ")).dimmed(),
+            width = width
+        );
+    }
+    if let Some(note) = hidden_note(hidden) {
+        println!("{}", note);
+    }
+
+    println!();
+    println!(
+        "  {} of {} owners own no files",
+        idle.len().to_string().yellow().bold(),
+        listed
+    );
+    println!();
+}
+
+fn output_idle_owners_json(listed: usize, idle: &[IdleOwner]) {
+    let idle: Vec<serde_json::Value> = idle
+        .iter()
+        .map(|o| {
+            serde_json::json!({
+                "owner": o.owner,
+                "reason": o.reason.label(),
+                "lines": o.lines,
+            })
+        })
+        .collect();
+
+    let output = serde_json::json!({
+        "owners_listed": listed,
+        "idle": idle,
+    });
+
+    println!(
+        "{}",
+        serde_json::to_string(&output).expect("Failed to serialize JSON")
+    );
+}
+
+/// How many distinct owners a directory has
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OwnerClass {
+    /// Exactly one owner, and it's an individual (user or email)
+    SingleUser,
+    /// Exactly one owner, and it's a team
+    SingleTeam,
+    /// More than one distinct owner
+    MultiOwner,
+}
+
+impl OwnerClass {
+    fn label(self) -> &'static str {
+        match self {
+            OwnerClass::SingleUser => "single-user",
+            OwnerClass::SingleTeam => "single-team",
+            OwnerClass::MultiOwner => "multi-owner",
+        }
+    }
+
+    /// Whether this directory counts as a bus-factor risk
+    fn at_risk(self, flag_teams: bool) -> bool {
+        match self {
+            OwnerClass::SingleUser => true,
+            OwnerClass::SingleTeam => flag_teams,
+            OwnerClass::MultiOwner => false,
+        }
+    }
+}
+
+/// Distinct owners for the owned files under one directory
+#[derive(Debug)]
+struct DirectoryOwnership {
+    directory: String,
+    owners: BTreeSet<String>,
+    files: usize,
+}
+
+impl DirectoryOwnership {
+    fn class(&self) -> OwnerClass {
+        match self.owners.iter().next() {
+            Some(owner) if self.owners.len() == 1 => {
+                if is_team(owner) {
+                    OwnerClass::SingleTeam
+                } else {
+                    OwnerClass::SingleUser
+                }
+            }
+            _ => OwnerClass::MultiOwner,
+        }
+    }
+}
+
+/// `@org/team` owners are teams; `@user` and emails are individuals
+fn is_team(owner: &str) -> bool {
+    owner.starts_with('@') && owner.contains('/')
+}
+
+/// Directory prefix of `file` truncated to `depth` components (`.` for root)
+pub(super) fn directory_at_depth(file: &str, depth: usize) -> String {
+    let parts: Vec<&str> = file.split('/').collect();
+    let dir_parts = &parts[..parts.len() - 1];
+    if dir_parts.is_empty() || depth == 0 {
+        ".".to_string()
+    } else {
+        dir_parts[..depth.min(dir_parts.len())].join("/")
+    }
+}
+
+/// Group owned files by directory and collect each directory's owner set.
+/// Sorted by file count descending, then directory name.
+fn compute_bus_factor(
+    codeowners: &CodeownersFile,
+    files: &[String],
+    depth: usize,
+    semantics: Semantics,
+) -> Vec<DirectoryOwnership> {
+    let winners = winning_owners(codeowners, files, semantics);
+
+    let mut by_dir: HashMap<String, DirectoryOwnership> = HashMap::new();
+    for (file, owners) in files.iter().zip(&winners) {
+        if owners.is_empty() {
+            continue;
+        }
+        let directory = directory_at_depth(file, depth);
+        let entry = by_dir
+            .entry(directory.clone())
+            .or_insert_with(|| DirectoryOwnership {
+                directory,
+                owners: BTreeSet::new(),
+                files: 0,
+            });
+        entry.files += 1;
+        entry.owners.extend(owners.iter().cloned());
+    }
+
+    let mut dirs: Vec<DirectoryOwnership> = by_dir.into_values().collect();
+    dirs.sort_by(|a, b| {
+        b.files
+            .cmp(&a.files)
+            .then_with(|| a.directory.cmp(&b.directory))
+    });
+    dirs
+}
+
+fn output_bus_factor_human(dirs: &[DirectoryOwnership], options: &StatsOptions) {
+    let at_risk: Vec<&DirectoryOwnership> = dirs
+        .iter()
+        .filter(|d| d.class().at_risk(options.flag_teams))
+        .collect();
+
+    println!();
+    println!(
+        "  {} {}",
+        "Bus Factor".bold(),
+        format!("(depth {})", options.depth).dimmed()
+    );
+    println!();
+
+    if at_risk.is_empty() {
+        println!("  {} No single-owner directories", "✓".green());
+        println!();
+        return;
+    }
+
+    let (shown, hidden) = top_n(&at_risk, options.top);
+    let width = shown.iter().map(|d| d.directory.len()).max().unwrap_or(0);
+    for dir in shown {
+        let owners: Vec<&str> = dir.owners.iter().map(|s| s.as_str()).collect();
+        println!(
+            "  {} {:<width$}  {:>7}  {}",
+            "⚠".yellow(),
+            dir.directory,
+            format!("{} files", dir.files).bold(),
+            owners.join(" ").cyan(),
+            width = width
+        );
+    }
+    if let Some(note) = hidden_note(hidden) {
+        println!("{}", note);
+    }
+
+    let files_at_risk: usize = at_risk.iter().map(|d| d.files).sum();
+    println!();
+    println!(
+        "  {} of {} directories ({} files) depend on a single owner",
+        at_risk.len().to_string().yellow().bold(),
+        dirs.len(),
+        files_at_risk
+    );
+    println!();
+}
+
+fn output_bus_factor_json(dirs: &[DirectoryOwnership], options: &StatsOptions) {
+    let at_risk: Vec<serde_json::Value> = dirs
+        .iter()
+        .filter(|d| d.class().at_risk(options.flag_teams))
+        .map(|d| {
+            serde_json::json!({
+                "directory": d.directory,
+                "owners": d.owners,
+                "files": d.files,
+                "classification": d.class().label(),
+            })
+        })
+        .collect();
+
+    let output = serde_json::json!({
+        "depth": options.depth,
+        "directories": dirs.len(),
+        "files_at_risk": dirs
+            .iter()
+            .filter(|d| d.class().at_risk(options.flag_teams))
+            .map(|d| d.files)
+            .sum::<usize>(),
+        "at_risk": at_risk,
+    });
+
+    println!(
+        "{}",
+        serde_json::to_string(&output).expect("Failed to serialize JSON")
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn cache(paths: &[&str]) -> FileCache {
+        FileCache::from_files(files(paths))
+    }
+
+    #[test]
+    fn test_compute_stats_counts_per_owner() {
+        let codeowners = CodeownersFile::parse("* @core\n*.rs @rust\n/docs/ @docs\n");
+        let files = cache(&["src/main.rs", "src/lib.rs", "docs/index.md", "Cargo.toml"]);
+
+        let stats = compute_stats(&codeowners, &files, Semantics::GitHub);
+
+        assert_eq!(stats.total_files, 4);
+        assert_eq!(stats.unowned_files, 0);
+        assert_eq!(
+            stats.owners,
+            vec![
+                ("@rust".to_string(), 2),
+                ("@core".to_string(), 1),
+                ("@docs".to_string(), 1),
+            ]
+        );
+        assert_eq!(stats.largest(), Some(&("@rust".to_string(), 2)));
+    }
+
+    #[test]
+    fn test_compute_stats_multi_owner_rule_counts_for_each() {
+        let codeowners = CodeownersFile::parse("*.rs @alice @org/team\n");
+        let files = cache(&["a.rs", "b.rs", "README.md"]);
+
+        let stats = compute_stats(&codeowners, &files, Semantics::GitHub);
+
+        assert_eq!(stats.unowned_files, 1);
+        assert_eq!(stats.owned_files(), 2);
+        assert_eq!(
+            stats.owners,
+            vec![("@alice".to_string(), 2), ("@org/team".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn test_compute_stats_ownerless_rule_is_unowned() {
+        // A later rule without owners removes ownership
+        let codeowners = CodeownersFile::parse("* @core\n/vendor/\n");
+        let files = cache(&["src/main.rs", "vendor/lib.c"]);
+
+        let stats = compute_stats(&codeowners, &files, Semantics::GitHub);
+
+        assert_eq!(stats.unowned_files, 1);
+        assert_eq!(stats.owners, vec![("@core".to_string(), 1)]);
+    }
+
+    #[test]
+    fn test_compute_stats_github_vs_gitlab_semantics() {
+        let codeowners = CodeownersFile::parse(
+            "* @core\n[Docs] @docs\n*.md\n[Security]\n/src/auth/ @security\n",
+        );
+        let files = cache(&["README.md", "src/auth/login.rs", "src/main.rs"]);
+
+        let github = compute_stats(&codeowners, &files, Semantics::GitHub);
+        // Last match wins: `*.md` has no owners, so README.md is unowned
+        assert_eq!(github.unowned_files, 1);
+        assert_eq!(
+            github.owners,
+            vec![("@core".to_string(), 1), ("@security".to_string(), 1)]
+        );
+
+        let gitlab = compute_stats(&codeowners, &files, Semantics::GitLab);
+        // Each section contributes its winner; `*.md` inherits `@docs`
+        assert_eq!(gitlab.unowned_files, 0);
+        assert_eq!(
+            gitlab.owners,
+            vec![
+                ("@core".to_string(), 3),
+                ("@docs".to_string(), 1),
+                ("@security".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compute_stats_empty() {
+        let codeowners = CodeownersFile::parse("");
+        let stats = compute_stats(&codeowners, &cache(&[]), Semantics::GitHub);
+
+        assert_eq!(stats.total_files, 0);
+        assert!(stats.largest().is_none());
+        assert_eq!(stats.percent(0), 0.0);
+    }
+
+    #[test]
+    fn test_sorted_owners() {
+        let stats = OwnershipStats {
+            total_files: 6,
+            unowned_files: 0,
+            owners: vec![
+                ("@web".to_string(), 3),
+                ("@api".to_string(), 2),
+                ("@docs".to_string(), 1),
+            ],
+        };
+        let names = |sort| -> Vec<&str> {
+            sorted_owners(&stats, sort)
+                .iter()
+                .map(|(o, _)| o.as_str())
+                .collect()
+        };
+        assert_eq!(names(SortBy::Count), ["@web", "@api", "@docs"]);
+        assert_eq!(names(SortBy::Name), ["@api", "@docs", "@web"]);
+        // Largest surface is by count whatever the display order
+        assert_eq!(stats.largest().unwrap().0, "@web");
+    }
+
+    #[test]
+    fn test_percent() {
+        let stats = OwnershipStats {
+            total_files: 4,
+            unowned_files: 1,
+            owners: vec![("@a".to_string(), 3)],
+        };
+        assert_eq!(stats.percent(3), 75.0);
+    }
+
+    #[test]
+    fn test_idle_owners_no_matches() {
+        let codeowners = CodeownersFile::parse("* @core\n/legacy/ @old-team\n*.cobol @old-team\n");
+        let files = cache(&["src/main.rs", "README.md"]);
+
+        let (listed, idle) = compute_idle_owners(&codeowners, &files, Semantics::GitHub);
+
+        assert_eq!(listed, 2);
+        assert_eq!(idle.len(), 1);
+        assert_eq!(idle[0].owner, "@old-team");
+        assert_eq!(idle[0].reason, IdleReason::NoMatches);
+        assert_eq!(idle[0].lines, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_idle_owners_shadowed() {
+        // `*.md` matches README.md, but the catch-all after it wins
+        let codeowners = CodeownersFile::parse("*.md @docs @docs\n* @core\n");
+        let files = cache(&["src/main.rs", "README.md"]);
+
+        let (listed, idle) = compute_idle_owners(&codeowners, &files, Semantics::GitHub);
+
+        assert_eq!(listed, 2);
+        assert_eq!(idle.len(), 1);
+        assert_eq!(idle[0].owner, "@docs");
+        assert_eq!(idle[0].reason, IdleReason::Shadowed);
+        assert_eq!(idle[0].lines, vec![1]);
+    }
+
+    #[test]
+    fn test_idle_owners_partially_shadowed_is_not_idle() {
+        // @rust loses src/auth/ but still wins src/main.rs
+        let codeowners = CodeownersFile::parse("*.rs @rust\n/src/auth/ @security\n");
+        let files = cache(&["src/main.rs", "src/auth/login.rs"]);
+
+        let (_, idle) = compute_idle_owners(&codeowners, &files, Semantics::GitHub);
+        assert!(idle.is_empty());
+    }
+
+    #[test]
+    fn test_idle_owners_gitlab_sections_are_not_shadowed() {
+        // Under GitLab semantics each section keeps its own winner
+        let codeowners = CodeownersFile::parse("*.md @docs\n[Core]\n* @core\n");
+        let files = cache(&["README.md"]);
+
+        let (_, github) = compute_idle_owners(&codeowners, &files, Semantics::GitHub);
+        assert_eq!(github.len(), 1);
+        let (_, gitlab) = compute_idle_owners(&codeowners, &files, Semantics::GitLab);
+        assert!(gitlab.is_empty());
+    }
+
+    #[test]
+    fn test_directory_at_depth() {
+        assert_eq!(directory_at_depth("README.md", 1), ".");
+        assert_eq!(directory_at_depth("src/main.rs", 1), "src");
+        assert_eq!(directory_at_depth("src/cmd/run.rs", 1), "src");
+        assert_eq!(directory_at_depth("src/cmd/run.rs", 2), "src/cmd");
+        assert_eq!(directory_at_depth("src/main.rs", 3), "src");
+    }
+
+    #[test]
+    fn test_bus_factor_classification() {
+        let codeowners = CodeownersFile::parse(
+            "/solo/ @alice\n/team/ @org/platform\n/shared/ @alice @bob\n/mixed/a.rs @alice\n/mixed/b.rs @org/platform\n",
+        );
+        let files = files(&[
+            "solo/a.rs",
+            "solo/b.rs",
+            "team/a.rs",
+            "shared/a.rs",
+            "mixed/a.rs",
+            "mixed/b.rs",
+        ]);
+
+        let dirs = compute_bus_factor(&codeowners, &files, 1, Semantics::GitHub);
+        let class_of = |name: &str| {
+            dirs.iter()
+                .find(|d| d.directory == name)
+                .map(|d| d.class())
+                .unwrap()
+        };
+
+        assert_eq!(class_of("solo"), OwnerClass::SingleUser);
+        assert_eq!(class_of("team"), OwnerClass::SingleTeam);
+        assert_eq!(class_of("shared"), OwnerClass::MultiOwner);
+        assert_eq!(class_of("mixed"), OwnerClass::MultiOwner);
+    }
+
+    #[test]
+    fn test_bus_factor_at_risk_respects_flag_teams() {
+        assert!(OwnerClass::SingleUser.at_risk(false));
+        assert!(!OwnerClass::SingleTeam.at_risk(false));
+        assert!(OwnerClass::SingleTeam.at_risk(true));
+        assert!(!OwnerClass::MultiOwner.at_risk(true));
+    }
+
+    #[test]
+    fn test_bus_factor_email_is_individual() {
+        let codeowners = CodeownersFile::parse("* dev@example.com\n");
+        let dirs = compute_bus_factor(&codeowners, &files(&["src/a.rs"]), 1, Semantics::GitHub);
+        assert_eq!(dirs[0].class(), OwnerClass::SingleUser);
+    }
+
+    #[test]
+    fn test_bus_factor_sorted_by_files_and_skips_unowned() {
+        let codeowners = CodeownersFile::parse("/big/ @alice\n/small/ @bob\n");
+        let files = files(&["small/a.rs", "big/a.rs", "big/b.rs", "other/x.rs"]);
+
+        let dirs = compute_bus_factor(&codeowners, &files, 1, Semantics::GitHub);
+
+        let names: Vec<&str> = dirs.iter().map(|d| d.directory.as_str()).collect();
+        assert_eq!(names, vec!["big", "small"]);
+        assert_eq!(dirs[0].files, 2);
+    }
+
+    #[test]
+    fn test_bus_factor_depth_two() {
+        let codeowners = CodeownersFile::parse("/src/a/ @alice\n/src/b/ @bob\n");
+        let files = files(&["src/a/x.rs", "src/b/y.rs"]);
+
+        let shallow = compute_bus_factor(&codeowners, &files, 1, Semantics::GitHub);
+        assert_eq!(shallow.len(), 1);
+        assert_eq!(shallow[0].class(), OwnerClass::MultiOwner);
+
+        let deep = compute_bus_factor(&codeowners, &files, 2, Semantics::GitHub);
+        assert_eq!(deep.len(), 2);
+        assert!(deep.iter().all(|d| d.class() == OwnerClass::SingleUser));
+    }
+
+    fn winners(owners: &[&[&str]]) -> Vec<Cow<'static, [String]>> {
+        owners
+            .iter()
+            .map(|o| Cow::Owned(o.iter().map(|s| s.to_string()).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn test_member_load_splits_files_between_people() {
+        let winners = winners(&[
+            &["@org/platform"],
+            &["@org/platform", "@dave"],
+            &["@dave"],
+            &[],
+        ]);
+        let members = HashMap::from([(
+            "@org/platform".to_string(),
+            vec!["@alice".to_string(), "@bob".to_string()],
+        )]);
+
+        let load = compute_member_load(&winners, &members);
+        let rows: Vec<(&str, f64, usize)> = load
+            .iter()
+            .map(|m| (m.member.as_str(), m.share, m.files))
+            .collect();
+        // File 1: alice/bob 0.5 each; file 2: dave 0.5, alice/bob 0.25 each
+        assert_eq!(
+            rows,
+            [("@dave", 1.5, 2), ("@alice", 0.75, 2), ("@bob", 0.75, 2)]
+        );
+        let total: f64 = load.iter().map(|m| m.share).sum();
+        assert!((total - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_member_load_keeps_unexpanded_teams_and_dedupes_people() {
+        let winners = winners(&[&["@org/a", "@org/b"], &["@org/unlisted", "@org/empty"]]);
+        let members = HashMap::from([
+            ("@org/a".to_string(), vec!["@alice".to_string()]),
+            (
+                "@org/b".to_string(),
+                vec!["@alice".to_string(), "@bob".to_string()],
+            ),
+            ("@org/empty".to_string(), vec![]),
+        ]);
+
+        let load = compute_member_load(&winners, &members);
+        let alice = load.iter().find(|m| m.member == "@alice").unwrap();
+        // In both owning teams, but only one file
+        assert_eq!((alice.share, alice.files), (0.75, 1));
+        assert!(load
+            .iter()
+            .any(|m| m.member == "@org/unlisted" && m.share == 0.5));
+        assert!(load
+            .iter()
+            .any(|m| m.member == "@org/empty" && m.share == 0.5));
+    }
+
+    fn review(pull: u64, login: &str, submitted_at: u64) -> PullReview {
+        PullReview {
+            pull,
+            login: login.to_string(),
+            submitted_at,
+        }
+    }
+
+    #[test]
+    fn test_last_reviews_matches_users_and_team_members() {
+        let owners: BTreeSet<String> = ["@Alice", "@org/web", "@org/unlisted", "dev@example.com"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let members = HashMap::from([(
+            "@org/web".to_string(),
+            vec!["@bob".to_string(), "@carol".to_string()],
+        )]);
+        let reviews = [
+            review(1, "alice", 100),
+            review(2, "alice", 300),
+            review(2, "carol", 200),
+            review(3, "mallory", 400),
+        ];
+
+        assert_eq!(
+            last_reviews(&owners, &reviews, &members),
+            [
+                OwnerReview {
+                    owner: "@Alice".to_string(),
+                    last_review: Some(300),
+                },
+                OwnerReview {
+                    owner: "@org/unlisted".to_string(),
+                    last_review: None,
+                },
+                OwnerReview {
+                    owner: "@org/web".to_string(),
+                    last_review: Some(200),
+                },
+                OwnerReview {
+                    owner: "dev@example.com".to_string(),
+                    last_review: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_github_remote() {
+        for url in [
+            "git@github.com:acme/app.git",
+            "https://github.com/acme/app",
+            "https://github.com/acme/app.git/",
+            "ssh://git@github.com/acme/app.git",
+        ] {
+            assert_eq!(
+                parse_github_remote(url).as_deref(),
+                Some("acme/app"),
+                "{url}"
+            );
+        }
+        assert_eq!(parse_github_remote("git@gitlab.com:acme/app.git"), None);
+        assert_eq!(parse_github_remote("https://github.com/acme"), None);
+    }
+
+    #[test]
+    fn test_days_ago() {
+        let now = 10 * 86_400;
+        assert_eq!(days_ago(now - 60, now), "today");
+        assert_eq!(days_ago(now - 86_400, now), "1 day ago");
+        assert_eq!(days_ago(now - 3 * 86_400 - 60, now), "3 days ago");
+        assert_eq!(days_ago(now + 60, now), "today");
+    }
+
+    #[tokio::test]
+    async fn test_directory_reviews_from_mocked_api() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/app/commits"))
+            .and(query_param("path", "src"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!([{"sha": "aaa"}, {"sha": "bbb"}])),
+            )
+            .mount(&mock_server)
+            .await;
+        for (sha, number) in [("aaa", 7), ("bbb", 8)] {
+            Mock::given(method("GET"))
+                .and(path(format!("/repos/acme/app/commits/{sha}/pulls")))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(serde_json::json!([{"number": number}])),
+                )
+                .mount(&mock_server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/app/pulls/7/reviews"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"user": {"login": "alice"}, "state": "APPROVED", "submitted_at": "1970-01-03T00:00:00Z"}
+            ])))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/app/pulls/8/reviews"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+
+        let client = GitHubClient::with_base_url(&mock_server.uri());
+        // #8's reviews can't be read, so it's skipped
+        let (pulls, reviews) = directory_reviews(&client, "acme/app", "src", 20, "fake-token")
+            .await
+            .unwrap();
+        assert_eq!(pulls, 2);
+        assert_eq!(reviews, [review(7, "alice", 2 * 86_400)]);
+
+        let owners = BTreeSet::from(["@alice".to_string(), "@bob".to_string()]);
+        let last = last_reviews(&owners, &reviews, &HashMap::new());
+        assert_eq!(last[0].last_review, Some(2 * 86_400));
+        assert_eq!(last[1].last_review, None);
+    }
+}
@@ -3,19 +3,33 @@
 //! Analyzes git commit history to determine who has been working on unowned
 //! files, then suggests appropriate CODEOWNERS entries.
 //!
-//! Requires `lookup_cmd` config to resolve git emails to team names.
+//! Contributors are resolved to owners through the curated
+//! `.codeowners-lsp/identities.toml` first, then the `lookup_cmd` config;
+//! at least one of the two is required.
 
 use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use std::process::ExitCode;
 use std::{env, fs};
 
 use colored::Colorize;
+use schemars::JsonSchema;
+use serde::Serialize;
 
-use crate::blame::{suggest_owners_for_files, OwnerSuggestion};
-use crate::file_cache::FileCache;
+use super::exit::Exit;
+use super::files::{OutputPaths, PathStyle};
+use super::{profile, progress, schema};
+use crate::blame::{
+    ensure_repo, is_shallow, safe_directory_command, suggest_owners_with_progress, AnalysisOptions,
+    AuthorActivity, BlameError, ConfidenceWeights, ContributorStats, OwnerSuggestion,
+    DEFAULT_GIT_CONCURRENCY, SHALLOW_WARNING,
+};
+use crate::file_cache::{FileCache, FileCacheOptions};
+use crate::identities::Identities;
 use crate::lookup::OwnerLookup;
 use crate::ownership::{find_codeowners, get_repo_root};
 use crate::parser::{self, find_insertion_point_with_owner, CodeownersLine};
+use crate::pattern::anchored_form;
 use crate::settings::load_settings;
 
 /// Output format for suggestions
@@ -43,8 +57,24 @@ pub struct SuggestOptions {
     pub include_owned: bool,
     /// Write suggestions to CODEOWNERS file
     pub write: bool,
+    /// With `write`, print the resulting file instead of writing it
+    pub dry_run: bool,
+    /// Print a unified diff against CODEOWNERS instead of the suggestions
+    pub patch: bool,
     /// Prepend / to paths (anchored patterns)
     pub anchored: bool,
+    /// Down-weight contributors with no commits in this many months
+    pub stale_after_months: Option<u32>,
+    /// Group files by their first N directory segments instead of parent
+    pub depth: Option<usize>,
+    /// Leave out files matching any of these patterns
+    pub exclude: Vec<String>,
+    /// Maximum git analyses to run at once
+    pub concurrency: usize,
+    /// How confidence is computed; None uses the `[suggest]` config
+    pub weights: Option<ConfidenceWeights>,
+    /// Print the CODEOWNERS path as found or relative to the repository root
+    pub path_style: PathStyle,
 }
 
 impl Default for SuggestOptions {
@@ -55,7 +85,15 @@ impl Default for SuggestOptions {
             limit: 50,
             include_owned: false,
             write: false,
+            dry_run: false,
+            patch: false,
             anchored: false,
+            stale_after_months: None,
+            depth: None,
+            exclude: Vec::new(),
+            concurrency: DEFAULT_GIT_CONCURRENCY,
+            weights: None,
+            path_style: PathStyle::AsIs,
         }
     }
 }
@@ -70,7 +108,7 @@ pub fn suggest(options: SuggestOptions) -> ExitCode {
                 "{} No CODEOWNERS file found. Create one first or run from a repo with CODEOWNERS.",
                 "Error:".red().bold()
             );
-            return ExitCode::from(1);
+            return Exit::Error.into();
         }
     };
 
@@ -83,22 +121,44 @@ pub fn suggest(options: SuggestOptions) -> ExitCode {
                 codeowners_path.display(),
                 e
             );
-            return ExitCode::from(1);
+            return Exit::Error.into();
         }
     };
 
     let repo_root = get_repo_root(&codeowners_path, &cwd);
-    let file_cache = FileCache::new(&repo_root);
-    let lines = parser::parse_codeowners_file_with_positions(&content);
+    if let Err(e) = ensure_repo(&repo_root) {
+        return report_blame_error(e, &repo_root, options.format, 0);
+    }
+    if is_shallow(&repo_root) {
+        eprintln!("{} {}", "Warning:".yellow().bold(), SHALLOW_WARNING);
+    }
+    let file_cache = profile::time(profile::WALK, || {
+        FileCache::with_options(
+            &repo_root,
+            FileCacheOptions {
+                exclude: options.exclude.clone(),
+                ..Default::default()
+            },
+        )
+    });
+    let lines = profile::time(profile::PARSE, || {
+        parser::parse_codeowners_file_with_positions(&content)
+    });
 
     // Get unowned files
-    let unowned: Vec<String> = file_cache
-        .get_unowned_files(&lines)
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
+    let unowned: Vec<String> = profile::time(profile::MATCH, || {
+        file_cache
+            .get_unowned_files(&lines)
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+    });
 
     if unowned.is_empty() {
+        if options.patch {
+            eprintln!("{} All files already have owners!", "✓".green());
+            return ExitCode::SUCCESS;
+        }
         match options.format {
             OutputFormat::Human => {
                 println!("{} All files already have owners!", "✓".green());
@@ -113,27 +173,20 @@ pub fn suggest(options: SuggestOptions) -> ExitCode {
         return ExitCode::SUCCESS;
     }
 
-    // Check for lookup_cmd - required for suggest to work
     let settings = load_settings();
-    let lookup_cmd = match settings.lookup_cmd() {
-        Some(cmd) => cmd,
-        None => {
-            eprintln!(
-                "{} The suggest command requires lookup_cmd to be configured.",
-                "Error:".red().bold()
-            );
-            eprintln!("  Add to .codeowners-lsp.toml:");
-            eprintln!("{}", "  [suggest]".dimmed());
-            eprintln!(
-                "  {}",
-                "  lookup_cmd = \"your-tool lookup {email} | jq -r .team\"".dimmed()
-            );
-            return ExitCode::from(1);
-        }
+    let (identities, lookup_cmd) = match owner_sources(&repo_root, "suggest") {
+        Ok(sources) => sources,
+        Err(code) => return code,
     };
 
     // Use anchored from config if not set via CLI
     let anchored = options.anchored || settings.suggest_anchored();
+    let weights = options
+        .weights
+        .unwrap_or_else(|| settings.confidence_weights());
+    if let Some(warning) = weights.warning() {
+        eprintln!("{} {}", "Warning:".yellow().bold(), warning);
+    }
 
     // Extract existing owners from CODEOWNERS for fuzzy matching
     let existing_owners: Vec<String> = lines
@@ -150,54 +203,57 @@ pub fn suggest(options: SuggestOptions) -> ExitCode {
         .into_iter()
         .collect();
 
-    let mut lookup = OwnerLookup::new(lookup_cmd, existing_owners);
+    let mut lookup = lookup_cmd
+        .map(|cmd| OwnerLookup::new(&cmd, existing_owners).with_progress(progress::enabled()));
 
     // Analyze git history and get suggestions
-    let suggestions = suggest_owners_for_files(&repo_root, &unowned, options.min_confidence);
-
-    // Collect all unique contributor emails for batch lookup
-    let all_emails: Vec<String> = suggestions
-        .iter()
-        .flat_map(|s| s.contributors.iter().map(|c| c.email.clone()))
-        .collect::<HashSet<_>>()
-        .into_iter()
-        .collect();
-
-    // Batch lookup all emails in parallel with progress bar
-    let email_to_owner = lookup.batch_lookup(&all_emails);
+    let mut activity = options
+        .stale_after_months
+        .map(|months| AuthorActivity::new(&repo_root, months));
+    let pb = progress::bar(0, "Analyzing history");
+    let suggestions = match profile::time(profile::BLAME, || {
+        suggest_owners_with_progress(
+            &repo_root,
+            &unowned,
+            options.min_confidence,
+            options.min_confidence,
+            &AnalysisOptions {
+                depth: options.depth,
+                concurrency: options.concurrency,
+                weights,
+            },
+            activity.as_mut(),
+            |event| progress::track_analysis(&pb, event),
+        )
+    }) {
+        Ok(s) => s,
+        Err(e) => {
+            pb.finish_and_clear();
+            return report_blame_error(e, &repo_root, options.format, unowned.len());
+        }
+    };
+    pb.finish_and_clear();
 
-    // Transform suggestions: use lookup results to pick best team
-    let suggestions: Vec<OwnerSuggestion> = suggestions
+    let suggestions: Vec<OwnerSuggestion> = pick_owners(suggestions, &identities, lookup.as_mut())
         .into_iter()
-        .filter_map(|mut s| {
-            // For each contributor, use cached lookup and accumulate weighted votes
-            let mut team_votes: HashMap<String, usize> = HashMap::new();
-
-            for contributor in &s.contributors {
-                if let Some(Some(resolved_owner)) = email_to_owner.get(&contributor.email) {
-                    *team_votes.entry(resolved_owner.clone()).or_insert(0) +=
-                        contributor.commit_count;
-                }
-            }
-
-            // Pick the team with the most weighted votes
-            let best_team = team_votes
-                .into_iter()
-                .max_by_key(|(_, votes)| *votes)
-                .map(|(team, _)| team)?;
-
-            s.suggested_owner = best_team;
-
+        .map(|mut s| {
             // Prepend / if anchored option is set (CLI or config)
             if anchored && !s.path.starts_with('/') {
                 s.path = format!("/{}", s.path);
             }
-
-            Some(s)
+            s
         })
         .collect();
 
     if suggestions.is_empty() {
+        if options.patch {
+            eprintln!(
+                "{} No confident suggestions found for {} unowned files.",
+                "!".yellow(),
+                unowned.len()
+            );
+            return ExitCode::SUCCESS;
+        }
         match options.format {
             OutputFormat::Human => {
                 println!(
@@ -229,6 +285,22 @@ pub fn suggest(options: SuggestOptions) -> ExitCode {
     // Limit suggestions
     let suggestions: Vec<_> = suggestions.into_iter().take(options.limit).collect();
 
+    if options.patch {
+        let path = codeowners_path
+            .strip_prefix(&repo_root)
+            .unwrap_or(&codeowners_path);
+        let (patch, skipped) = suggestions_patch(&content, &suggestions, path);
+        for pattern in &skipped {
+            eprintln!(
+                "{} Skipping '{}' - pattern already exists in CODEOWNERS",
+                "!".yellow(),
+                pattern
+            );
+        }
+        print!("{}", patch);
+        return ExitCode::SUCCESS;
+    }
+
     // Output based on format
     match options.format {
         OutputFormat::Human => output_human(&suggestions, &unowned),
@@ -238,51 +310,387 @@ pub fn suggest(options: SuggestOptions) -> ExitCode {
 
     // Write to file if requested
     if options.write && !suggestions.is_empty() {
-        let new_content = apply_suggestions(&content, &suggestions);
-        if let Err(e) = fs::write(&codeowners_path, &new_content) {
+        let applied = apply_suggestions(&content, &suggestions);
+        for pattern in &applied.skipped {
+            eprintln!(
+                "{} Skipping '{}' - pattern already exists in CODEOWNERS",
+                "!".yellow(),
+                pattern
+            );
+        }
+
+        if options.dry_run {
+            eprintln!("\n{}", "─".repeat(60).dimmed());
+            print!("{}", applied.content);
+            return ExitCode::SUCCESS;
+        }
+
+        if applied.added == 0 {
+            println!("\n{} No new rules to add", "✓".green());
+            return ExitCode::SUCCESS;
+        }
+
+        if let Err(e) = fs::write(&codeowners_path, &applied.content) {
             eprintln!(
                 "{} Failed to write {}: {}",
                 "Error:".red().bold(),
                 codeowners_path.display(),
                 e
             );
-            return ExitCode::from(1);
+            return Exit::Error.into();
         }
         println!(
             "\n{} Added {} rules to {}",
             "✓".green(),
-            suggestions.len(),
-            codeowners_path.display()
+            applied.added,
+            OutputPaths::new(options.path_style, &cwd, &repo_root).display(&codeowners_path)
         );
     }
 
     ExitCode::SUCCESS
 }
 
-/// Apply suggestions to CODEOWNERS content, inserting each rule at the best location
-fn apply_suggestions(content: &str, suggestions: &[OwnerSuggestion]) -> String {
-    let mut lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
+/// Result of applying suggestions to CODEOWNERS content
+struct AppliedSuggestions {
+    /// New file content
+    content: String,
+    /// Number of rules inserted
+    added: usize,
+    /// Suggested patterns skipped because they already exist
+    skipped: Vec<String>,
+}
+
+/// Rules to add to CODEOWNERS content, as `(line index, rule)` in file order.
+/// Each rule goes at the best location for its path; suggestions whose
+/// pattern already exists (in [`anchored_form`]) are returned as skipped.
+fn plan_insertions(
+    content: &str,
+    suggestions: &[OwnerSuggestion],
+) -> (Vec<(usize, String)>, Vec<String>) {
     let parsed = parser::parse_codeowners_file(content);
+    let line_count = content.lines().count();
 
-    // Insert suggestions in reverse order of insertion point to avoid index shifting
-    let mut insertions: Vec<(usize, String)> = suggestions
+    let mut existing: HashSet<String> = parsed
         .iter()
-        .map(|s| {
-            let insert_idx =
-                find_insertion_point_with_owner(&parsed, &s.path, Some(&s.suggested_owner));
-            let new_line = format!("{} {}", s.path, s.suggested_owner);
-            (insert_idx, new_line)
+        .filter_map(|line| match line {
+            CodeownersLine::Rule { pattern, .. } => Some(anchored_form(pattern).into_owned()),
+            _ => None,
         })
         .collect();
 
-    // Sort by insertion point descending so we insert from bottom to top
-    insertions.sort_by_key(|i| std::cmp::Reverse(i.0));
+    let mut insertions: Vec<(usize, String)> = Vec::new();
+    let mut skipped = Vec::new();
+    for s in suggestions {
+        if !existing.insert(anchored_form(&s.path).into_owned()) {
+            skipped.push(s.path.clone());
+            continue;
+        }
+        let insert_idx =
+            find_insertion_point_with_owner(&parsed, &s.path, Some(&s.suggested_owner));
+        insertions.push((
+            insert_idx.min(line_count),
+            format!("{} {}", s.path, s.suggested_owner),
+        ));
+    }
+    // Stable, so rules for the same spot keep suggestion order
+    insertions.sort_by_key(|i| i.0);
+    (insertions, skipped)
+}
+
+/// A line of CODEOWNERS after insertions: kept from the original, or new
+enum Edit<'a> {
+    Keep(&'a str),
+    Insert(&'a str),
+}
+
+/// Interleave `insertions` (sorted by index) with the original lines. Kept
+/// lines lose their `\n` but not a `\r`, so CRLF endings survive.
+fn merge_insertions<'a>(content: &'a str, insertions: &'a [(usize, String)]) -> Vec<Edit<'a>> {
+    let mut edits = Vec::new();
+    let mut pending = insertions.iter().peekable();
+    for (idx, line) in content.split_inclusive('\n').enumerate() {
+        while let Some((_, rule)) = pending.next_if(|(at, _)| *at == idx) {
+            edits.push(Edit::Insert(rule));
+        }
+        edits.push(Edit::Keep(line.strip_suffix('\n').unwrap_or(line)));
+    }
+    edits.extend(pending.map(|(_, rule)| Edit::Insert(rule.as_str())));
+    edits
+}
+
+/// `\r` when `content` uses CRLF line endings, for the lines we add
+fn carriage_return(content: &str) -> &'static str {
+    if content.contains("\r\n") {
+        "\r"
+    } else {
+        ""
+    }
+}
+
+/// Apply suggestions to CODEOWNERS content, inserting each rule at the best location.
+/// Suggestions whose pattern already exists (in [`anchored_form`]) are skipped.
+fn apply_suggestions(content: &str, suggestions: &[OwnerSuggestion]) -> AppliedSuggestions {
+    let (insertions, skipped) = plan_insertions(content, suggestions);
+    let cr = carriage_return(content);
+    let mut out = String::with_capacity(content.len() + 1);
+    for edit in merge_insertions(content, &insertions) {
+        match edit {
+            Edit::Keep(line) => out.push_str(line),
+            Edit::Insert(rule) => {
+                out.push_str(rule);
+                out.push_str(cr);
+            }
+        }
+        out.push('\n');
+    }
+    // An unterminated last line gets the file's line ending
+    if !content.is_empty() && !content.ends_with('\n') {
+        out.insert_str(out.len() - 1, cr);
+    }
+
+    AppliedSuggestions {
+        content: out,
+        added: insertions.len(),
+        skipped,
+    }
+}
+
+/// Lines of unchanged context around each hunk of a patch
+const PATCH_CONTEXT: usize = 3;
+
+/// A unified diff adding the suggestions to `content`, the CODEOWNERS at
+/// `path` (relative to the repo root), ready for `git apply`. Empty when
+/// there is nothing to add. Also returns the skipped patterns.
+fn suggestions_patch(
+    content: &str,
+    suggestions: &[OwnerSuggestion],
+    path: &Path,
+) -> (String, Vec<String>) {
+    let (insertions, skipped) = plan_insertions(content, suggestions);
+    if insertions.is_empty() {
+        return (String::new(), skipped);
+    }
+    let edits = merge_insertions(content, &insertions);
+    let cr = carriage_return(content);
+    // The last original line gets a newline if a hunk touches it
+    let missing_newline = !content.is_empty() && !content.ends_with('\n');
+    let last_kept = edits.iter().rposition(|e| matches!(e, Edit::Keep(_)));
+
+    // Group inserted lines into hunks, merging those whose context overlaps
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for (i, _) in edits
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| matches!(e, Edit::Insert(_)))
+    {
+        let start = i.saturating_sub(PATCH_CONTEXT);
+        let end = (i + PATCH_CONTEXT).min(edits.len() - 1);
+        match hunks.last_mut() {
+            Some(hunk) if start <= hunk.1 + 1 => hunk.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut patch = format!(
+        "diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n",
+        path = path
+    );
+    for (start, end) in hunks {
+        let kept_before = edits[..start]
+            .iter()
+            .filter(|e| matches!(e, Edit::Keep(_)))
+            .count();
+        let old_count = edits[start..=end]
+            .iter()
+            .filter(|e| matches!(e, Edit::Keep(_)))
+            .count();
+        let new_count = end - start + 1;
+        // An empty range is written as the line before it
+        let old_start = if old_count == 0 {
+            kept_before
+        } else {
+            kept_before + 1
+        };
+        patch.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_start,
+            old_count,
+            start + 1,
+            new_count
+        ));
+        for (i, edit) in edits.iter().enumerate().take(end + 1).skip(start) {
+            match edit {
+                Edit::Keep(line) if missing_newline && Some(i) == last_kept => {
+                    patch.push_str(&format!(
+                        "-{}\n\\ No newline at end of file\n+{}{}\n",
+                        line, line, cr
+                    ));
+                }
+                Edit::Keep(line) => patch.push_str(&format!(" {}\n", line)),
+                Edit::Insert(line) => patch.push_str(&format!("+{}{}\n", line, cr)),
+            }
+        }
+    }
+    (patch, skipped)
+}
+
+/// Where contributors are turned into owners: curated identities and the
+/// configured `lookup_cmd`. Reports (and fails) when neither is set up,
+/// naming `command` in the message.
+pub(super) fn owner_sources(
+    repo_root: &Path,
+    command: &str,
+) -> Result<(Identities, Option<String>), ExitCode> {
+    let identities = match Identities::load(repo_root) {
+        Ok(identities) => identities,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            return Err(Exit::Error.into());
+        }
+    };
+
+    // Need identities.toml or lookup_cmd to turn contributors into owners
+    let lookup_cmd = load_settings().lookup_cmd().map(str::to_string);
+    if lookup_cmd.is_none() && identities.is_empty() {
+        eprintln!(
+            "{} The {} command requires lookup_cmd or an identities file.",
+            "Error:".red().bold(),
+            command
+        );
+        eprintln!("  Add to .codeowners-lsp.toml:");
+        eprintln!("{}", "  [suggest]".dimmed());
+        eprintln!(
+            "  {}",
+            "  lookup_cmd = \"your-tool lookup {email} | jq -r .team\"".dimmed()
+        );
+        eprintln!(
+            "  or map emails to owners in {}",
+            Identities::path(repo_root).display()
+        );
+        return Err(Exit::Error.into());
+    }
+    Ok((identities, lookup_cmd))
+}
+
+/// Replace each suggestion's contributor with the owner their commits vote
+/// for, via identities then `lookup_cmd`. Suggestions none of whose
+/// contributors map to an owner are dropped.
+pub(super) fn pick_owners(
+    suggestions: Vec<OwnerSuggestion>,
+    identities: &Identities,
+    lookup: Option<&mut OwnerLookup>,
+) -> Vec<OwnerSuggestion> {
+    // Collect unique contributor emails not covered by identities.toml
+    let all_emails: Vec<String> = suggestions
+        .iter()
+        .flat_map(|s| s.contributors.iter())
+        .filter(|c| identities.resolve(&c.email, &c.name).is_none())
+        .map(|c| c.email.clone())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+
+    // Batch lookup all emails in parallel with progress bar
+    let email_to_owner = match lookup {
+        Some(lookup) => lookup.batch_lookup(&all_emails),
+        None => HashMap::new(),
+    };
+
+    suggestions
+        .into_iter()
+        .filter_map(|mut s| {
+            // For each contributor, use cached lookup and accumulate weighted votes
+            // (percentages already discount stale contributors)
+            let mut team_votes: HashMap<String, f64> = HashMap::new();
 
-    for (idx, line) in insertions {
-        lines.insert(idx, line);
+            for contributor in &s.contributors {
+                if let Some(owner) = resolve_owner(contributor, identities, &email_to_owner) {
+                    *team_votes.entry(owner).or_insert(0.0) += contributor.percentage;
+                }
+            }
+
+            // Pick the team with the most weighted votes (ties go to the
+            // first by name, so output doesn't depend on hash order)
+            s.suggested_owner = team_votes
+                .into_iter()
+                .max_by(|(a_team, a), (b_team, b)| a.total_cmp(b).then_with(|| b_team.cmp(a_team)))
+                .map(|(team, _)| team)?;
+            Some(s)
+        })
+        .collect()
+}
+
+/// The owner a contributor votes for: their curated identity if mapped,
+/// otherwise the `lookup_cmd` result for their email
+fn resolve_owner(
+    contributor: &ContributorStats,
+    identities: &Identities,
+    email_to_owner: &HashMap<String, Option<String>>,
+) -> Option<String> {
+    match identities.resolve(&contributor.email, &contributor.name) {
+        Some(owner) => Some(owner.to_string()),
+        None => email_to_owner.get(&contributor.email).cloned().flatten(),
     }
+}
 
-    lines.join("\n") + "\n"
+/// Explain why git history couldn't be used and what to do about it.
+/// Missing history is an expected state for brand new files, so it isn't
+/// treated as a failure.
+fn report_blame_error(
+    err: BlameError,
+    repo_root: &Path,
+    format: OutputFormat,
+    unowned_count: usize,
+) -> ExitCode {
+    match err {
+        BlameError::GitMissing => {
+            eprintln!(
+                "{} git is not installed or not on PATH.",
+                "Error:".red().bold()
+            );
+            eprintln!(
+                "  suggest reads commit history; install git and make sure `git --version` works."
+            );
+        }
+        BlameError::NotARepo => {
+            eprintln!("{} Not inside a git repository.", "Error:".red().bold());
+            eprintln!("  suggest reads commit history; run it from a git checkout (or `git init` and commit first).");
+        }
+        BlameError::DubiousOwnership => {
+            eprintln!(
+                "{} git refuses this repository because another user owns it.",
+                "Error:".red().bold()
+            );
+            eprintln!("  Trust it with `{}`.", safe_directory_command(repo_root));
+        }
+        BlameError::NoHistory => {
+            match format {
+                OutputFormat::Human => {
+                    println!(
+                        "{} No commit history yet for {} unowned files.",
+                        "!".yellow(),
+                        unowned_count
+                    );
+                    println!("  Commit them first so contributors can be suggested as owners.");
+                }
+                OutputFormat::Json => {
+                    println!(
+                        "{{\"suggestions\": [], \"unowned_count\": {}, \"message\": \"No commit history for unowned files\"}}",
+                        unowned_count
+                    );
+                }
+                OutputFormat::Codeowners => {
+                    println!(
+                        "# No commit history yet for {} unowned files",
+                        unowned_count
+                    );
+                }
+            }
+            return ExitCode::SUCCESS;
+        }
+    }
+    Exit::Error.into()
 }
 
 fn output_human(suggestions: &[OwnerSuggestion], unowned: &[String]) {
@@ -325,7 +733,13 @@ fn output_human(suggestions: &[OwnerSuggestion], unowned: &[String]) {
             .contributors
             .iter()
             .take(3)
-            .map(|c| format!("{} ({}%)", c.name, c.percentage as u32))
+            .map(|c| {
+                if c.stale {
+                    format!("{} ({}%, inactive)", c.name, c.percentage as u32)
+                } else {
+                    format!("{} ({}%)", c.name, c.percentage as u32)
+                }
+            })
             .collect();
 
         println!(
@@ -360,31 +774,321 @@ fn output_codeowners(suggestions: &[OwnerSuggestion]) {
 }
 
 fn output_json(suggestions: &[OwnerSuggestion], unowned: &[String]) {
-    let json_suggestions: Vec<serde_json::Value> = suggestions
-        .iter()
-        .map(|s| {
-            serde_json::json!({
-                "path": s.path,
-                "suggested_owner": s.suggested_owner,
-                "confidence": s.confidence,
-                "total_commits": s.total_commits,
-                "contributors": s.contributors.iter().map(|c| {
-                    serde_json::json!({
-                        "name": c.name,
-                        "email": c.email,
-                        "commits": c.commit_count,
-                        "percentage": c.percentage
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&suggestions_json(suggestions, unowned)).unwrap()
+    );
+}
+
+/// `--format json` output. The "nothing to suggest" results carry a
+/// `message` instead of counts.
+#[derive(Serialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+struct SuggestionsJson<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unowned_count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    suggestion_count: Option<usize>,
+    /// Why there are no suggestions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<&'a str>,
+    suggestions: Vec<SuggestionJson<'a>>,
+}
+
+#[derive(Serialize, JsonSchema)]
+#[schemars(rename = "Suggestion", deny_unknown_fields)]
+struct SuggestionJson<'a> {
+    /// CODEOWNERS pattern to add
+    path: &'a str,
+    suggested_owner: &'a str,
+    #[schemars(range(min = 0, max = 100))]
+    confidence: f64,
+    total_commits: usize,
+    contributors: Vec<ContributorJson<'a>>,
+}
+
+#[derive(Serialize, JsonSchema)]
+#[schemars(rename = "Contributor", deny_unknown_fields)]
+struct ContributorJson<'a> {
+    name: &'a str,
+    email: &'a str,
+    commits: usize,
+    #[schemars(range(min = 0, max = 100))]
+    percentage: f64,
+    /// No recent commits in the repo (--stale-after-months)
+    stale: bool,
+}
+
+fn suggestions_json<'a>(
+    suggestions: &'a [OwnerSuggestion],
+    unowned: &[String],
+) -> SuggestionsJson<'a> {
+    SuggestionsJson {
+        unowned_count: Some(unowned.len()),
+        suggestion_count: Some(suggestions.len()),
+        message: None,
+        suggestions: suggestions
+            .iter()
+            .map(|s| SuggestionJson {
+                path: &s.path,
+                suggested_owner: &s.suggested_owner,
+                confidence: s.confidence,
+                total_commits: s.total_commits,
+                contributors: s
+                    .contributors
+                    .iter()
+                    .map(|c| ContributorJson {
+                        name: &c.name,
+                        email: &c.email,
+                        commits: c.commit_count,
+                        percentage: c.percentage,
+                        stale: c.stale,
                     })
-                }).collect::<Vec<_>>()
+                    .collect(),
             })
-        })
-        .collect();
+            .collect(),
+    }
+}
 
-    let output = serde_json::json!({
-        "unowned_count": unowned.len(),
-        "suggestion_count": suggestions.len(),
-        "suggestions": json_suggestions
-    });
+/// JSON Schema for `--format json` output, derived from [`SuggestionsJson`]
+pub(super) fn json_schema() -> serde_json::Value {
+    schema::document::<SuggestionsJson>("codeowners-cli suggest --format json", "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::schema::validate;
 
-    println!("{}", serde_json::to_string_pretty(&output).unwrap());
+    fn suggestion(path: &str, owner: &str) -> OwnerSuggestion {
+        OwnerSuggestion {
+            path: path.to_string(),
+            suggested_owner: owner.to_string(),
+            confidence: 80.0,
+            contributors: vec![],
+            total_commits: 10,
+        }
+    }
+
+    #[test]
+    fn test_json_output_matches_schema() {
+        let mut with_contributors = suggestion("src/", "@org/core");
+        with_contributors.contributors = vec![crate::blame::ContributorStats {
+            email: "dev@example.com".to_string(),
+            name: "Dev".to_string(),
+            commit_count: 10,
+            percentage: 100.0,
+            stale: false,
+        }];
+        let suggestions = [with_contributors, suggestion("docs/", "@org/docs")];
+        let json = suggestions_json(&suggestions, &["src/main.rs".to_string()]);
+        validate(&json_schema(), &serde_json::to_value(&json).unwrap()).unwrap();
+
+        // The "nothing to suggest" outputs
+        for empty in [
+            r#"{"suggestions": [], "message": "All files have owners"}"#,
+            r#"{"suggestions": [], "unowned_count": 3, "message": "No confident suggestions"}"#,
+        ] {
+            validate(&json_schema(), &serde_json::from_str(empty).unwrap()).unwrap();
+        }
+    }
+
+    fn rules(content: &str) -> Vec<(String, Vec<String>)> {
+        parser::parse_codeowners_file(content)
+            .into_iter()
+            .filter_map(|line| match line {
+                CodeownersLine::Rule { pattern, owners } => Some((pattern, owners)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_apply_suggestions_output_is_parseable() {
+        let content = "# Owners\n* @default\n/src/ @core\n";
+        let applied = apply_suggestions(
+            content,
+            &[
+                suggestion("/src/new/", "@org/new-team"),
+                suggestion("docs/", "@docs"),
+            ],
+        );
+
+        assert_eq!(applied.added, 2);
+        assert!(applied.skipped.is_empty());
+        let parsed = rules(&applied.content);
+        assert_eq!(parsed.len(), 4);
+        assert!(parsed
+            .iter()
+            .any(|(p, o)| p == "/src/new/" && o == &vec!["@org/new-team".to_string()]));
+        assert!(parsed
+            .iter()
+            .any(|(p, o)| p == "docs/" && o == &vec!["@docs".to_string()]));
+        // Existing content preserved
+        assert!(applied.content.starts_with("# Owners\n"));
+    }
+
+    #[test]
+    fn test_apply_suggestions_no_trailing_newline() {
+        let content = "/src/ @core";
+        let applied = apply_suggestions(content, &[suggestion("/lib/", "@lib")]);
+
+        assert!(applied.content.ends_with('\n'));
+        assert!(!applied.content.contains("@core/lib/"));
+        assert_eq!(rules(&applied.content).len(), 2);
+    }
+
+    #[test]
+    fn test_apply_suggestions_skips_existing_pattern() {
+        let content = "/src/ @core\n/lib/util/ @lib\n";
+        let applied = apply_suggestions(
+            content,
+            &[
+                suggestion("lib/util/", "@other"),
+                suggestion("src/", "@any"),
+            ],
+        );
+
+        // `lib/util/` is rooted like `/lib/util/`; `src/` matches at any
+        // depth, so it isn't the same rule as `/src/`
+        assert_eq!(applied.added, 1);
+        assert_eq!(applied.skipped, vec!["lib/util/".to_string()]);
+        assert_eq!(rules(&applied.content).len(), 3);
+        assert!(applied.content.contains("src/ @any"));
+    }
+
+    #[test]
+    fn test_apply_suggestions_skips_duplicate_suggestions() {
+        let applied =
+            apply_suggestions("", &[suggestion("/lib/", "@a"), suggestion("/lib/", "@b")]);
+
+        assert_eq!(applied.added, 1);
+        assert_eq!(applied.content, "/lib/ @a\n");
+    }
+
+    /// Run `patch` through `git apply` against `file` holding `content`
+    fn git_apply(file: &str, content: &str, patch: &str) -> String {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        fs::write(dir.path().join("suggest.patch"), patch).unwrap();
+        let output = std::process::Command::new("git")
+            .args(["apply", "suggest.patch"])
+            .current_dir(dir.path())
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}\n{}",
+            String::from_utf8_lossy(&output.stderr),
+            patch
+        );
+        fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn test_patch_applies_cleanly() {
+        let content = "# Owners\n* @default\n\n# Backend\n/src/ @core\n/src/api/ @api\n\n\
+                       # Docs\n/docs/ @docs\n/docs/guides/ @docs\n/docs/api/ @docs\n";
+        let suggestions = [
+            suggestion("/src/billing/", "@core"),
+            suggestion("/docs/intro/", "@docs"),
+            suggestion("/tools/", "@tools"),
+            suggestion("src/api/", "@other"),
+        ];
+        let (patch, skipped) =
+            suggestions_patch(content, &suggestions, Path::new(".github/CODEOWNERS"));
+
+        assert!(patch.starts_with("diff --git a/.github/CODEOWNERS b/.github/CODEOWNERS\n"));
+        assert_eq!(skipped, vec!["src/api/".to_string()]);
+        let patched = git_apply(".github/CODEOWNERS", content, &patch);
+        // Same result as --write, which parses
+        assert_eq!(patched, apply_suggestions(content, &suggestions).content);
+        assert_eq!(rules(&patched).len(), 9);
+        assert!(patched.contains("/src/billing/ @core"));
+        assert!(!patched.contains("@other"));
+    }
+
+    #[test]
+    fn test_patch_edge_cases_apply() {
+        let cases = [
+            ("", vec![suggestion("/lib/", "@lib")]),
+            ("/src/ @core", vec![suggestion("/lib/", "@lib")]),
+            (
+                "/src/ @core\n/a/ @a\n/b/ @b\n/c/ @c\n/d/ @d\n/e/ @e\n/f/ @f\n/g/ @g",
+                vec![suggestion("/src/x/", "@core"), suggestion("/zzz/", "@z")],
+            ),
+        ];
+        for (content, suggestions) in cases {
+            let (patch, _) = suggestions_patch(content, &suggestions, Path::new("CODEOWNERS"));
+            let patched = git_apply("CODEOWNERS", content, &patch);
+            assert_eq!(patched, apply_suggestions(content, &suggestions).content);
+        }
+
+        // CRLF files keep their endings, and new lines use them too
+        let content = "# Owners\r\n* @default\r\n\r\n/src/ @core\r\n/docs/ @docs";
+        let suggestions = [suggestion("/src/api/", "@api"), suggestion("/zzz/", "@z")];
+        let (patch, _) = suggestions_patch(content, &suggestions, Path::new("CODEOWNERS"));
+        let patched = git_apply("CODEOWNERS", content, &patch);
+        assert_eq!(patched, apply_suggestions(content, &suggestions).content);
+        assert!(patched.contains("/src/ @core\r\n/src/api/ @api\r\n"));
+        assert!(patched.contains("/zzz/ @z\r\n"));
+        assert!(patched.ends_with("/docs/ @docs\r\n"));
+        assert!(!patched.replace("\r\n", "").contains('\n'));
+
+        // Nothing new to add gives an empty patch
+        let (patch, skipped) = suggestions_patch(
+            "/lib/ @lib\n",
+            &[suggestion("/lib/", "@lib")],
+            Path::new("CODEOWNERS"),
+        );
+        assert!(patch.is_empty());
+        assert_eq!(skipped.len(), 1);
+    }
+
+    #[test]
+    fn test_resolve_owner_prefers_identities() {
+        let identities = Identities::parse(
+            "[[identity]]\nowner = \"@org/platform\"\nemails = [\"bot@example.com\"]\n",
+        )
+        .unwrap();
+        let contributor = |email: &str| ContributorStats {
+            email: email.to_string(),
+            name: "Someone".to_string(),
+            commit_count: 1,
+            percentage: 100.0,
+            stale: false,
+        };
+        let email_to_owner = HashMap::from([
+            ("bot@example.com".to_string(), Some("@org/bots".to_string())),
+            ("bob@example.com".to_string(), Some("@org/web".to_string())),
+        ]);
+
+        assert_eq!(
+            resolve_owner(
+                &contributor("bot@example.com"),
+                &identities,
+                &email_to_owner
+            ),
+            Some("@org/platform".to_string())
+        );
+        // Unmapped contributors fall back to lookup_cmd results
+        assert_eq!(
+            resolve_owner(
+                &contributor("bob@example.com"),
+                &identities,
+                &email_to_owner
+            ),
+            Some("@org/web".to_string())
+        );
+        assert_eq!(
+            resolve_owner(
+                &contributor("eve@example.com"),
+                &identities,
+                &email_to_owner
+            ),
+            None
+        );
+    }
 }
@@ -4,9 +4,10 @@ use std::{env, fs};
 
 use colored::{Color, Colorize};
 
+use super::exit::Exit;
 use crate::file_cache::FileCache;
-use crate::ownership::{check_file_ownership_parsed, find_codeowners, get_repo_root};
-use crate::parser::parse_codeowners_file_with_positions;
+use crate::ownership::{find_codeowners, get_repo_root, CodeownersFile};
+use crate::settings::load_settings_from_path;
 
 /// Generate a consistent color from a string
 fn owner_color(owner: &str) -> Color {
@@ -39,7 +40,7 @@ pub fn tree() -> ExitCode {
         Some(p) => p,
         None => {
             eprintln!("No CODEOWNERS file found");
-            return ExitCode::from(1);
+            return Exit::Error.into();
         }
     };
 
@@ -47,19 +48,20 @@ pub fn tree() -> ExitCode {
         Ok(c) => c,
         Err(e) => {
             eprintln!("Failed to read {}: {}", codeowners_path.display(), e);
-            return ExitCode::from(1);
+            return Exit::Error.into();
         }
     };
 
     let repo_root = get_repo_root(&codeowners_path, &cwd);
     let file_cache = FileCache::new(&repo_root);
-    let parsed_lines = parse_codeowners_file_with_positions(&content);
+    let pattern_options = load_settings_from_path(&repo_root).pattern_options();
+    let codeowners = CodeownersFile::parse_with_options(&content, pattern_options);
 
     // Collect all files with their owners
     let mut files_with_owners: Vec<(String, Option<String>)> = Vec::new();
 
     for file in file_cache.all_files() {
-        let owners = check_file_ownership_parsed(&parsed_lines, file).map(|r| r.owners.join(" "));
+        let owners = codeowners.owners_for(file).map(|r| r.owners.join(" "));
         files_with_owners.push((file.clone(), owners));
     }
 
@@ -0,0 +1,72 @@
+//! Minimal tab-separated writer for `cut`/`awk` pipelines.
+//!
+//! There is no quoting: a field never contains a raw tab or line break.
+//! Those (and the backslash itself) are escaped as `\t`, `\n`, `\r` and
+//! `\\`, the same convention as PostgreSQL's text format.
+
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+/// Escape tabs, line breaks and backslashes in a field
+fn escape(field: &str) -> Cow<'_, str> {
+    if !field.contains(['\t', '\n', '\r', '\\']) {
+        return Cow::Borrowed(field);
+    }
+    let mut escaped = String::with_capacity(field.len() + 2);
+    for c in field.chars() {
+        match c {
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\\' => escaped.push_str("\\\\"),
+            c => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Write one tab-separated record followed by a newline
+pub fn write_record<W, I, S>(out: &mut W, fields: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut first = true;
+    for field in fields {
+        if !first {
+            out.write_all(b"\t")?;
+        }
+        first = false;
+        out.write_all(escape(field.as_ref()).as_bytes())?;
+    }
+    out.write_all(b"\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(fields: &[&str]) -> String {
+        let mut out = Vec::new();
+        write_record(&mut out, fields).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_plain_fields_unescaped() {
+        assert_eq!(
+            record(&["src/a b,c.rs", "*.rs", "2", "@a @b"]),
+            "src/a b,c.rs\t*.rs\t2\t@a @b\n"
+        );
+        assert_eq!(record(&["README.md", "", "", ""]), "README.md\t\t\t\n");
+    }
+
+    #[test]
+    fn test_escaping() {
+        assert_eq!(
+            record(&["tab\there", "two\r\nlines", "C:\\dir"]),
+            "tab\\there\ttwo\\r\\nlines\tC:\\\\dir\n"
+        );
+    }
+}
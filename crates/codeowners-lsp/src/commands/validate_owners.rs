@@ -5,23 +5,30 @@ use std::sync::Arc;
 use std::{env, fs};
 
 use colored::Colorize;
-use futures::stream::{self, StreamExt};
-use indicatif::{ProgressBar, ProgressStyle};
 use serde::Serialize;
 
+use super::exit::Exit;
 use super::files::collect_files;
-use crate::github::GitHubClient;
-use crate::ownership::find_codeowners;
+use super::{profile, progress};
+use crate::github::{
+    closest_owner, GitHubClient, LookupStats, OwnerInfo, TokenKind, ME_ALIAS, ME_IN_CODEOWNERS,
+};
+use crate::ownership::{find_codeowners, get_repo_root};
 use crate::parser::{parse_codeowners_file_with_positions, CodeownersLine};
-use crate::pattern::pattern_matches;
+use crate::pattern::{normalize_path, CompiledPattern};
+use crate::settings::{load_settings_from_path, Settings};
+use crate::skip_owners::SkipOwners;
 
-const CONCURRENCY: usize = 5;
+/// Reason given for owners GitHub doesn't know
+const NOT_FOUND: &str = "not found on GitHub";
 
 #[derive(Debug)]
 enum ValidationResult {
     Valid(String),
     Invalid(String, &'static str),
-    Unknown(String, &'static str),
+    Unknown(String, String),
+    /// Matched `--skip-owner` or `skip_owners`; never looked up
+    Skipped(String),
 }
 
 #[derive(Serialize)]
@@ -29,28 +36,53 @@ struct ValidateOwnersJson {
     valid: Vec<String>,
     invalid: Vec<InvalidOwner>,
     unknown: Vec<InvalidOwner>,
+    skipped: Vec<String>,
+    lookups: LookupStats,
 }
 
 #[derive(Serialize)]
 struct InvalidOwner {
     owner: String,
     reason: String,
+    /// Near-miss valid owner, for owners that weren't found
+    #[serde(skip_serializing_if = "Option::is_none")]
+    suggestion: Option<String>,
 }
 
-pub async fn validate_owners(
-    token: &str,
-    files: Option<Vec<String>>,
-    files_from: Option<PathBuf>,
-    stdin: bool,
-    json: bool,
-) -> ExitCode {
+/// Options for the validate-owners command
+#[derive(Debug, Clone, Default)]
+pub struct ValidateOwnersOptions {
+    /// GitHub token
+    pub token: String,
+    /// Only validate owners for rules matching these files
+    pub files: Option<Vec<String>>,
+    /// Read files to filter by from a file (one per line)
+    pub files_from: Option<PathBuf>,
+    /// Read files to filter by from stdin (one per line)
+    pub stdin: bool,
+    /// File lists are NUL-separated instead of one per line
+    pub null: bool,
+    /// Output as JSON
+    pub json: bool,
+    /// Maximum concurrent GitHub API requests (default: config)
+    pub concurrency: Option<usize>,
+    /// Exit 1 when some owners couldn't be checked
+    pub fail_on_unknown: bool,
+    /// Owners (`*` wildcards allowed) to report as skipped instead of
+    /// validating, on top of `skip_owners` in config
+    pub skip_owners: Vec<String>,
+    /// Client to validate with; share one to reuse its cache across commands
+    pub github: Arc<GitHubClient>,
+}
+
+pub async fn validate_owners(options: ValidateOwnersOptions) -> ExitCode {
     let cwd = env::current_dir().expect("Failed to get current directory");
 
     let codeowners_path = match find_codeowners(&cwd) {
         Some(p) => p,
         None => {
             eprintln!("No CODEOWNERS file found");
-            return ExitCode::from(1);
+            return Exit::Error.into();
         }
     };
 
@@ -58,22 +90,49 @@ pub async fn validate_owners(
         Ok(c) => c,
         Err(e) => {
             eprintln!("Failed to read {}: {}", codeowners_path.display(), e);
-            return ExitCode::from(1);
+            return Exit::Error.into();
         }
     };
 
+    let settings = load_settings_from_path(&get_repo_root(&codeowners_path, &cwd));
+    validate_content(&content, &settings, options).await
+}
+
+/// Validate the owners in CODEOWNERS `content` and report them
+async fn validate_content(
+    content: &str,
+    settings: &Settings,
+    options: ValidateOwnersOptions,
+) -> ExitCode {
+    let ValidateOwnersOptions {
+        token,
+        files,
+        files_from,
+        stdin,
+        null,
+        json,
+        concurrency,
+        fail_on_unknown,
+        skip_owners,
+        github: client,
+    } = options;
+    let token = token.as_str();
+
     // Collect files to filter by (if specified)
-    let files_filter = match collect_files(files, files_from, stdin) {
+    let files_filter = match collect_files(files, files_from, stdin, null) {
         Ok(f) => f,
         Err(e) => {
             eprintln!("{}", e);
-            return ExitCode::from(1);
+            return Exit::Error.into();
         }
     };
 
     // Collect owners (optionally filtered by which rules match specified files)
-    let lines = parse_codeowners_file_with_positions(&content);
+    let lines = profile::time(profile::PARSE, || {
+        parse_codeowners_file_with_positions(content)
+    });
     let mut owners: HashSet<String> = HashSet::new();
+    let pattern_options = settings.pattern_options();
 
     for line in &lines {
         if let CodeownersLine::Rule {
@@ -83,7 +142,10 @@ pub async fn validate_owners(
         {
             // If file filter specified, only include owners from matching rules
             let should_include = match &files_filter {
-                Some(filter) => filter.iter().any(|f| pattern_matches(pattern, f)),
+                Some(filter) => {
+                    let compiled = CompiledPattern::with_options(pattern, pattern_options);
+                    filter.iter().any(|f| compiled.matches(&normalize_path(f)))
+                }
                 None => true,
             };
 
@@ -120,52 +182,67 @@ pub async fn validate_owners(
         filter_msg
     );
 
-    // Progress bar
-    let pb = ProgressBar::new(total as u64);
-    pb.set_style(
-        ProgressStyle::default_bar()
-            .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} {msg}")
-            .unwrap()
-            .progress_chars("━╸─"),
-    );
+    if let Some(warning) = TokenKind::detect(token).scope_warning() {
+        eprintln!("{} {}\n", "Warning:".yellow().bold(), warning);
+    }
 
-    let client = Arc::new(GitHubClient::new());
-    let token = token.to_string();
-
-    // Validate in parallel with concurrency limit
-    let results: Vec<ValidationResult> = stream::iter(owners_vec)
-        .map(|owner| {
-            let client = Arc::clone(&client);
-            let token = token.clone();
-            let pb = pb.clone();
-            async move {
-                let result = validate_single(&client, &owner, &token).await;
-                pb.inc(1);
-                result
-            }
-        })
-        .buffer_unordered(CONCURRENCY)
-        .collect()
-        .await;
+    let pb = progress::bar(total as u64, "Validating owners");
+
+    let concurrency = concurrency.unwrap_or_else(|| settings.validation_concurrency());
+    let skip = SkipOwners::new(settings.skip_owners.iter().chain(&skip_owners));
+    let (skipped, owners_vec): (Vec<String>, Vec<String>) = owners_vec
+        .into_iter()
+        .partition(|owner| skip.matches(owner));
+    pb.inc(skipped.len() as u64);
+    // `@me` isn't expanded in CODEOWNERS, so don't validate the user `me`
+    let (written_me, owners_vec): (Vec<String>, Vec<String>) = owners_vec
+        .into_iter()
+        .partition(|owner| owner.eq_ignore_ascii_case(ME_ALIAS));
+    pb.inc(written_me.len() as u64);
+
+    client.reset_lookup_stats();
+    let validated = profile::time_async(
+        profile::VALIDATE,
+        client.validate_owners_with_progress(owners_vec, token, concurrency, |_, _| pb.inc(1)),
+    )
+    .await;
+    let results: Vec<ValidationResult> = validated
+        .into_iter()
+        .map(|(owner, info)| classify(owner, info.as_ref()))
+        .chain(
+            written_me
+                .into_iter()
+                .map(|owner| ValidationResult::Invalid(owner, ME_IN_CODEOWNERS)),
+        )
+        .chain(skipped.into_iter().map(ValidationResult::Skipped))
+        .collect();
 
     pb.finish_and_clear();
+    let lookups = client.lookup_stats();
 
     // Sort results for display
     let mut valid: Vec<&str> = Vec::new();
     let mut invalid: Vec<(&str, &str)> = Vec::new();
     let mut unknown: Vec<(&str, &str)> = Vec::new();
+    let mut skipped: Vec<&str> = Vec::new();
 
     for result in &results {
         match result {
             ValidationResult::Valid(owner) => valid.push(owner),
             ValidationResult::Invalid(owner, reason) => invalid.push((owner, reason)),
-            ValidationResult::Unknown(owner, reason) => unknown.push((owner, reason)),
+            ValidationResult::Unknown(owner, reason) => unknown.push((owner, reason.as_str())),
+            ValidationResult::Skipped(owner) => skipped.push(owner),
         }
     }
 
     valid.sort();
+    skipped.sort();
     invalid.sort_by_key(|(o, _)| *o);
     unknown.sort_by_key(|(o, _)| *o);
+    let suggestions: Vec<Option<&str>> = invalid
+        .iter()
+        .map(|(owner, reason)| suggest(owner, reason, &valid))
+        .collect();
 
     // JSON output
     if json {
@@ -173,9 +250,11 @@ pub async fn validate_owners(
             valid: valid.iter().map(|s| s.to_string()).collect(),
             invalid: invalid
                 .iter()
-                .map(|(owner, reason)| InvalidOwner {
+                .zip(&suggestions)
+                .map(|((owner, reason), suggestion)| InvalidOwner {
                     owner: owner.to_string(),
                     reason: reason.to_string(),
+                    suggestion: suggestion.map(str::to_string),
                 })
                 .collect(),
             unknown: unknown
@@ -183,61 +262,105 @@ pub async fn validate_owners(
                 .map(|(owner, reason)| InvalidOwner {
                     owner: owner.to_string(),
                     reason: reason.to_string(),
+                    suggestion: None,
                 })
                 .collect(),
+            skipped: skipped.iter().map(|s| s.to_string()).collect(),
+            lookups,
         };
         println!(
             "{}",
             serde_json::to_string(&output).expect("Failed to serialize JSON")
         );
-        return if !invalid.is_empty() {
-            ExitCode::from(1)
-        } else {
-            ExitCode::SUCCESS
-        };
+        return exit_status(invalid.len(), unknown.len(), fail_on_unknown).into();
     }
 
     // Print results
     for owner in &valid {
         println!("  {} {}", "✓".green(), owner);
     }
-    for (owner, reason) in &invalid {
+    for ((owner, reason), suggestion) in invalid.iter().zip(&suggestions) {
+        let hint = match suggestion {
+            Some(suggestion) => format!(" did you mean {}?", suggestion.cyan()),
+            None => String::new(),
+        };
         println!(
-            "  {} {} {}",
+            "  {} {} {}{}",
             "✗".red(),
             owner,
-            format!("({})", reason).dimmed()
+            format!("({})", reason).dimmed(),
+            hint
         );
     }
     for (owner, reason) in &unknown {
         println!("  {} {} {}", "?".yellow(), owner, reason.dimmed());
     }
+    for owner in &skipped {
+        println!("  {} {} {}", "-".dimmed(), owner, "(skipped)".dimmed());
+    }
 
     println!("\n{}:", "Summary".bold());
     println!("  {} {}", "Valid:".green(), valid.len());
     println!("  {} {}", "Invalid:".red(), invalid.len());
     println!("  {} {}", "Unknown:".yellow(), unknown.len());
+    if !skipped.is_empty() {
+        println!("  {} {}", "Skipped:".dimmed(), skipped.len());
+    }
+    println!(
+        "  {}",
+        format!(
+            "{} from cache, {} fetched, {} rate-limited",
+            lookups.cached, lookups.fetched, lookups.rate_limited
+        )
+        .dimmed()
+    );
+    if !unknown.is_empty() {
+        let note = if fail_on_unknown {
+            "Unknown owners fail validation (--fail-on-unknown)"
+        } else {
+            "Unknown owners don't fail validation (use --fail-on-unknown to require them)"
+        };
+        println!("  {}", note.dimmed());
+    }
 
-    if !invalid.is_empty() {
-        ExitCode::from(1)
-    } else {
-        ExitCode::SUCCESS
+    exit_status(invalid.len(), unknown.len(), fail_on_unknown).into()
+}
+
+/// "Did you mean" for an owner that wasn't found, from the owners that were
+fn suggest<'a>(owner: &str, reason: &str, valid: &[&'a str]) -> Option<&'a str> {
+    if reason != NOT_FOUND {
+        return None;
     }
+    closest_owner(owner, valid.iter().copied())
 }
 
-async fn validate_single(client: &GitHubClient, owner: &str, token: &str) -> ValidationResult {
-    let result = client.validate_owner(owner, token).await;
+/// Invalid owners always fail; unknown ones only with `--fail-on-unknown`
+fn exit_status(invalid: usize, unknown: usize, fail_on_unknown: bool) -> Exit {
+    if invalid > 0 || (fail_on_unknown && unknown > 0) {
+        Exit::InvalidOwners
+    } else {
+        Exit::Success
+    }
+}
 
-    match result {
-        Some(true) => ValidationResult::Valid(owner.to_string()),
-        Some(false) => ValidationResult::Invalid(owner.to_string(), "not found on GitHub"),
+fn classify(owner: String, info: Option<&OwnerInfo>) -> ValidationResult {
+    match info {
+        Some(OwnerInfo::User(_) | OwnerInfo::Team(_) | OwnerInfo::Org(_)) => {
+            ValidationResult::Valid(owner)
+        }
+        Some(OwnerInfo::Suspended(_)) => ValidationResult::Invalid(owner, "account suspended"),
+        Some(OwnerInfo::Invalid) => ValidationResult::Invalid(owner, NOT_FOUND),
+        // Say why, e.g. that an installation token can't see the team
+        Some(OwnerInfo::Unknown(reason)) => {
+            ValidationResult::Unknown(owner, format!("(couldn't validate: {})", reason))
+        }
         None => {
             let reason = if owner.contains('@') && !owner.starts_with('@') {
                 "(email, can't validate)"
             } else {
                 "(couldn't validate - check permissions)"
             };
-            ValidationResult::Unknown(owner.to_string(), reason)
+            ValidationResult::Unknown(owner, reason.to_string())
         }
     }
 }
@@ -248,6 +371,38 @@ mod tests {
     use wiremock::matchers::{method, path};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
+    async fn validate_single(client: &GitHubClient, owner: &str, token: &str) -> ValidationResult {
+        let mut results = client.validate_owners([owner.to_string()], token, 1).await;
+        classify(owner.to_string(), results.remove(owner).flatten().as_ref())
+    }
+
+    #[test]
+    fn test_exit_status_lenient_by_default() {
+        assert_eq!(exit_status(0, 0, false), Exit::Success);
+        assert_eq!(exit_status(0, 3, false), Exit::Success);
+        assert_eq!(exit_status(1, 0, false), Exit::InvalidOwners);
+        assert_eq!(exit_status(1, 3, false), Exit::InvalidOwners);
+    }
+
+    #[test]
+    fn test_exit_status_on_unknown_when_requested() {
+        assert_eq!(exit_status(0, 0, true), Exit::Success);
+        assert_eq!(exit_status(0, 3, true), Exit::InvalidOwners);
+        assert_eq!(exit_status(1, 0, true), Exit::InvalidOwners);
+    }
+
+    #[test]
+    fn test_suggest_only_for_owners_not_found() {
+        let valid = ["@org/platform", "@alice"];
+        assert_eq!(
+            suggest("@org/platfrm", NOT_FOUND, &valid),
+            Some("@org/platform")
+        );
+        assert_eq!(suggest("@org/frontend", NOT_FOUND, &valid), None);
+        // A suspended account is spelled right; suggesting another is noise
+        assert_eq!(suggest("@alicee", "account suspended", &valid), None);
+    }
+
     #[tokio::test]
     async fn test_validate_single_valid_user() {
         let mock_server = MockServer::start().await;
@@ -300,7 +455,7 @@ mod tests {
         );
         if let ValidationResult::Unknown(owner, reason) = result {
             assert_eq!(owner, "@surgeventures/tribe-growth");
-            assert!(reason.contains("check permissions"));
+            assert!(reason.contains("read:org scope"));
         }
     }
 
@@ -336,6 +491,139 @@ mod tests {
         assert!(matches!(result, ValidationResult::Unknown(..)));
     }
 
+    #[tokio::test]
+    async fn test_validate_single_team_installation_token_reason() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/orgs/someorg/teams/private"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+
+        let client = GitHubClient::with_base_url(&mock_server.uri());
+        let result = validate_single(&client, "@someorg/private", "ghs_installation").await;
+        assert!(
+            matches!(result, ValidationResult::Unknown(_, ref reason)
+                if reason.contains("GitHub App installation token")),
+            "{result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shared_client_caches_across_calls() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/alice"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "login": "alice",
+                "html_url": "https://github.com/alice"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let github = Arc::new(GitHubClient::with_base_url(&mock_server.uri()));
+        for _ in 0..2 {
+            let code = validate_content(
+                "*.rs @alice\n",
+                &Settings::default(),
+                ValidateOwnersOptions {
+                    token: "fake-token".to_string(),
+                    json: true,
+                    concurrency: Some(1),
+                    github: Arc::clone(&github),
+                    ..Default::default()
+                },
+            )
+            .await;
+            assert_eq!(code, ExitCode::from(Exit::Success));
+        }
+        // The second call was answered from the shared cache, and counted
+        // from zero again
+        assert!(github.is_cached("@alice"));
+        assert_eq!(
+            github.lookup_stats(),
+            LookupStats {
+                cached: 1,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_skipped_owners_never_fail_validation() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/orgs/acme/teams/deploy-bot"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users/ghost"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/orgs/ghost"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+
+        let github = Arc::new(GitHubClient::with_base_url(&mock_server.uri()));
+        let settings = Settings::default();
+        let run = |content: &'static str| {
+            validate_content(
+                content,
+                &settings,
+                ValidateOwnersOptions {
+                    token: "fake-token".to_string(),
+                    json: true,
+                    concurrency: Some(1),
+                    fail_on_unknown: true,
+                    skip_owners: vec!["@acme/*-bot".to_string(), "*@partner.example".to_string()],
+                    github: Arc::clone(&github),
+                    ..Default::default()
+                },
+            )
+        };
+
+        let code = run("* @acme/deploy-bot ops@partner.example\n").await;
+        assert_eq!(code, ExitCode::from(Exit::Success));
+        assert_eq!(github.lookup_stats(), LookupStats::default());
+
+        // Owners not on the list are still validated
+        let code = run("* @acme/deploy-bot @ghost\n").await;
+        assert_eq!(code, ExitCode::from(Exit::InvalidOwners));
+        assert_eq!(github.lookup_stats().fetched, 1);
+    }
+
+    #[tokio::test]
+    async fn test_me_in_codeowners_is_invalid_without_lookup() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/me"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let github = Arc::new(GitHubClient::with_base_url(&mock_server.uri()));
+        let code = validate_content(
+            "* @Me\n",
+            &Settings::default(),
+            ValidateOwnersOptions {
+                token: "fake-token".to_string(),
+                json: true,
+                github: Arc::clone(&github),
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(code, ExitCode::from(Exit::InvalidOwners));
+        assert_eq!(github.lookup_stats(), LookupStats::default());
+    }
+
     #[tokio::test]
     async fn test_validate_single_email_is_unknown() {
         let client = GitHubClient::new();
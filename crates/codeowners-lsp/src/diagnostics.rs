@@ -1,28 +1,91 @@
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 use tower_lsp::lsp_types::*;
 
 use crate::file_cache::FileCache;
-use crate::github::{GitHubClient, OwnerInfo};
-use crate::parser::{parse_codeowners_file_with_positions, CodeownersLine, ParsedLine};
-use crate::pattern::pattern_subsumes;
-use crate::validation::{validate_owner, validate_pattern};
+use crate::github::{closest_owner, GitHubClient, OwnerInfo};
+use crate::ownership::{check_file_ownership_sections_with_options, CodeownersFile, Semantics};
+use crate::parser::{
+    find_inline_comment_start, parse_codeowners_file_with_positions, tokenize, CodeownersLine,
+    ParsedLine,
+};
+use crate::pattern::{
+    anchored_form, brace_expansion_overflows, has_brace_group, pattern_subsumes,
+    MAX_BRACE_EXPANSIONS,
+};
+use crate::validation::{
+    canonical_owner, classify_owner, merge_conflict_markers, owner_syntax_errors,
+    rule_missing_pattern, validate_pattern, OwnerKind,
+};
 
 /// Diagnostic codes for CODEOWNERS issues
 pub mod codes {
     pub const INVALID_PATTERN: &str = "invalid-pattern";
+    /// The pattern is an owner (`@team`), so the line likely lacks a pattern
+    pub const MISSING_PATTERN: &str = "missing-pattern";
     pub const INVALID_OWNER: &str = "invalid-owner";
     pub const PATTERN_NO_MATCH: &str = "pattern-no-match";
+    pub const MISSING_DIRECTORY: &str = "missing-directory";
     pub const DUPLICATE_OWNER: &str = "duplicate-owner";
     pub const SHADOWED_RULE: &str = "shadowed-rule";
     pub const NO_OWNERS: &str = "no-owners";
+    pub const MULTIPLE_CODEOWNERS: &str = "multiple-codeowners";
+    pub const BRACE_EXPANSION: &str = "brace-expansion";
+    /// With `brace_expansion`, a pattern expands to too many alternatives
+    pub const BRACE_EXPANSION_LIMIT: &str = "brace-expansion-limit";
+    pub const OWNER_CASE: &str = "owner-case";
+    /// Opt-in: owner is a user or email where a team is required
+    pub const NON_TEAM_OWNER: &str = "non-team-owner";
+    /// Opt-in: rule lists more owners than `max_owners_per_rule`
+    pub const MAX_OWNERS_PER_RULE: &str = "max-owners-per-rule";
+    /// Opt-in: who owns the CODEOWNERS file itself (`lint` only)
+    pub const CODEOWNERS_FILE_OWNER: &str = "codeowners-file-owner";
 
     #[allow(dead_code)] // Used by LSP only
     pub const GITHUB_OWNER_NOT_FOUND: &str = "github-owner-not-found";
     #[allow(dead_code)] // Used by LSP only
+    pub const GITHUB_OWNER_SUSPENDED: &str = "github-owner-suspended";
+    #[allow(dead_code)] // Used by LSP only
     pub const GITHUB_OWNER_UNVERIFIED: &str = "github-owner-unverified";
     #[allow(dead_code)] // Used by LSP only
     pub const FILE_NOT_OWNED: &str = "file-not-owned";
+    /// A `codeowners-lsp:disable-*` comment names a code that doesn't exist
+    pub const UNKNOWN_SUPPRESSION: &str = "unknown-suppression";
+    /// File starts with a UTF-8 byte order mark
+    pub const BYTE_ORDER_MARK: &str = "byte-order-mark";
+    /// File uses CRLF (Windows) line endings
+    pub const CRLF_LINE_ENDINGS: &str = "crlf-line-endings";
+    /// Git conflict markers (`<<<<<<<`, `=======`, `>>>>>>>`) left in the file
+    pub const MERGE_CONFLICT: &str = "merge-conflict";
+
+    /// Every code, for checking the names in suppression comments
+    pub const ALL: &[&str] = &[
+        INVALID_PATTERN,
+        MISSING_PATTERN,
+        INVALID_OWNER,
+        PATTERN_NO_MATCH,
+        MISSING_DIRECTORY,
+        DUPLICATE_OWNER,
+        SHADOWED_RULE,
+        NO_OWNERS,
+        MULTIPLE_CODEOWNERS,
+        BRACE_EXPANSION,
+        BRACE_EXPANSION_LIMIT,
+        OWNER_CASE,
+        NON_TEAM_OWNER,
+        MAX_OWNERS_PER_RULE,
+        CODEOWNERS_FILE_OWNER,
+        GITHUB_OWNER_NOT_FOUND,
+        GITHUB_OWNER_SUSPENDED,
+        GITHUB_OWNER_UNVERIFIED,
+        FILE_NOT_OWNED,
+        UNKNOWN_SUPPRESSION,
+        BYTE_ORDER_MARK,
+        CRLF_LINE_ENDINGS,
+        MERGE_CONFLICT,
+    ];
 }
 
 /// Configuration for diagnostic severities
@@ -30,6 +93,15 @@ pub mod codes {
 #[derive(Debug, Clone, Default)]
 pub struct DiagnosticConfig {
     severities: HashMap<String, Option<DiagnosticSeverity>>,
+    /// Flag rules with more distinct owners than this; None disables
+    /// `max-owners-per-rule`
+    pub max_owners_per_rule: Option<usize>,
+    /// Owners allowed to own the CODEOWNERS file; non-empty enables
+    /// `codeowners-file-owner`
+    pub codeowners_admins: Vec<String>,
+    /// Patterns are matched with `{a,b}` groups expanded; enables
+    /// `brace-expansion-limit`
+    pub brace_expansion: bool,
 }
 
 impl DiagnosticConfig {
@@ -40,7 +112,12 @@ impl DiagnosticConfig {
         for (code, severity_str) in map {
             severities.insert(code.clone(), parse_severity(severity_str));
         }
-        Self { severities }
+        Self {
+            severities,
+            max_owners_per_rule: None,
+            codeowners_admins: Vec::new(),
+            brace_expansion: false,
+        }
     }
 
     /// Get severity for a diagnostic code, returning the default if not configured
@@ -50,6 +127,18 @@ impl DiagnosticConfig {
             None => Some(default),       // Not configured, use default
         }
     }
+
+    /// Get severity for an opt-in diagnostic, which is off unless configured
+    pub fn get_opt_in(&self, code: &str) -> Option<DiagnosticSeverity> {
+        self.severities.get(code).copied().flatten()
+    }
+
+    /// Turn a diagnostic on at `severity` unless it is already configured
+    pub fn enable(&mut self, code: &str, severity: DiagnosticSeverity) {
+        self.severities
+            .entry(code.to_string())
+            .or_insert(Some(severity));
+    }
 }
 
 /// Parse a severity string into DiagnosticSeverity
@@ -74,8 +163,16 @@ pub fn compute_diagnostics_sync(
     file_cache: Option<&FileCache>,
     config: &DiagnosticConfig,
 ) -> (Vec<Diagnostic>, Vec<OwnerValidationInfo>) {
+    // The rules around conflict markers are half of two versions, so any
+    // other finding would be noise until the conflict is resolved
+    let conflicts = merge_conflict_diagnostics(content, config);
+    if !conflicts.is_empty() {
+        return (conflicts, Vec::new());
+    }
+
     let mut diagnostics = Vec::new();
     let lines = parse_codeowners_file_with_positions(content);
+    let line_texts: Vec<&str> = content.lines().collect();
 
     // Track patterns for dead rule detection
     // Use HashMap for O(1) exact duplicate detection
@@ -93,7 +190,33 @@ pub fn compute_diagnostics_sync(
     for parsed_line in &lines {
         if let CodeownersLine::Rule { pattern, owners } = &parsed_line.content {
             // Check pattern validity
-            if let Some(error) = validate_pattern(pattern) {
+            let missing_pattern = rule_missing_pattern(pattern, owners);
+            if missing_pattern {
+                if let Some(severity) =
+                    config.get(codes::MISSING_PATTERN, DiagnosticSeverity::ERROR)
+                {
+                    diagnostics.push(Diagnostic {
+                        range: Range {
+                            start: Position {
+                                line: parsed_line.line_number,
+                                character: parsed_line.pattern_start,
+                            },
+                            end: Position {
+                                line: parsed_line.line_number,
+                                character: parsed_line.pattern_end,
+                            },
+                        },
+                        severity: Some(severity),
+                        code: Some(NumberOrString::String(codes::MISSING_PATTERN.to_string())),
+                        source: Some("codeowners".to_string()),
+                        message: format!(
+                            "Line looks like it's missing a file pattern: '{}' is an owner (write a pattern first, e.g. '* {}')",
+                            pattern, pattern
+                        ),
+                        ..Default::default()
+                    });
+                }
+            } else if let Some(error) = validate_pattern(pattern) {
                 if let Some(severity) =
                     config.get(codes::INVALID_PATTERN, DiagnosticSeverity::ERROR)
                 {
@@ -126,49 +249,138 @@ pub fn compute_diagnostics_sync(
             }
 
             // Check owner validity (format only)
+            let line_text = line_texts
+                .get(parsed_line.line_number as usize)
+                .copied()
+                .unwrap_or_default();
+            for error in owner_syntax_errors(line_text) {
+                if let Some(severity) = config.get(codes::INVALID_OWNER, DiagnosticSeverity::ERROR)
+                {
+                    diagnostics.push(Diagnostic {
+                        range: Range {
+                            start: Position {
+                                line: parsed_line.line_number,
+                                character: error.offset as u32,
+                            },
+                            end: Position {
+                                line: parsed_line.line_number,
+                                character: (error.offset + error.owner.len()) as u32,
+                            },
+                        },
+                        severity: Some(severity),
+                        code: Some(NumberOrString::String(codes::INVALID_OWNER.to_string())),
+                        source: Some("codeowners".to_string()),
+                        message: error.message(),
+                        ..Default::default()
+                    });
+                }
+            }
+
             for (i, owner) in owners.iter().enumerate() {
+                // Malformed owners were reported above
+                let Some(kind) = classify_owner(owner) else {
+                    continue;
+                };
                 let owner_offset = calculate_owner_offset(content, parsed_line, i, owner);
+                let owner_range = Range {
+                    start: Position {
+                        line: parsed_line.line_number,
+                        character: owner_offset,
+                    },
+                    end: Position {
+                        line: parsed_line.line_number,
+                        character: owner_offset + owner.len() as u32,
+                    },
+                };
+
+                let canonical = canonical_owner(owner);
+                if canonical != owner.as_str() {
+                    if let Some(severity) =
+                        config.get(codes::OWNER_CASE, DiagnosticSeverity::WARNING)
+                    {
+                        diagnostics.push(Diagnostic {
+                            range: owner_range,
+                            severity: Some(severity),
+                            code: Some(NumberOrString::String(codes::OWNER_CASE.to_string())),
+                            source: Some("codeowners".to_string()),
+                            message: format!(
+                                "Owner '{}' should be written '{}' (GitHub ignores case)",
+                                owner, canonical
+                            ),
+                            ..Default::default()
+                        });
+                    }
+                }
 
-                if let Some(error) = validate_owner(owner) {
+                if kind != OwnerKind::Team {
+                    if let Some(severity) = config.get_opt_in(codes::NON_TEAM_OWNER) {
+                        let kind = if kind == OwnerKind::User {
+                            "a user"
+                        } else {
+                            "an email"
+                        };
+                        diagnostics.push(Diagnostic {
+                            range: owner_range,
+                            severity: Some(severity),
+                            code: Some(NumberOrString::String(codes::NON_TEAM_OWNER.to_string())),
+                            source: Some("codeowners".to_string()),
+                            message: format!(
+                                "Owner '{}' is {}; only @org/team owners are allowed",
+                                owner, kind
+                            ),
+                            ..Default::default()
+                        });
+                    }
+                }
+
+                // Format valid, queue for GitHub validation
+                owners_to_validate.push((
+                    parsed_line.line_number,
+                    owner_offset,
+                    owner.clone(),
+                    owner.len() as u32,
+                ));
+            }
+
+            // Check for duplicate owners on same line
+            // Compare canonical forms: `@Team` and `@team` are the same owner
+            let mut seen_owners: HashSet<Cow<str>> = HashSet::new();
+            for owner in owners {
+                if !seen_owners.insert(canonical_owner(owner)) {
                     if let Some(severity) =
-                        config.get(codes::INVALID_OWNER, DiagnosticSeverity::ERROR)
+                        config.get(codes::DUPLICATE_OWNER, DiagnosticSeverity::WARNING)
                     {
                         diagnostics.push(Diagnostic {
                             range: Range {
                                 start: Position {
                                     line: parsed_line.line_number,
-                                    character: owner_offset,
+                                    character: parsed_line.owners_start,
                                 },
                                 end: Position {
                                     line: parsed_line.line_number,
-                                    character: owner_offset + owner.len() as u32,
+                                    character: u32::MAX,
                                 },
                             },
                             severity: Some(severity),
-                            code: Some(NumberOrString::String(codes::INVALID_OWNER.to_string())),
+                            code: Some(NumberOrString::String(codes::DUPLICATE_OWNER.to_string())),
                             source: Some("codeowners".to_string()),
-                            message: error,
+                            message: format!("Duplicate owner '{}' on this line", owner),
                             ..Default::default()
                         });
                     }
-                } else {
-                    // Format valid, queue for GitHub validation
-                    owners_to_validate.push((
-                        parsed_line.line_number,
-                        owner_offset,
-                        owner.clone(),
-                        owner.len() as u32,
-                    ));
                 }
             }
 
-            // Check for duplicate owners on same line
-            let mut seen_owners: HashSet<&str> = HashSet::new();
-            for owner in owners {
-                if !seen_owners.insert(owner.as_str()) {
+            // Check for rules with too many owners, counting each owner once
+            if let Some(max) = config.max_owners_per_rule {
+                if seen_owners.len() > max {
                     if let Some(severity) =
-                        config.get(codes::DUPLICATE_OWNER, DiagnosticSeverity::WARNING)
+                        config.get(codes::MAX_OWNERS_PER_RULE, DiagnosticSeverity::WARNING)
                     {
+                        let last = owners.len() - 1;
+                        let owners_end =
+                            calculate_owner_offset(content, parsed_line, last, &owners[last])
+                                + owners[last].len() as u32;
                         diagnostics.push(Diagnostic {
                             range: Range {
                                 start: Position {
@@ -177,13 +389,19 @@ pub fn compute_diagnostics_sync(
                                 },
                                 end: Position {
                                     line: parsed_line.line_number,
-                                    character: u32::MAX,
+                                    character: owners_end,
                                 },
                             },
                             severity: Some(severity),
-                            code: Some(NumberOrString::String(codes::DUPLICATE_OWNER.to_string())),
+                            code: Some(NumberOrString::String(
+                                codes::MAX_OWNERS_PER_RULE.to_string(),
+                            )),
                             source: Some("codeowners".to_string()),
-                            message: format!("Duplicate owner '{}' on this line", owner),
+                            message: format!(
+                                "Rule has {} owners (max {}); GitHub requests a review from every one",
+                                seen_owners.len(),
+                                max
+                            ),
                             ..Default::default()
                         });
                     }
@@ -191,10 +409,12 @@ pub fn compute_diagnostics_sync(
             }
 
             // Check for dead rules (earlier pattern completely shadowed by later)
-            let normalized_pattern = pattern.trim_start_matches('/');
+            // `build` (any depth) and `/build` (root only) differ, while
+            // `src/a` and `/src/a` are the same rule
+            let normalized_pattern = anchored_form(pattern);
 
             // Fast path: check for exact duplicates via HashMap
-            if let Some(&prev_line) = exact_patterns.get(normalized_pattern) {
+            if let Some(&prev_line) = exact_patterns.get(normalized_pattern.as_ref()) {
                 if let Some(severity) =
                     config.get(codes::SHADOWED_RULE, DiagnosticSeverity::WARNING)
                 {
@@ -241,15 +461,16 @@ pub fn compute_diagnostics_sync(
             }
 
             // Check subsumption if current pattern could subsume others
-            // (wildcards, directories) - these can shadow earlier rules
-            let could_subsume = pattern.contains('*') || pattern.ends_with('/');
+            // (wildcards, directories, bare names) - these can shadow earlier rules
+            let could_subsume =
+                pattern.contains('*') || pattern.ends_with('/') || !pattern.contains('/');
 
             if could_subsume {
                 if let Some(severity) =
                     config.get(codes::SHADOWED_RULE, DiagnosticSeverity::WARNING)
                 {
-                    let stripped = pattern.trim_start_matches('/');
-                    let is_catchall = stripped == "*" || stripped == "**";
+                    // `/*` is anchored to the root, so it's no catch-all
+                    let is_catchall = pattern == "*" || pattern.trim_start_matches('/') == "**";
 
                     for (prev_pattern, prev_line, already_shadowed) in &mut subsume_patterns {
                         // Skip already-shadowed patterns
@@ -261,7 +482,7 @@ pub fn compute_diagnostics_sync(
                             continue;
                         }
                         // Skip exact duplicates - already reported above
-                        if prev_pattern.trim_start_matches('/') == stripped {
+                        if anchored_form(prev_pattern) == normalized_pattern {
                             continue;
                         }
                         // Fast path: catch-all subsumes everything
@@ -296,15 +517,16 @@ pub fn compute_diagnostics_sync(
             }
 
             // Track this pattern
-            exact_patterns.insert(normalized_pattern.to_string(), parsed_line.line_number);
+            exact_patterns.insert(normalized_pattern.into_owned(), parsed_line.line_number);
 
             // Track ALL patterns for shadowing detection - any pattern can be shadowed by * or **
             subsume_patterns.push((pattern.to_string(), parsed_line.line_number, false));
 
-            // Check for rules without owners
-            if owners.is_empty() {
-                // This is often intentional (opt-out of ownership), so just a hint
-                if let Some(severity) = config.get(codes::NO_OWNERS, DiagnosticSeverity::HINT) {
+            // Brace groups are not part of GitHub's pattern syntax
+            if has_brace_group(pattern) {
+                if let Some(severity) =
+                    config.get(codes::BRACE_EXPANSION, DiagnosticSeverity::WARNING)
+                {
                     diagnostics.push(Diagnostic {
                         range: Range {
                             start: Position {
@@ -317,41 +539,63 @@ pub fn compute_diagnostics_sync(
                             },
                         },
                         severity: Some(severity),
-                        code: Some(NumberOrString::String(codes::NO_OWNERS.to_string())),
+                        code: Some(NumberOrString::String(codes::BRACE_EXPANSION.to_string())),
                         source: Some("codeowners".to_string()),
-                        message: "No owners specified (files will have no code owners)".to_string(),
+                        message: "GitHub does not support brace expansion; '{a,b}' will not expand (set brace_expansion = true if your tooling does)".to_string(),
                         ..Default::default()
                     });
                 }
+
+                if config.brace_expansion && brace_expansion_overflows(pattern) {
+                    if let Some(severity) =
+                        config.get(codes::BRACE_EXPANSION_LIMIT, DiagnosticSeverity::WARNING)
+                    {
+                        diagnostics.push(Diagnostic {
+                            range: Range {
+                                start: Position {
+                                    line: parsed_line.line_number,
+                                    character: parsed_line.pattern_start,
+                                },
+                                end: Position {
+                                    line: parsed_line.line_number,
+                                    character: parsed_line.pattern_end,
+                                },
+                            },
+                            severity: Some(severity),
+                            code: Some(NumberOrString::String(
+                                codes::BRACE_EXPANSION_LIMIT.to_string(),
+                            )),
+                            source: Some("codeowners".to_string()),
+                            message: format!(
+                                "Pattern expands to more than {} alternatives; only the first {} are matched",
+                                MAX_BRACE_EXPANSIONS, MAX_BRACE_EXPANSIONS
+                            ),
+                            ..Default::default()
+                        });
+                    }
+                }
             }
-        }
-    }
 
-    // Batch check for patterns with no matching files
-    if let Some(cache) = file_cache {
-        if let Some(severity) = config.get(codes::PATTERN_NO_MATCH, DiagnosticSeverity::WARNING) {
-            let patterns: Vec<&str> = patterns_to_check.iter().map(|(p, _, _, _)| *p).collect();
-            let matched = cache.find_patterns_with_matches(&patterns);
-
-            for (i, (_, line_number, pattern_start, pattern_end)) in
-                patterns_to_check.iter().enumerate()
-            {
-                if !matched.contains(&i) {
+            // Check for rules without owners (an owner in the pattern's place
+            // was reported above)
+            if owners.is_empty() && !missing_pattern {
+                // This is often intentional (opt-out of ownership), so just a hint
+                if let Some(severity) = config.get(codes::NO_OWNERS, DiagnosticSeverity::HINT) {
                     diagnostics.push(Diagnostic {
                         range: Range {
                             start: Position {
-                                line: *line_number,
-                                character: *pattern_start,
+                                line: parsed_line.line_number,
+                                character: parsed_line.pattern_start,
                             },
                             end: Position {
-                                line: *line_number,
-                                character: *pattern_end,
+                                line: parsed_line.line_number,
+                                character: parsed_line.pattern_end,
                             },
                         },
                         severity: Some(severity),
-                        code: Some(NumberOrString::String(codes::PATTERN_NO_MATCH.to_string())),
+                        code: Some(NumberOrString::String(codes::NO_OWNERS.to_string())),
                         source: Some("codeowners".to_string()),
-                        message: "Pattern matches no files in the repository".to_string(),
+                        message: "No owners specified (files will have no code owners)".to_string(),
                         ..Default::default()
                     });
                 }
@@ -359,9 +603,353 @@ pub fn compute_diagnostics_sync(
         }
     }
 
+    // Batch check for patterns with no matching files
+    if let Some(cache) = file_cache {
+        let patterns: Vec<&str> = patterns_to_check.iter().map(|(p, _, _, _)| *p).collect();
+        let matched = if unmatched_pattern_checks_enabled(config) {
+            cache.find_patterns_with_matches(&patterns)
+        } else {
+            (0..patterns.len()).collect()
+        };
+
+        for (i, (pattern, line_number, pattern_start, pattern_end)) in
+            patterns_to_check.iter().enumerate()
+        {
+            if matched.contains(&i) {
+                continue;
+            }
+            let range = Range {
+                start: Position {
+                    line: *line_number,
+                    character: *pattern_start,
+                },
+                end: Position {
+                    line: *line_number,
+                    character: *pattern_end,
+                },
+            };
+            diagnostics.extend(unmatched_pattern_diagnostic(pattern, range, cache, config));
+        }
+    }
+
+    diagnostics.extend(encoding_diagnostics(content, config));
+
+    let suppressions = Suppressions::parse(content);
+    diagnostics.retain(|d| !suppressions.is_suppressed(d));
+    diagnostics.extend(suppressions.unknown_code_diagnostics(config));
+
     (diagnostics, owners_to_validate)
 }
 
+/// An error on every line holding a git merge-conflict marker
+fn merge_conflict_diagnostics(content: &str, config: &DiagnosticConfig) -> Vec<Diagnostic> {
+    let Some(severity) = config.get(codes::MERGE_CONFLICT, DiagnosticSeverity::ERROR) else {
+        return Vec::new();
+    };
+    let line_texts: Vec<&str> = content.lines().collect();
+    merge_conflict_markers(content)
+        .into_iter()
+        .map(|line| {
+            let text = line_texts[line as usize];
+            Diagnostic {
+                range: Range {
+                    start: Position { line, character: 0 },
+                    end: Position {
+                        line,
+                        character: text.trim_end().len() as u32,
+                    },
+                },
+                severity: Some(severity),
+                code: Some(NumberOrString::String(codes::MERGE_CONFLICT.to_string())),
+                source: Some("codeowners".to_string()),
+                message: format!(
+                    "Unresolved merge conflict at line {} (`{}` marker)",
+                    line + 1,
+                    &text[..7]
+                ),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+/// Warn about a byte order mark and CRLF line endings. The parser ignores
+/// both, but other tools reading CODEOWNERS may not: a BOM can end up in the
+/// first pattern. `fmt` removes them.
+fn encoding_diagnostics(content: &str, config: &DiagnosticConfig) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    if content.starts_with('\u{feff}') {
+        if let Some(severity) = config.get(codes::BYTE_ORDER_MARK, DiagnosticSeverity::WARNING) {
+            diagnostics.push(Diagnostic {
+                range: Range {
+                    start: Position {
+                        line: 0,
+                        character: 0,
+                    },
+                    end: Position {
+                        line: 0,
+                        character: 1,
+                    },
+                },
+                severity: Some(severity),
+                code: Some(NumberOrString::String(codes::BYTE_ORDER_MARK.to_string())),
+                source: Some("codeowners".to_string()),
+                message: "File starts with a byte order mark, which some tools read as part of the first pattern (run `fmt` to remove it)".to_string(),
+                ..Default::default()
+            });
+        }
+    }
+
+    let crlf_lines: Vec<u32> = content
+        .split_inclusive('\n')
+        .enumerate()
+        .filter(|(_, line)| line.ends_with("\r\n"))
+        .map(|(idx, _)| idx as u32)
+        .collect();
+    if let Some(&first) = crlf_lines.first() {
+        if let Some(severity) = config.get(codes::CRLF_LINE_ENDINGS, DiagnosticSeverity::WARNING) {
+            diagnostics.push(Diagnostic {
+                range: Range {
+                    start: Position {
+                        line: first,
+                        character: 0,
+                    },
+                    end: Position {
+                        line: first,
+                        character: u32::MAX,
+                    },
+                },
+                severity: Some(severity),
+                code: Some(NumberOrString::String(codes::CRLF_LINE_ENDINGS.to_string())),
+                source: Some("codeowners".to_string()),
+                message: format!(
+                    "{} line(s) end with CRLF; use LF line endings (run `fmt` to convert)",
+                    crlf_lines.len()
+                ),
+                ..Default::default()
+            });
+        }
+    }
+
+    diagnostics
+}
+
+/// Marker in comments that silences diagnostics
+const SUPPRESSION_PREFIX: &str = "codeowners-lsp:";
+
+/// Diagnostics silenced by comments: `# codeowners-lsp:disable-next-line
+/// <code>...` on the line above a rule, or `# codeowners-lsp:disable-line
+/// <code>...` at the end of it. Codes may be separated by spaces or commas;
+/// listing none silences every diagnostic on that line.
+#[derive(Debug, Default)]
+pub struct Suppressions {
+    /// Line -> silenced codes (`None` = all of them)
+    lines: HashMap<u32, Option<HashSet<String>>>,
+    /// Codes that don't exist: (line, start byte, end byte, code)
+    unknown: Vec<(u32, u32, u32, String)>,
+}
+
+impl Suppressions {
+    /// Find the suppression comments in CODEOWNERS `content`
+    pub fn parse(content: &str) -> Self {
+        let mut suppressions = Self::default();
+        for (line_number, text) in content.lines().enumerate() {
+            let line_number = line_number as u32;
+            // Byte offset of the comment's `#`, on a line of its own or
+            // after a rule
+            let comment_start = if text.trim_start().starts_with('#') {
+                text.len() - text.trim_start().len()
+            } else {
+                match find_inline_comment_start(text)
+                    .and_then(|chars| text.char_indices().nth(chars))
+                {
+                    Some((pos, _)) => pos,
+                    None => continue,
+                }
+            };
+            let comment = &text[comment_start..];
+            let Some(directive) = comment
+                .trim_start_matches('#')
+                .trim_start()
+                .strip_prefix(SUPPRESSION_PREFIX)
+            else {
+                continue;
+            };
+
+            let (target, names) = if let Some(names) = directive.strip_prefix("disable-next-line") {
+                (line_number + 1, names)
+            } else if let Some(names) = directive.strip_prefix("disable-line") {
+                (line_number, names)
+            } else {
+                continue;
+            };
+            // `disable-lines` isn't `disable-line`
+            if names.starts_with(|c: char| !c.is_whitespace() && c != ',') {
+                continue;
+            }
+
+            // `names` runs to the end of the line, so its length places it
+            let names_offset = text.len() - names.len();
+            let mut codes = HashSet::new();
+            for (token_start, token) in tokenize(names) {
+                let mut start = names_offset + token_start;
+                for name in token.split(',') {
+                    let name_start = start;
+                    start += name.len() + 1;
+                    if name.is_empty() {
+                        continue;
+                    }
+                    codes.insert(name.to_string());
+                    if codes::ALL.contains(&name) {
+                        continue;
+                    }
+                    suppressions.unknown.push((
+                        line_number,
+                        name_start as u32,
+                        (name_start + name.len()) as u32,
+                        name.to_string(),
+                    ));
+                }
+            }
+
+            let entry = suppressions
+                .lines
+                .entry(target)
+                .or_insert_with(|| Some(HashSet::new()));
+            match (entry.as_mut(), codes.is_empty()) {
+                (_, true) => *entry = None,
+                (Some(existing), false) => existing.extend(codes),
+                (None, false) => {}
+            }
+        }
+        suppressions
+    }
+
+    /// Whether a comment silences `diagnostic` (matched by code and start line)
+    pub fn is_suppressed(&self, diagnostic: &Diagnostic) -> bool {
+        let Some(silenced) = self.lines.get(&diagnostic.range.start.line) else {
+            return false;
+        };
+        match (silenced, &diagnostic.code) {
+            (None, _) => true,
+            (Some(codes), Some(NumberOrString::String(code))) => codes.contains(code),
+            (Some(_), _) => false,
+        }
+    }
+
+    /// Warnings for codes in suppression comments that don't exist
+    fn unknown_code_diagnostics(&self, config: &DiagnosticConfig) -> Vec<Diagnostic> {
+        let Some(severity) = config.get(codes::UNKNOWN_SUPPRESSION, DiagnosticSeverity::WARNING)
+        else {
+            return Vec::new();
+        };
+        self.unknown
+            .iter()
+            .map(|(line, start, end, code)| Diagnostic {
+                range: Range {
+                    start: Position {
+                        line: *line,
+                        character: *start,
+                    },
+                    end: Position {
+                        line: *line,
+                        character: *end,
+                    },
+                },
+                severity: Some(severity),
+                code: Some(NumberOrString::String(
+                    codes::UNKNOWN_SUPPRESSION.to_string(),
+                )),
+                source: Some("codeowners".to_string()),
+                message: format!("Unknown diagnostic code '{}' in suppression comment", code),
+                ..Default::default()
+            })
+            .collect()
+    }
+}
+
+/// Drop diagnostics silenced by suppression comments in `content`, for
+/// diagnostics added after [`compute_diagnostics_sync`] (e.g. GitHub checks)
+pub fn remove_suppressed(content: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let suppressions = Suppressions::parse(content);
+    diagnostics.retain(|d| !suppressions.is_suppressed(d));
+}
+
+/// Whether either check for patterns matching no files is on
+pub fn unmatched_pattern_checks_enabled(config: &DiagnosticConfig) -> bool {
+    config
+        .get(codes::PATTERN_NO_MATCH, DiagnosticSeverity::WARNING)
+        .is_some()
+        || config
+            .get(codes::MISSING_DIRECTORY, DiagnosticSeverity::WARNING)
+            .is_some()
+}
+
+/// Diagnostic for a pattern that matches no files: `missing-directory` when
+/// the directory it names doesn't exist (likely a typo), otherwise
+/// `pattern-no-match`
+pub fn unmatched_pattern_diagnostic(
+    pattern: &str,
+    range: Range,
+    cache: &FileCache,
+    config: &DiagnosticConfig,
+) -> Option<Diagnostic> {
+    let missing = config
+        .get(codes::MISSING_DIRECTORY, DiagnosticSeverity::WARNING)
+        .and_then(|severity| Some((severity, missing_directory(pattern, cache)?)));
+
+    let (severity, code, message) = match missing {
+        Some((severity, directory)) => (
+            severity,
+            codes::MISSING_DIRECTORY,
+            format!("Directory '{}' doesn't exist in the repository", directory),
+        ),
+        None => (
+            config.get(codes::PATTERN_NO_MATCH, DiagnosticSeverity::WARNING)?,
+            codes::PATTERN_NO_MATCH,
+            "Pattern matches no files in the repository".to_string(),
+        ),
+    };
+    Some(Diagnostic {
+        range,
+        severity: Some(severity),
+        code: Some(NumberOrString::String(code.to_string())),
+        source: Some("codeowners".to_string()),
+        message,
+        ..Default::default()
+    })
+}
+
+/// The literal directory an anchored pattern starts with (`src/app/` for
+/// `/src/app/*.rs`). Unanchored patterns and ones whose first segment is a
+/// glob can match anywhere, so they have none.
+fn literal_directory(pattern: &str) -> Option<&str> {
+    let trimmed = pattern.trim_end_matches('/');
+    let anchored = pattern.starts_with('/') || trimmed.contains('/');
+    if !anchored {
+        return None;
+    }
+
+    let path = pattern.trim_start_matches('/');
+    let literal = path
+        .find(['*', '?', '[', '{'])
+        .map_or(path, |glob| &path[..glob]);
+    // Up to the last `/`, so a glob-free `dir/` is the directory itself
+    let end = literal.rfind('/')? + 1;
+    Some(&literal[..end])
+}
+
+/// The shortest directory prefix of `pattern` with no files under it
+fn missing_directory(pattern: &str, cache: &FileCache) -> Option<String> {
+    let directory = literal_directory(pattern)?;
+    directory
+        .match_indices('/')
+        .map(|(i, _)| &directory[..=i])
+        .find(|prefix| !cache.has_directory(prefix))
+        .map(str::to_string)
+}
+
 /// Add GitHub validation diagnostics (async)
 #[allow(dead_code)] // Used by LSP only
 pub async fn add_github_diagnostics(
@@ -372,12 +960,17 @@ pub async fn add_github_diagnostics(
     config: &DiagnosticConfig,
 ) {
     let not_found_severity = config.get(codes::GITHUB_OWNER_NOT_FOUND, DiagnosticSeverity::WARNING);
+    let suspended_severity = config.get(codes::GITHUB_OWNER_SUSPENDED, DiagnosticSeverity::WARNING);
     let unverified_severity = config.get(codes::GITHUB_OWNER_UNVERIFIED, DiagnosticSeverity::HINT);
 
-    if not_found_severity.is_none() && unverified_severity.is_none() {
-        return; // Both disabled
+    if not_found_severity.is_none() && suspended_severity.is_none() && unverified_severity.is_none()
+    {
+        return; // All disabled
     }
 
+    // Not-found owners wait until every owner is validated, so suggestions
+    // can draw on the whole file
+    let mut not_found = Vec::new();
     for (line_number, owner_offset, owner, owner_len) in owners_to_validate {
         let range = Range {
             start: Position {
@@ -391,16 +984,20 @@ pub async fn add_github_diagnostics(
         };
 
         match github_client.validate_owner_with_info(&owner, token).await {
-            Some(OwnerInfo::Invalid) => {
-                if let Some(severity) = not_found_severity {
+            Some(OwnerInfo::Invalid) => not_found.push((range, owner)),
+            Some(OwnerInfo::Suspended(_)) => {
+                if let Some(severity) = suspended_severity {
                     diagnostics.push(Diagnostic {
                         range,
                         severity: Some(severity),
                         code: Some(NumberOrString::String(
-                            codes::GITHUB_OWNER_NOT_FOUND.to_string(),
+                            codes::GITHUB_OWNER_SUSPENDED.to_string(),
                         )),
                         source: Some("codeowners".to_string()),
-                        message: format!("Owner '{}' not found on GitHub", owner),
+                        message: format!(
+                            "Owner '{}' is a suspended GitHub account and can't review",
+                            owner
+                        ),
                         ..Default::default()
                     });
                 }
@@ -422,6 +1019,242 @@ pub async fn add_github_diagnostics(
             _ => {} // Valid or network failure — skip
         }
     }
+
+    if let Some(severity) = not_found_severity {
+        let known = github_client.get_cached_owners();
+        for (range, owner) in not_found {
+            diagnostics.push(Diagnostic {
+                range,
+                severity: Some(severity),
+                code: Some(NumberOrString::String(
+                    codes::GITHUB_OWNER_NOT_FOUND.to_string(),
+                )),
+                source: Some("codeowners".to_string()),
+                message: not_found_message(&owner, &known),
+                ..Default::default()
+            });
+        }
+    }
+}
+
+/// "not found" message, with a "did you mean" hint when a known-valid owner
+/// is a near miss
+#[allow(dead_code)] // Used by LSP only
+fn not_found_message(owner: &str, known: &[String]) -> String {
+    let message = format!("Owner '{}' not found on GitHub", owner);
+    match closest_owner(owner, known.iter().map(String::as_str)) {
+        Some(suggestion) => format!("{}; did you mean {}?", message, suggestion),
+        None => message,
+    }
+}
+
+/// Warn when more than one CODEOWNERS file exists in the repository.
+///
+/// `all` is the precedence-ordered list from `find_all_codeowners`; GitHub
+/// only honours the first entry. The diagnostic is attached to line 0 of
+/// `path` and worded depending on whether `path` is the file GitHub uses
+/// or one it ignores. Paths are displayed relative to `repo_root`.
+pub fn multiple_codeowners_diagnostic(
+    path: &Path,
+    all: &[PathBuf],
+    repo_root: &Path,
+    config: &DiagnosticConfig,
+) -> Option<Diagnostic> {
+    if all.len() < 2 {
+        return None;
+    }
+    let severity = config.get(codes::MULTIPLE_CODEOWNERS, DiagnosticSeverity::WARNING)?;
+    let display = |p: &Path| p.strip_prefix(repo_root).unwrap_or(p).display().to_string();
+
+    let message = if path == all[0] {
+        let ignored: Vec<String> = all[1..].iter().map(|p| display(p)).collect();
+        format!(
+            "Multiple CODEOWNERS files found; GitHub uses this one and ignores {}",
+            ignored.join(", ")
+        )
+    } else {
+        format!(
+            "Multiple CODEOWNERS files found; GitHub ignores this one because {} takes precedence",
+            display(&all[0])
+        )
+    };
+
+    Some(Diagnostic {
+        range: Range {
+            start: Position {
+                line: 0,
+                character: 0,
+            },
+            end: Position {
+                line: 0,
+                character: u32::MAX,
+            },
+        },
+        severity: Some(severity),
+        code: Some(NumberOrString::String(
+            codes::MULTIPLE_CODEOWNERS.to_string(),
+        )),
+        source: Some("codeowners".to_string()),
+        message,
+        ..Default::default()
+    })
+}
+
+/// Report who owns the CODEOWNERS file itself, at `path` (repo-relative).
+///
+/// Whoever owns it can approve changes to everyone's ownership, so a broad
+/// rule like `.github/** @team` quietly hands that power to `@team`. A file
+/// no rule owns (or whose rules list no owners) is always reported. With
+/// `codeowners_admins` set, an owned file is reported only when its rules
+/// list someone else; without it, the owning rules are simply named. Under
+/// GitLab semantics the owners are the union of each section's winning rule.
+pub fn codeowners_file_owner_diagnostic(
+    content: &str,
+    path: &str,
+    semantics: Semantics,
+    config: &DiagnosticConfig,
+) -> Option<Diagnostic> {
+    let severity = if config.codeowners_admins.is_empty() {
+        config.get_opt_in(codes::CODEOWNERS_FILE_OWNER)
+    } else {
+        config.get(codes::CODEOWNERS_FILE_OWNER, DiagnosticSeverity::WARNING)
+    }?;
+
+    let codeowners = CodeownersFile::parse(content);
+    // Winning rules with their owners (section defaults applied under GitLab)
+    let winners: Vec<(&ParsedLine, &str, Vec<String>)> = match semantics {
+        Semantics::GitHub => codeowners
+            .rule_for(path)
+            .and_then(|line| match &line.content {
+                CodeownersLine::Rule { pattern, owners } => {
+                    Some((line, pattern.as_str(), owners.clone()))
+                }
+                _ => None,
+            })
+            .into_iter()
+            .collect(),
+        Semantics::GitLab => check_file_ownership_sections_with_options(
+            codeowners.lines(),
+            path,
+            codeowners.pattern_options(),
+        )
+        .into_iter()
+        .filter_map(|section| {
+            let line = codeowners
+                .lines()
+                .iter()
+                .find(|line| line.line_number == section.result.line_number)?;
+            match &line.content {
+                CodeownersLine::Rule { pattern, .. } => {
+                    Some((line, pattern.as_str(), section.result.owners))
+                }
+                _ => None,
+            }
+        })
+        .collect(),
+    };
+
+    let rule_range = |line: &ParsedLine| Range {
+        start: Position {
+            line: line.line_number,
+            character: line.pattern_start,
+        },
+        end: Position {
+            line: line.line_number,
+            character: line.pattern_end,
+        },
+    };
+    // "Rule 'a' owns" or "Rules 'a', 'b' own"
+    let subject = |rules: &[&(&ParsedLine, &str, Vec<String>)], one: &str, many: &str| {
+        let patterns: Vec<String> = rules
+            .iter()
+            .map(|(_, pattern, _)| format!("'{}'", pattern))
+            .collect();
+        if rules.len() == 1 {
+            format!("Rule {} {}", patterns[0], one)
+        } else {
+            format!("Rules {} {}", patterns.join(", "), many)
+        }
+    };
+    let admins: HashSet<String> = config
+        .codeowners_admins
+        .iter()
+        .map(|a| canonical_owner(a).into_owned())
+        .collect();
+    let is_admin = |owner: &str| admins.contains(canonical_owner(owner).as_ref());
+    let mut seen = HashSet::new();
+    let owners: Vec<&str> = winners
+        .iter()
+        .flat_map(|(_, _, owners)| owners)
+        .filter(|o| seen.insert(canonical_owner(o).into_owned()))
+        .map(String::as_str)
+        .collect();
+    let others: Vec<&str> = owners.iter().copied().filter(|o| !is_admin(o)).collect();
+    let all: Vec<_> = winners.iter().collect();
+
+    let (range, message) = match winners.first() {
+        None => (
+            Range {
+                start: Position {
+                    line: 0,
+                    character: 0,
+                },
+                end: Position {
+                    line: 0,
+                    character: u32::MAX,
+                },
+            },
+            format!(
+                "No rule owns this CODEOWNERS file ({}); ownership changes need no code owner review",
+                path
+            ),
+        ),
+        Some((first, _, _)) if owners.is_empty() => (
+            rule_range(first),
+            format!(
+                "{} this CODEOWNERS file ({}) but no owners are listed; ownership changes need no code owner review",
+                subject(&all, "matches", "match"),
+                path
+            ),
+        ),
+        Some((first, _, _)) if admins.is_empty() => (
+            rule_range(first),
+            format!(
+                "{} this CODEOWNERS file ({}): {}",
+                subject(&all, "owns", "own"),
+                path,
+                owners.join(" ")
+            ),
+        ),
+        Some(_) if !others.is_empty() => {
+            // Point at the rules that let non-admins in
+            let offending: Vec<_> = winners
+                .iter()
+                .filter(|(_, _, owners)| owners.iter().any(|o| !is_admin(o)))
+                .collect();
+            (
+                rule_range(offending[0].0),
+                format!(
+                    "{} this CODEOWNERS file ({}), including non-admin owners {}; they can approve ownership changes",
+                    subject(&offending, "owns", "own"),
+                    path,
+                    others.join(" ")
+                ),
+            )
+        }
+        Some(_) => return None,
+    };
+
+    Some(Diagnostic {
+        range,
+        severity: Some(severity),
+        code: Some(NumberOrString::String(
+            codes::CODEOWNERS_FILE_OWNER.to_string(),
+        )),
+        source: Some("codeowners".to_string()),
+        message,
+        ..Default::default()
+    })
 }
 
 fn calculate_owner_offset(
@@ -436,9 +1269,9 @@ fn calculate_owner_offset(
         let line_text = content.lines().nth(parsed_line.line_number as usize);
         if let Some(text) = line_text {
             // Only search within the non-comment portion of the line
-            let search_text: String = match parsed_line.comment_start {
-                Some(cs) => text.chars().take(cs as usize).collect(),
-                None => text.to_string(),
+            let search_text = match parsed_line.comment_start {
+                Some(cs) => text.get(..cs as usize).unwrap_or(text),
+                None => text,
             };
             search_text.find(owner).unwrap_or(0) as u32
         } else {
@@ -465,6 +1298,35 @@ mod tests {
         assert!(diagnostics[0].message.contains("Invalid glob pattern"));
     }
 
+    #[test]
+    fn test_missing_pattern_diagnostic() {
+        let content = "*.rs @rust\n  @web @docs\n@org/web\n";
+        let (diagnostics, _) = compute_diagnostics_sync(content, None, &default_config());
+
+        assert_eq!(diagnostics.len(), 2, "{diagnostics:?}");
+        for (d, line, character, end) in [(&diagnostics[0], 1, 2, 6), (&diagnostics[1], 2, 0, 8)] {
+            assert_eq!(
+                d.code,
+                Some(NumberOrString::String(codes::MISSING_PATTERN.to_string()))
+            );
+            assert_eq!(d.severity, Some(DiagnosticSeverity::ERROR));
+            assert_eq!(d.range.start, Position { line, character });
+            assert_eq!(d.range.end.character, end);
+            assert!(
+                d.message.contains("missing a file pattern"),
+                "{}",
+                d.message
+            );
+        }
+    }
+
+    #[test]
+    fn test_paths_starting_with_at_are_patterns() {
+        let content = "@types/ @web\n/@scope/pkg/ @web\n**/@generated/** @web\n@babel/core @web\n";
+        let (diagnostics, _) = compute_diagnostics_sync(content, None, &default_config());
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
     #[test]
     fn test_invalid_owner_diagnostic() {
         let content = "*.rs invalid-owner";
@@ -521,6 +1383,22 @@ mod tests {
         assert!(shadowed_lines.contains(&2), "Line 2 should be shadowed");
     }
 
+    #[test]
+    fn test_anchored_star_only_shadows_root_files() {
+        let content = "/src/a.rs @x
+/*.md @docs
+build @ci
+/* @y";
+        let (diagnostics, _) = compute_diagnostics_sync(content, None, &default_config());
+
+        let shadowed: Vec<u32> = diagnostics
+            .iter()
+            .filter(|d| d.message.contains("shadowed"))
+            .map(|d| d.range.start.line)
+            .collect();
+        assert_eq!(shadowed, [1], "{:?}", diagnostics);
+    }
+
     #[test]
     fn test_catchall_shadows_mixed_patterns() {
         // Real-world case: mix of exact paths, directories, wildcards, then catch-all
@@ -559,6 +1437,309 @@ src/apps/foo/priv/gettext/ @team2
         assert!(diagnostics[0].message.contains("No owners"));
     }
 
+    #[test]
+    fn test_owner_case_diagnostic() {
+        let content = "*.rs @org/rust @MyOrg/Web Jane@Example.com";
+        let (diagnostics, _) = compute_diagnostics_sync(content, None, &default_config());
+
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics
+            .iter()
+            .all(|d| d.code == Some(NumberOrString::String(codes::OWNER_CASE.to_string()))));
+        assert_eq!(diagnostics[0].range.start.character, 15);
+        assert_eq!(
+            diagnostics[0].message,
+            "Owner '@MyOrg/Web' should be written '@myorg/web' (GitHub ignores case)"
+        );
+        assert_eq!(diagnostics[1].range.start.character, 26);
+        assert!(diagnostics[1].message.contains("'Jane@example.com'"));
+    }
+
+    #[test]
+    fn test_duplicate_owner_ignores_case() {
+        let content = "*.rs @owner @Owner";
+        let (diagnostics, _) = compute_diagnostics_sync(content, None, &default_config());
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.code == Some(NumberOrString::String(codes::DUPLICATE_OWNER.to_string()))));
+    }
+
+    #[test]
+    fn test_non_team_owner_is_opt_in() {
+        let content = "*.rs @org/rust @alice dev@example.com @org/a/b\n";
+        let (diagnostics, _) = compute_diagnostics_sync(content, None, &default_config());
+        assert!(!diagnostics
+            .iter()
+            .any(|d| d.code == Some(NumberOrString::String(codes::NON_TEAM_OWNER.to_string()))));
+
+        let mut config = default_config();
+        config.enable(codes::NON_TEAM_OWNER, DiagnosticSeverity::ERROR);
+        let (diagnostics, _) = compute_diagnostics_sync(content, None, &config);
+        let non_team: Vec<_> = diagnostics
+            .iter()
+            .filter(|d| d.code == Some(NumberOrString::String(codes::NON_TEAM_OWNER.to_string())))
+            .collect();
+
+        // The malformed @org/a/b is an invalid-owner error, not a non-team owner
+        assert_eq!(non_team.len(), 2);
+        assert_eq!(non_team[0].range.start.character, 15);
+        assert_eq!(
+            non_team[0].message,
+            "Owner '@alice' is a user; only @org/team owners are allowed"
+        );
+        assert_eq!(non_team[1].range.start.character, 22);
+        assert_eq!(
+            non_team[1].message,
+            "Owner 'dev@example.com' is an email; only @org/team owners are allowed"
+        );
+        assert_eq!(non_team[1].severity, Some(DiagnosticSeverity::ERROR));
+    }
+
+    #[test]
+    fn test_non_team_owner_respects_configured_severity() {
+        let mut map = HashMap::new();
+        map.insert(codes::NON_TEAM_OWNER.to_string(), "hint".to_string());
+        let mut config = DiagnosticConfig::from_map(&map);
+        config.enable(codes::NON_TEAM_OWNER, DiagnosticSeverity::ERROR);
+        assert_eq!(
+            config.get_opt_in(codes::NON_TEAM_OWNER),
+            Some(DiagnosticSeverity::HINT)
+        );
+    }
+
+    fn max_owner_diagnostics(content: &str, config: &DiagnosticConfig) -> Vec<Diagnostic> {
+        let (diagnostics, _) = compute_diagnostics_sync(content, None, config);
+        diagnostics
+            .into_iter()
+            .filter(|d| {
+                d.code
+                    == Some(NumberOrString::String(
+                        codes::MAX_OWNERS_PER_RULE.to_string(),
+                    ))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_max_owners_per_rule_threshold() {
+        let content = "*.rs @a @b @c\n/docs/ @a @b @c @d # four\n";
+        // Off unless a maximum is configured
+        assert!(max_owner_diagnostics(content, &default_config()).is_empty());
+
+        let mut config = default_config();
+        config.max_owners_per_rule = Some(3);
+        let diags = max_owner_diagnostics(content, &config);
+        // At the threshold is fine; over it is flagged across the owners
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 1);
+        assert_eq!(diags[0].range.start.character, 7);
+        assert_eq!(diags[0].range.end.character, 18);
+        assert_eq!(diags[0].severity, Some(DiagnosticSeverity::WARNING));
+        assert_eq!(
+            diags[0].message,
+            "Rule has 4 owners (max 3); GitHub requests a review from every one"
+        );
+    }
+
+    #[test]
+    fn test_max_owners_per_rule_counts_distinct_owners() {
+        let mut config = default_config();
+        config.max_owners_per_rule = Some(2);
+        // @A and @a are the same owner, so this rule has two
+        assert!(max_owner_diagnostics("*.rs @a @b @A @b\n", &config).is_empty());
+        assert_eq!(
+            max_owner_diagnostics("*.rs @a @b @org/c\n", &config).len(),
+            1
+        );
+
+        let mut map = HashMap::new();
+        map.insert(codes::MAX_OWNERS_PER_RULE.to_string(), "off".to_string());
+        let mut config = DiagnosticConfig::from_map(&map);
+        config.max_owners_per_rule = Some(1);
+        assert!(max_owner_diagnostics("*.rs @a @b\n", &config).is_empty());
+    }
+
+    fn unmatched_diagnostics(content: &str, config: &DiagnosticConfig) -> Vec<(u32, String)> {
+        let cache = FileCache::from_files(vec![
+            "src/main.rs".to_string(),
+            "src/app/mod.rs".to_string(),
+            "docs/guide.md".to_string(),
+        ]);
+        let (diags, _) = compute_diagnostics_sync(content, Some(&cache), config);
+        diags
+            .into_iter()
+            .filter_map(|d| match d.code {
+                Some(NumberOrString::String(code)) => Some((d.range.start.line, code)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_missing_directory_vs_pattern_no_match() {
+        let content = "/src/app/ @a\n\
+                       /srcc/app/ @a\n\
+                       /src/ap/*.rs @a\n\
+                       docs/missing.md @a\n\
+                       /doc/**/*.md @a\n";
+        assert_eq!(
+            unmatched_diagnostics(content, &default_config()),
+            vec![
+                (1, codes::MISSING_DIRECTORY.to_string()),
+                (2, codes::MISSING_DIRECTORY.to_string()),
+                // The directory exists, the file doesn't
+                (3, codes::PATTERN_NO_MATCH.to_string()),
+                (4, codes::MISSING_DIRECTORY.to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_directory_reports_first_missing_segment() {
+        let cache = FileCache::from_files(vec!["src/main.rs".to_string()]);
+        let (diags, _) =
+            compute_diagnostics_sync("/src/ap/deep/ @a\n", Some(&cache), &default_config());
+        assert_eq!(
+            diags[0].message,
+            "Directory 'src/ap/' doesn't exist in the repository"
+        );
+    }
+
+    #[test]
+    fn test_missing_directory_skips_unanchored_and_glob_patterns() {
+        // Unanchored or glob-first patterns could match anywhere: only
+        // pattern-no-match applies
+        let content = "nope/ @a\n*.xyz @a\n/**/nope/ @a\n/*/nope.rs @a\n";
+        let found = unmatched_diagnostics(content, &default_config());
+        assert_eq!(found.len(), 4);
+        assert!(found
+            .iter()
+            .all(|(_, code)| code == codes::PATTERN_NO_MATCH));
+    }
+
+    #[test]
+    fn test_missing_directory_off_falls_back_to_pattern_no_match() {
+        let mut map = HashMap::new();
+        map.insert(codes::MISSING_DIRECTORY.to_string(), "off".to_string());
+        assert_eq!(
+            unmatched_diagnostics("/srcc/ @a\n", &DiagnosticConfig::from_map(&map)),
+            vec![(0, codes::PATTERN_NO_MATCH.to_string())]
+        );
+
+        map.insert(codes::PATTERN_NO_MATCH.to_string(), "off".to_string());
+        assert!(unmatched_diagnostics("/srcc/ @a\n", &DiagnosticConfig::from_map(&map)).is_empty());
+    }
+
+    #[test]
+    fn test_literal_directory() {
+        assert_eq!(literal_directory("/src/app/*.rs"), Some("src/app/"));
+        assert_eq!(literal_directory("/docs/"), Some("docs/"));
+        assert_eq!(literal_directory("src/lib.rs"), Some("src/"));
+        assert_eq!(literal_directory("/src/ap*"), Some("src/"));
+        assert_eq!(literal_directory("/Makefile"), None);
+        assert_eq!(literal_directory("docs/"), None);
+        assert_eq!(literal_directory("/**/docs/"), None);
+    }
+
+    #[test]
+    fn test_brace_expansion_diagnostic() {
+        let content = "src/**/*.{js,ts} @web\n{}.rs @rust";
+        let (diagnostics, _) = compute_diagnostics_sync(content, None, &default_config());
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::String(codes::BRACE_EXPANSION.to_string()))
+        );
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::WARNING));
+        assert_eq!(diagnostics[0].range.start.line, 0);
+    }
+
+    #[test]
+    fn test_brace_expansion_limit_diagnostic() {
+        let content = format!("/src/{} @web\n*.{{js,ts}} @web\n", "{a,b}".repeat(9));
+        let (diagnostics, _) = compute_diagnostics_sync(&content, None, &default_config());
+        assert!(diagnostics
+            .iter()
+            .all(|d| d.code != Some(NumberOrString::String(codes::BRACE_EXPANSION_LIMIT.into()))));
+
+        let mut config = default_config();
+        config.brace_expansion = true;
+        let (diagnostics, _) = compute_diagnostics_sync(&content, None, &config);
+        let limits: Vec<_> = diagnostics
+            .iter()
+            .filter(|d| d.code == Some(NumberOrString::String(codes::BRACE_EXPANSION_LIMIT.into())))
+            .collect();
+        assert_eq!(limits.len(), 1);
+        assert_eq!(limits[0].range.start.line, 0);
+    }
+
+    #[test]
+    fn test_byte_order_mark_and_crlf_diagnostics() {
+        let content = "\u{feff}*.rs @rust\n/src/ @team\r\n/docs/ @docs\r\n";
+        let (diagnostics, owners) = compute_diagnostics_sync(content, None, &default_config());
+
+        // Only the encoding warnings: the BOM doesn't leak into the pattern
+        let codes: Vec<_> = diagnostics.iter().map(|d| d.code.clone()).collect();
+        assert_eq!(
+            codes,
+            vec![
+                Some(NumberOrString::String(codes::BYTE_ORDER_MARK.to_string())),
+                Some(NumberOrString::String(codes::CRLF_LINE_ENDINGS.to_string())),
+            ]
+        );
+        assert_eq!(diagnostics[1].range.start.line, 1);
+        assert!(diagnostics[1].message.starts_with("2 line(s)"));
+        // Owner offsets are bytes into the raw line, BOM included
+        assert_eq!(owners[0], (0, 8, "@rust".to_string(), 5));
+    }
+
+    #[test]
+    fn test_encoding_diagnostics_can_be_disabled() {
+        let config = DiagnosticConfig::from_map(&HashMap::from([
+            (codes::BYTE_ORDER_MARK.to_string(), "off".to_string()),
+            (codes::CRLF_LINE_ENDINGS.to_string(), "off".to_string()),
+        ]));
+        let (diagnostics, _) = compute_diagnostics_sync("\u{feff}*.rs @rust\r\n", None, &config);
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_merge_conflict_short_circuits_other_diagnostics() {
+        let content = "* @all\n<<<<<<< HEAD\n/src/ @web\n=======\n/src/ @@platform\n>>>>>>> main\n";
+        let (diagnostics, owners) = compute_diagnostics_sync(content, None, &default_config());
+
+        // Only the markers are reported, and nothing goes to GitHub
+        let lines: Vec<u32> = diagnostics.iter().map(|d| d.range.start.line).collect();
+        assert_eq!(lines, vec![1, 3, 5]);
+        for d in &diagnostics {
+            assert_eq!(
+                d.code,
+                Some(NumberOrString::String(codes::MERGE_CONFLICT.to_string()))
+            );
+            assert_eq!(d.severity, Some(DiagnosticSeverity::ERROR));
+        }
+        assert_eq!(
+            diagnostics[0].message,
+            "Unresolved merge conflict at line 2 (`<<<<<<<` marker)"
+        );
+        assert_eq!(diagnostics[0].range.end.character, 12);
+        assert!(owners.is_empty());
+
+        // Turned off, the file is analysed as usual
+        let config = DiagnosticConfig::from_map(&HashMap::from([(
+            codes::MERGE_CONFLICT.to_string(),
+            "off".to_string(),
+        )]));
+        let (diagnostics, _) = compute_diagnostics_sync(content, None, &config);
+        assert!(diagnostics
+            .iter()
+            .all(|d| d.code != Some(NumberOrString::String(codes::MERGE_CONFLICT.to_string()))));
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.code == Some(NumberOrString::String(codes::INVALID_OWNER.to_string()))));
+    }
+
     #[test]
     fn test_valid_content_no_diagnostics() {
         let content = "# Comment\n*.rs @owner\n/src/ @team/name";
@@ -726,6 +1907,24 @@ src/apps/foo/priv/gettext/ @team2
         assert_eq!(shadowed[0].range.start.line, 0);
     }
 
+    #[test]
+    fn test_bare_name_and_rooted_name_are_different_rules() {
+        // `build` matches at any depth, `/build` only at the root
+        let content = "build @a\n/build @b";
+        let (diagnostics, _) = compute_diagnostics_sync(content, None, &default_config());
+        assert!(!diagnostics.iter().any(|d| d.message.contains("shadowed")));
+
+        // ...but a later `build` does cover an earlier `/build`
+        let content = "/build @a\nbuild @b";
+        let (diagnostics, _) = compute_diagnostics_sync(content, None, &default_config());
+        let shadowed: Vec<_> = diagnostics
+            .iter()
+            .filter(|d| d.message.contains("shadowed"))
+            .collect();
+        assert_eq!(shadowed.len(), 1);
+        assert_eq!(shadowed[0].range.start.line, 0);
+    }
+
     #[test]
     fn test_shadowed_by_double_star() {
         let content = "*.rs @rust\n** @all";
@@ -813,12 +2012,15 @@ src/apps/foo/priv/gettext/ @team2
     fn test_code_constants() {
         // Ensure code constants are what we expect
         assert_eq!(codes::INVALID_PATTERN, "invalid-pattern");
+        assert_eq!(codes::MISSING_PATTERN, "missing-pattern");
         assert_eq!(codes::INVALID_OWNER, "invalid-owner");
         assert_eq!(codes::PATTERN_NO_MATCH, "pattern-no-match");
+        assert_eq!(codes::MISSING_DIRECTORY, "missing-directory");
         assert_eq!(codes::DUPLICATE_OWNER, "duplicate-owner");
         assert_eq!(codes::SHADOWED_RULE, "shadowed-rule");
         assert_eq!(codes::NO_OWNERS, "no-owners");
         assert_eq!(codes::GITHUB_OWNER_NOT_FOUND, "github-owner-not-found");
+        assert_eq!(codes::GITHUB_OWNER_SUSPENDED, "github-owner-suspended");
         assert_eq!(codes::GITHUB_OWNER_UNVERIFIED, "github-owner-unverified");
         assert_eq!(codes::FILE_NOT_OWNED, "file-not-owned");
     }
@@ -846,4 +2048,301 @@ src/apps/foo/priv/gettext/ @team2
             Some(DiagnosticSeverity::INFORMATION)
         );
     }
+
+    fn codeowners_file_owner(content: &str, admins: &[&str]) -> Option<Diagnostic> {
+        let mut config = default_config();
+        config.codeowners_admins = admins.iter().map(|a| a.to_string()).collect();
+        if admins.is_empty() {
+            config.enable(
+                codes::CODEOWNERS_FILE_OWNER,
+                DiagnosticSeverity::INFORMATION,
+            );
+        }
+        codeowners_file_owner_diagnostic(content, ".github/CODEOWNERS", Semantics::GitHub, &config)
+    }
+
+    #[test]
+    fn test_codeowners_file_owner_is_opt_in() {
+        let content = "*.rs @rust\n";
+        assert!(codeowners_file_owner_diagnostic(
+            content,
+            ".github/CODEOWNERS",
+            Semantics::GitHub,
+            &default_config()
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_codeowners_file_owned_by_admins() {
+        let content = "* @org/web\n/.github/CODEOWNERS @org/admins @Org/Security\n";
+        assert!(codeowners_file_owner(content, &["@org/admins", "@org/security"]).is_none());
+
+        // Without admins configured the owning rule is reported
+        let d = codeowners_file_owner(content, &[]).unwrap();
+        assert_eq!(d.severity, Some(DiagnosticSeverity::INFORMATION));
+        assert_eq!(
+            d.range.start,
+            Position {
+                line: 1,
+                character: 0
+            }
+        );
+        assert_eq!(
+            d.message,
+            "Rule '/.github/CODEOWNERS' owns this CODEOWNERS file (.github/CODEOWNERS): @org/admins @Org/Security"
+        );
+    }
+
+    #[test]
+    fn test_codeowners_file_unowned() {
+        for content in ["*.rs @rust\n", "*.rs @rust\n/.github/\n"] {
+            let d = codeowners_file_owner(content, &["@org/admins"]).unwrap();
+            assert_eq!(d.severity, Some(DiagnosticSeverity::WARNING));
+            assert!(
+                d.message.contains("need no code owner review"),
+                "{}",
+                d.message
+            );
+        }
+        let d = codeowners_file_owner("*.rs @rust\n", &[]).unwrap();
+        assert_eq!(d.range.start.line, 0);
+        assert!(d.message.starts_with("No rule owns"), "{}", d.message);
+    }
+
+    #[test]
+    fn test_codeowners_file_owned_by_broad_rule() {
+        let content = "/.github/CODEOWNERS @org/admins\n.github/** @org/ci @org/admins\n";
+        let d = codeowners_file_owner(content, &["@org/admins"]).unwrap();
+        assert_eq!(d.severity, Some(DiagnosticSeverity::WARNING));
+        assert_eq!(
+            d.range.start,
+            Position {
+                line: 1,
+                character: 0
+            }
+        );
+        assert_eq!(d.range.end.character, 10);
+        assert_eq!(
+            d.message,
+            "Rule '.github/**' owns this CODEOWNERS file (.github/CODEOWNERS), including non-admin owners @org/ci; they can approve ownership changes"
+        );
+    }
+
+    #[test]
+    fn test_codeowners_file_owner_under_gitlab() {
+        let mut config = default_config();
+        config.codeowners_admins = vec!["@org/admins".to_string()];
+        let content = "/.github/ @org/admins\n[CI] @org/ci\n.github/**\n";
+        let file_owner = |semantics, config: &DiagnosticConfig| {
+            codeowners_file_owner_diagnostic(content, ".github/CODEOWNERS", semantics, config)
+        };
+
+        // Last match wins: the CI section's default owners
+        let d = file_owner(Semantics::GitHub, &config).unwrap();
+        assert_eq!(d.range.start.line, 2);
+
+        // Union of section winners: admins and CI both own it
+        let d = file_owner(Semantics::GitLab, &config).unwrap();
+        assert_eq!(d.range.start.line, 2);
+        assert_eq!(
+            d.message,
+            "Rule '.github/**' owns this CODEOWNERS file (.github/CODEOWNERS), including non-admin owners @org/ci; they can approve ownership changes"
+        );
+
+        config.codeowners_admins.clear();
+        config.enable(
+            codes::CODEOWNERS_FILE_OWNER,
+            DiagnosticSeverity::INFORMATION,
+        );
+        let d = file_owner(Semantics::GitLab, &config).unwrap();
+        assert_eq!(d.range.start.line, 0);
+        assert_eq!(
+            d.message,
+            "Rules '/.github/', '.github/**' own this CODEOWNERS file (.github/CODEOWNERS): @org/admins @org/ci"
+        );
+    }
+
+    #[test]
+    fn test_multiple_codeowners_single_file_no_diagnostic() {
+        let all = vec![PathBuf::from("/repo/.github/CODEOWNERS")];
+        let diag =
+            multiple_codeowners_diagnostic(&all[0], &all, Path::new("/repo"), &default_config());
+        assert!(diag.is_none());
+    }
+
+    #[test]
+    fn test_multiple_codeowners_active_file() {
+        let all = vec![
+            PathBuf::from("/repo/.github/CODEOWNERS"),
+            PathBuf::from("/repo/CODEOWNERS"),
+        ];
+        let diag =
+            multiple_codeowners_diagnostic(&all[0], &all, Path::new("/repo"), &default_config())
+                .unwrap();
+        assert_eq!(diag.severity, Some(DiagnosticSeverity::WARNING));
+        assert_eq!(
+            diag.code,
+            Some(NumberOrString::String("multiple-codeowners".to_string()))
+        );
+        assert!(diag
+            .message
+            .contains("uses this one and ignores CODEOWNERS"));
+    }
+
+    #[test]
+    fn test_multiple_codeowners_ignored_file() {
+        let all = vec![
+            PathBuf::from("/repo/.github/CODEOWNERS"),
+            PathBuf::from("/repo/CODEOWNERS"),
+        ];
+        let diag =
+            multiple_codeowners_diagnostic(&all[1], &all, Path::new("/repo"), &default_config())
+                .unwrap();
+        assert!(diag
+            .message
+            .contains("ignores this one because .github/CODEOWNERS takes precedence"));
+    }
+
+    #[test]
+    fn test_multiple_codeowners_disabled() {
+        let mut map = HashMap::new();
+        map.insert("multiple-codeowners".to_string(), "off".to_string());
+        let config = DiagnosticConfig::from_map(&map);
+        let all = vec![
+            PathBuf::from("/repo/.github/CODEOWNERS"),
+            PathBuf::from("/repo/CODEOWNERS"),
+        ];
+        assert!(
+            multiple_codeowners_diagnostic(&all[0], &all, Path::new("/repo"), &config).is_none()
+        );
+    }
+
+    fn codes_by_line(diagnostics: &[Diagnostic]) -> Vec<(u32, String)> {
+        diagnostics
+            .iter()
+            .filter_map(|d| match &d.code {
+                Some(NumberOrString::String(code)) => Some((d.range.start.line, code.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_disable_next_line_suppresses_listed_codes() {
+        let content = "# codeowners-lsp:disable-next-line duplicate-owner\n\
+                       *.rs @owner @owner\n\
+                       *.md @docs @docs\n";
+        let (diagnostics, _) = compute_diagnostics_sync(content, None, &default_config());
+        assert_eq!(
+            codes_by_line(&diagnostics),
+            vec![(2, codes::DUPLICATE_OWNER.to_string())]
+        );
+    }
+
+    #[test]
+    fn test_disable_next_line_only_silences_listed_codes() {
+        let content = "# codeowners-lsp:disable-next-line shadowed-rule\n\
+                       *.rs @owner @owner\n";
+        let (diagnostics, _) = compute_diagnostics_sync(content, None, &default_config());
+        assert_eq!(
+            codes_by_line(&diagnostics),
+            vec![(1, codes::DUPLICATE_OWNER.to_string())]
+        );
+    }
+
+    #[test]
+    fn test_disable_line_suppresses_same_line() {
+        let content =
+            "*.rs @owner @owner # codeowners-lsp:disable-line duplicate-owner,owner-case\n\
+                       *.md @a @A\n";
+        let (diagnostics, _) = compute_diagnostics_sync(content, None, &default_config());
+        let found = codes_by_line(&diagnostics);
+        assert!(found.iter().all(|(line, _)| *line == 1), "{:?}", found);
+        assert!(!found.is_empty());
+    }
+
+    #[test]
+    fn test_suppression_without_codes_silences_everything() {
+        let content = "# codeowners-lsp:disable-next-line\n\
+                       *.rs @owner @owner\n\
+                       *.rs @x\n";
+        let (diagnostics, _) = compute_diagnostics_sync(content, None, &default_config());
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    }
+
+    #[test]
+    fn test_unknown_suppression_code_warns() {
+        let content = "# codeowners-lsp:disable-next-line duplicate-owner, no-such-code\n\
+                       *.rs @owner @owner\n";
+        let (diagnostics, _) = compute_diagnostics_sync(content, None, &default_config());
+        assert_eq!(
+            codes_by_line(&diagnostics),
+            vec![(0, codes::UNKNOWN_SUPPRESSION.to_string())]
+        );
+        let range = diagnostics[0].range;
+        assert_eq!(
+            &content[range.start.character as usize..range.end.character as usize],
+            "no-such-code"
+        );
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::WARNING));
+
+        let mut map = HashMap::new();
+        map.insert(codes::UNKNOWN_SUPPRESSION.to_string(), "off".to_string());
+        let config = DiagnosticConfig::from_map(&map);
+        let (diagnostics, _) = compute_diagnostics_sync(content, None, &config);
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_suppression_code_with_trailing_whitespace() {
+        // Trailing space after a non-ASCII name used to shift the offset
+        // into the middle of a character
+        let content = "*.rs @a # codeowners-lsp:disable-line ü \n";
+        let (diagnostics, _) = compute_diagnostics_sync(content, None, &default_config());
+        assert_eq!(
+            codes_by_line(&diagnostics),
+            vec![(0, codes::UNKNOWN_SUPPRESSION.to_string())]
+        );
+        let range = diagnostics[0].range;
+        assert_eq!(
+            &content[range.start.character as usize..range.end.character as usize],
+            "ü"
+        );
+
+        let content = "*.rs @a # codeowners-lsp:disable-line no-such-code   \n";
+        let (diagnostics, _) = compute_diagnostics_sync(content, None, &default_config());
+        let range = diagnostics[0].range;
+        assert_eq!(
+            &content[range.start.character as usize..range.end.character as usize],
+            "no-such-code"
+        );
+    }
+
+    #[test]
+    fn test_remove_suppressed_applies_to_later_diagnostics() {
+        let content = "# codeowners-lsp:disable-next-line github-owner-not-found\n*.rs @ghost\n";
+        let mut diagnostics = vec![Diagnostic {
+            range: Range::new(Position::new(1, 5), Position::new(1, 11)),
+            code: Some(NumberOrString::String(
+                codes::GITHUB_OWNER_NOT_FOUND.to_string(),
+            )),
+            ..Default::default()
+        }];
+        remove_suppressed(content, &mut diagnostics);
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_not_found_message_suggests_close_owner() {
+        let known = vec!["@org/platform".to_string(), "@alice".to_string()];
+        assert_eq!(
+            not_found_message("@org/platfrm", &known),
+            "Owner '@org/platfrm' not found on GitHub; did you mean @org/platform?"
+        );
+        assert_eq!(
+            not_found_message("@org/frontend", &known),
+            "Owner '@org/frontend' not found on GitHub"
+        );
+    }
 }
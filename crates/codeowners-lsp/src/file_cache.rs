@@ -1,13 +1,14 @@
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
-use std::process::Command;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::RwLock;
 
 use rayon::prelude::*;
 
 use crate::parser::{CodeownersLine, ParsedLine};
-use crate::pattern::CompiledPattern;
+use crate::pattern::{CompiledPattern, PatternOptions};
 
 /// Check if characters in needle appear in order in haystack (fuzzy match)
 fn fuzzy_match(haystack: &str, needle: &str) -> bool {
@@ -25,6 +26,66 @@ fn fuzzy_match(haystack: &str, needle: &str) -> bool {
     needle_chars.peek().is_none()
 }
 
+/// Options for building a [`FileCache`] from a repository
+#[derive(Debug, Clone, Default)]
+pub struct FileCacheOptions {
+    /// Drop files marked `export-ignore` in `.gitattributes`. These are
+    /// tracked but left out of archives, so some teams don't want them
+    /// counted for coverage.
+    pub exclude_export_ignore: bool,
+    /// Drop files matching any of these CODEOWNERS-style patterns (e.g.
+    /// vendored or generated code)
+    pub exclude: Vec<String>,
+}
+
+/// Matcher that's true for paths matching any of `patterns`
+pub fn matches_any(patterns: &[String]) -> impl Fn(&str) -> bool {
+    let compiled: Vec<CompiledPattern> = patterns.iter().map(|p| CompiledPattern::new(p)).collect();
+    move |file| compiled.iter().any(|pattern| pattern.matches(file))
+}
+
+/// Ask git which of `files` have the `export-ignore` attribute set.
+/// Uses `git check-attr` so nested `.gitattributes` files and macros are
+/// honored exactly as `git archive` would. Returns an empty set on failure.
+fn export_ignored_files(root: &Path, files: &[String]) -> HashSet<String> {
+    let Ok(mut child) = Command::new("git")
+        .args(["check-attr", "-z", "--stdin", "export-ignore"])
+        .current_dir(root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return HashSet::new();
+    };
+
+    // Feed paths from a separate thread so a full stdout pipe can't deadlock us
+    let input: Vec<u8> = files.iter().flat_map(|f| f.bytes().chain([0])).collect();
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let writer = std::thread::spawn(move || stdin.write_all(&input));
+
+    let output = child.wait_with_output();
+    let _ = writer.join();
+
+    match output {
+        Ok(output) if output.status.success() => {
+            parse_check_attr_set(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => HashSet::new(),
+    }
+}
+
+/// Parse `git check-attr -z` output (`path NUL attr NUL value NUL` triples)
+/// into the paths whose attribute is `set`
+fn parse_check_attr_set(output: &str) -> HashSet<String> {
+    let fields: Vec<&str> = output.split('\0').collect();
+    fields
+        .chunks_exact(3)
+        .filter(|triple| triple[2] == "set")
+        .map(|triple| triple[0].to_string())
+        .collect()
+}
+
 /// Cached list of files in the workspace with pattern match caching
 pub struct FileCache {
     files: Vec<String>,
@@ -32,6 +93,8 @@ pub struct FileCache {
     count_cache: RwLock<HashMap<String, usize>>,
     /// Cache of pattern -> has_match (lazily populated)
     has_match_cache: RwLock<HashSet<String>>,
+    /// How patterns are compiled for matching
+    pattern_options: PatternOptions,
 }
 
 impl FileCache {
@@ -41,30 +104,77 @@ impl FileCache {
             files,
             count_cache: RwLock::new(HashMap::new()),
             has_match_cache: RwLock::new(HashSet::new()),
+            pattern_options: PatternOptions::default(),
         }
     }
 
     /// Create a new FileCache using git ls-files to get tracked files
     pub fn new(root: &PathBuf) -> Self {
-        let files = Command::new("git")
-            .args(["ls-files", "--cached", "--others", "--exclude-standard"])
+        Self::with_options(root, FileCacheOptions::default())
+    }
+
+    /// Create a new FileCache using git ls-files, filtered per `options`
+    ///
+    /// `root` may be a subdirectory of the git repository (e.g. a monorepo
+    /// service with its own CODEOWNERS). git still resolves `.gitignore`
+    /// files and `.git/info/exclude` from the real git root, while the
+    /// returned paths stay relative to `root`.
+    pub fn with_options(root: &PathBuf, options: FileCacheOptions) -> Self {
+        // -z: without it git C-quotes paths with tabs, quotes or non-ASCII
+        let mut files: Vec<String> = Command::new("git")
+            .args([
+                "ls-files",
+                "-z",
+                "--cached",
+                "--others",
+                "--exclude-standard",
+            ])
             .current_dir(root)
             .output()
             .map(|output| {
                 String::from_utf8_lossy(&output.stdout)
-                    .lines()
+                    .split_terminator('\0')
                     .map(|s| s.to_string())
                     .collect()
             })
             .unwrap_or_default();
 
+        if options.exclude_export_ignore {
+            let ignored = export_ignored_files(root, &files);
+            if !ignored.is_empty() {
+                files.retain(|f| !ignored.contains(f));
+            }
+        }
+
+        if !options.exclude.is_empty() {
+            let excluded = matches_any(&options.exclude);
+            files.retain(|f| !excluded(f));
+        }
+
         Self {
             files,
             count_cache: RwLock::new(HashMap::new()),
             has_match_cache: RwLock::new(HashSet::new()),
+            pattern_options: PatternOptions::default(),
         }
     }
 
+    /// Match patterns with `options` (e.g. brace expansion), dropping
+    /// anything cached under the previous options
+    pub fn with_pattern_options(mut self, options: PatternOptions) -> Self {
+        if options != self.pattern_options {
+            self.pattern_options = options;
+            self.count_cache.get_mut().unwrap().clear();
+            self.has_match_cache.get_mut().unwrap().clear();
+        }
+        self
+    }
+
+    /// Compile `pattern` with the options this cache matches with
+    pub fn compile(&self, pattern: &str) -> CompiledPattern {
+        CompiledPattern::with_options(pattern, self.pattern_options)
+    }
+
     /// Count files matching a pattern (blocking, computes and caches)
     /// For CLI and sync contexts
     #[allow(dead_code)] // Used by CLI, not LSP
@@ -78,7 +188,7 @@ impl FileCache {
         }
 
         // Compile pattern once, then iterate
-        let compiled = CompiledPattern::new(pattern);
+        let compiled = self.compile(pattern);
         let count = self.files.iter().filter(|f| compiled.matches(f)).count();
 
         self.count_cache
@@ -116,7 +226,7 @@ impl FileCache {
         }
 
         // Compile once, early exit on first match
-        let compiled = CompiledPattern::new(pattern);
+        let compiled = self.compile(pattern);
         let has_match = self.files.iter().any(|f| compiled.matches(f));
 
         if has_match {
@@ -162,7 +272,7 @@ impl FileCache {
         // Second pass: compute uncached patterns in parallel
         let compiled: Vec<(usize, CompiledPattern)> = uncached_patterns
             .iter()
-            .map(|(i, p)| (*i, CompiledPattern::new(p)))
+            .map(|(i, p)| (*i, self.compile(p)))
             .collect();
 
         let flags: Vec<AtomicBool> = (0..compiled.len())
@@ -195,7 +305,7 @@ impl FileCache {
     /// Get files matching a pattern
     #[allow(dead_code)]
     pub fn get_matches(&self, pattern: &str) -> Vec<&String> {
-        let compiled = CompiledPattern::new(pattern);
+        let compiled = self.compile(pattern);
         self.files.iter().filter(|f| compiled.matches(f)).collect()
     }
 
@@ -205,6 +315,11 @@ impl FileCache {
         &self.files
     }
 
+    /// Whether any file lives under `directory` (a repo-relative `dir/`)
+    pub fn has_directory(&self, directory: &str) -> bool {
+        self.files.iter().any(|f| f.starts_with(directory))
+    }
+
     /// Get files matching a query for path completion (fzf-style)
     ///
     /// Matches files where the query appears anywhere in the path.
@@ -292,29 +407,102 @@ impl FileCache {
     /// Get files with no owners according to the given rules
     #[allow(dead_code)] // Used by CLI binary
     pub fn get_unowned_files(&self, rules: &[ParsedLine]) -> Vec<&String> {
-        // Extract and compile patterns once
-        let compiled: Vec<CompiledPattern> = rules
-            .iter()
-            .filter_map(|rule| {
-                if let CodeownersLine::Rule { pattern, .. } = &rule.content {
-                    Some(CompiledPattern::new(pattern))
-                } else {
-                    None
-                }
-            })
-            .collect();
+        let index = RuleIndex::new(rules, self.pattern_options);
+        if index.match_all {
+            return Vec::new();
+        }
 
         // Check files in parallel
         self.files
             .par_iter()
-            .filter(|file| !compiled.iter().any(|pattern| pattern.matches(file)))
+            .filter(|file| !index.matches(file))
             .collect()
     }
 }
 
+/// Rules bucketed for "does any rule match" checks: literal paths and
+/// names become hash lookups, and only globs are tried one by one.
+struct RuleIndex {
+    /// A `*` or `**` rule owns everything
+    match_all: bool,
+    /// `Exact` paths, matching themselves and anything beneath them
+    prefixes: HashSet<String>,
+    /// Anchored directories (`/docs/`), matching only what's beneath them
+    dirs: HashSet<String>,
+    /// Slash-free unanchored names, matching any path segment
+    names: HashSet<String>,
+    /// Slash-free unanchored directories (`docs/`), matching any segment
+    /// but the last
+    dir_names: HashSet<String>,
+    /// Everything else
+    scan: Vec<CompiledPattern>,
+}
+
+impl RuleIndex {
+    fn new(rules: &[ParsedLine], options: PatternOptions) -> Self {
+        let mut index = RuleIndex {
+            match_all: false,
+            prefixes: HashSet::new(),
+            dirs: HashSet::new(),
+            names: HashSet::new(),
+            dir_names: HashSet::new(),
+            scan: Vec::new(),
+        };
+        for rule in rules {
+            let CodeownersLine::Rule { pattern, .. } = &rule.content else {
+                continue;
+            };
+            match CompiledPattern::with_options(pattern, options) {
+                CompiledPattern::MatchAll => index.match_all = true,
+                CompiledPattern::Exact(path) | CompiledPattern::AnchoredDirectory(path) => {
+                    index.prefixes.insert(path);
+                }
+                CompiledPattern::UnanchoredExact(name) if !name.contains('/') => {
+                    index.names.insert(name);
+                }
+                CompiledPattern::DirectoryContents(dir) => match *dir {
+                    CompiledPattern::AnchoredDirectory(path) => {
+                        index.dirs.insert(path);
+                    }
+                    CompiledPattern::UnanchoredDirectory(name) if !name.contains('/') => {
+                        index.dir_names.insert(name);
+                    }
+                    dir => index
+                        .scan
+                        .push(CompiledPattern::DirectoryContents(Box::new(dir))),
+                },
+                compiled => index.scan.push(compiled),
+            }
+        }
+        index
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        if path.is_empty() {
+            return false;
+        }
+        let ancestors = || path.rmatch_indices('/').map(|(i, _)| &path[..i]);
+        let dir_segments = || {
+            path.rsplit_once('/')
+                .into_iter()
+                .flat_map(|(d, _)| d.split('/'))
+        };
+        self.match_all
+            || std::iter::once(path)
+                .chain(ancestors())
+                .any(|prefix| self.prefixes.contains(prefix))
+            || ancestors().any(|dir| self.dirs.contains(dir))
+            || path.split('/').any(|segment| self.names.contains(segment))
+            || dir_segments().any(|segment| self.dir_names.contains(segment))
+            || self.scan.iter().any(|pattern| pattern.matches(path))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ownership::get_repo_root;
+    use crate::parser::parse_codeowners_file_with_positions;
     use std::fs::{self, File};
     use std::process::Command;
     use tempfile::tempdir;
@@ -343,6 +531,115 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_get_unowned_files_matches_naive_scan() {
+        let files: Vec<String> = [
+            "Makefile",
+            "README.md",
+            "build",
+            "build/out.js",
+            "docs/api/index.md",
+            "docs/guide.md",
+            "lib/build/tool.rs",
+            "lib/vendor/x.c",
+            "src/main.rs",
+            "src/mainrs",
+            "src/util/build.rs",
+            "tools/Makefile",
+            "vendor/docs/api/a.md",
+            "web/app.ts",
+            "web/app.tsx",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let cache = FileCache::from_files(files);
+
+        let naive = |content: &str| -> Vec<String> {
+            let compiled: Vec<CompiledPattern> = parse_codeowners_file_with_positions(content)
+                .iter()
+                .filter_map(|rule| match &rule.content {
+                    CodeownersLine::Rule { pattern, .. } => Some(CompiledPattern::new(pattern)),
+                    _ => None,
+                })
+                .collect();
+            let mut unowned: Vec<String> = cache
+                .files
+                .iter()
+                .filter(|f| !compiled.iter().any(|p| p.matches(f)))
+                .cloned()
+                .collect();
+            unowned.sort();
+            unowned
+        };
+        let indexed = |content: &str| -> Vec<String> {
+            let rules = parse_codeowners_file_with_positions(content);
+            let mut unowned: Vec<String> = cache
+                .get_unowned_files(&rules)
+                .into_iter()
+                .cloned()
+                .collect();
+            unowned.sort();
+            unowned
+        };
+
+        for content in [
+            "",
+            "# only comments\n",
+            "* @all\n",
+            "/src/main.rs @a\n/docs/ @b\nMakefile @c\n",
+            "build @a\nbuild/ @b\ndocs/api/ @c\n/lib/vendor @d\n",
+            "*.ts @a\nsrc/**/*.rs @b\n/* @c\nfile?.md @d\n",
+            "/src/ @a\n**/docs @b\nsrc/*/ @c\n*.md @d\nweb/app.ts @e\n",
+            "build/ @a\n/build/ @b\nMakefile/ @c\nsrc/main.rs/ @d\n",
+        ] {
+            assert_eq!(indexed(content), naive(content), "rules: {:?}", content);
+        }
+        assert!(indexed("*.md @a\n** @b\n").is_empty());
+        // Directory rules leave a file of the same name unowned
+        let unowned = indexed("build/ @a\n/src/main.rs/ @b\n");
+        assert!(unowned.contains(&"build".to_string()));
+        assert!(unowned.contains(&"src/main.rs".to_string()));
+        assert!(!unowned.contains(&"lib/build/tool.rs".to_string()));
+    }
+
+    #[test]
+    fn test_negation_lines_do_not_change_coverage() {
+        // CODEOWNERS has no `!` negation (validate_pattern rejects it), so a
+        // `!vendor/**` line is a literal pattern that matches nothing: it
+        // neither un-owns files for coverage nor adds to the match count.
+        let cache =
+            FileCache::from_files(vec!["src/main.rs".to_string(), "vendor/lib.c".to_string()]);
+        let with_negation = parse_codeowners_file_with_positions("** @team\n!vendor/** @nobody\n");
+        assert!(cache.get_unowned_files(&with_negation).is_empty());
+        assert_eq!(cache.count_matches("!vendor/**"), 0);
+
+        let only_negation = parse_codeowners_file_with_positions("!vendor/** @nobody\n");
+        assert_eq!(cache.get_unowned_files(&only_negation).len(), 2);
+    }
+
+    #[test]
+    fn test_brace_expansion_pattern_options() {
+        let files = vec![
+            "a.ts".to_string(),
+            "web/b.js".to_string(),
+            "c.rs".to_string(),
+        ];
+        let rules = parse_codeowners_file_with_positions("*.{js,ts} @web\n");
+
+        let cache = FileCache::from_files(files.clone());
+        assert_eq!(cache.count_matches("*.{js,ts}"), 0);
+        assert_eq!(cache.get_unowned_files(&rules).len(), 3);
+
+        let cache = FileCache::from_files(files).with_pattern_options(PatternOptions {
+            brace_expansion: true,
+        });
+        assert_eq!(cache.count_matches("*.{js,ts}"), 2);
+        assert!(cache.has_matches("*.{js,ts}"));
+        assert_eq!(cache.find_patterns_with_matches(&["*.{js,ts}"]).len(), 1);
+        assert_eq!(cache.get_unowned_files(&rules), vec!["c.rs"]);
+    }
+
     #[test]
     fn test_file_cache_creation() {
         let dir = tempdir().unwrap();
@@ -364,6 +661,152 @@ mod tests {
         assert_eq!(cache.count_matches("*"), 4);
     }
 
+    #[test]
+    fn test_nested_gitignore_and_info_exclude() {
+        let dir = tempdir().unwrap();
+        create_test_repo(dir.path());
+        fs::create_dir_all(dir.path().join("src/target")).unwrap();
+        fs::write(dir.path().join("src/.gitignore"), "target/\n").unwrap();
+        File::create(dir.path().join("src/target/out.o")).unwrap();
+        fs::write(dir.path().join(".git/info/exclude"), "*.log\n").unwrap();
+        File::create(dir.path().join("debug.log")).unwrap();
+        File::create(dir.path().join("src/new.rs")).unwrap();
+
+        let cache = FileCache::new(&dir.path().to_path_buf());
+
+        // Untracked files count unless an ignore rule excludes them
+        assert_eq!(cache.count_matches("src/new.rs"), 1);
+        assert_eq!(cache.count_matches("src/target/"), 0);
+        assert_eq!(cache.count_matches("*.log"), 0);
+    }
+
+    #[test]
+    fn test_subdirectory_root_resolves_ignores_from_git_root() {
+        // Monorepo: git root above, CODEOWNERS in a service's .github/
+        let dir = tempdir().unwrap();
+        Command::new("git")
+            .args(["init"])
+            .current_dir(dir.path())
+            .output()
+            .unwrap();
+        let service = dir.path().join("services/api");
+        fs::create_dir_all(service.join(".github")).unwrap();
+        fs::create_dir_all(service.join("src")).unwrap();
+        fs::create_dir_all(service.join("build")).unwrap();
+        fs::create_dir_all(service.join("dist")).unwrap();
+        fs::write(dir.path().join(".gitignore"), "build/\n").unwrap();
+        fs::write(dir.path().join(".git/info/exclude"), "*.tmp\n").unwrap();
+        fs::write(service.join(".gitignore"), "dist/\n").unwrap();
+        File::create(service.join(".github/CODEOWNERS")).unwrap();
+        File::create(service.join("src/app.rs")).unwrap();
+        File::create(service.join("build/app.o")).unwrap();
+        File::create(service.join("dist/app.js")).unwrap();
+        File::create(service.join("scratch.tmp")).unwrap();
+        File::create(dir.path().join("other.rs")).unwrap();
+
+        let root = get_repo_root(&service.join(".github/CODEOWNERS"), dir.path());
+        assert_eq!(root, service);
+
+        let cache = FileCache::new(&root);
+        let mut files = cache.all_files().to_vec();
+        files.sort();
+
+        // Paths are relative to the CODEOWNERS root, and ignore rules from
+        // every level (git root, service, .git/info/exclude) apply
+        assert_eq!(
+            files,
+            vec![".github/CODEOWNERS", ".gitignore", "src/app.rs"]
+        );
+    }
+
+    #[test]
+    fn test_export_ignore_excluded_when_enabled() {
+        let dir = tempdir().unwrap();
+        create_test_repo(dir.path());
+        fs::write(
+            dir.path().join(".gitattributes"),
+            "docs/ export-ignore\ndocs/** export-ignore\n*.toml -export-ignore\n",
+        )
+        .unwrap();
+        Command::new("git")
+            .args(["add", "."])
+            .current_dir(dir.path())
+            .output()
+            .unwrap();
+
+        let root = dir.path().to_path_buf();
+
+        // Default: docs/ is tracked, so it's counted
+        let cache = FileCache::new(&root);
+        assert_eq!(cache.count_matches("*.md"), 1);
+        assert_eq!(cache.count_matches("*"), 5);
+
+        let cache = FileCache::with_options(
+            &root,
+            FileCacheOptions {
+                exclude_export_ignore: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(cache.count_matches("*.md"), 0);
+        assert_eq!(cache.count_matches("docs/"), 0);
+        // Explicitly unset attribute and unrelated files stay
+        assert_eq!(cache.count_matches("Cargo.toml"), 1);
+        assert_eq!(cache.count_matches("*"), 4);
+    }
+
+    #[test]
+    fn test_exclude_patterns() {
+        let dir = tempdir().unwrap();
+        create_test_repo(dir.path());
+        fs::create_dir_all(dir.path().join("vendor/lib")).unwrap();
+        File::create(dir.path().join("vendor/lib/dep.rs")).unwrap();
+        File::create(dir.path().join("src/schema.generated.rs")).unwrap();
+
+        let cache = FileCache::with_options(
+            &dir.path().to_path_buf(),
+            FileCacheOptions {
+                exclude: vec!["/vendor/".to_string(), "*.generated.rs".to_string()],
+                ..Default::default()
+            },
+        );
+        assert_eq!(cache.count_matches("*"), 4);
+
+        // Excluded files are neither counted nor reported as unowned
+        let rules = vec![ParsedLine {
+            line_number: 0,
+            content: CodeownersLine::Rule {
+                pattern: "*.md".to_string(),
+                owners: vec!["@docs".to_string()],
+            },
+            pattern_start: 0,
+            pattern_end: 4,
+            owners_start: 5,
+            comment_start: None,
+        }];
+        let mut unowned = cache.get_unowned_files(&rules);
+        unowned.sort();
+        assert_eq!(unowned, vec!["Cargo.toml", "src/lib.rs", "src/main.rs"]);
+    }
+
+    #[test]
+    fn test_matches_any() {
+        let excluded = matches_any(&["vendor/".to_string(), "*.pb.go".to_string()]);
+        assert!(excluded("vendor/a.go"));
+        assert!(excluded("api/v1/service.pb.go"));
+        assert!(!excluded("api/v1/service.go"));
+        assert!(!matches_any(&[])("anything"));
+    }
+
+    #[test]
+    fn test_parse_check_attr_set() {
+        let output = "a.md\0export-ignore\0set\0b.md\0export-ignore\0unspecified\0c.md\0export-ignore\0unset\0";
+        let set = parse_check_attr_set(output);
+        assert_eq!(set.len(), 1);
+        assert!(set.contains("a.md"));
+        assert!(parse_check_attr_set("").is_empty());
+    }
+
     #[test]
     fn test_get_unowned_files() {
         let dir = tempdir().unwrap();
@@ -388,6 +831,74 @@ mod tests {
         assert_eq!(unowned.len(), 2); // docs/readme.md and Cargo.toml
     }
 
+    #[test]
+    fn test_dotfiles_are_listed_and_matched() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        Command::new("git")
+            .args(["init"])
+            .current_dir(root)
+            .output()
+            .unwrap();
+        for file in [
+            ".env",
+            ".gitignore",
+            ".github/workflows/ci.yml",
+            ".config/tool/settings.toml",
+            "src/.eslintrc",
+            "src/main.rs",
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            File::create(path).unwrap();
+        }
+
+        let cache = FileCache::new(&root.to_path_buf());
+        let mut files = cache.all_files().to_vec();
+        files.sort();
+        assert_eq!(
+            files,
+            [
+                ".config/tool/settings.toml",
+                ".env",
+                ".github/workflows/ci.yml",
+                ".gitignore",
+                "src/.eslintrc",
+                "src/main.rs",
+            ]
+        );
+
+        let rules = parse_codeowners_file_with_positions("*.yml @ci\n/.config/ @ops\nsrc/* @src\n");
+        let mut unowned = cache.get_unowned_files(&rules);
+        unowned.sort();
+        assert_eq!(unowned, [".env", ".gitignore"]);
+
+        let rules = parse_codeowners_file_with_positions("* @everyone\n");
+        assert!(cache.get_unowned_files(&rules).is_empty());
+    }
+
+    #[test]
+    fn test_unusual_paths_are_listed_verbatim() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        Command::new("git")
+            .args(["init"])
+            .current_dir(root)
+            .output()
+            .unwrap();
+        let names = ["a\tb.rs", "say \"hi\".md", "caf\u{e9}.txt"];
+        for name in names {
+            File::create(root.join(name)).unwrap();
+        }
+
+        let cache = FileCache::new(&root.to_path_buf());
+        let mut files = cache.all_files().to_vec();
+        files.sort();
+        let mut expected = names.to_vec();
+        expected.sort();
+        assert_eq!(files, expected);
+    }
+
     #[test]
     fn test_all_files_owned() {
         let dir = tempdir().unwrap();
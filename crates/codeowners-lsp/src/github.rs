@@ -1,10 +1,21 @@
-use std::collections::HashMap;
-use std::fs;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::RwLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use futures::stream::{self, StreamExt};
 use serde::{Deserialize, Serialize};
 
+use crate::validation::{classify_owner, OwnerKind};
+
+/// Default number of owners validated concurrently. Kept low because GitHub
+/// applies secondary rate limits to bursts of concurrent requests.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
 /// Metadata for a GitHub user
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserInfo {
@@ -28,6 +39,16 @@ pub struct TeamInfo {
     pub repos_count: Option<u32>,
 }
 
+/// Metadata for a GitHub organization (an `@org` owner means all its members)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrgInfo {
+    pub login: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub html_url: String,
+    pub avatar_url: Option<String>,
+}
+
 /// Validation result with optional metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum OwnerInfo {
@@ -35,6 +56,10 @@ pub enum OwnerInfo {
     User(UserInfo),
     /// Valid team with metadata
     Team(TeamInfo),
+    /// Valid organization with metadata
+    Org(OrgInfo),
+    /// User exists but the account is suspended, so it can't review
+    Suspended(UserInfo),
     /// Invalid owner (doesn't exist)
     Invalid,
     /// Couldn't validate (no permission, rate limited, network error, etc)
@@ -43,13 +68,63 @@ pub enum OwnerInfo {
 
 impl OwnerInfo {
     pub fn is_valid(&self) -> bool {
-        matches!(self, OwnerInfo::User(_) | OwnerInfo::Team(_))
+        matches!(
+            self,
+            OwnerInfo::User(_) | OwnerInfo::Team(_) | OwnerInfo::Org(_)
+        )
     }
 
-    #[allow(dead_code)] // May be used later
     pub fn is_invalid(&self) -> bool {
         matches!(self, OwnerInfo::Invalid)
     }
+
+    pub fn is_suspended(&self) -> bool {
+        matches!(self, OwnerInfo::Suspended(_))
+    }
+
+    /// GitHub page for a user or team we have metadata for
+    pub fn html_url(&self) -> Option<&str> {
+        match self {
+            OwnerInfo::User(user) | OwnerInfo::Suspended(user) => Some(&user.html_url),
+            OwnerInfo::Team(team) => Some(&team.html_url),
+            OwnerInfo::Org(org) => Some(&org.html_url),
+            OwnerInfo::Invalid | OwnerInfo::Unknown(_) => None,
+        }
+    }
+}
+
+/// The known-valid owner closest to a typo'd `owner`, for "did you mean"
+/// hints. Comparison ignores case; candidates further than a third of the
+/// owner's length (at most 3 edits) away aren't suggested.
+pub fn closest_owner<'a>(
+    owner: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let owner = owner.to_lowercase();
+    let threshold = (owner.chars().count() / 3).clamp(1, 3);
+    candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let distance = edit_distance(&owner, &candidate.to_lowercase());
+            (distance > 0 && distance <= threshold).then_some((distance, candidate))
+        })
+        .min_by(|(a, a_name), (b, b_name)| a.cmp(b).then_with(|| a_name.cmp(b_name)))
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between two strings, in chars
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, &b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
 }
 
 /// In-memory cache for GitHub owner validation results
@@ -57,46 +132,237 @@ impl OwnerInfo {
 pub struct GitHubCache {
     /// Map from owner string to validation result with metadata
     pub owners: HashMap<String, OwnerInfo>,
+    /// Map from `@org/team` to its members
+    pub team_members: HashMap<String, Vec<UserInfo>>,
+    /// Map from token to the login of the user it authenticates as
+    pub authenticated_logins: HashMap<String, String>,
+    /// Map from `owner/repo@sha` to the numbers of the PRs containing it
+    pub commit_pulls: HashMap<String, Vec<u64>>,
+    /// Map from `owner/repo#number` to the PR's submitted reviews
+    pub pull_reviews: HashMap<String, Vec<PullReview>>,
+}
+
+/// Pages of commits [`GitHubClient::recent_pulls`] reads (100 each) while
+/// looking for pull requests, so history made of direct pushes ends the
+/// search
+pub const MAX_COMMIT_PAGES: usize = 5;
+
+/// Reason given for requests GitHub turned away for rate limiting
+const RATE_LIMITED: &str = "rate limit exceeded";
+
+/// Outcome of one owner request against the API
+enum Lookup {
+    /// GitHub's answer, cached for later
+    Answered(OwnerInfo),
+    /// Turned away for rate limiting; not cached, so the owner is asked
+    /// about again next time
+    RateLimited,
+}
+
+impl Lookup {
+    /// The answer, with a rate limit reported as [`OwnerInfo::Unknown`]
+    fn into_info(self) -> OwnerInfo {
+        match self {
+            Lookup::Answered(info) => info,
+            Lookup::RateLimited => OwnerInfo::Unknown(RATE_LIMITED.to_string()),
+        }
+    }
+}
+
+/// Where owner lookups were answered from, counted by
+/// [`GitHubClient::validate_owner_with_info`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct LookupStats {
+    /// Answered from the in-memory cache
+    pub cached: usize,
+    /// Answered by the GitHub API
+    pub fetched: usize,
+    /// Sent to the GitHub API but turned away for rate limiting
+    pub rate_limited: usize,
+}
+
+/// Owner alias for the user the GitHub token belongs to
+pub const ME_ALIAS: &str = "@me";
+
+/// Why [`ME_ALIAS`] written in CODEOWNERS is an error: GitHub doesn't
+/// expand it
+pub const ME_IN_CODEOWNERS: &str =
+    "`@me` only means you on the command line; GitHub reads it as the user `me`";
+
+/// Whether `owner` is [`ME_ALIAS`], in any case and with or without the `@`
+/// (owner filters take it as optional)
+pub fn is_me_alias(owner: &str) -> bool {
+    owner
+        .strip_prefix('@')
+        .unwrap_or(owner)
+        .eq_ignore_ascii_case(&ME_ALIAS[1..])
+}
+
+/// Version of the cache file format; files with another version are
+/// discarded on load rather than misread
+pub const CACHE_VERSION: u32 = 2;
+
+/// Key of the cache scope on `host`: shared by every token with `None`,
+/// private to `token` otherwise. Only a fingerprint of the token is kept.
+pub fn cache_scope(host: &str, token: Option<&str>) -> String {
+    match token {
+        None => host.to_string(),
+        Some(token) => format!("{}#{:016x}", host, fingerprint(token)),
+    }
+}
+
+/// FNV-1a, which unlike `DefaultHasher` is stable across builds
+fn fingerprint(token: &str) -> u64 {
+    token.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Whether a result holds whichever token asked: teams can be secret, and
+/// a lookup that failed may have failed for lack of permissions
+fn is_shareable(owner: &str, info: &OwnerInfo) -> bool {
+    !owner.contains('/') && !matches!(info, OwnerInfo::Unknown(_))
 }
 
 /// Persistent cache stored in .codeowners-lsp/cache.json
-#[derive(Debug, Default, Serialize, Deserialize)]
+///
+/// Results are grouped by [`cache_scope`]: users and orgs are shared by
+/// every token on the same API host, while teams and failed lookups belong
+/// to the token that fetched them, so one account never sees another's
+/// private teams. Pull request history is always the token's own, since
+/// the repository may be private; it comes from merged history, so it
+/// doesn't go stale.
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PersistentCache {
-    /// Validated owners with metadata
+    /// Format version, [`CACHE_VERSION`] when written by this build
     #[serde(default)]
-    pub owners: HashMap<String, OwnerInfo>,
+    pub version: u32,
+    /// Validated owners with metadata, by scope and then owner
+    #[serde(default)]
+    pub scopes: HashMap<String, HashMap<String, OwnerInfo>>,
     /// Timestamp of last validation (Unix seconds)
     #[serde(default)]
     pub last_updated: u64,
+    /// PRs containing each commit, by scope and then `owner/repo@sha`
+    #[serde(default)]
+    pub commit_pulls: HashMap<String, HashMap<String, Vec<u64>>>,
+    /// Submitted reviews, by scope and then `owner/repo#number`
+    #[serde(default)]
+    pub pull_reviews: HashMap<String, HashMap<String, Vec<PullReview>>>,
+}
+
+impl Default for PersistentCache {
+    fn default() -> Self {
+        Self {
+            version: CACHE_VERSION,
+            scopes: HashMap::new(),
+            last_updated: 0,
+            commit_pulls: HashMap::new(),
+            pull_reviews: HashMap::new(),
+        }
+    }
+}
+
+/// Ignore everything in the cache directory except identities.toml, which
+/// is meant to be committed. The `.gitignore` this wrote before
+/// identities.toml existed gets the exception; one the user wrote is left
+/// alone.
+fn ensure_gitignore(path: &Path) -> std::io::Result<()> {
+    const GITIGNORE: &str = "*\n!identities.toml\n";
+    match fs::read_to_string(path) {
+        Ok(existing) if existing == "*\n" => fs::write(path, GITIGNORE),
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => fs::write(path, GITIGNORE),
+        Err(e) => Err(e),
+    }
 }
 
 impl PersistentCache {
+    /// Location of the cache file under `workspace_root`
+    pub fn path(workspace_root: &Path) -> PathBuf {
+        workspace_root.join(".codeowners-lsp").join("cache.json")
+    }
+
     /// Load cache from disk
     #[allow(dead_code)] // Used by LSP only
     pub fn load(workspace_root: &Path) -> Self {
-        let cache_path = workspace_root.join(".codeowners-lsp").join("cache.json");
-        if let Ok(content) = fs::read_to_string(&cache_path) {
-            serde_json::from_str(&content).unwrap_or_default()
-        } else {
-            Self::default()
-        }
+        fs::read_to_string(Self::path(workspace_root))
+            .ok()
+            .and_then(|content| serde_json::from_str::<Self>(&content).ok())
+            .filter(|cache| cache.version == CACHE_VERSION)
+            .unwrap_or_default()
     }
 
-    /// Save cache to disk
+    /// Save cache to disk, merged with whatever is already there.
+    ///
+    /// The LSP and CLI can share a workspace, so owners saved by another
+    /// process since this cache was loaded are kept; for owners in both,
+    /// this cache's result wins.
     #[allow(dead_code)] // Used by LSP only
     pub fn save(&self, workspace_root: &Path) -> std::io::Result<()> {
+        self.write_locked(workspace_root, true)
+    }
+
+    /// Save cache to disk, replacing what's there (e.g. after pruning)
+    pub fn overwrite(&self, workspace_root: &Path) -> std::io::Result<()> {
+        self.write_locked(workspace_root, false)
+    }
+
+    /// Add `other`'s entries, preferring them over ours
+    fn merge(&mut self, other: &PersistentCache) {
+        for (scope, owners) in &other.scopes {
+            self.scopes.entry(scope.clone()).or_default().extend(
+                owners
+                    .iter()
+                    .map(|(owner, info)| (owner.clone(), info.clone())),
+            );
+        }
+        self.last_updated = self.last_updated.max(other.last_updated);
+        for (scope, pulls) in &other.commit_pulls {
+            self.commit_pulls.entry(scope.clone()).or_default().extend(
+                pulls
+                    .iter()
+                    .map(|(key, pulls)| (key.clone(), pulls.clone())),
+            );
+        }
+        for (scope, reviews) in &other.pull_reviews {
+            self.pull_reviews.entry(scope.clone()).or_default().extend(
+                reviews
+                    .iter()
+                    .map(|(key, reviews)| (key.clone(), reviews.clone())),
+            );
+        }
+    }
+
+    /// Write under an exclusive lock on `cache.lock`, via a temp file and
+    /// rename so readers never see truncated JSON
+    fn write_locked(&self, workspace_root: &Path, merge: bool) -> std::io::Result<()> {
         let cache_dir = workspace_root.join(".codeowners-lsp");
         fs::create_dir_all(&cache_dir)?;
 
-        // Create .gitignore if it doesn't exist
-        let gitignore_path = cache_dir.join(".gitignore");
-        if !gitignore_path.exists() {
-            fs::write(&gitignore_path, "*\n")?;
-        }
+        ensure_gitignore(&cache_dir.join(".gitignore"))?;
+
+        // Released when `lock` is dropped
+        let lock = File::options()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(cache_dir.join("cache.lock"))?;
+        lock.lock()?;
+
+        let content = if merge {
+            let mut merged = Self::load(workspace_root);
+            merged.merge(self);
+            serde_json::to_string_pretty(&merged)?
+        } else {
+            serde_json::to_string_pretty(self)?
+        };
 
-        let cache_path = cache_dir.join("cache.json");
-        let content = serde_json::to_string_pretty(self)?;
-        fs::write(cache_path, content)
+        let tmp_path = cache_dir.join("cache.json.tmp");
+        let mut tmp = File::create(&tmp_path)?;
+        tmp.write_all(content.as_bytes())?;
+        tmp.sync_all()?;
+        fs::rename(&tmp_path, Self::path(workspace_root))
     }
 
     /// Check if cache is stale (older than 24 hours)
@@ -117,6 +383,49 @@ impl PersistentCache {
             .map(|d| d.as_secs())
             .unwrap_or(0);
     }
+
+    /// Cached result for `owner` in `scope`
+    pub fn get(&self, scope: &str, owner: &str) -> Option<&OwnerInfo> {
+        self.scopes.get(scope)?.get(owner)
+    }
+
+    /// Cache `info` for `owner` in `scope`
+    pub fn insert(&mut self, scope: &str, owner: &str, info: OwnerInfo) {
+        self.scopes
+            .entry(scope.to_string())
+            .or_default()
+            .insert(owner.to_string(), info);
+    }
+
+    /// Number of distinct owners cached, across all scopes
+    pub fn owner_count(&self) -> usize {
+        self.scopes
+            .values()
+            .flat_map(HashMap::keys)
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    /// Remove an owner from every scope, returning one of its cached results
+    pub fn remove(&mut self, owner: &str) -> Option<OwnerInfo> {
+        let mut removed = None;
+        for owners in self.scopes.values_mut() {
+            removed = owners.remove(owner).or(removed);
+        }
+        self.scopes.retain(|_, owners| !owners.is_empty());
+        removed
+    }
+
+    /// Keep only the entries for which `keep` returns true, in every scope
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&str, &OwnerInfo) -> bool,
+    {
+        for owners in self.scopes.values_mut() {
+            owners.retain(|owner, info| keep(owner, info));
+        }
+        self.scopes.retain(|_, owners| !owners.is_empty());
+    }
 }
 
 /// Response from GitHub user API (subset of fields we care about)
@@ -128,6 +437,22 @@ struct GitHubUserResponse {
     avatar_url: Option<String>,
     bio: Option<String>,
     company: Option<String>,
+    /// Set when the account is suspended
+    #[serde(default)]
+    suspended_at: Option<String>,
+    /// "User" or "Organization"
+    #[serde(rename = "type", default)]
+    account_type: Option<String>,
+}
+
+/// Response from GitHub organization API (subset of fields we care about)
+#[derive(Debug, Deserialize)]
+struct GitHubOrgResponse {
+    login: String,
+    name: Option<String>,
+    description: Option<String>,
+    html_url: String,
+    avatar_url: Option<String>,
 }
 
 /// Response from GitHub team API (subset of fields we care about)
@@ -141,12 +466,242 @@ struct GitHubTeamResponse {
     repos_count: Option<u32>,
 }
 
-/// GitHub API client for validating owners
+/// Item from the GitHub team members API (subset of fields we care about)
+#[derive(Debug, Deserialize)]
+struct GitHubMemberResponse {
+    login: String,
+    html_url: String,
+    avatar_url: Option<String>,
+}
+
+/// Item from the GitHub commits API (only the SHA is needed)
+#[derive(Debug, Deserialize)]
+struct GitHubCommitResponse {
+    sha: String,
+}
+
+/// Item from the GitHub "pull requests for a commit" API
+#[derive(Debug, Deserialize)]
+struct GitHubPullResponse {
+    number: u64,
+}
+
+/// Item from the GitHub pull request reviews API
+#[derive(Debug, Deserialize)]
+struct GitHubReviewResponse {
+    /// `None` for reviews by deleted accounts
+    user: Option<GitHubReviewer>,
+    /// Missing on pending (unsubmitted) reviews
+    submitted_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubReviewer {
+    login: String,
+}
+
+/// A submitted review on a pull request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PullReview {
+    /// Number of the reviewed pull request
+    pub pull: u64,
+    /// Login of the reviewer, without `@`
+    pub login: String,
+    /// When the review was submitted, as Unix time
+    pub submitted_at: u64,
+}
+
+/// Parse an ISO 8601 UTC timestamp as GitHub returns it
+/// (`2024-05-01T12:30:00Z`, or with fractional seconds) into Unix time
+fn parse_timestamp(timestamp: &str) -> Option<u64> {
+    let (date, time) = timestamp.strip_suffix('Z')?.split_once('T')?;
+    let time = match time.split_once('.') {
+        Some((time, fraction))
+            if !fraction.is_empty() && fraction.bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            time
+        }
+        Some(_) => return None,
+        None => time,
+    };
+    let mut date = date.splitn(3, '-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+    let mut time = time.splitn(3, ':').map(|part| part.parse::<i64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    // Days since 1970-01-01 in the proleptic Gregorian calendar
+    let (y, m) = if month <= 2 {
+        (year - 1, month + 9)
+    } else {
+        (year, month - 3)
+    };
+    let era = y.div_euclid(400);
+    let year_of_era = y - era * 400;
+    let day_of_year = (153 * m + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    u64::try_from(days * 86_400 + hour * 3_600 + minute * 60 + second).ok()
+}
+
+/// Whether GitHub turned `response` away for rate limiting: 429 for the
+/// secondary limits, or 403 with no requests remaining for the primary one
+fn is_rate_limited(response: &reqwest::Response) -> bool {
+    match response.status().as_u16() {
+        429 => true,
+        403 => response
+            .headers()
+            .get("x-ratelimit-remaining")
+            .is_some_and(|remaining| remaining == "0"),
+        _ => false,
+    }
+}
+
+/// Error for a failed request against `/repos/{repo}/...`
+fn repo_access_error(response: &reqwest::Response, repo: &str) -> String {
+    let status = response.status();
+    match status.as_u16() {
+        _ if is_rate_limited(response) => RATE_LIMITED.to_string(),
+        403 | 404 => format!(
+            "HTTP {} for {} (check the repository name and that the token can read it)",
+            status.as_u16(),
+            repo
+        ),
+        _ => format!("HTTP {status}"),
+    }
+}
+
+/// The `rel="next"` URL from a GitHub `Link` pagination header
+fn next_page_url(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    headers
+        .get(reqwest::header::LINK)?
+        .to_str()
+        .ok()?
+        .split(',')
+        .find_map(|link| {
+            let (url, params) = link.split_once(';')?;
+            params
+                .split(';')
+                .any(|param| param.trim() == "rel=\"next\"")
+                .then(|| {
+                    url.trim()
+                        .trim_start_matches('<')
+                        .trim_end_matches('>')
+                        .to_string()
+                })
+        })
+}
+
+/// Kind of GitHub token, detected from its prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// Classic personal access token (`ghp_`)
+    Classic,
+    /// Fine-grained personal access token (`github_pat_`)
+    FineGrained,
+    /// GitHub App installation token (`ghs_`), including the Actions
+    /// `GITHUB_TOKEN`
+    Installation,
+    /// OAuth app or GitHub App user token (`gho_`, `ghu_`)
+    OAuth,
+    /// Anything else (legacy 40-hex tokens, test tokens)
+    Unknown,
+}
+
+impl TokenKind {
+    /// Detect the kind of `token` from its prefix
+    pub fn detect(token: &str) -> Self {
+        let token = token.trim();
+        if token.starts_with("ghp_") {
+            TokenKind::Classic
+        } else if token.starts_with("github_pat_") {
+            TokenKind::FineGrained
+        } else if token.starts_with("ghs_") {
+            TokenKind::Installation
+        } else if token.starts_with("gho_") || token.starts_with("ghu_") {
+            TokenKind::OAuth
+        } else {
+            TokenKind::Unknown
+        }
+    }
+
+    /// Caveat about what this kind of token can read, if it's limited
+    pub fn scope_warning(self) -> Option<&'static str> {
+        match self {
+            TokenKind::Installation => Some(
+                "GitHub App installation token: teams are only visible with the \
+                 Members (read) organization permission, and the Actions GITHUB_TOKEN \
+                 can't read teams at all",
+            ),
+            _ => None,
+        }
+    }
+}
+
+/// Reason a team couldn't be read, from its 403/404 status and the token
+fn team_access_error(status: u16, token: &str) -> String {
+    match (status, TokenKind::detect(token)) {
+        (403 | 404, TokenKind::Installation) => {
+            "team not visible to the GitHub App installation token \
+             (needs the Members (read) organization permission)"
+                .to_string()
+        }
+        (404, _) => "team not found or token lacks read:org scope".to_string(),
+        _ => "forbidden - check token permissions".to_string(),
+    }
+}
+
+/// Timeout and retry behaviour for GitHub API requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Limit for each attempt, from connecting to reading the body
+    pub timeout: Duration,
+    /// Extra attempts after network errors, timeouts and 5xx responses
+    pub max_retries: u32,
+    /// Wait before the first retry; doubles for each one after, plus up
+    /// to 50% random jitter
+    pub backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            max_retries: 2,
+            backoff: Duration::from_millis(250),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait before retry number `retry` (0-based)
+    fn delay(&self, retry: u32) -> Duration {
+        let base = self.backoff.saturating_mul(1 << retry.min(16));
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.subsec_nanos());
+        base + base.mul_f64(f64::from(nanos % 1000) / 2000.0)
+    }
+}
+
+/// GitHub API client for validating owners.
+///
+/// Lookups are cached in memory behind a lock, so one client wrapped in an
+/// `Arc` can be shared by several commands in a process (or concurrent
+/// tasks) and each owner is fetched once. The cache isn't keyed by token:
+/// share a client only between callers using the same token.
 pub struct GitHubClient {
     http_client: reqwest::Client,
     cache: RwLock<GitHubCache>,
     /// Base URL for API requests (allows testing with mock server)
     base_url: String,
+    retry: RetryPolicy,
+    cached_lookups: AtomicUsize,
+    fetched_lookups: AtomicUsize,
+    rate_limited_lookups: AtomicUsize,
 }
 
 impl GitHubClient {
@@ -155,6 +710,19 @@ impl GitHubClient {
             http_client: reqwest::Client::new(),
             cache: RwLock::new(GitHubCache::default()),
             base_url: "https://api.github.com".to_string(),
+            retry: RetryPolicy::default(),
+            cached_lookups: AtomicUsize::new(0),
+            fetched_lookups: AtomicUsize::new(0),
+            rate_limited_lookups: AtomicUsize::new(0),
+        }
+    }
+
+    /// Create a client that sends requests through `http_client`, e.g. one
+    /// configured with a proxy or extra root certificates
+    pub fn with_client(http_client: reqwest::Client) -> Self {
+        Self {
+            http_client,
+            ..Self::new()
         }
     }
 
@@ -162,33 +730,90 @@ impl GitHubClient {
     #[doc(hidden)]
     pub fn with_base_url(base_url: &str) -> Self {
         Self {
-            http_client: reqwest::Client::new(),
-            cache: RwLock::new(GitHubCache::default()),
             base_url: base_url.to_string(),
+            ..Self::new()
         }
     }
 
-    /// Load validation results from persistent cache
+    /// Use `retry` for request timeouts and retries instead of the default
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// API host the client talks to, e.g. `api.github.com`
+    pub fn host(&self) -> &str {
+        self.base_url
+            .split_once("://")
+            .map_or(self.base_url.as_str(), |(_, host)| host)
+            .trim_end_matches('/')
+    }
+
+    /// Load validation results from persistent cache: the results shared on
+    /// this host, plus `token`'s own
     #[allow(dead_code)] // Used by LSP only
-    pub fn load_from_persistent(&self, persistent: &PersistentCache) {
+    pub fn load_from_persistent(&self, persistent: &PersistentCache, token: Option<&str>) {
+        let host = self.host();
+        let shared = persistent.scopes.get(&cache_scope(host, None));
+        let private =
+            token.and_then(|token| persistent.scopes.get(&cache_scope(host, Some(token))));
         let mut cache = self.cache.write().unwrap();
-        for (owner, info) in &persistent.owners {
-            cache.owners.insert(owner.clone(), info.clone());
+        for owners in shared.into_iter().chain(private) {
+            for (owner, info) in owners {
+                cache.owners.insert(owner.clone(), info.clone());
+            }
         }
     }
 
-    /// Export validation results to persistent cache
+    /// Export validation results to persistent cache. Results only `token`
+    /// may see go in its own scope, and are left out without a token.
     #[allow(dead_code)] // Used by LSP only
-    pub fn export_to_persistent(&self) -> PersistentCache {
+    pub fn export_to_persistent(&self, token: Option<&str>) -> PersistentCache {
+        let host = self.host();
         let cache = self.cache.read().unwrap();
-        let mut persistent = PersistentCache {
-            owners: cache.owners.clone(),
-            ..Default::default()
-        };
+        let mut persistent = PersistentCache::default();
+        for (owner, info) in &cache.owners {
+            let scope = if is_shareable(owner, info) {
+                cache_scope(host, None)
+            } else if let Some(token) = token {
+                cache_scope(host, Some(token))
+            } else {
+                continue;
+            };
+            persistent.insert(&scope, owner, info.clone());
+        }
         persistent.touch();
         persistent
     }
 
+    /// Load `token`'s pull request history (commit PRs and reviews) from
+    /// persistent cache
+    pub fn load_pull_history(&self, persistent: &PersistentCache, token: &str) {
+        let scope = cache_scope(self.host(), Some(token));
+        let mut cache = self.cache.write().unwrap();
+        if let Some(pulls) = persistent.commit_pulls.get(&scope) {
+            cache.commit_pulls.extend(pulls.clone());
+        }
+        if let Some(reviews) = persistent.pull_reviews.get(&scope) {
+            cache.pull_reviews.extend(reviews.clone());
+        }
+    }
+
+    /// Export pull request history to persistent cache, in `token`'s scope.
+    /// Owner results are left out, and so is the validation timestamp.
+    pub fn export_pull_history(&self, token: &str) -> PersistentCache {
+        let scope = cache_scope(self.host(), Some(token));
+        let cache = self.cache.read().unwrap();
+        let mut persistent = PersistentCache::default();
+        persistent
+            .commit_pulls
+            .insert(scope.clone(), cache.commit_pulls.clone());
+        persistent
+            .pull_reviews
+            .insert(scope, cache.pull_reviews.clone());
+        persistent
+    }
+
     /// Get all cached owners (for autocomplete)
     #[allow(dead_code)] // Used by LSP only
     pub fn get_cached_owners(&self) -> Vec<String> {
@@ -201,66 +826,132 @@ impl GitHubClient {
             .collect()
     }
 
+    /// GET an API URL, retrying network errors, timeouts and 5xx responses
+    /// per the retry policy. The last response is returned even if it's a
+    /// 5xx; `Err` describes a request that never got one.
+    async fn get(&self, url: &str, token: &str) -> Result<reqwest::Response, String> {
+        let mut retry = 0;
+        loop {
+            let result = self
+                .http_client
+                .get(url)
+                .header("Authorization", format!("Bearer {}", token))
+                .header("User-Agent", "codeowners-lsp")
+                .header("Accept", "application/vnd.github+json")
+                .timeout(self.retry.timeout)
+                .send()
+                .await;
+
+            let retryable = match &result {
+                Ok(response) => response.status().is_server_error(),
+                Err(_) => true,
+            };
+            if !retryable || retry >= self.retry.max_retries {
+                return result.map_err(|e| {
+                    if e.is_timeout() {
+                        format!("request timed out after {:?}", self.retry.timeout)
+                    } else {
+                        format!("network error: {e}")
+                    }
+                });
+            }
+
+            tokio::time::sleep(self.retry.delay(retry)).await;
+            retry += 1;
+        }
+    }
+
     /// Fetch GitHub user info
-    async fn fetch_user(&self, username: &str, token: &str) -> Option<OwnerInfo> {
+    async fn fetch_user(&self, username: &str, token: &str) -> Lookup {
         let url = format!("{}/users/{}", self.base_url, username);
-        let response = match self
-            .http_client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", token))
-            .header("User-Agent", "codeowners-lsp")
-            .header("Accept", "application/vnd.github+json")
-            .send()
-            .await
-        {
+        let response = match self.get(&url, token).await {
             Ok(r) => r,
-            Err(e) => return Some(OwnerInfo::Unknown(format!("network error: {e}"))),
+            Err(e) => return Lookup::Answered(OwnerInfo::Unknown(e)),
         };
 
         let status = response.status();
         if status.is_success() {
             if let Ok(user) = response.json::<GitHubUserResponse>().await {
-                return Some(OwnerInfo::User(UserInfo {
+                if user.account_type.as_deref() == Some("Organization") {
+                    return Lookup::Answered(OwnerInfo::Org(OrgInfo {
+                        login: user.login,
+                        name: user.name,
+                        description: user.bio,
+                        html_url: user.html_url,
+                        avatar_url: user.avatar_url,
+                    }));
+                }
+                let suspended = user.suspended_at.is_some();
+                let info = UserInfo {
                     login: user.login,
                     name: user.name,
                     html_url: user.html_url,
                     avatar_url: user.avatar_url,
                     bio: user.bio,
                     company: user.company,
+                };
+                return Lookup::Answered(if suspended {
+                    OwnerInfo::Suspended(info)
+                } else {
+                    OwnerInfo::User(info)
+                });
+            }
+        } else if status.as_u16() == 404 {
+            // `@name` may be an organization the users endpoint doesn't list
+            return self.fetch_org(username, token).await;
+        } else if is_rate_limited(&response) {
+            return Lookup::RateLimited;
+        } else if status.as_u16() == 403 {
+            return Lookup::Answered(OwnerInfo::Unknown(
+                "forbidden - check token permissions".to_string(),
+            ));
+        }
+        Lookup::Answered(OwnerInfo::Unknown(format!("HTTP {status}")))
+    }
+
+    /// Fetch GitHub organization info
+    async fn fetch_org(&self, org: &str, token: &str) -> Lookup {
+        let url = format!("{}/orgs/{}", self.base_url, org);
+        let response = match self.get(&url, token).await {
+            Ok(r) => r,
+            Err(e) => return Lookup::Answered(OwnerInfo::Unknown(e)),
+        };
+
+        let status = response.status();
+        if status.is_success() {
+            if let Ok(org) = response.json::<GitHubOrgResponse>().await {
+                return Lookup::Answered(OwnerInfo::Org(OrgInfo {
+                    login: org.login,
+                    name: org.name,
+                    description: org.description,
+                    html_url: org.html_url,
+                    avatar_url: org.avatar_url,
                 }));
             }
         } else if status.as_u16() == 404 {
-            return Some(OwnerInfo::Invalid);
-        } else if status.as_u16() == 429 {
-            return Some(OwnerInfo::Unknown("rate limit exceeded".to_string()));
+            return Lookup::Answered(OwnerInfo::Invalid);
+        } else if is_rate_limited(&response) {
+            return Lookup::RateLimited;
         } else if status.as_u16() == 403 {
-            return Some(OwnerInfo::Unknown(
+            return Lookup::Answered(OwnerInfo::Unknown(
                 "forbidden - check token permissions".to_string(),
             ));
         }
-        Some(OwnerInfo::Unknown(format!("HTTP {status}")))
+        Lookup::Answered(OwnerInfo::Unknown(format!("HTTP {status}")))
     }
 
     /// Fetch GitHub team info
-    async fn fetch_team(&self, org: &str, team_slug: &str, token: &str) -> Option<OwnerInfo> {
+    async fn fetch_team(&self, org: &str, team_slug: &str, token: &str) -> Lookup {
         let url = format!("{}/orgs/{}/teams/{}", self.base_url, org, team_slug);
-        let response = match self
-            .http_client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", token))
-            .header("User-Agent", "codeowners-lsp")
-            .header("Accept", "application/vnd.github+json")
-            .send()
-            .await
-        {
+        let response = match self.get(&url, token).await {
             Ok(r) => r,
-            Err(e) => return Some(OwnerInfo::Unknown(format!("network error: {e}"))),
+            Err(e) => return Lookup::Answered(OwnerInfo::Unknown(e)),
         };
 
         let status = response.status();
         if status.is_success() {
             if let Ok(team) = response.json::<GitHubTeamResponse>().await {
-                return Some(OwnerInfo::Team(TeamInfo {
+                return Lookup::Answered(OwnerInfo::Team(TeamInfo {
                     slug: team.slug,
                     name: team.name,
                     org: org.to_string(),
@@ -275,89 +966,367 @@ impl GitHubClient {
             // but token lacks visibility" (no read:org scope). Unlike /users/
             // which is public, we can't distinguish these cases, so treat as
             // Unknown rather than Invalid to avoid false positives.
-            return Some(OwnerInfo::Unknown(
-                "team not found or token lacks read:org scope".to_string(),
-            ));
-        } else if status.as_u16() == 429 {
-            return Some(OwnerInfo::Unknown("rate limit exceeded".to_string()));
+            return Lookup::Answered(OwnerInfo::Unknown(team_access_error(404, token)));
+        } else if is_rate_limited(&response) {
+            return Lookup::RateLimited;
         } else if status.as_u16() == 403 {
-            return Some(OwnerInfo::Unknown(
-                "forbidden - check token permissions".to_string(),
-            ));
+            return Lookup::Answered(OwnerInfo::Unknown(team_access_error(403, token)));
         }
-        Some(OwnerInfo::Unknown(format!("HTTP {status}")))
+        Lookup::Answered(OwnerInfo::Unknown(format!("HTTP {status}")))
     }
 
-    /// Validate a GitHub user exists (returns bool for backwards compat)
-    #[allow(dead_code)] // Used by CLI
-    pub async fn validate_user(&self, username: &str, token: &str) -> Option<bool> {
-        match self.fetch_user(username, token).await {
-            Some(OwnerInfo::User(_)) => Some(true),
-            Some(OwnerInfo::Invalid) => Some(false),
-            _ => None,
+    /// List the members of `@org/team_slug`, following every page. GitHub
+    /// includes members of child teams, so nested teams come back expanded.
+    /// Results are cached for the life of the client.
+    pub async fn list_team_members(
+        &self,
+        org: &str,
+        team_slug: &str,
+        token: &str,
+    ) -> Result<Vec<UserInfo>, String> {
+        let key = format!("@{}/{}", org, team_slug);
+        if let Some(members) = self.cache.read().unwrap().team_members.get(&key) {
+            return Ok(members.clone());
         }
-    }
 
-    /// Validate a GitHub team exists in the org (returns bool for backwards compat)
-    #[allow(dead_code)] // Used by CLI
-    pub async fn validate_team(&self, org: &str, team_slug: &str, token: &str) -> Option<bool> {
-        match self.fetch_team(org, team_slug, token).await {
-            Some(OwnerInfo::Team(_)) => Some(true),
-            Some(OwnerInfo::Invalid) => Some(false),
-            _ => None,
+        let mut members = Vec::new();
+        let mut next = Some(format!(
+            "{}/orgs/{}/teams/{}/members?per_page=100",
+            self.base_url, org, team_slug
+        ));
+        while let Some(url) = next.take() {
+            let response = self.get(&url, token).await?;
+            let status = response.status();
+            if !status.is_success() {
+                return Err(match status.as_u16() {
+                    _ if is_rate_limited(&response) => RATE_LIMITED.to_string(),
+                    code @ (403 | 404) => team_access_error(code, token),
+                    _ => format!("HTTP {status}"),
+                });
+            }
+            next = next_page_url(response.headers());
+            let page: Vec<GitHubMemberResponse> = response
+                .json()
+                .await
+                .map_err(|e| format!("invalid members response: {e}"))?;
+            members.extend(page.into_iter().map(|member| UserInfo {
+                login: member.login,
+                name: None,
+                html_url: member.html_url,
+                avatar_url: member.avatar_url,
+                bio: None,
+                company: None,
+            }));
         }
+
+        self.cache
+            .write()
+            .unwrap()
+            .team_members
+            .insert(key, members.clone());
+        Ok(members)
     }
 
-    /// Validate an owner and fetch metadata (cached)
-    pub async fn validate_owner_with_info(&self, owner: &str, token: &str) -> Option<OwnerInfo> {
-        // Check cache first
-        {
-            let cache = self.cache.read().unwrap();
-            if let Some(info) = cache.owners.get(owner) {
-                return Some(info.clone());
-            }
+    /// Numbers of the latest `limit` pull requests behind commits on the
+    /// default branch touching `path` (the whole repository for `.`), most
+    /// recent first. Commits are read a page at a time until `limit` PRs
+    /// turn up, for at most [`MAX_COMMIT_PAGES`] pages. Each commit's PRs
+    /// are cached for the life of the client.
+    pub async fn recent_pulls(
+        &self,
+        repo: &str,
+        path: &str,
+        limit: usize,
+        token: &str,
+    ) -> Result<Vec<u64>, String> {
+        let mut url = reqwest::Url::parse(&format!("{}/repos/{}/commits", self.base_url, repo))
+            .map_err(|e| format!("invalid repository {repo}: {e}"))?;
+        url.query_pairs_mut().append_pair("per_page", "100");
+        if path != "." {
+            url.query_pairs_mut().append_pair("path", path);
         }
 
-        let result = if let Some(username) = owner.strip_prefix('@') {
-            if username.contains('/') {
-                // Team: @org/team
-                let parts: Vec<&str> = username.split('/').collect();
-                if parts.len() == 2 {
-                    let org = parts[0];
-                    let team = parts[1];
-                    self.fetch_team(org, team, token).await
-                } else {
-                    None
+        let mut pulls = Vec::new();
+        let mut next = Some(url.to_string());
+        let mut pages = 0;
+        while let Some(url) = next.take() {
+            if pulls.len() >= limit || pages == MAX_COMMIT_PAGES {
+                break;
+            }
+            pages += 1;
+            let response = self.get(&url, token).await?;
+            let status = response.status();
+            if !status.is_success() {
+                return Err(repo_access_error(&response, repo));
+            }
+            next = next_page_url(response.headers());
+            let commits: Vec<GitHubCommitResponse> = response
+                .json()
+                .await
+                .map_err(|e| format!("invalid commits response: {e}"))?;
+
+            for commit in commits {
+                for number in self.commit_pulls(repo, &commit.sha, token).await? {
+                    if !pulls.contains(&number) {
+                        pulls.push(number);
+                    }
+                }
+                if pulls.len() >= limit {
+                    break;
                 }
-            } else {
-                // User: @username
-                self.fetch_user(username, token).await
             }
-        } else {
-            // Email - can't validate via GitHub
-            None
-        };
-
-        // Cache the result
-        if let Some(ref info) = result {
-            let mut cache = self.cache.write().unwrap();
-            cache.owners.insert(owner.to_string(), info.clone());
         }
-
-        result
+        pulls.truncate(limit);
+        Ok(pulls)
     }
 
-    /// Validate an owner against GitHub API (cached, returns bool for backwards compat)
-    pub async fn validate_owner(&self, owner: &str, token: &str) -> Option<bool> {
-        let info = self.validate_owner_with_info(owner, token).await?;
-        match info {
-            OwnerInfo::User(_) | OwnerInfo::Team(_) => Some(true),
-            OwnerInfo::Invalid => Some(false),
-            OwnerInfo::Unknown(_) => None,
+    /// Numbers of the pull requests that contain commit `sha`
+    async fn commit_pulls(&self, repo: &str, sha: &str, token: &str) -> Result<Vec<u64>, String> {
+        let key = format!("{}@{}", repo, sha);
+        if let Some(pulls) = self.cache.read().unwrap().commit_pulls.get(&key) {
+            return Ok(pulls.clone());
         }
-    }
 
-    /// Check if an owner is cached
+        let url = format!("{}/repos/{}/commits/{}/pulls", self.base_url, repo, sha);
+        let response = self.get(&url, token).await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(repo_access_error(&response, repo));
+        }
+        let pulls: Vec<GitHubPullResponse> = response
+            .json()
+            .await
+            .map_err(|e| format!("invalid pulls response: {e}"))?;
+        let pulls: Vec<u64> = pulls.into_iter().map(|pull| pull.number).collect();
+
+        self.cache
+            .write()
+            .unwrap()
+            .commit_pulls
+            .insert(key, pulls.clone());
+        Ok(pulls)
+    }
+
+    /// Submitted reviews on pull request `number`, following every page.
+    /// Pending reviews and reviews by deleted accounts are left out. Results
+    /// are cached for the life of the client.
+    pub async fn pull_reviews(
+        &self,
+        repo: &str,
+        number: u64,
+        token: &str,
+    ) -> Result<Vec<PullReview>, String> {
+        let key = format!("{}#{}", repo, number);
+        if let Some(reviews) = self.cache.read().unwrap().pull_reviews.get(&key) {
+            return Ok(reviews.clone());
+        }
+
+        let mut reviews = Vec::new();
+        let mut next = Some(format!(
+            "{}/repos/{}/pulls/{}/reviews?per_page=100",
+            self.base_url, repo, number
+        ));
+        while let Some(url) = next.take() {
+            let response = self.get(&url, token).await?;
+            let status = response.status();
+            if !status.is_success() {
+                return Err(repo_access_error(&response, repo));
+            }
+            next = next_page_url(response.headers());
+            let page: Vec<GitHubReviewResponse> = response
+                .json()
+                .await
+                .map_err(|e| format!("invalid reviews response: {e}"))?;
+            reviews.extend(page.into_iter().filter_map(|review| {
+                Some(PullReview {
+                    pull: number,
+                    login: review.user?.login,
+                    submitted_at: parse_timestamp(review.submitted_at.as_deref()?)?,
+                })
+            }));
+        }
+
+        self.cache
+            .write()
+            .unwrap()
+            .pull_reviews
+            .insert(key, reviews.clone());
+        Ok(reviews)
+    }
+
+    /// Login of the user `token` authenticates as (`GET /user`), cached per
+    /// token for the life of the client
+    pub async fn authenticated_login(&self, token: &str) -> Result<String, String> {
+        if let Some(login) = self.cache.read().unwrap().authenticated_logins.get(token) {
+            return Ok(login.clone());
+        }
+
+        let url = format!("{}/user", self.base_url);
+        let response = self.get(&url, token).await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(match status.as_u16() {
+                _ if is_rate_limited(&response) => RATE_LIMITED.to_string(),
+                401 => "GitHub rejected the token (HTTP 401)".to_string(),
+                _ => format!("HTTP {status}"),
+            });
+        }
+        let user: GitHubUserResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("invalid user response: {e}"))?;
+
+        self.cache
+            .write()
+            .unwrap()
+            .authenticated_logins
+            .insert(token.to_string(), user.login.clone());
+        Ok(user.login)
+    }
+
+    /// Replace each [`ME_ALIAS`] (or bare `me`) in `owners` with `@login` of
+    /// the token's user. Fails if `@me` is used without a token or the login
+    /// can't be fetched; without `@me`, nothing is requested.
+    pub async fn expand_me(
+        &self,
+        owners: Vec<String>,
+        token: Option<&str>,
+    ) -> Result<Vec<String>, String> {
+        if !owners.iter().any(|owner| is_me_alias(owner)) {
+            return Ok(owners);
+        }
+        let token = token.ok_or(
+            "`@me` needs a GitHub token to look up your login \
+             (set GITHUB_TOKEN or github_token in .codeowners-lsp.toml)",
+        )?;
+        let login = self
+            .authenticated_login(token)
+            .await
+            .map_err(|e| format!("couldn't resolve `@me`: {e}"))?;
+        Ok(owners
+            .into_iter()
+            .map(|owner| {
+                if is_me_alias(&owner) {
+                    format!("@{login}")
+                } else {
+                    owner
+                }
+            })
+            .collect())
+    }
+
+    /// Validate a GitHub user exists (returns bool for backwards compat)
+    #[allow(dead_code)] // Used by CLI
+    pub async fn validate_user(&self, username: &str, token: &str) -> Option<bool> {
+        match self.fetch_user(username, token).await.into_info() {
+            OwnerInfo::User(_) | OwnerInfo::Org(_) => Some(true),
+            OwnerInfo::Suspended(_) | OwnerInfo::Invalid => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Validate a GitHub team exists in the org (returns bool for backwards compat)
+    #[allow(dead_code)] // Used by CLI
+    pub async fn validate_team(&self, org: &str, team_slug: &str, token: &str) -> Option<bool> {
+        match self.fetch_team(org, team_slug, token).await.into_info() {
+            OwnerInfo::Team(_) => Some(true),
+            OwnerInfo::Invalid => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Validate an owner and fetch metadata (cached)
+    pub async fn validate_owner_with_info(&self, owner: &str, token: &str) -> Option<OwnerInfo> {
+        // Check cache first
+        {
+            let cache = self.cache.read().unwrap();
+            if let Some(info) = cache.owners.get(owner) {
+                self.cached_lookups.fetch_add(1, Ordering::Relaxed);
+                return Some(info.clone());
+            }
+        }
+
+        let lookup = match classify_owner(owner) {
+            Some(OwnerKind::Team) => {
+                // Team: @org/team
+                let (org, team) = owner[1..].split_once('/')?;
+                self.fetch_team(org, team, token).await
+            }
+            // User: @username
+            Some(OwnerKind::User) => self.fetch_user(&owner[1..], token).await,
+            // Email can't be validated via GitHub; malformed owners are
+            // already reported by format validation
+            Some(OwnerKind::Email) | None => return None,
+        };
+
+        // Count the lookup, caching GitHub's answer
+        match lookup {
+            Lookup::RateLimited => {
+                self.rate_limited_lookups.fetch_add(1, Ordering::Relaxed);
+            }
+            Lookup::Answered(ref info) => {
+                self.fetched_lookups.fetch_add(1, Ordering::Relaxed);
+                let mut cache = self.cache.write().unwrap();
+                cache.owners.insert(owner.to_string(), info.clone());
+            }
+        }
+        Some(lookup.into_info())
+    }
+
+    /// Validate an owner against GitHub API (cached, returns bool for backwards compat)
+    pub async fn validate_owner(&self, owner: &str, token: &str) -> Option<bool> {
+        let info = self.validate_owner_with_info(owner, token).await?;
+        match info {
+            OwnerInfo::User(_) | OwnerInfo::Team(_) | OwnerInfo::Org(_) => Some(true),
+            OwnerInfo::Suspended(_) | OwnerInfo::Invalid => Some(false),
+            OwnerInfo::Unknown(_) => None,
+        }
+    }
+
+    /// Validate many owners, with up to `concurrency` requests in flight.
+    ///
+    /// Duplicates are validated once; cached owners are answered from the
+    /// cache and new results are added to it. Owners that can't be checked
+    /// against GitHub (emails, malformed) map to `None`.
+    pub async fn validate_owners<I>(
+        &self,
+        owners: I,
+        token: &str,
+        concurrency: usize,
+    ) -> HashMap<String, Option<OwnerInfo>>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        self.validate_owners_with_progress(owners, token, concurrency, |_, _| {})
+            .await
+    }
+
+    /// [`validate_owners`](Self::validate_owners), calling `on_result` as
+    /// each owner completes (e.g. to advance a progress bar)
+    pub async fn validate_owners_with_progress<I, F>(
+        &self,
+        owners: I,
+        token: &str,
+        concurrency: usize,
+        on_result: F,
+    ) -> HashMap<String, Option<OwnerInfo>>
+    where
+        I: IntoIterator<Item = String>,
+        F: Fn(&str, Option<&OwnerInfo>),
+    {
+        let unique: HashSet<String> = owners.into_iter().collect();
+        stream::iter(unique)
+            .map(|owner| async move {
+                let info = self.validate_owner_with_info(&owner, token).await;
+                (owner, info)
+            })
+            .buffer_unordered(concurrency.max(1))
+            .inspect(|(owner, info)| on_result(owner, info.as_ref()))
+            .collect()
+            .await
+    }
+
+    /// Check if an owner is cached
     #[allow(dead_code)] // Used by LSP, not CLI
     pub fn is_cached(&self, owner: &str) -> bool {
         self.cache.read().unwrap().owners.contains_key(owner)
@@ -371,7 +1340,7 @@ impl GitHubClient {
             .unwrap()
             .owners
             .get(owner)
-            .map(|info| matches!(info, OwnerInfo::User(_) | OwnerInfo::Team(_)))
+            .map(OwnerInfo::is_valid)
     }
 
     /// Get owner info from cache (None if not cached)
@@ -390,6 +1359,25 @@ impl GitHubClient {
             .insert(owner.to_string(), info);
     }
 
+    /// How owner lookups have been answered since the client was created
+    /// or [`reset_lookup_stats`](Self::reset_lookup_stats) was last called.
+    /// Emails and malformed owners aren't looked up, so they aren't counted.
+    pub fn lookup_stats(&self) -> LookupStats {
+        LookupStats {
+            cached: self.cached_lookups.load(Ordering::Relaxed),
+            fetched: self.fetched_lookups.load(Ordering::Relaxed),
+            rate_limited: self.rate_limited_lookups.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Start counting lookups from zero, e.g. at the start of a command run
+    /// on a shared client
+    pub fn reset_lookup_stats(&self) {
+        self.cached_lookups.store(0, Ordering::Relaxed);
+        self.fetched_lookups.store(0, Ordering::Relaxed);
+        self.rate_limited_lookups.store(0, Ordering::Relaxed);
+    }
+
     /// Clear the cache
     #[cfg(test)]
     pub fn clear_cache(&self) {
@@ -403,9 +1391,20 @@ impl Default for GitHubClient {
     }
 }
 
+impl fmt::Debug for GitHubClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GitHubClient")
+            .field("base_url", &self.base_url)
+            .field("cached_owners", &self.cache.read().unwrap().owners.len())
+            .field("lookups", &self.lookup_stats())
+            .finish_non_exhaustive()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Arc;
     use tempfile::tempdir;
 
     #[test]
@@ -483,7 +1482,8 @@ mod tests {
         let dir = tempdir().unwrap();
         let cache = PersistentCache::load(dir.path());
 
-        assert!(cache.owners.is_empty());
+        assert!(cache.scopes.is_empty());
+        assert_eq!(cache.version, CACHE_VERSION);
         assert_eq!(cache.last_updated, 0);
     }
 
@@ -492,8 +1492,9 @@ mod tests {
         let dir = tempdir().unwrap();
 
         let mut cache = PersistentCache::default();
-        cache.owners.insert(
-            "@user".to_string(),
+        cache.insert(
+            HOST,
+            "@user",
             OwnerInfo::User(UserInfo {
                 login: "user".to_string(),
                 name: Some("Test User".to_string()),
@@ -515,15 +1516,102 @@ mod tests {
 
         // Verify .gitignore contents
         let gitignore = fs::read_to_string(dir.path().join(".codeowners-lsp/.gitignore")).unwrap();
-        assert_eq!(gitignore, "*\n");
+        assert_eq!(gitignore, "*\n!identities.toml\n");
 
         // Load and verify
         let loaded = PersistentCache::load(dir.path());
-        assert_eq!(loaded.owners.len(), 1);
-        assert!(loaded.owners.contains_key("@user"));
+        assert_eq!(loaded.owner_count(), 1);
+        assert!(loaded.get(HOST, "@user").is_some());
         assert!(loaded.last_updated > 0);
     }
 
+    const HOST: &str = "api.github.com";
+
+    fn cache_of(owners: &[&str], last_updated: u64) -> PersistentCache {
+        let mut cache = PersistentCache {
+            last_updated,
+            ..Default::default()
+        };
+        for owner in owners {
+            cache.insert(HOST, owner, OwnerInfo::Invalid);
+        }
+        cache
+    }
+
+    fn owners_in(cache: &PersistentCache, scope: &str) -> Vec<String> {
+        let mut owners: Vec<String> = cache
+            .scopes
+            .get(scope)
+            .map(|owners| owners.keys().cloned().collect())
+            .unwrap_or_default();
+        owners.sort();
+        owners
+    }
+
+    #[test]
+    fn test_persistent_cache_save_merges_with_disk() {
+        let dir = tempdir().unwrap();
+        cache_of(&["@a", "@b"], 20).save(dir.path()).unwrap();
+
+        let mut ours = cache_of(&["@c"], 10);
+        ours.insert(HOST, "@b", OwnerInfo::Unknown("rate limited".into()));
+        ours.save(dir.path()).unwrap();
+
+        let loaded = PersistentCache::load(dir.path());
+        assert_eq!(owners_in(&loaded, HOST), vec!["@a", "@b", "@c"]);
+        // Ours wins for owners in both
+        assert!(matches!(
+            loaded.get(HOST, "@b"),
+            Some(OwnerInfo::Unknown(_))
+        ));
+        assert_eq!(loaded.last_updated, 20);
+        assert!(!dir.path().join(".codeowners-lsp/cache.json.tmp").exists());
+    }
+
+    #[test]
+    fn test_persistent_cache_overwrite_replaces_disk() {
+        let dir = tempdir().unwrap();
+        cache_of(&["@a", "@b"], 20).save(dir.path()).unwrap();
+        cache_of(&["@a"], 20).overwrite(dir.path()).unwrap();
+
+        let loaded = PersistentCache::load(dir.path());
+        assert_eq!(owners_in(&loaded, HOST), vec!["@a"]);
+    }
+
+    #[test]
+    fn test_persistent_cache_concurrent_saves_keep_all_entries() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let barrier = std::sync::Arc::new(std::sync::Barrier::new(2));
+
+        let writers: Vec<_> = ["lsp", "cli"]
+            .into_iter()
+            .map(|writer| {
+                let root = root.clone();
+                let barrier = barrier.clone();
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    // Each save adds one new owner alongside a shared one
+                    for i in 0..25 {
+                        let own = format!("@{}-{}", writer, i);
+                        cache_of(&[own.as_str(), "@shared"], i).save(&root).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let loaded = PersistentCache::load(&root);
+        assert_eq!(loaded.owner_count(), 51);
+        for writer in ["lsp", "cli"] {
+            for i in 0..25 {
+                assert!(loaded.get(HOST, &format!("@{}-{}", writer, i)).is_some());
+            }
+        }
+    }
+
     #[test]
     fn test_persistent_cache_is_stale() {
         let mut cache = PersistentCache::default();
@@ -540,6 +1628,25 @@ mod tests {
         assert!(cache.is_stale());
     }
 
+    #[test]
+    fn test_persistent_cache_remove_and_retain() {
+        let token_scope = cache_scope(HOST, Some("ghp_token"));
+        let mut cache = PersistentCache::default();
+        cache.insert(HOST, "@a", OwnerInfo::Invalid);
+        cache.insert(HOST, "@b", OwnerInfo::Unknown("rate limited".into()));
+        cache.insert(&token_scope, "@a", OwnerInfo::Invalid);
+        cache.insert(&token_scope, "@org/c", OwnerInfo::Invalid);
+
+        assert!(matches!(cache.remove("@a"), Some(OwnerInfo::Invalid)));
+        assert!(cache.remove("@a").is_none());
+
+        cache.retain(|owner, _| owner.contains('/'));
+        assert_eq!(cache.owner_count(), 1);
+        assert!(cache.get(&token_scope, "@org/c").is_some());
+        // Emptied scopes are dropped
+        assert_eq!(cache.scopes.len(), 1);
+    }
+
     #[test]
     fn test_persistent_cache_touch() {
         let mut cache = PersistentCache::default();
@@ -567,7 +1674,45 @@ mod tests {
 
         // Should return default on parse error
         let cache = PersistentCache::load(dir.path());
-        assert!(cache.owners.is_empty());
+        assert!(cache.scopes.is_empty());
+    }
+
+    #[test]
+    fn test_persistent_cache_from_older_version_is_discarded() {
+        let dir = tempdir().unwrap();
+        let cache_dir = dir.path().join(".codeowners-lsp");
+        fs::create_dir_all(&cache_dir).unwrap();
+        // Version 1 kept one flat map for every token
+        fs::write(
+            cache_dir.join("cache.json"),
+            r#"{"owners": {"@org/secret": "Invalid"}, "last_updated": 42}"#,
+        )
+        .unwrap();
+
+        let cache = PersistentCache::load(dir.path());
+        assert!(cache.scopes.is_empty());
+        assert_eq!(cache.version, CACHE_VERSION);
+        assert_eq!(cache.last_updated, 0);
+    }
+
+    #[test]
+    fn test_cache_scope_keys() {
+        assert_eq!(cache_scope(HOST, None), HOST);
+        let a = cache_scope(HOST, Some("ghp_a"));
+        assert!(a.starts_with("api.github.com#"));
+        assert!(!a.contains("ghp_a"));
+        assert_eq!(a, cache_scope(HOST, Some("ghp_a")));
+        assert_ne!(a, cache_scope(HOST, Some("ghp_b")));
+        assert_ne!(a, cache_scope("github.example.com/api/v3", Some("ghp_a")));
+    }
+
+    #[test]
+    fn test_client_host() {
+        assert_eq!(GitHubClient::new().host(), HOST);
+        assert_eq!(
+            GitHubClient::with_base_url("https://ghe.example.com/api/v3/").host(),
+            "ghe.example.com/api/v3"
+        );
     }
 
     #[test]
@@ -626,8 +1771,9 @@ mod tests {
         let client = GitHubClient::new();
 
         let mut persistent = PersistentCache::default();
-        persistent.owners.insert(
-            "@persisted_user".to_string(),
+        persistent.insert(
+            HOST,
+            "@persisted_user",
             OwnerInfo::User(UserInfo {
                 login: "persisted_user".to_string(),
                 name: Some("Persisted".to_string()),
@@ -639,7 +1785,7 @@ mod tests {
         );
 
         assert!(!client.is_cached("@persisted_user"));
-        client.load_from_persistent(&persistent);
+        client.load_from_persistent(&persistent, None);
         assert!(client.is_cached("@persisted_user"));
 
         let info = client.get_owner_info("@persisted_user");
@@ -665,11 +1811,91 @@ mod tests {
             );
         }
 
-        let exported = client.export_to_persistent();
-        assert!(exported.owners.contains_key("@export_user"));
+        let exported = client.export_to_persistent(None);
+        assert!(exported.get(HOST, "@export_user").is_some());
         assert!(exported.last_updated > 0); // touch() was called
     }
 
+    fn secret_team() -> OwnerInfo {
+        OwnerInfo::Team(TeamInfo {
+            slug: "secret".to_string(),
+            name: "Secret".to_string(),
+            org: "org".to_string(),
+            description: None,
+            html_url: "https://github.com/orgs/org/teams/secret".to_string(),
+            members_count: None,
+            repos_count: None,
+        })
+    }
+
+    #[test]
+    fn test_export_splits_shared_and_token_results() {
+        let client = GitHubClient::new();
+        client.insert_cached("@alice", OwnerInfo::Invalid);
+        client.insert_cached("@org/secret", secret_team());
+        client.insert_cached("@bob", OwnerInfo::Unknown("rate limit exceeded".into()));
+
+        let exported = client.export_to_persistent(Some("ghp_member"));
+        let member = cache_scope(HOST, Some("ghp_member"));
+        assert_eq!(owners_in(&exported, HOST), vec!["@alice"]);
+        assert_eq!(owners_in(&exported, &member), vec!["@bob", "@org/secret"]);
+
+        // Without a token, token-dependent results aren't written at all
+        let exported = client.export_to_persistent(None);
+        assert_eq!(exported.scopes.keys().collect::<Vec<_>>(), vec![HOST]);
+    }
+
+    #[test]
+    fn test_tokens_do_not_read_each_others_private_teams() {
+        let dir = tempdir().unwrap();
+
+        // A member of the org sees its secret team
+        let member = GitHubClient::new();
+        member.insert_cached("@alice", OwnerInfo::Invalid);
+        member.insert_cached("@org/secret", secret_team());
+        member
+            .export_to_persistent(Some("ghp_member"))
+            .save(dir.path())
+            .unwrap();
+
+        // An outsider's token gets a 404 for the same team
+        let outsider = GitHubClient::new();
+        outsider.insert_cached("@org/secret", OwnerInfo::Invalid);
+        outsider
+            .export_to_persistent(Some("ghp_outsider"))
+            .save(dir.path())
+            .unwrap();
+
+        let persistent = PersistentCache::load(dir.path());
+        let load = |token: Option<&str>| {
+            let client = GitHubClient::new();
+            client.load_from_persistent(&persistent, token);
+            client
+        };
+
+        let client = load(Some("ghp_member"));
+        assert!(matches!(
+            client.get_owner_info("@org/secret"),
+            Some(OwnerInfo::Team(_))
+        ));
+        let client = load(Some("ghp_outsider"));
+        assert!(matches!(
+            client.get_owner_info("@org/secret"),
+            Some(OwnerInfo::Invalid)
+        ));
+        // A third token starts from scratch for teams, but still shares the
+        // public user lookups
+        let client = load(Some("ghp_new"));
+        assert!(!client.is_cached("@org/secret"));
+        assert!(client.is_cached("@alice"));
+
+        // The same token against another host shares nothing
+        let other_host = GitHubClient::with_base_url("https://ghe.example.com/api/v3");
+        other_host.load_from_persistent(&persistent, Some("ghp_member"));
+        assert!(!other_host.is_cached("@org/secret"));
+        assert!(!other_host.is_cached("@alice"));
+    }
+
     #[test]
     fn test_get_cached_returns_none_for_uncached() {
         let client = GitHubClient::new();
@@ -736,112 +1962,656 @@ mod tests {
             company: Some("Company".to_string()),
         });
 
-        let json = serde_json::to_string(&user).unwrap();
-        let deserialized: OwnerInfo = serde_json::from_str(&json).unwrap();
-        assert!(matches!(deserialized, OwnerInfo::User(ref u) if u.login == "test"));
+        let json = serde_json::to_string(&user).unwrap();
+        let deserialized: OwnerInfo = serde_json::from_str(&json).unwrap();
+        assert!(matches!(deserialized, OwnerInfo::User(ref u) if u.login == "test"));
+    }
+
+    #[test]
+    fn test_team_info_serialization() {
+        let team = OwnerInfo::Team(TeamInfo {
+            slug: "my-team".to_string(),
+            name: "My Team".to_string(),
+            org: "my-org".to_string(),
+            description: Some("Description".to_string()),
+            html_url: "https://github.com/orgs/my-org/teams/my-team".to_string(),
+            members_count: Some(10),
+            repos_count: Some(5),
+        });
+
+        let json = serde_json::to_string(&team).unwrap();
+        let deserialized: OwnerInfo = serde_json::from_str(&json).unwrap();
+        assert!(matches!(deserialized, OwnerInfo::Team(ref t) if t.slug == "my-team"));
+    }
+
+    #[test]
+    fn test_invalid_and_unknown_serialization() {
+        let invalid = OwnerInfo::Invalid;
+        let json = serde_json::to_string(&invalid).unwrap();
+        let deserialized: OwnerInfo = serde_json::from_str(&json).unwrap();
+        assert!(matches!(deserialized, OwnerInfo::Invalid));
+
+        let unknown = OwnerInfo::Unknown("rate limit exceeded".into());
+        let json = serde_json::to_string(&unknown).unwrap();
+        let deserialized: OwnerInfo = serde_json::from_str(&json).unwrap();
+        assert!(matches!(deserialized, OwnerInfo::Unknown(ref r) if r == "rate limit exceeded"));
+    }
+
+    #[test]
+    fn test_persistent_cache_save_keeps_existing_gitignore() {
+        let dir = tempdir().unwrap();
+        let cache = PersistentCache::default();
+
+        // First save creates .gitignore
+        cache.save(dir.path()).unwrap();
+        let gitignore_path = dir.path().join(".codeowners-lsp/.gitignore");
+        assert!(gitignore_path.exists());
+
+        let content = fs::read_to_string(&gitignore_path).unwrap();
+        assert_eq!(content, "*\n!identities.toml\n");
+
+        // A .gitignore the user wrote is left alone
+        fs::write(&gitignore_path, "custom content").unwrap();
+        cache.save(dir.path()).unwrap();
+        let content = fs::read_to_string(&gitignore_path).unwrap();
+        assert_eq!(content, "custom content");
+
+        // As written before identities.toml existed, it gets the exception
+        // once
+        fs::write(&gitignore_path, "*\n").unwrap();
+        cache.save(dir.path()).unwrap();
+        cache.save(dir.path()).unwrap();
+        let content = fs::read_to_string(&gitignore_path).unwrap();
+        assert_eq!(content, "*\n!identities.toml\n");
+    }
+
+    // =========================================================================
+    // ASYNC TESTS WITH MOCK HTTP SERVER
+    // =========================================================================
+
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_fetch_user_success() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/users/testuser"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "login": "testuser",
+                "name": "Test User",
+                "html_url": "https://github.com/testuser",
+                "avatar_url": "https://avatars.githubusercontent.com/u/123",
+                "bio": "A test user",
+                "company": "Test Corp"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = GitHubClient::with_base_url(&mock_server.uri());
+        let result = client.validate_owner("@testuser", "fake-token").await;
+
+        assert_eq!(result, Some(true));
+        // Should be cached now
+        assert!(client.is_cached("@testuser"));
+        let info = client.get_owner_info("@testuser").unwrap();
+        assert!(matches!(info, OwnerInfo::User(ref u) if u.login == "testuser"));
+    }
+
+    #[tokio::test]
+    async fn test_expand_me_to_authenticated_login() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/user"))
+            .and(header("Authorization", "Bearer my-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "login": "octocat",
+                "html_url": "https://github.com/octocat"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = GitHubClient::with_base_url(&mock_server.uri());
+        let owners = vec!["@me".to_string(), "@org/team".to_string()];
+        assert_eq!(
+            client.expand_me(owners, Some("my-token")).await.unwrap(),
+            vec!["@octocat", "@org/team"]
+        );
+        // The login is cached, so this doesn't hit /user again; `me`
+        // without the `@` is the alias too
+        assert_eq!(
+            client
+                .expand_me(vec!["@ME".to_string(), "me".to_string()], Some("my-token"))
+                .await
+                .unwrap(),
+            vec!["@octocat", "@octocat"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_authenticated_login_is_cached_per_token() {
+        let mock_server = MockServer::start().await;
+
+        for (token, login) in [("token-a", "alice"), ("token-b", "bob")] {
+            Mock::given(method("GET"))
+                .and(path("/user"))
+                .and(header("Authorization", format!("Bearer {token}").as_str()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "login": login,
+                    "html_url": format!("https://github.com/{login}")
+                })))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+        }
+
+        let client = GitHubClient::with_base_url(&mock_server.uri());
+        for _ in 0..2 {
+            assert_eq!(
+                client.authenticated_login("token-a").await.unwrap(),
+                "alice"
+            );
+            assert_eq!(client.authenticated_login("token-b").await.unwrap(), "bob");
+        }
+    }
+
+    #[test]
+    fn test_is_me_alias() {
+        assert!(is_me_alias("@me"));
+        assert!(is_me_alias("@Me"));
+        assert!(is_me_alias("me"));
+        assert!(!is_me_alias("@meg"));
+        assert!(!is_me_alias("@@me"));
+        assert!(!is_me_alias("@org/me"));
+    }
+
+    #[tokio::test]
+    async fn test_expand_me_errors() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/user"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&mock_server)
+            .await;
+
+        let client = GitHubClient::with_base_url(&mock_server.uri());
+        let me = || vec!["@me".to_string()];
+        let missing_token = client.expand_me(me(), None).await.unwrap_err();
+        assert!(missing_token.contains("needs a GitHub token"));
+        let rejected = client.expand_me(me(), Some("bad")).await.unwrap_err();
+        assert!(rejected.contains("HTTP 401"));
+
+        // No `@me`, no token needed and no request made
+        let owners = vec!["@alice".to_string()];
+        assert_eq!(client.expand_me(owners.clone(), None).await, Ok(owners));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_user_not_found() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/users/nonexistent"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+
+        let client = GitHubClient::with_base_url(&mock_server.uri());
+        let result = client.validate_owner("@nonexistent", "fake-token").await;
+
+        assert_eq!(result, Some(false));
+        // Invalid should also be cached
+        assert!(client.is_cached("@nonexistent"));
+        let info = client.get_owner_info("@nonexistent").unwrap();
+        assert!(matches!(info, OwnerInfo::Invalid));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_user_suspended() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/users/suspended"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "login": "suspended",
+                "name": null,
+                "html_url": "https://github.com/suspended",
+                "suspended_at": "2024-01-15T10:00:00Z"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = GitHubClient::with_base_url(&mock_server.uri());
+        let result = client.validate_owner("@suspended", "fake-token").await;
+
+        assert_eq!(result, Some(false));
+        let info = client.get_owner_info("@suspended").unwrap();
+        assert!(matches!(info, OwnerInfo::Suspended(ref u) if u.login == "suspended"));
+        assert!(!info.is_valid());
+        assert!(!info.is_invalid());
+        assert_eq!(client.get_cached("@suspended"), Some(false));
+    }
+
+    #[tokio::test]
+    async fn test_with_client_uses_provided_client() {
+        let mock_server = MockServer::start().await;
+
+        // Only requests carrying the injected client's default header succeed
+        Mock::given(method("GET"))
+            .and(path("/users/proxied"))
+            .and(header("x-injected-client", "yes"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "login": "proxied",
+                "name": null,
+                "html_url": "https://github.com/proxied"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("x-injected-client", "yes".parse().unwrap());
+        let http_client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .unwrap();
+
+        let mut client = GitHubClient::with_client(http_client);
+        assert_eq!(client.base_url, "https://api.github.com");
+        client.base_url = mock_server.uri();
+        assert_eq!(
+            client.validate_owner("@proxied", "fake-token").await,
+            Some(true)
+        );
+
+        // A default client doesn't send the header, so the mock has no match
+        let plain = GitHubClient::with_base_url(&mock_server.uri());
+        assert_ne!(
+            plain.validate_owner("@proxied", "fake-token").await,
+            Some(true)
+        );
+    }
+
+    fn fast_retry(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            timeout: Duration::from_millis(100),
+            max_retries,
+            backoff: Duration::from_millis(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_user_timeout_is_unknown_after_retries() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/users/slow"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({
+                        "login": "slow",
+                        "name": null,
+                        "html_url": "https://github.com/slow"
+                    }))
+                    .set_delay(Duration::from_secs(2)),
+            )
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let client =
+            GitHubClient::with_base_url(&mock_server.uri()).with_retry_policy(fast_retry(2));
+        let result = client.validate_owner_with_info("@slow", "fake-token").await;
+
+        match result {
+            Some(OwnerInfo::Unknown(reason)) => assert!(reason.contains("timed out"), "{reason}"),
+            other => panic!("expected Unknown, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_team_retries_server_error() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/orgs/myorg/teams/flaky"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/orgs/myorg/teams/flaky"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "slug": "flaky",
+                "name": "Flaky",
+                "html_url": "https://github.com/orgs/myorg/teams/flaky"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client =
+            GitHubClient::with_base_url(&mock_server.uri()).with_retry_policy(fast_retry(2));
+        assert_eq!(
+            client.validate_owner("@myorg/flaky", "fake-token").await,
+            Some(true)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_server_error_without_retries_is_unknown() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/users/broken"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client =
+            GitHubClient::with_base_url(&mock_server.uri()).with_retry_policy(fast_retry(0));
+        let result = client
+            .validate_owner_with_info("@broken", "fake-token")
+            .await;
+        assert!(matches!(result, Some(OwnerInfo::Unknown(ref r)) if r.contains("503")));
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off_with_bounded_jitter() {
+        let policy = RetryPolicy {
+            backoff: Duration::from_millis(100),
+            ..RetryPolicy::default()
+        };
+        for retry in 0..3 {
+            let base = Duration::from_millis(100 << retry);
+            let delay = policy.delay(retry);
+            assert!(delay >= base && delay <= base.mul_f64(1.5), "{:?}", delay);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_team_members_follows_pagination() {
+        let mock_server = MockServer::start().await;
+        let member = |login: &str| {
+            serde_json::json!({
+                "login": login,
+                "html_url": format!("https://github.com/{}", login),
+                "avatar_url": null
+            })
+        };
+
+        Mock::given(method("GET"))
+            .and(path("/orgs/myorg/teams/platform/members"))
+            .and(query_param("page", "2"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([member("carol")])),
+            )
+            .with_priority(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/orgs/myorg/teams/platform/members"))
+            .and(query_param("per_page", "100"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(
+                        "link",
+                        format!(
+                            "<{0}/orgs/myorg/teams/platform/members?per_page=100&page=2>; rel=\"next\", \
+                             <{0}/orgs/myorg/teams/platform/members?per_page=100&page=2>; rel=\"last\"",
+                            mock_server.uri()
+                        )
+                        .as_str(),
+                    )
+                    .set_body_json(serde_json::json!([member("alice"), member("bob")])),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = GitHubClient::with_base_url(&mock_server.uri());
+        let members = client
+            .list_team_members("myorg", "platform", "fake-token")
+            .await
+            .unwrap();
+        let logins: Vec<&str> = members.iter().map(|m| m.login.as_str()).collect();
+        assert_eq!(logins, ["alice", "bob", "carol"]);
+
+        // Served from cache the second time (mocks expect one call each)
+        let again = client
+            .list_team_members("myorg", "platform", "fake-token")
+            .await
+            .unwrap();
+        assert_eq!(again.len(), 3);
     }
 
-    #[test]
-    fn test_team_info_serialization() {
-        let team = OwnerInfo::Team(TeamInfo {
-            slug: "my-team".to_string(),
-            name: "My Team".to_string(),
-            org: "my-org".to_string(),
-            description: Some("Description".to_string()),
-            html_url: "https://github.com/orgs/my-org/teams/my-team".to_string(),
-            members_count: Some(10),
-            repos_count: Some(5),
-        });
+    #[tokio::test]
+    async fn test_list_team_members_not_found() {
+        let mock_server = MockServer::start().await;
 
-        let json = serde_json::to_string(&team).unwrap();
-        let deserialized: OwnerInfo = serde_json::from_str(&json).unwrap();
-        assert!(matches!(deserialized, OwnerInfo::Team(ref t) if t.slug == "my-team"));
+        Mock::given(method("GET"))
+            .and(path("/orgs/myorg/teams/ghost/members"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+
+        let client = GitHubClient::with_base_url(&mock_server.uri());
+        let err = client
+            .list_team_members("myorg", "ghost", "fake-token")
+            .await
+            .unwrap_err();
+        assert!(err.contains("read:org"));
     }
 
     #[test]
-    fn test_invalid_and_unknown_serialization() {
-        let invalid = OwnerInfo::Invalid;
-        let json = serde_json::to_string(&invalid).unwrap();
-        let deserialized: OwnerInfo = serde_json::from_str(&json).unwrap();
-        assert!(matches!(deserialized, OwnerInfo::Invalid));
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(parse_timestamp("2000-03-01T00:00:00Z"), Some(951_868_800));
+        assert_eq!(parse_timestamp("2024-05-01T12:30:15Z"), Some(1_714_566_615));
+        assert_eq!(parse_timestamp("2024-05-01"), None);
+        assert_eq!(parse_timestamp("2024-13-01T00:00:00Z"), None);
+        assert_eq!(parse_timestamp("2024-05-01T12:30:15+02:00"), None);
+        assert_eq!(
+            parse_timestamp("2024-05-01T12:30:15.250Z"),
+            Some(1_714_566_615)
+        );
+        assert_eq!(parse_timestamp("2024-05-01T12:30:15.Z"), None);
+        assert_eq!(parse_timestamp("2024-05-01T12:30:15.2x5Z"), None);
+    }
 
-        let unknown = OwnerInfo::Unknown("rate limit exceeded".into());
-        let json = serde_json::to_string(&unknown).unwrap();
-        let deserialized: OwnerInfo = serde_json::from_str(&json).unwrap();
-        assert!(matches!(deserialized, OwnerInfo::Unknown(ref r) if r == "rate limit exceeded"));
+    #[tokio::test]
+    async fn test_recent_pulls_reads_commit_pages_until_enough_pulls() {
+        let mock_server = MockServer::start().await;
+
+        let next = format!(
+            "<{}/repos/acme/app/commits?per_page=100&page=2>; rel=\"next\"",
+            mock_server.uri()
+        );
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/app/commits"))
+            .and(query_param("page", "2"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([{"sha": "ccc"}])),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/app/commits"))
+            .and(query_param("per_page", "100"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("link", next.as_str())
+                    .set_body_json(serde_json::json!([{"sha": "aaa"}, {"sha": "bbb"}])),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        // Two commits of one PR, then a direct push
+        for (sha, pulls) in [("aaa", vec![5]), ("bbb", vec![5]), ("ccc", vec![3])] {
+            let pulls: Vec<_> = pulls
+                .into_iter()
+                .map(|number| serde_json::json!({"number": number}))
+                .collect();
+            Mock::given(method("GET"))
+                .and(path(format!("/repos/acme/app/commits/{sha}/pulls")))
+                .respond_with(ResponseTemplate::new(200).set_body_json(pulls))
+                .mount(&mock_server)
+                .await;
+        }
+
+        let client = GitHubClient::with_base_url(&mock_server.uri());
+        let pulls = client
+            .recent_pulls("acme/app", ".", 2, "fake-token")
+            .await
+            .unwrap();
+        assert_eq!(pulls, [5, 3]);
     }
 
     #[test]
-    fn test_persistent_cache_save_creates_gitignore_only_once() {
-        let dir = tempdir().unwrap();
-        let cache = PersistentCache::default();
+    fn test_pull_history_round_trips_through_persistent_cache() {
+        let client = GitHubClient::new();
+        {
+            let mut cache = client.cache.write().unwrap();
+            cache
+                .commit_pulls
+                .insert("acme/app@aaa".to_string(), vec![7]);
+            cache.pull_reviews.insert(
+                "acme/app#7".to_string(),
+                vec![PullReview {
+                    pull: 7,
+                    login: "alice".to_string(),
+                    submitted_at: 100,
+                }],
+            );
+        }
 
-        // First save creates .gitignore
-        cache.save(dir.path()).unwrap();
-        let gitignore_path = dir.path().join(".codeowners-lsp/.gitignore");
-        assert!(gitignore_path.exists());
+        let dir = tempfile::tempdir().unwrap();
+        client
+            .export_pull_history("token-a")
+            .save(dir.path())
+            .unwrap();
+        let persistent = PersistentCache::load(dir.path());
+        // History doesn't count as an owner validation
+        assert_eq!(persistent.last_updated, 0);
+
+        let other = GitHubClient::new();
+        other.load_pull_history(&persistent, "token-b");
+        assert!(other.cache.read().unwrap().commit_pulls.is_empty());
+
+        let fresh = GitHubClient::new();
+        fresh.load_pull_history(&persistent, "token-a");
+        let cache = fresh.cache.read().unwrap();
+        assert_eq!(cache.commit_pulls["acme/app@aaa"], [7]);
+        assert_eq!(cache.pull_reviews["acme/app#7"][0].login, "alice");
+    }
+
+    #[tokio::test]
+    async fn test_recent_pulls_dedupes_and_caches_commit_lookups() {
+        let mock_server = MockServer::start().await;
 
-        // Modify the gitignore
-        fs::write(&gitignore_path, "custom content\n").unwrap();
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/app/commits"))
+            .and(query_param("path", "src/web app"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"sha": "aaa"},
+                {"sha": "bbb"},
+                {"sha": "ccc"}
+            ])))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        for (sha, pulls) in [("aaa", vec![12]), ("bbb", vec![12, 9]), ("ccc", vec![])] {
+            let pulls: Vec<_> = pulls
+                .into_iter()
+                .map(|number| serde_json::json!({"number": number}))
+                .collect();
+            Mock::given(method("GET"))
+                .and(path(format!("/repos/acme/app/commits/{sha}/pulls")))
+                .respond_with(ResponseTemplate::new(200).set_body_json(pulls))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+        }
 
-        // Second save should NOT overwrite existing .gitignore
-        cache.save(dir.path()).unwrap();
-        let content = fs::read_to_string(&gitignore_path).unwrap();
-        assert_eq!(content, "custom content\n");
+        let client = GitHubClient::with_base_url(&mock_server.uri());
+        for _ in 0..2 {
+            let pulls = client
+                .recent_pulls("acme/app", "src/web app", 10, "fake-token")
+                .await
+                .unwrap();
+            assert_eq!(pulls, [12, 9]);
+        }
     }
 
-    // =========================================================================
-    // ASYNC TESTS WITH MOCK HTTP SERVER
-    // =========================================================================
+    #[tokio::test]
+    async fn test_recent_pulls_repo_not_found() {
+        let mock_server = MockServer::start().await;
 
-    use wiremock::matchers::{method, path};
-    use wiremock::{Mock, MockServer, ResponseTemplate};
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/ghost/commits"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+
+        let client = GitHubClient::with_base_url(&mock_server.uri());
+        let err = client
+            .recent_pulls("acme/ghost", ".", 10, "fake-token")
+            .await
+            .unwrap_err();
+        assert!(err.contains("acme/ghost"), "{err}");
+    }
 
     #[tokio::test]
-    async fn test_fetch_user_success() {
+    async fn test_recent_pulls_rate_limited() {
         let mock_server = MockServer::start().await;
 
         Mock::given(method("GET"))
-            .and(path("/users/testuser"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-                "login": "testuser",
-                "name": "Test User",
-                "html_url": "https://github.com/testuser",
-                "avatar_url": "https://avatars.githubusercontent.com/u/123",
-                "bio": "A test user",
-                "company": "Test Corp"
-            })))
+            .and(path("/repos/acme/app/commits"))
+            .respond_with(ResponseTemplate::new(403).insert_header("x-ratelimit-remaining", "0"))
             .mount(&mock_server)
             .await;
 
         let client = GitHubClient::with_base_url(&mock_server.uri());
-        let result = client.validate_owner("@testuser", "fake-token").await;
-
-        assert_eq!(result, Some(true));
-        // Should be cached now
-        assert!(client.is_cached("@testuser"));
-        let info = client.get_owner_info("@testuser").unwrap();
-        assert!(matches!(info, OwnerInfo::User(ref u) if u.login == "testuser"));
+        let err = client
+            .recent_pulls("acme/app", ".", 10, "fake-token")
+            .await
+            .unwrap_err();
+        assert_eq!(err, RATE_LIMITED);
     }
 
     #[tokio::test]
-    async fn test_fetch_user_not_found() {
+    async fn test_pull_reviews_skips_pending_and_deleted_reviewers() {
         let mock_server = MockServer::start().await;
 
         Mock::given(method("GET"))
-            .and(path("/users/nonexistent"))
-            .respond_with(ResponseTemplate::new(404))
+            .and(path("/repos/acme/app/pulls/12/reviews"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"user": {"login": "alice"}, "state": "APPROVED", "submitted_at": "2024-05-01T12:30:15Z"},
+                {"user": {"login": "bob"}, "state": "PENDING"},
+                {"user": null, "state": "COMMENTED", "submitted_at": "2024-05-02T00:00:00Z"}
+            ])))
+            .expect(1)
             .mount(&mock_server)
             .await;
 
         let client = GitHubClient::with_base_url(&mock_server.uri());
-        let result = client.validate_owner("@nonexistent", "fake-token").await;
-
-        assert_eq!(result, Some(false));
-        // Invalid should also be cached
-        assert!(client.is_cached("@nonexistent"));
-        let info = client.get_owner_info("@nonexistent").unwrap();
-        assert!(matches!(info, OwnerInfo::Invalid));
+        let expected = [PullReview {
+            pull: 12,
+            login: "alice".to_string(),
+            submitted_at: 1_714_566_615,
+        }];
+        for _ in 0..2 {
+            let reviews = client.pull_reviews("acme/app", 12, "fake-token").await;
+            assert_eq!(reviews.unwrap(), expected);
+        }
     }
 
     #[tokio::test]
@@ -861,6 +2631,79 @@ mod tests {
         assert_eq!(result, None);
     }
 
+    #[tokio::test]
+    async fn test_lookup_stats_count_cached_fetched_and_rate_limited() {
+        let mock_server = MockServer::start().await;
+        let user = |login: &str| serde_json::json!({"login": login, "html_url": format!("https://github.com/{}", login)});
+
+        Mock::given(method("GET"))
+            .and(path("/users/alice"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(user("alice")))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users/bob"))
+            .respond_with(ResponseTemplate::new(429))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users/carol"))
+            .respond_with(ResponseTemplate::new(403).insert_header("x-ratelimit-remaining", "0"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users/dave"))
+            .respond_with(ResponseTemplate::new(403).insert_header("x-ratelimit-remaining", "12"))
+            .mount(&mock_server)
+            .await;
+
+        let client = GitHubClient::with_base_url(&mock_server.uri());
+        assert_eq!(client.lookup_stats(), LookupStats::default());
+
+        for owner in [
+            "@alice",
+            "@alice",
+            "@bob",
+            "@carol",
+            "@dave",
+            "dev@example.com",
+        ] {
+            client.validate_owner_with_info(owner, "fake-token").await;
+        }
+        assert_eq!(
+            client.lookup_stats(),
+            LookupStats {
+                cached: 1,
+                fetched: 2,
+                rate_limited: 2,
+            }
+        );
+        // Rate-limited lookups aren't cached, so they're retried
+        assert!(client.get_owner_info("@carol").is_none());
+        assert!(matches!(
+            client.validate_owner_with_info("@carol", "fake-token").await,
+            Some(OwnerInfo::Unknown(ref r)) if r == RATE_LIMITED
+        ));
+        // A 403 with requests left is a permissions problem, not a rate limit
+        assert!(matches!(
+            client.get_owner_info("@dave"),
+            Some(OwnerInfo::Unknown(ref r)) if r.contains("forbidden")
+        ));
+
+        client.reset_lookup_stats();
+        client
+            .validate_owner_with_info("@alice", "fake-token")
+            .await;
+        assert_eq!(
+            client.lookup_stats(),
+            LookupStats {
+                cached: 1,
+                ..LookupStats::default()
+            }
+        );
+    }
+
     #[tokio::test]
     async fn test_fetch_team_success() {
         let mock_server = MockServer::start().await;
@@ -998,6 +2841,89 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_org_owner_not_a_user_resolves_via_orgs_endpoint() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/users/acme"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/orgs/acme"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "login": "acme",
+                "name": "Acme Corp",
+                "description": "We make everything",
+                "html_url": "https://github.com/acme",
+                "avatar_url": "https://avatars.githubusercontent.com/u/789"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = GitHubClient::with_base_url(&mock_server.uri());
+        let info = client
+            .validate_owner_with_info("@acme", "fake-token")
+            .await
+            .unwrap();
+
+        match info {
+            OwnerInfo::Org(ref org) => {
+                assert_eq!(org.login, "acme");
+                assert_eq!(org.name.as_deref(), Some("Acme Corp"));
+                assert_eq!(org.description.as_deref(), Some("We make everything"));
+            }
+            other => panic!("Expected Org info, got {:?}", other),
+        }
+        assert!(info.is_valid());
+        assert_eq!(info.html_url(), Some("https://github.com/acme"));
+        assert_eq!(
+            client.validate_owner("@acme", "fake-token").await,
+            Some(true)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_org_owner_reported_by_users_endpoint() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/users/acme"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "login": "acme",
+                "type": "Organization",
+                "html_url": "https://github.com/acme"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = GitHubClient::with_base_url(&mock_server.uri());
+        let info = client.validate_owner_with_info("@acme", "fake-token").await;
+        assert!(matches!(info, Some(OwnerInfo::Org(ref org)) if org.login == "acme"));
+    }
+
+    #[tokio::test]
+    async fn test_owner_neither_user_nor_org_is_invalid() {
+        let mock_server = MockServer::start().await;
+
+        for endpoint in ["/users/ghost", "/orgs/ghost"] {
+            Mock::given(method("GET"))
+                .and(path(endpoint))
+                .respond_with(ResponseTemplate::new(404))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+        }
+
+        let client = GitHubClient::with_base_url(&mock_server.uri());
+        let info = client
+            .validate_owner_with_info("@ghost", "fake-token")
+            .await;
+        assert!(matches!(info, Some(OwnerInfo::Invalid)));
+    }
+
     #[tokio::test]
     async fn test_cache_cleared_properly() {
         let mock_server = MockServer::start().await;
@@ -1150,4 +3076,234 @@ mod tests {
         // 403 on team = Unknown
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("@org/platfrm", "@org/platform"), 1);
+    }
+
+    #[test]
+    fn test_closest_owner_suggests_near_typo() {
+        let known = ["@org/platform", "@org/payments", "@alice"];
+        assert_eq!(
+            closest_owner("@org/platfrm", known.iter().copied()),
+            Some("@org/platform")
+        );
+        assert_eq!(
+            closest_owner("@Org/Paymnets", known.iter().copied()),
+            Some("@org/payments")
+        );
+    }
+
+    #[test]
+    fn test_closest_owner_no_close_match() {
+        let known = ["@org/platform", "@alice"];
+        assert_eq!(closest_owner("@org/frontend", known.iter().copied()), None);
+        // Short handles only tolerate a single edit
+        assert_eq!(
+            closest_owner("@bob", ["@alice", "@rob"].into_iter()),
+            Some("@rob")
+        );
+        assert_eq!(closest_owner("@bo", ["@alice", "@joe"].into_iter()), None);
+        // The owner itself isn't a suggestion
+        assert_eq!(closest_owner("@alice", known.iter().copied()), None);
+        assert_eq!(closest_owner("@alice", std::iter::empty()), None);
+    }
+
+    #[test]
+    fn test_token_kind_detect() {
+        assert_eq!(TokenKind::detect("ghp_abc123"), TokenKind::Classic);
+        assert_eq!(
+            TokenKind::detect("github_pat_11ABC_def"),
+            TokenKind::FineGrained
+        );
+        assert_eq!(TokenKind::detect("ghs_abc123"), TokenKind::Installation);
+        assert_eq!(TokenKind::detect(" ghs_abc123\n"), TokenKind::Installation);
+        assert_eq!(TokenKind::detect("gho_abc123"), TokenKind::OAuth);
+        assert_eq!(TokenKind::detect("ghu_abc123"), TokenKind::OAuth);
+        assert_eq!(
+            TokenKind::detect("0123456789abcdef0123456789abcdef01234567"),
+            TokenKind::Unknown
+        );
+        assert_eq!(TokenKind::detect("fake-token"), TokenKind::Unknown);
+
+        assert!(TokenKind::Installation.scope_warning().is_some());
+        assert!(TokenKind::Classic.scope_warning().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_team_unreadable_by_installation_token_explains_why() {
+        let mock_server = MockServer::start().await;
+        for team in ["hidden", "forbidden"] {
+            Mock::given(method("GET"))
+                .and(path(format!("/orgs/org/teams/{team}")))
+                .respond_with(ResponseTemplate::new(if team == "hidden" {
+                    404
+                } else {
+                    403
+                }))
+                .mount(&mock_server)
+                .await;
+        }
+
+        let client = GitHubClient::with_base_url(&mock_server.uri());
+        for owner in ["@org/hidden", "@org/forbidden"] {
+            let info = client.validate_owner_with_info(owner, "ghs_app").await;
+            match info {
+                Some(OwnerInfo::Unknown(reason)) => {
+                    assert!(reason.contains("GitHub App installation token"), "{reason}");
+                    assert!(reason.contains("Members (read)"), "{reason}");
+                }
+                other => panic!("expected Unknown for {owner}, got {other:?}"),
+            }
+        }
+
+        // A PAT keeps the read:org hint
+        let client = GitHubClient::with_base_url(&mock_server.uri());
+        let info = client
+            .validate_owner_with_info("@org/hidden", "ghp_pat")
+            .await;
+        assert!(
+            matches!(info, Some(OwnerInfo::Unknown(ref r)) if r.contains("read:org")),
+            "{info:?}"
+        );
+    }
+
+    // =========================================================================
+    // Concurrent validation
+    // =========================================================================
+
+    /// Mock GitHub that answers every `/users/{login}` after a delay while
+    /// recording the peak number of requests being served at once
+    async fn start_counting_server(delay_ms: u64) -> (String, Arc<AtomicUsize>, Arc<AtomicUsize>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let uri = format!("http://{}", listener.local_addr().unwrap());
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        let requests = Arc::new(AtomicUsize::new(0));
+
+        let (current, max, total) = (in_flight, Arc::clone(&max_in_flight), Arc::clone(&requests));
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else {
+                    return;
+                };
+                let (current, max, total) = (current.clone(), max.clone(), total.clone());
+                tokio::spawn(async move {
+                    let mut buf = Vec::new();
+                    let mut chunk = [0u8; 1024];
+                    loop {
+                        // Requests are bodiless GETs: wait for end of headers
+                        while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+                            match socket.read(&mut chunk).await {
+                                Ok(0) | Err(_) => return,
+                                Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                            }
+                        }
+                        let end = buf.windows(4).position(|w| w == b"\r\n\r\n").unwrap() + 4;
+                        let head = String::from_utf8_lossy(&buf[..end]).to_string();
+                        buf.drain(..end);
+
+                        total.fetch_add(1, Ordering::SeqCst);
+                        let now = current.fetch_add(1, Ordering::SeqCst) + 1;
+                        max.fetch_max(now, Ordering::SeqCst);
+                        tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+                        current.fetch_sub(1, Ordering::SeqCst);
+
+                        let login = head
+                            .split_whitespace()
+                            .nth(1)
+                            .and_then(|p| p.strip_prefix("/users/"))
+                            .unwrap_or_default()
+                            .to_string();
+                        let body = serde_json::json!({
+                            "login": login,
+                            "html_url": format!("https://github.com/{login}"),
+                        })
+                        .to_string();
+                        let response = format!(
+                            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                            body.len(),
+                            body
+                        );
+                        if socket.write_all(response.as_bytes()).await.is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+
+        (uri, max_in_flight, requests)
+    }
+
+    #[tokio::test]
+    async fn test_validate_owners_bounds_in_flight_requests() {
+        let (uri, max_in_flight, requests) = start_counting_server(50).await;
+        let client = GitHubClient::with_base_url(&uri);
+        let owners: Vec<String> = (0..12).map(|i| format!("@user{i}")).collect();
+
+        let results = client.validate_owners(owners, "fake-token", 3).await;
+
+        assert_eq!(results.len(), 12);
+        assert!(results
+            .values()
+            .all(|r| r.as_ref().is_some_and(OwnerInfo::is_valid)));
+        assert_eq!(requests.load(Ordering::SeqCst), 12);
+        let peak = max_in_flight.load(Ordering::SeqCst);
+        assert!(peak <= 3, "at most 3 requests in flight, saw {peak}");
+        assert!(peak > 1, "requests should overlap, saw {peak}");
+    }
+
+    #[tokio::test]
+    async fn test_validate_owners_uses_and_populates_cache() {
+        let (uri, _, requests) = start_counting_server(0).await;
+        let client = GitHubClient::with_base_url(&uri);
+        client.insert_cached("@cached", OwnerInfo::Invalid);
+
+        let owners = ["@cached", "@fresh", "@fresh", "dev@example.com"].map(String::from);
+        let results = client.validate_owners(owners, "fake-token", 0).await;
+
+        // Duplicates collapse, the cached owner and the email never hit the API
+        assert_eq!(results.len(), 3);
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+        assert!(matches!(results["@cached"], Some(OwnerInfo::Invalid)));
+        assert!(results["dev@example.com"].is_none());
+        assert!(matches!(
+            client.get_owner_info("@fresh"),
+            Some(OwnerInfo::User(ref u)) if u.login == "fresh"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_validate_owners_reports_progress() {
+        let (uri, _, _) = start_counting_server(0).await;
+        let client = GitHubClient::with_base_url(&uri);
+        let seen = std::sync::Mutex::new(Vec::new());
+
+        client
+            .validate_owners_with_progress(
+                ["@a", "@b"].map(String::from),
+                "fake-token",
+                DEFAULT_CONCURRENCY,
+                |owner, info| {
+                    seen.lock()
+                        .unwrap()
+                        .push((owner.to_string(), info.is_some()))
+                },
+            )
+            .await;
+
+        let mut seen = seen.into_inner().unwrap();
+        seen.sort();
+        assert_eq!(
+            seen,
+            vec![("@a".to_string(), true), ("@b".to_string(), true)]
+        );
+    }
 }
@@ -4,7 +4,7 @@ use tower_lsp::lsp_types::*;
 
 use crate::parser::{find_owner_at_position, parse_codeowners_file_with_positions, CodeownersLine};
 
-use super::util::find_nth_owner_position;
+use super::util::owner_ranges;
 
 /// Find all ranges where the same owner appears for linked editing
 pub fn linked_editing_ranges(content: &str, position: Position) -> Option<LinkedEditingRanges> {
@@ -31,25 +31,9 @@ pub fn linked_editing_ranges(content: &str, position: Position) -> Option<Linked
             ..
         } = &parsed_line.content
         {
-            let line_text = lines.get(parsed_line.line_number as usize).unwrap_or(&"");
-
-            let mut occurrence = 0;
-            for o in line_owners.iter() {
-                if o == &owner {
-                    if let Some(pos) = find_nth_owner_position(line_text, &owner, occurrence) {
-                        ranges.push(Range {
-                            start: Position {
-                                line: parsed_line.line_number,
-                                character: pos as u32,
-                            },
-                            end: Position {
-                                line: parsed_line.line_number,
-                                character: (pos + owner.len()) as u32,
-                            },
-                        });
-                    }
-                    occurrence += 1;
-                }
+            if line_owners.contains(&owner) {
+                let line_text = lines.get(parsed_line.line_number as usize).unwrap_or(&"");
+                ranges.extend(owner_ranges(parsed_line.line_number, line_text, &owner));
             }
         }
     }
@@ -1,12 +1,14 @@
-//! Navigation handlers: references, rename
+//! Navigation handlers: references, rename, owner definitions
 
 use std::collections::HashMap;
 
 use tower_lsp::lsp_types::*;
 
+use crate::github::OwnerInfo;
 use crate::parser::{find_owner_at_position, parse_codeowners_file_with_positions, CodeownersLine};
+use crate::validation::{classify_owner, OwnerKind};
 
-use super::util::find_nth_owner_position;
+use super::util::owner_ranges;
 
 /// Find all references to an owner in CODEOWNERS file
 pub fn find_references(content: &str, position: Position, uri: &Url) -> Option<Vec<Location>> {
@@ -32,27 +34,16 @@ pub fn find_references(content: &str, position: Position, uri: &Url) -> Option<V
             ..
         } = &parsed_line.content
         {
-            let line_text = lines.get(parsed_line.line_number as usize).unwrap_or(&"");
-            let mut occurrence = 0;
-            for o in line_owners.iter() {
-                if o == &owner {
-                    if let Some(pos) = find_nth_owner_position(line_text, &owner, occurrence) {
-                        locations.push(Location {
+            if line_owners.contains(&owner) {
+                let line_text = lines.get(parsed_line.line_number as usize).unwrap_or(&"");
+                locations.extend(
+                    owner_ranges(parsed_line.line_number, line_text, &owner)
+                        .into_iter()
+                        .map(|range| Location {
                             uri: uri.clone(),
-                            range: Range {
-                                start: Position {
-                                    line: parsed_line.line_number,
-                                    character: pos as u32,
-                                },
-                                end: Position {
-                                    line: parsed_line.line_number,
-                                    character: (pos + owner.len()) as u32,
-                                },
-                            },
-                        });
-                    }
-                    occurrence += 1;
-                }
+                            range,
+                        }),
+                );
             }
         }
     }
@@ -78,41 +69,12 @@ pub fn prepare_rename(content: &str, position: Position) -> Option<Range> {
     // Only allow renaming @owners
     let owner = find_owner_at_position(line, char_idx)?;
 
-    // Find the occurrence of this owner that contains the cursor.
-    // We can't just use line.find() because that always returns the first occurrence.
-    let mut start = 0;
-    while let Some(pos) = line[start..].find(&owner) {
-        let abs_pos = start + pos;
-        let end_pos = abs_pos + owner.len();
-        // Check word boundary
-        let before_ok = abs_pos == 0
-            || line
-                .as_bytes()
-                .get(abs_pos - 1)
-                .map(|&b| b == b' ' || b == b'\t')
-                .unwrap_or(true);
-        let after_ok = end_pos >= line.len()
-            || line
-                .as_bytes()
-                .get(end_pos)
-                .map(|&b| b == b' ' || b == b'\t')
-                .unwrap_or(true);
-
-        if before_ok && after_ok && char_idx >= abs_pos && char_idx < end_pos {
-            return Some(Range {
-                start: Position {
-                    line: position.line,
-                    character: abs_pos as u32,
-                },
-                end: Position {
-                    line: position.line,
-                    character: end_pos as u32,
-                },
-            });
-        }
-        start = abs_pos + 1;
-    }
-    None
+    // The occurrence of this owner that contains the cursor
+    owner_ranges(position.line, line, &owner)
+        .into_iter()
+        .find(|range| {
+            range.start.character <= position.character && position.character < range.end.character
+        })
 }
 
 /// Rename an owner across all rules
@@ -142,27 +104,16 @@ pub fn rename_owner(
             ..
         } = &parsed_line.content
         {
-            let line_text = lines.get(parsed_line.line_number as usize).unwrap_or(&"");
-            let mut occurrence = 0;
-            for o in line_owners.iter() {
-                if o == &old_owner {
-                    if let Some(pos) = find_nth_owner_position(line_text, &old_owner, occurrence) {
-                        edits.push(TextEdit {
-                            range: Range {
-                                start: Position {
-                                    line: parsed_line.line_number,
-                                    character: pos as u32,
-                                },
-                                end: Position {
-                                    line: parsed_line.line_number,
-                                    character: (pos + old_owner.len()) as u32,
-                                },
-                            },
+            if line_owners.contains(&old_owner) {
+                let line_text = lines.get(parsed_line.line_number as usize).unwrap_or(&"");
+                edits.extend(
+                    owner_ranges(parsed_line.line_number, line_text, &old_owner)
+                        .into_iter()
+                        .map(|range| TextEdit {
+                            range,
                             new_text: new_name.to_string(),
-                        });
-                    }
-                    occurrence += 1;
-                }
+                        }),
+                );
             }
         }
     }
@@ -178,3 +129,162 @@ pub fn rename_owner(
         })
     }
 }
+
+/// GitHub page for an owner, as a definition target.
+///
+/// Cached metadata wins; without it (not validated yet, or validation
+/// couldn't reach GitHub) the URL is derived from the owner itself. Owners
+/// known not to exist and email owners have no page.
+pub fn owner_definition(owner: &str, info: Option<&OwnerInfo>) -> Option<Location> {
+    if matches!(info, Some(OwnerInfo::Invalid)) {
+        return None;
+    }
+    let url = match info.and_then(OwnerInfo::html_url) {
+        Some(url) => url.to_string(),
+        None => match classify_owner(owner)? {
+            OwnerKind::User => format!("https://github.com/{}", &owner[1..]),
+            OwnerKind::Team => {
+                let (org, team) = owner[1..].split_once('/')?;
+                format!("https://github.com/orgs/{}/teams/{}", org, team)
+            }
+            OwnerKind::Email => return None,
+        },
+    };
+    Some(Location {
+        uri: Url::parse(&url).ok()?,
+        range: Range::default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::{TeamInfo, UserInfo};
+
+    fn url(owner: &str, info: Option<&OwnerInfo>) -> Option<String> {
+        owner_definition(owner, info).map(|location| location.uri.to_string())
+    }
+
+    fn rename(content: &str, position: Position, new_name: &str) -> Vec<TextEdit> {
+        let uri = Url::parse("file:///repo/CODEOWNERS").unwrap();
+        let mut edit = rename_owner(content, position, new_name, &uri).unwrap();
+        let mut edits = edit.changes.take().unwrap().remove(&uri).unwrap();
+        edits.sort_by_key(|e| (e.range.start.line, e.range.start.character));
+        edits
+    }
+
+    fn position(line: u32, character: u32) -> Position {
+        Position { line, character }
+    }
+
+    #[test]
+    fn test_rename_owner_whole_tokens_only() {
+        let content = "*.rs @org/old @alice\n\
+                       /docs/ @org/old-archive @org/old\n\
+                       /legacy/ @org/old-archive\n";
+        let edits = rename(content, position(0, 7), "@org/new");
+        let ranges: Vec<_> = edits
+            .iter()
+            .map(|e| {
+                (
+                    e.range.start.line,
+                    e.range.start.character,
+                    e.range.end.character,
+                )
+            })
+            .collect();
+        assert_eq!(ranges, vec![(0, 5, 13), (1, 24, 32)]);
+        assert!(edits.iter().all(|e| e.new_text == "@org/new"));
+    }
+
+    #[test]
+    fn test_rename_owner_skips_pattern_spelled_like_owner() {
+        // The second line's pattern is the literal text "@alice"
+        let content = "*.rs @alice\n@alice @bob @alice # @alice\n";
+        let edits = rename(content, position(0, 6), "@alicia");
+        let ranges: Vec<_> = edits
+            .iter()
+            .map(|e| (e.range.start.line, e.range.start.character))
+            .collect();
+        assert_eq!(ranges, vec![(0, 5), (1, 12)]);
+    }
+
+    #[test]
+    fn test_rename_owner_when_pattern_starts_with_owner() {
+        // Non-ASCII before the owner: edits are in characters, not bytes
+        let content = "@alice @alice\n/café/ @alice\n";
+        let edits = rename(content, position(0, 8), "@alicia");
+        let ranges: Vec<_> = edits
+            .iter()
+            .map(|e| {
+                (
+                    e.range.start.line,
+                    e.range.start.character,
+                    e.range.end.character,
+                )
+            })
+            .collect();
+        assert_eq!(ranges, vec![(0, 7, 13), (1, 7, 13)]);
+    }
+
+    #[test]
+    fn test_prepare_rename_only_on_owners() {
+        let content = "*.rs @org/old @org/old\n";
+        assert_eq!(
+            prepare_rename(content, position(0, 15)),
+            Some(Range {
+                start: position(0, 14),
+                end: position(0, 22),
+            })
+        );
+        assert_eq!(prepare_rename(content, position(0, 1)), None);
+    }
+
+    #[test]
+    fn test_owner_definition_uses_cached_url() {
+        let team = OwnerInfo::Team(TeamInfo {
+            slug: "core".to_string(),
+            name: "Core".to_string(),
+            org: "acme".to_string(),
+            description: None,
+            html_url: "https://github.example.com/orgs/acme/teams/core".to_string(),
+            members_count: None,
+            repos_count: None,
+        });
+        assert_eq!(
+            url("@acme/core", Some(&team)).as_deref(),
+            Some("https://github.example.com/orgs/acme/teams/core")
+        );
+
+        let user = OwnerInfo::Suspended(UserInfo {
+            login: "gone".to_string(),
+            name: None,
+            html_url: "https://github.com/gone".to_string(),
+            avatar_url: None,
+            bio: None,
+            company: None,
+        });
+        assert_eq!(
+            url("@gone", Some(&user)).as_deref(),
+            Some("https://github.com/gone")
+        );
+    }
+
+    #[test]
+    fn test_owner_definition_without_metadata() {
+        assert_eq!(
+            url("@alice", None).as_deref(),
+            Some("https://github.com/alice")
+        );
+        assert_eq!(
+            url(
+                "@acme/core",
+                Some(&OwnerInfo::Unknown("rate limited".to_string()))
+            )
+            .as_deref(),
+            Some("https://github.com/orgs/acme/teams/core")
+        );
+        assert_eq!(url("@ghost", Some(&OwnerInfo::Invalid)), None);
+        assert_eq!(url("dev@example.com", None), None);
+    }
+}
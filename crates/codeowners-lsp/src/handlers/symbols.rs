@@ -1,152 +1,180 @@
 //! Document and workspace symbol handlers
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use tower_lsp::lsp_types::*;
 
-use crate::parser::{parse_codeowners_file_with_positions, CodeownersLine};
+use crate::parser::{
+    parse_codeowners_file_with_positions, parse_section_header, CodeownersLine, ParsedLine,
+};
 
-/// Generate document symbols (outline) for CODEOWNERS file
+/// Generate document symbols (outline) for CODEOWNERS file.
+///
+/// GitLab `[Section]` headers and capitalized comment headers
+/// (`# Frontend`) become containers for the rules below them. A file with
+/// no sections groups rules by their top-level directory instead.
 pub fn document_symbols(content: &str) -> Vec<DocumentSymbol> {
     let lines = parse_codeowners_file_with_positions(content);
+    let texts: Vec<&str> = content.lines().collect();
     let mut symbols = Vec::new();
     let mut current_section: Option<(String, u32, Vec<DocumentSymbol>)> = None;
+    let mut has_sections = false;
 
     for line in &lines {
-        match &line.content {
-            CodeownersLine::Comment(text) => {
-                // Check if this is a section header (e.g., "# Section Name")
-                let section_text = text.trim().trim_start_matches('#').trim();
-                if !section_text.is_empty()
-                    && section_text
-                        .chars()
-                        .next()
-                        .map(|c| c.is_uppercase())
-                        .unwrap_or(false)
-                {
-                    // Finish previous section
-                    if let Some((name, start_line, children)) = current_section.take() {
-                        let end_line = if line.line_number > 0 {
-                            line.line_number - 1
-                        } else {
-                            0
-                        };
-                        #[allow(deprecated)]
-                        symbols.push(DocumentSymbol {
-                            name,
-                            detail: None,
-                            kind: SymbolKind::NAMESPACE,
-                            tags: None,
-                            deprecated: None,
-                            range: Range {
-                                start: Position {
-                                    line: start_line,
-                                    character: 0,
-                                },
-                                end: Position {
-                                    line: end_line,
-                                    character: u32::MAX,
-                                },
-                            },
-                            selection_range: Range {
-                                start: Position {
-                                    line: start_line,
-                                    character: 0,
-                                },
-                                end: Position {
-                                    line: start_line,
-                                    character: u32::MAX,
-                                },
-                            },
-                            children: if children.is_empty() {
-                                None
-                            } else {
-                                Some(children)
-                            },
-                        });
-                    }
-                    // Start new section
-                    current_section =
-                        Some((section_text.to_string(), line.line_number, Vec::new()));
-                }
+        let section_name = match &line.content {
+            CodeownersLine::Comment(text) => comment_section_name(text),
+            CodeownersLine::Rule { .. } => texts
+                .get(line.line_number as usize)
+                .and_then(|text| parse_section_header(text))
+                .map(|header| header.name),
+            CodeownersLine::Empty => None,
+        };
+        if let Some(name) = section_name {
+            // Finish previous section
+            if let Some(section) = current_section.take() {
+                symbols.push(section_symbol(section, line.line_number.saturating_sub(1)));
             }
-            CodeownersLine::Rule { pattern, owners } => {
-                let owners_str = owners.join(" ");
-                #[allow(deprecated)]
-                let symbol = DocumentSymbol {
-                    name: pattern.clone(),
-                    detail: Some(owners_str),
-                    kind: SymbolKind::FILE,
-                    tags: None,
-                    deprecated: None,
-                    range: Range {
-                        start: Position {
-                            line: line.line_number,
-                            character: 0,
-                        },
-                        end: Position {
-                            line: line.line_number,
-                            character: u32::MAX,
-                        },
-                    },
-                    selection_range: Range {
-                        start: Position {
-                            line: line.line_number,
-                            character: line.pattern_start,
-                        },
-                        end: Position {
-                            line: line.line_number,
-                            character: line.pattern_end,
-                        },
-                    },
-                    children: None,
-                };
+            current_section = Some((name, line.line_number, Vec::new()));
+            has_sections = true;
+            continue;
+        }
 
-                if let Some((_, _, ref mut children)) = current_section {
-                    children.push(symbol);
-                } else {
-                    symbols.push(symbol);
-                }
+        if let CodeownersLine::Rule { pattern, owners } = &line.content {
+            let symbol = rule_symbol(line, pattern, owners);
+            if let Some((_, _, ref mut children)) = current_section {
+                children.push(symbol);
+            } else {
+                symbols.push(symbol);
             }
-            CodeownersLine::Empty => {}
         }
     }
 
     // Finish last section
-    if let Some((name, start_line, children)) = current_section {
-        let end_line = lines.last().map(|l| l.line_number).unwrap_or(start_line);
+    if let Some(section) = current_section {
+        let end_line = lines.last().map(|l| l.line_number).unwrap_or(section.1);
+        symbols.push(section_symbol(section, end_line));
+    }
+
+    if has_sections {
+        symbols
+    } else {
+        group_by_directory(symbols)
+    }
+}
+
+/// Name of a section-header comment like `# Frontend` (capitalized text)
+fn comment_section_name(text: &str) -> Option<String> {
+    let section_text = text.trim().trim_start_matches('#').trim();
+    section_text
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_uppercase())
+        .then(|| section_text.to_string())
+}
+
+fn line_range(line: u32) -> Range {
+    Range {
+        start: Position { line, character: 0 },
+        end: Position {
+            line,
+            character: u32::MAX,
+        },
+    }
+}
+
+fn rule_symbol(line: &ParsedLine, pattern: &str, owners: &[String]) -> DocumentSymbol {
+    #[allow(deprecated)]
+    DocumentSymbol {
+        name: pattern.to_string(),
+        detail: Some(owners.join(" ")),
+        kind: SymbolKind::FILE,
+        tags: None,
+        deprecated: None,
+        range: line_range(line.line_number),
+        selection_range: Range {
+            start: Position {
+                line: line.line_number,
+                character: line.pattern_start,
+            },
+            end: Position {
+                line: line.line_number,
+                character: line.pattern_end,
+            },
+        },
+        children: None,
+    }
+}
+
+/// A section container spanning from its header to `end_line`
+fn section_symbol(
+    (name, start_line, children): (String, u32, Vec<DocumentSymbol>),
+    end_line: u32,
+) -> DocumentSymbol {
+    #[allow(deprecated)]
+    DocumentSymbol {
+        name,
+        detail: None,
+        kind: SymbolKind::NAMESPACE,
+        tags: None,
+        deprecated: None,
+        range: Range {
+            start: Position {
+                line: start_line,
+                character: 0,
+            },
+            end: Position {
+                line: end_line.max(start_line),
+                character: u32::MAX,
+            },
+        },
+        selection_range: line_range(start_line),
+        children: if children.is_empty() {
+            None
+        } else {
+            Some(children)
+        },
+    }
+}
+
+/// The first directory of a pattern (`src/` for `/src/**/*.rs`), unless the
+/// pattern has none or starts with a glob
+fn top_level_directory(pattern: &str) -> Option<String> {
+    let (first, _) = pattern.trim_start_matches('/').split_once('/')?;
+    if first.is_empty() || first.contains(['*', '?', '[']) {
+        return None;
+    }
+    Some(format!("{}/", first))
+}
+
+/// Nest rules under a package symbol for their top-level directory, placed
+/// where the directory first appears. Rules without one stay top-level.
+fn group_by_directory(rules: Vec<DocumentSymbol>) -> Vec<DocumentSymbol> {
+    let mut symbols: Vec<DocumentSymbol> = Vec::new();
+    let mut groups: HashMap<String, usize> = HashMap::new();
+
+    for rule in rules {
+        let Some(directory) = top_level_directory(&rule.name) else {
+            symbols.push(rule);
+            continue;
+        };
+        if let Some(&index) = groups.get(&directory) {
+            let group = &mut symbols[index];
+            group.range.end = rule.range.end;
+            group.children.get_or_insert_with(Vec::new).push(rule);
+            continue;
+        }
+
+        groups.insert(directory.clone(), symbols.len());
         #[allow(deprecated)]
         symbols.push(DocumentSymbol {
-            name,
+            name: directory,
             detail: None,
-            kind: SymbolKind::NAMESPACE,
+            kind: SymbolKind::PACKAGE,
             tags: None,
             deprecated: None,
-            range: Range {
-                start: Position {
-                    line: start_line,
-                    character: 0,
-                },
-                end: Position {
-                    line: end_line,
-                    character: u32::MAX,
-                },
-            },
-            selection_range: Range {
-                start: Position {
-                    line: start_line,
-                    character: 0,
-                },
-                end: Position {
-                    line: start_line,
-                    character: u32::MAX,
-                },
-            },
-            children: if children.is_empty() {
-                None
-            } else {
-                Some(children)
-            },
+            range: rule.range,
+            selection_range: rule.selection_range,
+            children: Some(vec![rule]),
         });
     }
 
@@ -235,3 +263,104 @@ pub fn workspace_symbols(content: &str, query: &str, uri: &Url) -> Vec<SymbolInf
 
     symbols
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (name, kind, line, children) for each symbol
+    fn tree(symbols: &[DocumentSymbol]) -> Vec<(String, SymbolKind, u32, Vec<String>)> {
+        symbols
+            .iter()
+            .map(|s| {
+                let children = s
+                    .children
+                    .iter()
+                    .flatten()
+                    .map(|c| c.name.clone())
+                    .collect();
+                (s.name.clone(), s.kind, s.range.start.line, children)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_document_symbols_sections() {
+        let content = "* @core\n\
+                       \n\
+                       [Frontend Docs] @docs\n\
+                       /docs/ @docs\n\
+                       *.md\n\
+                       # Backend\n\
+                       /src/ @rust\n";
+        let symbols = document_symbols(content);
+        assert_eq!(
+            tree(&symbols),
+            vec![
+                ("*".to_string(), SymbolKind::FILE, 0, vec![]),
+                (
+                    "Frontend Docs".to_string(),
+                    SymbolKind::NAMESPACE,
+                    2,
+                    vec!["/docs/".to_string(), "*.md".to_string()]
+                ),
+                (
+                    "Backend".to_string(),
+                    SymbolKind::NAMESPACE,
+                    5,
+                    vec!["/src/".to_string()]
+                ),
+            ]
+        );
+        // The section ends on the line before the next header
+        assert_eq!(symbols[1].range.end.line, 4);
+        // Rules select their pattern
+        let docs = &symbols[1].children.as_ref().unwrap()[0];
+        assert_eq!(docs.detail.as_deref(), Some("@docs"));
+        assert_eq!(docs.selection_range.start.character, 0);
+        assert_eq!(docs.selection_range.end.character, 6);
+    }
+
+    #[test]
+    fn test_document_symbols_group_by_directory_without_sections() {
+        let content = "*.rs @rust\n\
+                       /src/app/ @app\n\
+                       docs/ @docs\n\
+                       src/lib.rs @lib\n\
+                       **/logs @ops\n\
+                       # lowercase comments aren't sections\n\
+                       Makefile @build\n";
+        let symbols = document_symbols(content);
+        assert_eq!(
+            tree(&symbols),
+            vec![
+                ("*.rs".to_string(), SymbolKind::FILE, 0, vec![]),
+                (
+                    "src/".to_string(),
+                    SymbolKind::PACKAGE,
+                    1,
+                    vec!["/src/app/".to_string(), "src/lib.rs".to_string()]
+                ),
+                (
+                    "docs/".to_string(),
+                    SymbolKind::PACKAGE,
+                    2,
+                    vec!["docs/".to_string()]
+                ),
+                ("**/logs".to_string(), SymbolKind::FILE, 4, vec![]),
+                ("Makefile".to_string(), SymbolKind::FILE, 6, vec![]),
+            ]
+        );
+        // A group spans its first to last rule
+        assert_eq!(symbols[1].range.end.line, 3);
+    }
+
+    #[test]
+    fn test_top_level_directory() {
+        assert_eq!(top_level_directory("/src/**/*.rs").as_deref(), Some("src/"));
+        assert_eq!(top_level_directory("docs/").as_deref(), Some("docs/"));
+        assert_eq!(top_level_directory("*.rs"), None);
+        assert_eq!(top_level_directory("Makefile"), None);
+        assert_eq!(top_level_directory("**/build/"), None);
+    }
+}
@@ -1,91 +1,81 @@
 //! Shared utilities for LSP handlers
 
-/// Find the byte position of the nth occurrence of an owner string in a line.
-///
-/// `n` is the occurrence count (0-indexed) of this specific owner as a
-/// whitespace-delimited word. This is NOT the index in the owners vec --
-/// callers must track per-owner occurrence counts separately.
-pub fn find_nth_owner_position(line: &str, owner: &str, n: usize) -> Option<usize> {
-    let mut count = 0;
-    let mut start = 0;
-    while let Some(pos) = line[start..].find(owner) {
-        let abs_pos = start + pos;
-        // Verify it's a whole word (not part of pattern)
-        let before_ok = abs_pos == 0
-            || line
-                .as_bytes()
-                .get(abs_pos - 1)
-                .map(|&b| b == b' ' || b == b'\t')
-                .unwrap_or(true);
-        let after_ok = abs_pos + owner.len() >= line.len()
-            || line
-                .as_bytes()
-                .get(abs_pos + owner.len())
-                .map(|&b| b == b' ' || b == b'\t')
-                .unwrap_or(true);
+use tower_lsp::lsp_types::{Position, Range};
 
-        if before_ok && after_ok {
-            if count == n {
-                return Some(abs_pos);
-            }
-            count += 1;
-        }
-        start = abs_pos + 1;
-    }
-    None
+use crate::parser::tokenize;
+
+/// Ranges of every occurrence of `owner` among the owners of a rule line.
+///
+/// Only whole owner tokens count: never the pattern, a longer owner
+/// (`@alice-admin` for `@alice`) or anything in an inline comment. Offsets
+/// come from the tokenizer in bytes and are reported in characters.
+pub fn owner_ranges(line_number: u32, line: &str, owner: &str) -> Vec<Range> {
+    let column = |byte: usize| line[..byte].chars().count() as u32;
+    tokenize(line)
+        .skip(1)
+        .take_while(|(_, token)| !token.starts_with('#'))
+        .filter(|(_, token)| *token == owner)
+        .map(|(pos, token)| Range {
+            start: Position {
+                line: line_number,
+                character: column(pos),
+            },
+            end: Position {
+                line: line_number,
+                character: column(pos + token.len()),
+            },
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_find_nth_owner_basic() {
-        let line = "*.rs @alice @bob @charlie";
-        assert_eq!(find_nth_owner_position(line, "@alice", 0), Some(5));
-        assert_eq!(find_nth_owner_position(line, "@bob", 0), Some(12));
-        assert_eq!(find_nth_owner_position(line, "@charlie", 0), Some(17));
+    fn starts(line: &str, owner: &str) -> Vec<u32> {
+        owner_ranges(0, line, owner)
+            .iter()
+            .map(|r| r.start.character)
+            .collect()
     }
 
     #[test]
-    fn test_find_nth_owner_duplicate() {
-        let line = "*.rs @alice @bob @alice";
-        assert_eq!(find_nth_owner_position(line, "@alice", 0), Some(5));
-        assert_eq!(find_nth_owner_position(line, "@alice", 1), Some(17));
-        assert_eq!(find_nth_owner_position(line, "@alice", 2), None);
+    fn test_owner_ranges_basic() {
+        let line = "*.rs @alice @bob @charlie";
+        assert_eq!(starts(line, "@alice"), [5]);
+        assert_eq!(starts(line, "@bob"), [12]);
+        assert_eq!(starts(line, "@charlie"), [17]);
+        assert_eq!(owner_ranges(3, line, "@bob")[0].end, Position::new(3, 16));
     }
 
     #[test]
-    fn test_find_nth_owner_not_found() {
-        let line = "*.rs @alice";
-        assert_eq!(find_nth_owner_position(line, "@bob", 0), None);
+    fn test_owner_ranges_duplicates() {
+        assert_eq!(starts("*.rs @alice @bob @alice", "@alice"), [5, 17]);
+        assert_eq!(starts("*.rs @a @b @a @a", "@a"), [5, 11, 14]);
     }
 
     #[test]
-    fn test_find_nth_owner_word_boundary() {
-        // @alice should not match inside @alice-admin
-        let line = "*.rs @alice-admin @alice";
-        assert_eq!(find_nth_owner_position(line, "@alice", 0), Some(18));
+    fn test_owner_ranges_not_found() {
+        assert!(starts("*.rs @alice", "@bob").is_empty());
     }
 
     #[test]
-    fn test_find_nth_owner_at_start() {
-        let line = "@owner pattern";
-        assert_eq!(find_nth_owner_position(line, "@owner", 0), Some(0));
+    fn test_owner_ranges_word_boundary() {
+        // @alice should not match inside @alice-admin
+        assert_eq!(starts("*.rs @alice-admin @alice", "@alice"), [18]);
     }
 
     #[test]
-    fn test_find_nth_owner_at_end() {
-        let line = "*.rs @owner";
-        assert_eq!(find_nth_owner_position(line, "@owner", 0), Some(5));
+    fn test_owner_ranges_skip_pattern_and_comment() {
+        assert_eq!(starts("@owner @owner # @owner", "@owner"), [7]);
+        assert_eq!(starts("@owner/x\t@owner", "@owner"), [9]);
     }
 
     #[test]
-    fn test_find_nth_owner_triple_duplicate() {
-        let line = "*.rs @a @b @a @a";
-        assert_eq!(find_nth_owner_position(line, "@a", 0), Some(5));
-        assert_eq!(find_nth_owner_position(line, "@a", 1), Some(11));
-        assert_eq!(find_nth_owner_position(line, "@a", 2), Some(14));
-        assert_eq!(find_nth_owner_position(line, "@a", 3), None);
+    fn test_owner_ranges_count_characters() {
+        // "é" is two bytes but one column
+        let ranges = owner_ranges(0, "/café/ @ops", "@ops");
+        assert_eq!(ranges[0].start.character, 7);
+        assert_eq!(ranges[0].end.character, 11);
     }
 }
@@ -0,0 +1,204 @@
+//! Curated git identity to owner mappings.
+//!
+//! Teams can list who's who once in `.codeowners-lsp/identities.toml`, and
+//! `suggest` uses it before falling back to `lookup_cmd`:
+//!
+//! ```toml
+//! [[identity]]
+//! owner = "@alice"
+//! emails = ["alice@example.com", "alice@users.noreply.github.com"]
+//! names = ["Alice Smith"]
+//! ```
+//!
+//! Emails and names are matched case-insensitively; an email match wins over
+//! a name match.
+//!
+//! For the mapping to be committed, the directory must not be ignored from
+//! the repository root: a root `.gitignore` entry `.codeowners-lsp/` hides
+//! identities.toml whatever the directory's own `.gitignore` says. Ignore
+//! `.codeowners-lsp/*` with `!.codeowners-lsp/identities.toml` instead.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::validation::{classify_owner, OwnerKind};
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct IdentitiesFile {
+    #[serde(default)]
+    identity: Vec<IdentityEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct IdentityEntry {
+    owner: String,
+    #[serde(default)]
+    emails: Vec<String>,
+    #[serde(default)]
+    names: Vec<String>,
+}
+
+/// Email and author-name lookups for curated identities
+#[derive(Debug, Default)]
+pub struct Identities {
+    /// Lowercased email -> owner
+    by_email: HashMap<String, String>,
+    /// Lowercased author name -> owner
+    by_name: HashMap<String, String>,
+}
+
+impl Identities {
+    /// Location of the mapping file under `workspace_root`
+    pub fn path(workspace_root: &Path) -> PathBuf {
+        workspace_root
+            .join(".codeowners-lsp")
+            .join("identities.toml")
+    }
+
+    /// Load the mapping for `workspace_root`. A missing file is an empty
+    /// mapping; a malformed one is an error.
+    pub fn load(workspace_root: &Path) -> Result<Self, String> {
+        let path = Self::path(workspace_root);
+        match fs::read_to_string(&path) {
+            Ok(content) => Self::parse(&content).map_err(|e| format!("{}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    /// Parse `identities.toml` content
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let file: IdentitiesFile = toml::from_str(content).map_err(|e| e.to_string())?;
+
+        let mut identities = Self::default();
+        for entry in file.identity {
+            if !matches!(
+                classify_owner(&entry.owner),
+                Some(OwnerKind::User | OwnerKind::Team)
+            ) {
+                return Err(format!(
+                    "Identity owner must be @user or @org/team, got '{}'",
+                    entry.owner
+                ));
+            }
+            for email in &entry.emails {
+                insert_unique(&mut identities.by_email, email, &entry.owner, "Email")?;
+            }
+            for name in &entry.names {
+                insert_unique(&mut identities.by_name, name, &entry.owner, "Name")?;
+            }
+        }
+        Ok(identities)
+    }
+
+    /// Whether no identities are mapped
+    pub fn is_empty(&self) -> bool {
+        self.by_email.is_empty() && self.by_name.is_empty()
+    }
+
+    /// The curated owner for a git author, by email first, then by name
+    pub fn resolve(&self, email: &str, name: &str) -> Option<&str> {
+        self.by_email
+            .get(&email.to_lowercase())
+            .or_else(|| self.by_name.get(&name.to_lowercase()))
+            .map(String::as_str)
+    }
+}
+
+/// Map `key` to `owner`, rejecting a key already mapped to another owner
+fn insert_unique(
+    map: &mut HashMap<String, String>,
+    key: &str,
+    owner: &str,
+    what: &str,
+) -> Result<(), String> {
+    match map.insert(key.to_lowercase(), owner.to_string()) {
+        Some(previous) if previous != owner => Err(format!(
+            "{} '{}' is mapped to both {} and {}",
+            what, key, previous, owner
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = r#"
+[[identity]]
+owner = "@alice"
+emails = ["alice@example.com", "Alice@Users.Noreply.GitHub.com"]
+names = ["Alice Smith"]
+
+[[identity]]
+owner = "@org/platform"
+emails = ["ci-bot@example.com"]
+"#;
+
+    #[test]
+    fn test_resolve_mapped_emails() {
+        let identities = Identities::parse(CONTENT).unwrap();
+        assert_eq!(
+            identities.resolve("alice@example.com", "Alice"),
+            Some("@alice")
+        );
+        // Every listed email maps, case-insensitively
+        assert_eq!(
+            identities.resolve("alice@users.noreply.github.com", "alice"),
+            Some("@alice")
+        );
+        assert_eq!(
+            identities.resolve("CI-BOT@example.com", "CI"),
+            Some("@org/platform")
+        );
+    }
+
+    #[test]
+    fn test_resolve_by_name_alias() {
+        let identities = Identities::parse(CONTENT).unwrap();
+        assert_eq!(
+            identities.resolve("alice@laptop.local", "alice smith"),
+            Some("@alice")
+        );
+    }
+
+    #[test]
+    fn test_resolve_unmapped() {
+        let identities = Identities::parse(CONTENT).unwrap();
+        assert_eq!(identities.resolve("bob@example.com", "Bob"), None);
+        assert!(Identities::default().is_empty());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let not_an_owner = "[[identity]]\nowner = \"alice@example.com\"\n";
+        assert!(Identities::parse(not_an_owner)
+            .unwrap_err()
+            .contains("@user or @org/team"));
+
+        let conflict = "[[identity]]\nowner = \"@a\"\nemails = [\"x@y.z\"]\n\
+                        [[identity]]\nowner = \"@b\"\nemails = [\"X@y.z\"]\n";
+        assert!(Identities::parse(conflict)
+            .unwrap_err()
+            .contains("mapped to both @a and @b"));
+
+        assert!(Identities::parse("[[identity]]\nowner = \"@a\"\nemail = \"x@y.z\"\n").is_err());
+    }
+
+    #[test]
+    fn test_load_missing_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(Identities::load(dir.path()).unwrap().is_empty());
+
+        fs::create_dir_all(dir.path().join(".codeowners-lsp")).unwrap();
+        fs::write(Identities::path(dir.path()), CONTENT).unwrap();
+        let identities = Identities::load(dir.path()).unwrap();
+        assert_eq!(identities.resolve("alice@example.com", ""), Some("@alice"));
+    }
+}
@@ -16,6 +16,13 @@ pub mod diagnostics;
 pub mod file_cache;
 pub mod github;
 pub mod handlers;
+pub mod identities;
 pub mod lookup;
+pub mod nested;
+pub mod owner_index;
 pub mod ownership;
 pub mod settings;
+pub mod skip_owners;
+
+#[cfg(test)]
+mod test_git;
@@ -17,6 +17,8 @@ pub struct OwnerLookup {
     cache: HashMap<String, Option<String>>,
     /// Existing owners from CODEOWNERS for fuzzy matching
     existing_owners: Vec<String>,
+    /// Show a progress bar during batch lookups
+    progress: bool,
 }
 
 impl OwnerLookup {
@@ -30,10 +32,17 @@ impl OwnerLookup {
             cmd_template: cmd_template.to_string(),
             cache: HashMap::new(),
             existing_owners,
+            progress: false,
         }
     }
 
-    /// Batch lookup emails in parallel with progress bar
+    /// Show or hide (the default) the batch lookup progress bar
+    pub fn with_progress(mut self, progress: bool) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Batch lookup emails in parallel, with a progress bar if enabled
     ///
     /// Returns a map of email -> resolved owner (None if lookup failed)
     pub fn batch_lookup(&mut self, emails: &[String]) -> HashMap<String, Option<String>> {
@@ -44,7 +53,11 @@ impl OwnerLookup {
             .collect();
 
         if !uncached.is_empty() {
-            let pb = ProgressBar::new(uncached.len() as u64);
+            let pb = if self.progress {
+                ProgressBar::new(uncached.len() as u64)
+            } else {
+                ProgressBar::hidden()
+            };
             pb.set_style(
                 ProgressStyle::default_bar()
                     .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} {msg}")
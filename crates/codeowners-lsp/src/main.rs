@@ -8,6 +8,7 @@ pub use lib::ownership;
 pub use lib::parser;
 pub use lib::pattern;
 pub use lib::settings;
+pub use lib::skip_owners;
 pub use lib::validation;
 
 use std::collections::{HashMap, HashSet};
@@ -19,18 +20,19 @@ use tower_lsp::jsonrpc::Result;
 use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer, LspService, Server};
 
-use diagnostics::{compute_diagnostics_sync, DiagnosticConfig};
+use diagnostics::{
+    compute_diagnostics_sync, unmatched_pattern_checks_enabled, unmatched_pattern_diagnostic,
+    DiagnosticConfig,
+};
 use file_cache::FileCache;
 use github::{GitHubClient, PersistentCache};
-use ownership::{
-    apply_safe_fixes, check_file_ownership, check_file_ownership_parsed, find_codeowners,
-};
+use ownership::{apply_safe_fixes, check_file_ownership_parsed_with_options, find_codeowners};
 use parser::{
     find_insertion_point_with_owner, find_owner_at_position, format_codeowners,
     parse_codeowners_file, parse_codeowners_file_with_positions, serialize_codeowners,
     CodeownersLine, ParsedLine,
 };
-use pattern::pattern_matches;
+use pattern::{CompiledPattern, PatternOptions};
 use settings::{load_settings_from_path, Settings, CONFIG_FILE, CONFIG_FILE_LOCAL};
 
 struct Backend {
@@ -68,6 +70,11 @@ impl Backend {
         }
     }
 
+    /// How patterns are matched, per the `brace_expansion` setting
+    fn pattern_options(&self) -> PatternOptions {
+        self.settings.read().unwrap().pattern_options()
+    }
+
     /// Get the GitHub token from settings (resolving env: prefix)
     fn get_github_token(&self) -> Option<String> {
         self.settings.read().unwrap().resolve_token()
@@ -117,9 +124,12 @@ impl Backend {
         };
 
         // Heavy work in blocking thread
-        let cache = tokio::task::spawn_blocking(move || FileCache::new(&root))
-            .await
-            .ok();
+        let options = self.pattern_options();
+        let cache = tokio::task::spawn_blocking(move || {
+            FileCache::new(&root).with_pattern_options(options)
+        })
+        .await
+        .ok();
 
         // Write back (fast)
         if let Some(cache) = cache {
@@ -132,7 +142,8 @@ impl Backend {
         let root = self.workspace_root.read().unwrap();
         if let Some(root) = root.as_ref() {
             let persistent = PersistentCache::load(root);
-            self.github_client.load_from_persistent(&persistent);
+            self.github_client
+                .load_from_persistent(&persistent, self.get_github_token().as_deref());
         }
     }
 
@@ -140,7 +151,9 @@ impl Backend {
     fn save_persistent_cache(&self) {
         let root = self.workspace_root.read().unwrap();
         if let Some(root) = root.as_ref() {
-            let persistent = self.github_client.export_to_persistent();
+            let persistent = self
+                .github_client
+                .export_to_persistent(self.get_github_token().as_deref());
             let _ = persistent.save(root);
         }
     }
@@ -208,10 +221,11 @@ impl Backend {
             return;
         };
 
-        // Find owners not already in cache
+        // Find owners not already in cache, leaving out skipped ones
+        let skip = settings.skip_owners();
         let uncached: Vec<_> = owners
             .iter()
-            .filter(|(_, _, owner, _)| !github_client.is_cached(owner))
+            .filter(|(_, _, owner, _)| !skip.matches(owner) && !github_client.is_cached(owner))
             .map(|(_, _, owner, _)| owner.clone())
             .collect();
 
@@ -220,16 +234,10 @@ impl Backend {
         }
 
         // Validate uncached owners and fetch metadata
-        let mut any_validated = false;
-        for owner in uncached {
-            if github_client
-                .validate_owner_with_info(&owner, &token)
-                .await
-                .is_some()
-            {
-                any_validated = true;
-            }
-        }
+        let results = github_client
+            .validate_owners(uncached, &token, settings.validation_concurrency())
+            .await;
+        let any_validated = results.values().any(Option::is_some);
 
         // If we validated anything, refresh diagnostics
         // (cache save happens on file save or shutdown - skipping here to keep task simple)
@@ -275,11 +283,12 @@ impl Backend {
         fs::read_to_string(path).ok()
     }
 
-    /// Collect all unique owners from CODEOWNERS file
+    /// Collect all unique owners from CODEOWNERS file, but skipped ones
     fn collect_owners_from_codeowners(&self) -> Vec<String> {
         let Some(content) = self.get_codeowners_content() else {
             return Vec::new();
         };
+        let skip = self.settings.read().unwrap().skip_owners();
 
         let lines = parse_codeowners_file_with_positions(&content);
         let mut owners: HashSet<String> = HashSet::new();
@@ -291,7 +300,7 @@ impl Backend {
             } = &line.content
             {
                 for owner in line_owners {
-                    if owner.starts_with('@') {
+                    if owner.starts_with('@') && !skip.matches(owner) {
                         owners.insert(owner.clone());
                     }
                 }
@@ -316,7 +325,8 @@ impl Backend {
         let parsed = codeowners.as_ref()?;
 
         let path_str = relative_path.to_str()?;
-        let result = check_file_ownership_parsed(parsed, path_str)?;
+        let result =
+            check_file_ownership_parsed_with_options(parsed, path_str, self.pattern_options())?;
 
         if result.owners.is_empty() {
             Some(None) // Rule matches but no owners
@@ -329,22 +339,37 @@ impl Backend {
         self.get_ownership_status(uri).flatten()
     }
 
+    /// Cached GitHub info for `owner`, or `None` if `skip_owners` leaves it
+    /// out of validation
+    fn owner_info(&self, owner: &str) -> Option<github::OwnerInfo> {
+        if self.settings.read().unwrap().skip_owners().matches(owner) {
+            return None;
+        }
+        self.github_client.get_owner_info(owner)
+    }
+
     /// Compute diagnostics for the CODEOWNERS file
     async fn compute_diagnostics(&self, content: &str) -> Vec<Diagnostic> {
         // Check if GitHub validation is enabled and get diagnostic config
-        let (validate_owners, token, diag_config) = {
+        let (validate_owners, token, diag_config, skip) = {
             let settings = self.settings.read().unwrap();
             let enabled = settings.validate_owners;
             let token = self.get_github_token();
             let config = settings.diagnostic_config();
-            (enabled && token.is_some(), token, config)
+            (
+                enabled && token.is_some(),
+                token,
+                config,
+                settings.skip_owners(),
+            )
         };
 
         // Phase 1: Synchronous diagnostics (holds file_cache lock)
-        let (mut diagnostics, owners_to_validate) = {
+        let (mut diagnostics, mut owners_to_validate) = {
             let file_cache = self.file_cache.read().unwrap();
             compute_diagnostics_sync(content, file_cache.as_ref(), &diag_config)
         };
+        owners_to_validate.retain(|(_, _, owner, _)| !skip.matches(owner));
 
         // Phase 2: Async GitHub validation (no locks held)
         if validate_owners {
@@ -360,6 +385,7 @@ impl Backend {
             }
         }
 
+        diagnostics::remove_suppressed(content, &mut diagnostics);
         diagnostics
     }
 
@@ -849,6 +875,7 @@ impl Backend {
 
         // Find the matching rule by checking which pattern in our parsed lines
         // would match this file. We iterate in reverse since last match wins.
+        let options = self.pattern_options();
         let mut matching_idx = None;
         for (idx, line) in lines.iter().enumerate().rev() {
             if let CodeownersLine::Rule {
@@ -856,7 +883,7 @@ impl Backend {
                 ..
             } = line
             {
-                if pattern_matches(rule_pattern, relative_path) {
+                if CompiledPattern::with_options(rule_pattern, options).matches(relative_path) {
                     matching_idx = Some(idx);
                     break;
                 }
@@ -889,7 +916,9 @@ impl Backend {
         let path = codeowners_path.as_ref()?;
         let content = fs::read_to_string(path).ok()?;
 
-        check_file_ownership(&content, file_path).map(|r| (r.line_number, r.pattern))
+        let lines = parse_codeowners_file_with_positions(&content);
+        check_file_ownership_parsed_with_options(&lines, file_path, self.pattern_options())
+            .map(|r| (r.line_number, r.pattern))
     }
 }
 
@@ -924,9 +953,13 @@ impl LanguageServer for Backend {
         self.load_persistent_cache();
 
         // Check if we should run background validation
-        let (should_validate, token) = {
+        let (should_validate, token, concurrency) = {
             let settings = self.settings.read().unwrap();
-            (settings.validate_owners, self.get_github_token())
+            (
+                settings.validate_owners,
+                self.get_github_token(),
+                settings.validation_concurrency(),
+            )
         };
 
         // Collect owners for validation
@@ -956,23 +989,18 @@ impl LanguageServer for Backend {
                     )
                     .await;
 
-                // Validate in parallel (5 concurrent)
-                use futures::stream::{self, StreamExt};
-                let results: Vec<_> = stream::iter(owners_to_validate)
-                    .map(|owner| {
-                        let client = github_client.clone();
-                        let token = token.clone();
-                        async move {
-                            let result = client.validate_owner(&owner, &token).await;
-                            (owner, result)
-                        }
-                    })
-                    .buffer_unordered(5)
-                    .collect()
+                let results = github_client
+                    .validate_owners(owners_to_validate, &token, concurrency)
                     .await;
 
-                let valid_count = results.iter().filter(|(_, r)| *r == Some(true)).count();
-                let invalid_count = results.iter().filter(|(_, r)| *r == Some(false)).count();
+                let valid_count = results
+                    .values()
+                    .filter(|r| r.as_ref().is_some_and(github::OwnerInfo::is_valid))
+                    .count();
+                let invalid_count = results
+                    .values()
+                    .filter(|r| r.as_ref().is_some_and(github::OwnerInfo::is_invalid))
+                    .count();
 
                 client_clone
                     .log_message(
@@ -986,7 +1014,7 @@ impl LanguageServer for Backend {
 
                 // Save to persistent cache
                 if let Some(root) = workspace_root {
-                    let persistent = github_client.export_to_persistent();
+                    let persistent = github_client.export_to_persistent(Some(&token));
                     let _ = persistent.save(&root);
                 }
             });
@@ -1030,7 +1058,7 @@ impl LanguageServer for Backend {
                     ],
                     work_done_progress_options: Default::default(),
                 }),
-                definition_provider: Some(OneOf::Left(false)),
+                definition_provider: Some(OneOf::Left(true)),
                 completion_provider: Some(CompletionOptions {
                     trigger_characters: Some(vec!["/".to_string(), "@".to_string()]),
                     ..Default::default()
@@ -1319,7 +1347,7 @@ impl LanguageServer for Backend {
 
                     // Find if we're hovering over an @owner
                     if let Some(owner) = find_owner_at_position(line, char_idx) {
-                        let info = self.github_client.get_owner_info(&owner);
+                        let info = self.owner_info(&owner);
                         let formatted = format_owner_hover(&owner, info.as_ref());
                         return Ok(Some(Hover {
                             contents: HoverContents::Markup(MarkupContent {
@@ -1433,7 +1461,7 @@ impl LanguageServer for Backend {
 
                 // Look up owner info from cache for rich hover
                 let format_with_cache = |owner: &str| -> String {
-                    let info = self.github_client.get_owner_info(owner);
+                    let info = self.owner_info(owner);
                     format_owner_with_info(owner, info.as_ref())
                 };
 
@@ -1472,8 +1500,34 @@ impl LanguageServer for Backend {
     ) -> Result<Option<GotoDefinitionResponse>> {
         let uri = &params.text_document_position_params.text_document.uri;
 
+        // In CODEOWNERS, an @owner's definition is its GitHub page
         if self.is_codeowners_file(uri) {
-            return Ok(None);
+            let position = params.text_document_position_params.position;
+            let Some(owner) = self.get_codeowners_content().and_then(|content| {
+                let line = content.lines().nth(position.line as usize)?;
+                find_owner_at_position(line, position.character as usize)
+            }) else {
+                return Ok(None);
+            };
+
+            // Validate first so the link comes from GitHub's own metadata
+            let mut info = self.owner_info(&owner);
+            let validate = {
+                let settings = self.settings.read().unwrap();
+                settings.validate_owners && !settings.skip_owners().matches(&owner)
+            };
+            if info.is_none() && validate {
+                if let Some(token) = self.get_github_token() {
+                    info = self
+                        .github_client
+                        .validate_owner_with_info(&owner, &token)
+                        .await;
+                }
+            }
+            return Ok(
+                handlers::navigation::owner_definition(&owner, info.as_ref())
+                    .map(GotoDefinitionResponse::Scalar),
+            );
         }
 
         let root = self.workspace_root.read().unwrap();
@@ -2395,7 +2449,8 @@ fn find_changed_lines(old: &str, new: &str) -> Vec<usize> {
     changed
 }
 
-/// Check patterns on specific lines and return pattern-no-match diagnostics
+/// Check patterns on specific lines and return diagnostics for those that
+/// match no files
 fn check_patterns_for_lines(
     content: &str,
     line_numbers: &[usize],
@@ -2405,6 +2460,9 @@ fn check_patterns_for_lines(
     use parser::CodeownersLine;
 
     let mut diagnostics = Vec::new();
+    if !unmatched_pattern_checks_enabled(config) {
+        return diagnostics;
+    }
     let parsed = parse_codeowners_file_with_positions(content);
 
     for &line_num in line_numbers {
@@ -2414,32 +2472,25 @@ fn check_patterns_for_lines(
             if let CodeownersLine::Rule { ref pattern, .. } = parsed_line.content {
                 // Check if pattern matches any files
                 if !file_cache.has_matches(pattern) {
-                    if let Some(severity) =
-                        config.get("pattern-no-match", DiagnosticSeverity::WARNING)
-                    {
-                        diagnostics.push(Diagnostic {
-                            range: Range {
-                                start: Position {
-                                    line: line_num_u32,
-                                    character: parsed_line.pattern_start,
-                                },
-                                end: Position {
-                                    line: line_num_u32,
-                                    character: parsed_line.pattern_end,
-                                },
-                            },
-                            severity: Some(severity),
-                            code: Some(NumberOrString::String("pattern-no-match".to_string())),
-                            source: Some("codeowners".to_string()),
-                            message: format!("Pattern '{}' doesn't match any files", pattern),
-                            ..Default::default()
-                        });
-                    }
+                    let range = Range {
+                        start: Position {
+                            line: line_num_u32,
+                            character: parsed_line.pattern_start,
+                        },
+                        end: Position {
+                            line: line_num_u32,
+                            character: parsed_line.pattern_end,
+                        },
+                    };
+                    diagnostics.extend(unmatched_pattern_diagnostic(
+                        pattern, range, file_cache, config,
+                    ));
                 }
             }
         }
     }
 
+    diagnostics::remove_suppressed(content, &mut diagnostics);
     diagnostics
 }
 
@@ -2501,6 +2552,29 @@ fn format_owner_hover(owner: &str, info: Option<&github::OwnerInfo>) -> String {
 
             lines.join("\n")
         }
+        Some(github::OwnerInfo::Org(org)) => {
+            let mut lines = vec![format!("## [`{}`]({})", owner, org.html_url)];
+
+            if let Some(ref name) = org.name {
+                lines.push(format!("**{}**", name));
+            }
+            lines.push("🏢 Organization (every member is an owner)".to_string());
+
+            if let Some(ref desc) = org.description {
+                if !desc.is_empty() {
+                    lines.push(String::new());
+                    lines.push(format!("*{}*", desc));
+                }
+            }
+
+            lines.join("\n")
+        }
+        Some(github::OwnerInfo::Suspended(user)) => {
+            format!(
+                "## [`{}`]({})\n\n⚠️ **Account suspended** (can't review pull requests)",
+                owner, user.html_url
+            )
+        }
         Some(github::OwnerInfo::Invalid) => {
             format!("## {}\n\n⚠️ **Owner not found on GitHub**", owner)
         }
@@ -2616,6 +2690,16 @@ fn format_owner_with_info(owner: &str, info: Option<&github::OwnerInfo>) -> Stri
 
             parts.join(" — ")
         }
+        Some(github::OwnerInfo::Org(org)) => {
+            let mut parts = vec![format!("[`{}`]({})", owner, org.html_url)];
+            if let Some(ref name) = org.name {
+                if name != &org.login {
+                    parts.push(format!("*{}*", name));
+                }
+            }
+            parts.push("(organization)".to_string());
+            parts.join(" — ")
+        }
         _ => format_owner_link(owner),
     }
 }
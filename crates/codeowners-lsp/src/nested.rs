@@ -0,0 +1,416 @@
+//! Per-package CODEOWNERS files merged into one ruleset (`--merge-nested`).
+//!
+//! GitHub only reads the CODEOWNERS at the repository root, but some
+//! monorepos keep one per package as well. Merging rewrites every nested
+//! rule's pattern relative to the root and appends the files after the
+//! root one, shallowest first. Under last-match-wins a package's own rules
+//! then override the root's, and a nested package overrides its parent.
+
+use std::fs;
+use std::path::Path;
+
+use crate::ownership::{Semantics, CODEOWNERS_LOCATIONS};
+use crate::parser::{parse_codeowners_file_with_positions, parse_section_header, CodeownersLine};
+
+/// A CODEOWNERS file below the repository root
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NestedCodeowners {
+    /// Directory the file's rules apply to, relative to the root (`packages/web`)
+    pub dir: String,
+    /// The file itself, relative to the root (`packages/web/.github/CODEOWNERS`)
+    pub path: String,
+}
+
+/// Pick the CODEOWNERS files below the root out of a repo-relative file
+/// listing, one per directory (by GitHub's location precedence), in merge
+/// order: shallowest first, then by path
+pub fn find_nested_codeowners(files: &[String]) -> Vec<NestedCodeowners> {
+    let mut found: Vec<(usize, NestedCodeowners)> = Vec::new();
+    for path in files {
+        // `a/.github/CODEOWNERS` also ends in `CODEOWNERS`: the longest
+        // location wins, as with `get_repo_root`
+        let Some((rank, location)) = CODEOWNERS_LOCATIONS
+            .iter()
+            .enumerate()
+            .filter(|(_, loc)| path.ends_with(*loc))
+            .max_by_key(|(_, loc)| loc.len())
+        else {
+            continue;
+        };
+        let Some(dir) = path[..path.len() - location.len()].strip_suffix('/') else {
+            continue;
+        };
+        match found.iter_mut().find(|(_, n)| n.dir == dir) {
+            Some(existing) if rank < existing.0 => existing.1.path = path.clone(),
+            Some(_) => {}
+            None => found.push((
+                rank,
+                NestedCodeowners {
+                    dir: dir.to_string(),
+                    path: path.clone(),
+                },
+            )),
+        }
+    }
+    let mut nested: Vec<NestedCodeowners> = found.into_iter().map(|(_, n)| n).collect();
+    nested.sort_by(|a, b| {
+        (a.dir.matches('/').count(), &a.dir).cmp(&(b.dir.matches('/').count(), &b.dir))
+    });
+    nested
+}
+
+/// Rewrite a pattern from a CODEOWNERS in `dir` so it means the same thing
+/// from the repository root
+pub fn scope_pattern(pattern: &str, dir: &str) -> String {
+    // Escape what would otherwise end the pattern or start a comment
+    let mut escaped = String::with_capacity(dir.len());
+    for c in dir.chars() {
+        if c.is_whitespace() || c == '#' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    let dir = escaped.as_str();
+    if let Some(anchored) = pattern.strip_prefix('/') {
+        return format!("/{}/{}", dir, anchored);
+    }
+    if pattern == "*" || pattern == "**" {
+        return format!("/{}/", dir);
+    }
+    // A slash anywhere but the end anchors the pattern to the file's directory
+    if pattern.trim_end_matches('/').contains('/') {
+        format!("/{}/{}", dir, pattern)
+    } else {
+        format!("/{}/**/{}", dir, pattern)
+    }
+}
+
+/// Rewrite every rule in `content` with [`scope_pattern`], leaving owners,
+/// comments and section headers as written
+pub fn scope_codeowners(content: &str, dir: &str) -> String {
+    let mut scoped = String::with_capacity(content.len());
+    for (line, _) in scope_lines(content, dir) {
+        scoped.push_str(&line);
+        scoped.push('\n');
+    }
+    scoped
+}
+
+/// Each line of `content` with its pattern scoped to `dir`, and how many
+/// bytes scoping added before the owners
+fn scope_lines(content: &str, dir: &str) -> Vec<(String, usize)> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let parsed = parse_codeowners_file_with_positions(content);
+    content
+        .lines()
+        .zip(parsed)
+        .map(|(line, parsed)| {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            match parsed.content {
+                CodeownersLine::Rule { .. } if parse_section_header(line).is_none() => {
+                    let (start, end) = (parsed.pattern_start as usize, parsed.pattern_end as usize);
+                    let pattern = scope_pattern(&line[start..end], dir);
+                    let added = pattern.len() - (end - start);
+                    (
+                        format!("{}{}{}", &line[..start], pattern, &line[end..]),
+                        added,
+                    )
+                }
+                _ => (line.to_string(), 0),
+            }
+        })
+        .collect()
+}
+
+/// Where a line of merged content came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LineOrigin {
+    /// Index of the nested file in [`MergedCodeowners::nested`], or `None`
+    /// for the root CODEOWNERS
+    pub file: Option<usize>,
+    /// 0-indexed line in that file
+    pub line: u32,
+    /// Bytes scoping added to the line's pattern, so columns after it
+    /// (owners) are this much further right than in the file
+    pub shift: usize,
+}
+
+/// The root CODEOWNERS with the nested ones merged in, and where each of
+/// its lines came from
+#[derive(Debug, Clone, Default)]
+pub struct MergedCodeowners {
+    /// The merged ruleset
+    pub content: String,
+    /// The nested files merged in, with their content as read
+    pub nested: Vec<(NestedCodeowners, String)>,
+    /// Origin of each line of `content`; `None` for lines the merge added
+    origins: Vec<Option<LineOrigin>>,
+}
+
+impl MergedCodeowners {
+    /// Where the 0-indexed `line` of the merged content came from, or
+    /// `None` for the comments and section headers the merge added
+    pub fn origin(&self, line: u32) -> Option<LineOrigin> {
+        self.origins.get(line as usize).copied().flatten()
+    }
+
+    /// Repo-relative path of the nested file `origin` came from, or `None`
+    /// for the root CODEOWNERS
+    pub fn source(&self, origin: LineOrigin) -> Option<&str> {
+        origin.file.map(|i| self.nested[i].0.path.as_str())
+    }
+}
+
+/// The root CODEOWNERS with every nested one in `files` appended, scoped to
+/// its directory. Under GitLab semantics each nested file starts a section
+/// of its own (named after the file), so its first rules don't land in
+/// whichever section the file before it ended in.
+pub fn merge_nested_codeowners(
+    repo_root: &Path,
+    root_content: &str,
+    files: &[String],
+    semantics: Semantics,
+) -> Result<MergedCodeowners, String> {
+    let mut merged = MergedCodeowners {
+        content: root_content.to_string(),
+        ..Default::default()
+    };
+    if !merged.content.is_empty() && !merged.content.ends_with('\n') {
+        merged.content.push('\n');
+    }
+    merged.origins = (0..root_content.lines().count() as u32)
+        .map(|line| {
+            Some(LineOrigin {
+                file: None,
+                line,
+                shift: 0,
+            })
+        })
+        .collect();
+
+    for (index, file) in find_nested_codeowners(files).into_iter().enumerate() {
+        let content = fs::read_to_string(repo_root.join(&file.path))
+            .map_err(|e| format!("Failed to read {}: {}", file.path, e))?;
+        merged
+            .content
+            .push_str(&format!("\n# Merged from {}\n", file.path));
+        merged.origins.extend([None, None]);
+        if semantics == Semantics::GitLab {
+            merged.content.push_str(&format!("[{}]\n", file.path));
+            merged.origins.push(None);
+        }
+        for (line, (scoped, shift)) in scope_lines(&content, &file.dir).into_iter().enumerate() {
+            merged.content.push_str(&scoped);
+            merged.content.push('\n');
+            merged.origins.push(Some(LineOrigin {
+                file: Some(index),
+                line: line as u32,
+                shift,
+            }));
+        }
+        merged.nested.push((file, content));
+    }
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ownership::{check_file_ownership, check_file_ownership_sections};
+    use crate::pattern::pattern_matches;
+
+    fn files(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_find_nested_codeowners() {
+        let nested = find_nested_codeowners(&files(&[
+            ".github/CODEOWNERS",
+            "packages/web/src/main.ts",
+            "packages/web/CODEOWNERS",
+            "packages/web/.github/CODEOWNERS",
+            "packages/web/admin/docs/CODEOWNERS",
+            "packages/api/CODEOWNERS",
+            "tools/NOT_CODEOWNERS",
+        ]));
+        assert_eq!(
+            nested,
+            [
+                NestedCodeowners {
+                    dir: "packages/api".to_string(),
+                    path: "packages/api/CODEOWNERS".to_string(),
+                },
+                NestedCodeowners {
+                    dir: "packages/web".to_string(),
+                    path: "packages/web/.github/CODEOWNERS".to_string(),
+                },
+                NestedCodeowners {
+                    dir: "packages/web/admin".to_string(),
+                    path: "packages/web/admin/docs/CODEOWNERS".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scoped_patterns_match_only_inside_the_package() {
+        for (pattern, inside, outside) in [
+            ("*", "pkg/a/x.rs", "other/x.rs"),
+            ("*.rs", "pkg/a/deep/x.rs", "x.rs"),
+            ("/build/", "pkg/a/build/out.js", "pkg/a/src/build/out.js"),
+            ("docs/", "pkg/a/src/docs/x.md", "docs/x.md"),
+            ("src/main.rs", "pkg/a/src/main.rs", "pkg/a/lib/src/main.rs"),
+            ("**/logs", "pkg/a/x/logs/today", "logs/today"),
+            ("Makefile", "pkg/a/tools/Makefile", "Makefile"),
+        ] {
+            let scoped = scope_pattern(pattern, "pkg/a");
+            assert!(pattern_matches(&scoped, inside), "{scoped} vs {inside}");
+            assert!(!pattern_matches(&scoped, outside), "{scoped} vs {outside}");
+        }
+    }
+
+    #[test]
+    fn test_scoped_patterns_escape_the_directory() {
+        assert_eq!(scope_pattern("*.rs", "my pkg"), "/my\\ pkg/**/*.rs");
+        assert_eq!(scope_pattern("/docs/", "c#/lib"), "/c\\#/lib/docs/");
+        for (pattern, inside) in [
+            ("*", "my pkg/x.rs"),
+            ("*.rs", "my pkg/deep/x.rs"),
+            ("/docs/", "my pkg/docs/x.md"),
+            ("src/main.rs", "my pkg/src/main.rs"),
+        ] {
+            let scoped = scope_pattern(pattern, "my pkg");
+            assert!(pattern_matches(&scoped, inside), "{scoped} vs {inside}");
+            assert!(!pattern_matches(&scoped, "pkg/x.rs"), "{scoped}");
+        }
+    }
+
+    #[test]
+    fn test_merge_package_in_a_spaced_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("my pkg")).unwrap();
+        fs::write(root.join("my pkg/CODEOWNERS"), "*.rs @pkg\n").unwrap();
+
+        let merged = merge_nested_codeowners(
+            root,
+            "* @root\n",
+            &files(&["CODEOWNERS", "my pkg/CODEOWNERS"]),
+            Semantics::GitHub,
+        )
+        .unwrap();
+        let owners = |path: &str| check_file_ownership(&merged.content, path).unwrap().owners;
+        assert_eq!(owners("my pkg/a.rs"), ["@pkg"]);
+        assert_eq!(owners("my pkg/README.md"), ["@root"]);
+    }
+
+    #[test]
+    fn test_scope_codeowners_keeps_everything_but_patterns() {
+        let content = "\u{feff}# Web\r\n*.ts @web # typescript\r\n\n[Docs] @docs\n/docs/ @docs\n";
+        assert_eq!(
+            scope_codeowners(content, "pkg"),
+            "# Web\n/pkg/**/*.ts @web # typescript\n\n[Docs] @docs\n/pkg/docs/ @docs\n"
+        );
+    }
+
+    #[test]
+    fn test_merge_two_packages_with_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("packages/web")).unwrap();
+        fs::create_dir_all(root.join("packages/api/.github")).unwrap();
+        fs::write(
+            root.join("packages/web/CODEOWNERS"),
+            "* @web\n/legacy/ @legacy\n",
+        )
+        .unwrap();
+        fs::write(root.join("packages/api/.github/CODEOWNERS"), "*.sql @dba\n").unwrap();
+
+        let merged = merge_nested_codeowners(
+            root,
+            "* @core\n/packages/web/ @frontend\n*.sql @data",
+            &files(&[
+                "CODEOWNERS",
+                "packages/web/CODEOWNERS",
+                "packages/api/.github/CODEOWNERS",
+            ]),
+            Semantics::GitHub,
+        )
+        .unwrap();
+        assert_eq!(merged.nested.len(), 2);
+
+        let owners = |path: &str| check_file_ownership(&merged.content, path).unwrap().owners;
+        assert_eq!(owners("README.md"), ["@core"]);
+        // The package file overrides the root's rule for its directory
+        assert_eq!(owners("packages/web/index.ts"), ["@web"]);
+        assert_eq!(owners("packages/web/legacy/old.js"), ["@legacy"]);
+        assert_eq!(owners("packages/api/schema.sql"), ["@dba"]);
+        // ...and only there
+        assert_eq!(owners("db/schema.sql"), ["@data"]);
+        assert_eq!(owners("packages/api/main.go"), ["@core"]);
+    }
+
+    #[test]
+    fn test_merge_reports_unreadable_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = merge_nested_codeowners(
+            dir.path(),
+            "",
+            &files(&["pkg/CODEOWNERS"]),
+            Semantics::GitHub,
+        )
+        .unwrap_err();
+        assert!(err.contains("pkg/CODEOWNERS"), "{err}");
+    }
+
+    #[test]
+    fn test_merged_lines_map_back_to_their_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("pkg")).unwrap();
+        fs::write(dir.path().join("pkg/CODEOWNERS"), "# Pkg\n*.ts @web\n").unwrap();
+
+        let merged = merge_nested_codeowners(
+            dir.path(),
+            "* @core\n# end",
+            &files(&["pkg/CODEOWNERS"]),
+            Semantics::GitHub,
+        )
+        .unwrap();
+        let lines: Vec<&str> = merged.content.lines().collect();
+        let rule = lines.iter().position(|l| l.ends_with("@web")).unwrap() as u32;
+        let origin = merged.origin(rule).unwrap();
+        assert_eq!((origin.file, origin.line), (Some(0), 1));
+        assert_eq!(merged.source(origin), Some("pkg/CODEOWNERS"));
+        // `*.ts` became `/pkg/**/*.ts`, pushing the owner right
+        assert_eq!(origin.shift, "/pkg/**/".len());
+
+        let root = merged.origin(0).unwrap();
+        assert_eq!((root.file, root.line), (None, 0));
+        assert_eq!(merged.source(root), None);
+        // The `# Merged from` comment isn't from any file
+        assert_eq!(merged.origin(rule - 2), None);
+    }
+
+    #[test]
+    fn test_gitlab_merge_starts_a_section_per_file() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("pkg")).unwrap();
+        fs::write(dir.path().join("pkg/CODEOWNERS"), "*.ts @web\n").unwrap();
+        let root = "* @core\n[Docs] @docs\n*.md\n";
+
+        let merged = merge_nested_codeowners(
+            dir.path(),
+            root,
+            &files(&["pkg/CODEOWNERS"]),
+            Semantics::GitLab,
+        )
+        .unwrap();
+        let lines = parse_codeowners_file_with_positions(&merged.content);
+        let sections = check_file_ownership_sections(&lines, "pkg/a.ts");
+        let names: Vec<Option<&str>> = sections.iter().map(|s| s.section.as_deref()).collect();
+        // The package rule isn't in the root's [Docs] section
+        assert_eq!(names, [None, Some("pkg/CODEOWNERS")]);
+        assert_eq!(sections[1].result.owners, ["@web"]);
+    }
+}
@@ -0,0 +1,215 @@
+//! Reverse index from each owner to the files they own.
+//!
+//! Answering "which files does @team own" by resolving every file per query
+//! is O(files × rules) each time. [`OwnerIndex`] resolves every file in a
+//! [`FileCache`] once and keeps the result grouped by owner, trading memory
+//! for constant-time lookups.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use rayon::prelude::*;
+
+use crate::file_cache::FileCache;
+use crate::ownership::{resolve_owners_parsed_with_options, CodeownersFile, Semantics};
+use crate::parser::CodeownersLine;
+
+/// The owners of each file in `files` (empty if unowned), in order
+pub fn winning_owners<'a>(
+    codeowners: &'a CodeownersFile,
+    files: &[String],
+    semantics: Semantics,
+) -> Vec<Cow<'a, [String]>> {
+    if semantics == Semantics::GitLab {
+        return files
+            .par_iter()
+            .map(|file| {
+                Cow::Owned(resolve_owners_parsed_with_options(
+                    codeowners.lines(),
+                    file,
+                    semantics,
+                    codeowners.pattern_options(),
+                ))
+            })
+            .collect();
+    }
+
+    // Last match wins
+    files
+        .par_iter()
+        .map(
+            |file| match codeowners.rule_for(file).map(|line| &line.content) {
+                Some(CodeownersLine::Rule { owners, .. }) => Cow::Borrowed(owners.as_slice()),
+                _ => Cow::Borrowed(&[][..]),
+            },
+        )
+        .collect()
+}
+
+/// Files grouped by the owners of their winning rule
+#[derive(Debug, Default)]
+pub struct OwnerIndex {
+    /// Owner (as written in CODEOWNERS) -> files, in file cache order
+    files_by_owner: HashMap<String, Vec<String>>,
+    /// Files with no owners, including those matched by an ownerless rule
+    unowned: Vec<String>,
+    total_files: usize,
+}
+
+impl OwnerIndex {
+    /// Resolve every file in `file_cache` once and index it by owner
+    pub fn new(codeowners: &CodeownersFile, file_cache: &FileCache, semantics: Semantics) -> Self {
+        let files = file_cache.all_files();
+        let winners = winning_owners(codeowners, files, semantics);
+
+        let mut index = OwnerIndex {
+            total_files: files.len(),
+            ..Default::default()
+        };
+        for (file, owners) in files.iter().zip(&winners) {
+            if owners.is_empty() {
+                index.unowned.push(file.clone());
+            }
+            for owner in owners.iter() {
+                let owned = index.files_by_owner.entry(owner.clone()).or_default();
+                // A rule can list the same owner twice; count the file once
+                if owned.last() != Some(file) {
+                    owned.push(file.clone());
+                }
+            }
+        }
+        index
+    }
+
+    /// Files whose winning rule lists `owner` (exactly as written)
+    pub fn files_for_owner(&self, owner: &str) -> &[String] {
+        self.files_by_owner
+            .get(owner)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Files with no owners
+    pub fn unowned_files(&self) -> &[String] {
+        &self.unowned
+    }
+
+    /// Every owner with at least one file, in no particular order
+    pub fn owners(&self) -> impl Iterator<Item = (&str, &[String])> {
+        self.files_by_owner
+            .iter()
+            .map(|(owner, files)| (owner.as_str(), files.as_slice()))
+    }
+
+    /// Number of files indexed, owned or not
+    pub fn total_files(&self) -> usize {
+        self.total_files
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ownership::{check_file_ownership_parsed, resolve_owners_parsed};
+
+    const CONTENT: &str = "* @core\n\
+        *.rs @rust @org/team\n\
+        /docs/ @docs @docs\n\
+        /vendor/\n\
+        [Security] @security\n\
+        /src/auth/\n";
+
+    fn cache() -> FileCache {
+        FileCache::from_files(
+            [
+                "README.md",
+                "src/main.rs",
+                "src/auth/login.rs",
+                "src/auth/keys.json",
+                "docs/index.md",
+                "vendor/lib.c",
+                "vendor/lib.rs",
+            ]
+            .iter()
+            .map(|f| f.to_string())
+            .collect(),
+        )
+    }
+
+    /// Resolve each file on its own, the way per-query lookups would
+    fn brute_force(
+        codeowners: &CodeownersFile,
+        files: &[String],
+        owner: &str,
+        semantics: Semantics,
+    ) -> Vec<String> {
+        files
+            .iter()
+            .filter(|file| {
+                let owners = match semantics {
+                    Semantics::GitHub => check_file_ownership_parsed(codeowners.lines(), file)
+                        .map(|r| r.owners)
+                        .unwrap_or_default(),
+                    Semantics::GitLab => resolve_owners_parsed(codeowners.lines(), file, semantics),
+                };
+                owners.iter().any(|o| o == owner)
+            })
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn test_index_matches_brute_force() {
+        let codeowners = CodeownersFile::parse(CONTENT);
+        let cache = cache();
+
+        for semantics in [Semantics::GitHub, Semantics::GitLab] {
+            let index = OwnerIndex::new(&codeowners, &cache, semantics);
+            assert_eq!(index.total_files(), cache.all_files().len());
+
+            for owner in ["@core", "@rust", "@org/team", "@docs", "@security"] {
+                assert_eq!(
+                    index.files_for_owner(owner),
+                    brute_force(&codeowners, cache.all_files(), owner, semantics),
+                    "{} under {:?}",
+                    owner,
+                    semantics
+                );
+            }
+
+            let indexed: usize = index.owners().map(|(_, files)| files.len()).sum();
+            let expected: usize = index
+                .owners()
+                .map(|(owner, _)| {
+                    brute_force(&codeowners, cache.all_files(), owner, semantics).len()
+                })
+                .sum();
+            assert_eq!(indexed, expected);
+        }
+    }
+
+    #[test]
+    fn test_index_unowned_bucket() {
+        let codeowners = CodeownersFile::parse(CONTENT);
+        let index = OwnerIndex::new(&codeowners, &cache(), Semantics::GitHub);
+
+        // Matched last by the ownerless `/src/auth/` and `/vendor/` rules
+        assert_eq!(
+            index.unowned_files(),
+            [
+                "src/auth/login.rs",
+                "src/auth/keys.json",
+                "vendor/lib.c",
+                "vendor/lib.rs"
+            ]
+        );
+        assert!(index.files_for_owner("@nobody").is_empty());
+    }
+
+    #[test]
+    fn test_index_duplicate_owner_counts_file_once() {
+        let codeowners = CodeownersFile::parse(CONTENT);
+        let index = OwnerIndex::new(&codeowners, &cache(), Semantics::GitHub);
+        assert_eq!(index.files_for_owner("@docs"), ["docs/index.md"]);
+    }
+}
@@ -5,14 +5,24 @@
 //! consults the file system via [`FileCache`], and re-exports the parser's
 //! ownership helpers for call-site ergonomics.
 
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 
-use codeowners_parser::parser::{parse_codeowners_file_with_positions, CodeownersLine};
+use codeowners_parser::parser::{
+    find_inline_comment_start, parse_codeowners_file_with_positions, CodeownersLine,
+};
+use codeowners_parser::pattern::anchored_form;
+use codeowners_parser::validation::canonical_owner;
 
 pub use codeowners_parser::ownership::{
-    check_file_ownership, check_file_ownership_parsed, find_codeowners, get_repo_root,
-    OwnershipResult,
+    check_file_ownership, check_file_ownership_parsed, check_file_ownership_parsed_with_options,
+    check_file_ownership_sections, check_file_ownership_sections_with_options,
+    explain_file_ownership_parsed, find_all_codeowners, find_codeowners,
+    find_matching_rules_parsed, get_repo_root, resolve_owners_parsed,
+    resolve_owners_parsed_with_options, FileOwnership, OwnershipResult, SectionOwnership,
+    Semantics, CODEOWNERS_LOCATIONS,
 };
+pub use codeowners_parser::CodeownersFile;
 
 use crate::file_cache::FileCache;
 
@@ -23,8 +33,9 @@ pub struct FixResult {
 }
 
 /// Apply safe fixes to CODEOWNERS content.
-/// Safe fixes: duplicate owners, exact duplicate patterns (shadowed rules),
-/// and patterns matching no files (when file_cache is provided).
+/// Safe fixes: owner casing, duplicate owners, exact duplicate patterns
+/// (shadowed rules), and patterns matching no files (when file_cache is
+/// provided).
 pub fn apply_safe_fixes(content: &str, file_cache: Option<&FileCache>) -> FixResult {
     let lines = parse_codeowners_file_with_positions(content);
     let original_lines: Vec<&str> = content.lines().collect();
@@ -38,26 +49,38 @@ pub fn apply_safe_fixes(content: &str, file_cache: Option<&FileCache>) -> FixRes
     for parsed_line in &lines {
         if let CodeownersLine::Rule { pattern, owners } = &parsed_line.content {
             let line_num = parsed_line.line_number as usize;
-            let normalized_pattern = pattern.trim_start_matches('/');
+            let normalized_pattern = anchored_form(pattern);
 
+            // Lowercase handles, then drop duplicates (case-insensitively,
+            // keeping the first occurrence)
+            let canonical: Vec<Cow<str>> = owners.iter().map(|o| canonical_owner(o)).collect();
+            let lowercased = canonical
+                .iter()
+                .zip(owners)
+                .any(|(c, o)| c.as_ref() != o.as_str());
             let mut seen_owners: HashSet<&str> = HashSet::new();
-            let deduped: Vec<&str> = owners
+            let deduped: Vec<&str> = canonical
                 .iter()
-                .map(|s| s.as_str())
+                .map(|o| o.as_ref())
                 .filter(|o| seen_owners.insert(*o))
                 .collect();
 
-            if deduped.len() < owners.len() {
-                let new_line = if deduped.is_empty() {
-                    pattern.clone()
-                } else {
-                    format!("{} {}", pattern, deduped.join(" "))
-                };
+            if lowercased || deduped.len() < owners.len() {
+                let new_line = replace_owners(
+                    original_lines[line_num],
+                    parsed_line.pattern_end as usize,
+                    &deduped,
+                );
                 line_replacements.insert(line_num, new_line);
-                fixes.push(format!("line {}: removed duplicate owners", line_num + 1));
+                if lowercased {
+                    fixes.push(format!("line {}: lowercased owners", line_num + 1));
+                }
+                if deduped.len() < owners.len() {
+                    fixes.push(format!("line {}: removed duplicate owners", line_num + 1));
+                }
             }
 
-            if let Some(&prev_line) = exact_patterns.get(normalized_pattern) {
+            if let Some(&prev_line) = exact_patterns.get(normalized_pattern.as_ref()) {
                 lines_to_delete.insert(prev_line);
                 fixes.push(format!(
                     "line {}: removed shadowed rule (duplicated on line {})",
@@ -65,7 +88,7 @@ pub fn apply_safe_fixes(content: &str, file_cache: Option<&FileCache>) -> FixRes
                     line_num + 1
                 ));
             }
-            exact_patterns.insert(normalized_pattern.to_string(), line_num);
+            exact_patterns.insert(normalized_pattern.into_owned(), line_num);
 
             if let Some(cache) = file_cache {
                 if !cache.has_matches(pattern) {
@@ -103,6 +126,25 @@ pub fn apply_safe_fixes(content: &str, file_cache: Option<&FileCache>) -> FixRes
     }
 }
 
+/// `line` with the owners after its pattern (ending at byte `pattern_end`)
+/// replaced by `owners`, keeping the whitespace around them and any inline
+/// comment as written
+pub fn replace_owners(line: &str, pattern_end: usize, owners: &[&str]) -> String {
+    let owners_end = find_inline_comment_start(line)
+        .and_then(|chars| line.char_indices().nth(chars).map(|(i, _)| i))
+        .unwrap_or(line.len());
+    let region = &line[pattern_end..owners_end];
+    let leading = region.len() - region.trim_start().len();
+    let trailing = region.len() - region.trim_end().len();
+
+    format!(
+        "{}{}{}",
+        &line[..pattern_end + leading],
+        owners.join(" "),
+        &line[owners_end - trailing..]
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -115,6 +157,52 @@ mod tests {
         assert_eq!(result.fixes.len(), 1);
     }
 
+    #[test]
+    fn test_apply_safe_fixes_keeps_bare_and_rooted_rules() {
+        // `build` matches at any depth, `/build` only at the root
+        let content = "build @a\n/build @b\n";
+        let result = apply_safe_fixes(content, None);
+        assert_eq!(result.content, content);
+        assert!(result.fixes.is_empty());
+
+        // Implicitly rooted paths are the same rule either way
+        let result = apply_safe_fixes("src/a.rs @a\n/src/a.rs @b\n", None);
+        assert_eq!(result.content, "/src/a.rs @b\n");
+    }
+
+    #[test]
+    fn test_apply_safe_fixes_lowercases_owners() {
+        let content = "*.rs @Owner @MyOrg/Rust-Team Jane@Example.COM\n*.md @docs\n";
+        let result = apply_safe_fixes(content, None);
+        assert_eq!(
+            result.content,
+            "*.rs @owner @myorg/rust-team Jane@example.com\n*.md @docs\n"
+        );
+        assert_eq!(result.fixes, vec!["line 1: lowercased owners"]);
+    }
+
+    #[test]
+    fn test_apply_safe_fixes_case_insensitive_duplicates_keep_order() {
+        let content = "*.rs @b @Org/Team @a @org/team @B\n";
+        let result = apply_safe_fixes(content, None);
+        assert_eq!(result.content, "*.rs @b @org/team @a\n");
+        assert_eq!(
+            result.fixes,
+            vec![
+                "line 1: lowercased owners",
+                "line 1: removed duplicate owners"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_safe_fixes_emails_differing_in_local_case_kept() {
+        let content = "*.rs Jane@example.com jane@example.com\n";
+        let result = apply_safe_fixes(content, None);
+        assert_eq!(result.content, content);
+        assert!(result.fixes.is_empty());
+    }
+
     #[test]
     fn test_apply_safe_fixes_shadowed_rules() {
         let content = "*.rs @first\n*.rs @second\n";
@@ -123,6 +211,16 @@ mod tests {
         assert_eq!(result.fixes.len(), 1);
     }
 
+    #[test]
+    fn test_apply_safe_fixes_keeps_comments_and_alignment() {
+        let content = "/src/       @Core @core   # platform team\n/docs/      @docs\n";
+        let result = apply_safe_fixes(content, None);
+        assert_eq!(
+            result.content,
+            "/src/       @core   # platform team\n/docs/      @docs\n"
+        );
+    }
+
     #[test]
     fn test_apply_safe_fixes_all_duplicate_owners_removed() {
         let content = "*.rs @owner @owner\n";
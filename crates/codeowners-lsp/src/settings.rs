@@ -4,7 +4,11 @@ use std::path::Path;
 
 use serde::Deserialize;
 
-use crate::diagnostics::DiagnosticConfig;
+use crate::blame::ConfidenceWeights;
+use crate::diagnostics::{codes, DiagnosticConfig};
+use crate::github::DEFAULT_CONCURRENCY;
+use crate::pattern::PatternOptions;
+use crate::skip_owners::SkipOwners;
 
 pub const CONFIG_FILE: &str = ".codeowners-lsp.toml";
 pub const CONFIG_FILE_LOCAL: &str = ".codeowners-lsp.local.toml";
@@ -18,6 +22,12 @@ pub struct SuggestSettings {
     /// Prepend / to paths (anchored patterns)
     #[serde(default)]
     pub anchored: bool,
+    /// Weight of the top contributor's share of commits in confidence (default 0.7)
+    pub dominance_weight: Option<f64>,
+    /// Weight of the commit count in confidence (default 0.3)
+    pub volume_weight: Option<f64>,
+    /// Commit count at which volume stops adding confidence (default 100)
+    pub volume_cap: Option<usize>,
 }
 
 /// Shared settings for both LSP and CLI
@@ -34,12 +44,29 @@ pub struct Settings {
     /// Whether to validate owners against GitHub API
     #[serde(default)]
     pub validate_owners: bool,
+    /// Maximum concurrent GitHub API requests when validating owners
+    pub validate_owners_concurrency: Option<usize>,
     /// Diagnostic severity overrides (code -> "off"|"hint"|"info"|"warning"|"error")
     #[serde(default)]
     pub diagnostics: HashMap<String, String>,
     /// Suggest command settings
     #[serde(default)]
     pub suggest: SuggestSettings,
+    /// Patterns are consumed by tooling that expands `{a,b}` groups
+    /// (not GitHub): match with braces expanded and silence the
+    /// brace-expansion warning
+    #[serde(default)]
+    pub brace_expansion: bool,
+    /// Flag rules listing more than this many distinct owners
+    pub max_owners_per_rule: Option<usize>,
+    /// Owners allowed to own the CODEOWNERS file itself; `lint` flags
+    /// anyone else its rule lists
+    #[serde(default)]
+    pub codeowners_admins: Vec<String>,
+    /// Owners never validated against GitHub (`*` wildcards allowed), for
+    /// accounts the API can't see
+    #[serde(default)]
+    pub skip_owners: Vec<String>,
 }
 
 impl Settings {
@@ -60,6 +87,9 @@ impl Settings {
         if other.validate_owners {
             self.validate_owners = true;
         }
+        if other.validate_owners_concurrency.is_some() {
+            self.validate_owners_concurrency = other.validate_owners_concurrency;
+        }
         // Merge diagnostics (other overwrites same keys)
         for (k, v) in other.diagnostics {
             self.diagnostics.insert(k, v);
@@ -71,11 +101,62 @@ impl Settings {
         if other.suggest.anchored {
             self.suggest.anchored = true;
         }
+        if other.suggest.dominance_weight.is_some() {
+            self.suggest.dominance_weight = other.suggest.dominance_weight;
+        }
+        if other.suggest.volume_weight.is_some() {
+            self.suggest.volume_weight = other.suggest.volume_weight;
+        }
+        if other.suggest.volume_cap.is_some() {
+            self.suggest.volume_cap = other.suggest.volume_cap;
+        }
+        if other.brace_expansion {
+            self.brace_expansion = true;
+        }
+        if other.max_owners_per_rule.is_some() {
+            self.max_owners_per_rule = other.max_owners_per_rule;
+        }
+        if !other.codeowners_admins.is_empty() {
+            self.codeowners_admins = other.codeowners_admins;
+        }
+        if !other.skip_owners.is_empty() {
+            self.skip_owners = other.skip_owners;
+        }
     }
 
     /// Get DiagnosticConfig from settings
     pub fn diagnostic_config(&self) -> DiagnosticConfig {
-        DiagnosticConfig::from_map(&self.diagnostics)
+        let mut config =
+            if self.brace_expansion && !self.diagnostics.contains_key(codes::BRACE_EXPANSION) {
+                let mut diagnostics = self.diagnostics.clone();
+                diagnostics.insert(codes::BRACE_EXPANSION.to_string(), "off".to_string());
+                DiagnosticConfig::from_map(&diagnostics)
+            } else {
+                DiagnosticConfig::from_map(&self.diagnostics)
+            };
+        config.max_owners_per_rule = self.max_owners_per_rule;
+        config.codeowners_admins = self.codeowners_admins.clone();
+        config.brace_expansion = self.brace_expansion;
+        config
+    }
+
+    /// How patterns are compiled when matching files
+    pub fn pattern_options(&self) -> PatternOptions {
+        PatternOptions {
+            brace_expansion: self.brace_expansion,
+        }
+    }
+
+    /// Owners to leave out of GitHub validation
+    pub fn skip_owners(&self) -> SkipOwners {
+        SkipOwners::new(&self.skip_owners)
+    }
+
+    /// Concurrency for owner validation (configured, or a conservative default)
+    pub fn validation_concurrency(&self) -> usize {
+        self.validate_owners_concurrency
+            .unwrap_or(DEFAULT_CONCURRENCY)
+            .max(1)
     }
 
     /// Resolve GitHub token (handles env: prefix)
@@ -100,6 +181,17 @@ impl Settings {
     pub fn suggest_anchored(&self) -> bool {
         self.suggest.anchored
     }
+
+    /// Confidence weights for suggest and init, defaults filling any unset
+    #[allow(dead_code)] // Used by CLI only
+    pub fn confidence_weights(&self) -> ConfidenceWeights {
+        let defaults = ConfidenceWeights::default();
+        ConfidenceWeights {
+            dominance: self.suggest.dominance_weight.unwrap_or(defaults.dominance),
+            volume: self.suggest.volume_weight.unwrap_or(defaults.volume),
+            volume_cap: self.suggest.volume_cap.unwrap_or(defaults.volume_cap),
+        }
+    }
 }
 
 /// Load settings from config files in a directory
@@ -198,6 +290,19 @@ mod tests {
         assert!(base.validate_owners);
     }
 
+    #[test]
+    fn test_validation_concurrency() {
+        let mut settings = Settings::default();
+        assert_eq!(settings.validation_concurrency(), DEFAULT_CONCURRENCY);
+
+        settings.merge(toml::from_str("validate_owners_concurrency = 8").unwrap());
+        assert_eq!(settings.validation_concurrency(), 8);
+
+        // Zero would never make progress
+        settings.validate_owners_concurrency = Some(0);
+        assert_eq!(settings.validation_concurrency(), 1);
+    }
+
     #[test]
     fn test_settings_merge_diagnostics() {
         let mut base = Settings::default();
@@ -224,6 +329,7 @@ mod tests {
             suggest: SuggestSettings {
                 lookup_cmd: Some("lookup {email}".to_string()),
                 anchored: true,
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -276,12 +382,31 @@ mod tests {
             suggest: SuggestSettings {
                 lookup_cmd: Some("my-cmd {email}".to_string()),
                 anchored: false,
+                ..Default::default()
             },
             ..Default::default()
         };
         assert_eq!(settings.lookup_cmd(), Some("my-cmd {email}"));
     }
 
+    #[test]
+    fn test_confidence_weights() {
+        let mut settings = Settings::default();
+        assert_eq!(settings.confidence_weights(), ConfidenceWeights::default());
+
+        settings.merge(toml::from_str("[suggest]\nvolume_weight = 0.5").unwrap());
+        settings
+            .merge(toml::from_str("[suggest]\ndominance_weight = 0.5\nvolume_cap = 20").unwrap());
+        assert_eq!(
+            settings.confidence_weights(),
+            ConfidenceWeights {
+                dominance: 0.5,
+                volume: 0.5,
+                volume_cap: 20,
+            }
+        );
+    }
+
     #[test]
     fn test_suggest_anchored() {
         let mut settings = Settings::default();
@@ -379,6 +504,97 @@ mod tests {
         let _config = settings.diagnostic_config();
     }
 
+    #[test]
+    fn test_codeowners_admins_setting() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join(CONFIG_FILE),
+            "codeowners_admins = [\"@org/admins\"]\n",
+        )
+        .unwrap();
+        let config = load_settings_from_path(dir.path()).diagnostic_config();
+        assert_eq!(config.codeowners_admins, ["@org/admins"]);
+        assert!(Settings::default()
+            .diagnostic_config()
+            .codeowners_admins
+            .is_empty());
+    }
+
+    #[test]
+    fn test_skip_owners_setting() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join(CONFIG_FILE),
+            "skip_owners = [\"@org/*-bot\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join(CONFIG_FILE_LOCAL),
+            "validate_owners = true\n",
+        )
+        .unwrap();
+        let skip = load_settings_from_path(dir.path()).skip_owners();
+        assert!(skip.matches("@org/deploy-bot"));
+        assert!(!skip.matches("@org/web"));
+        assert!(Settings::default().skip_owners().is_empty());
+    }
+
+    #[test]
+    fn test_max_owners_per_rule_setting() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join(CONFIG_FILE), "max_owners_per_rule = 5\n").unwrap();
+        let settings = load_settings_from_path(dir.path());
+        assert_eq!(settings.max_owners_per_rule, Some(5));
+        assert_eq!(settings.diagnostic_config().max_owners_per_rule, Some(5));
+
+        // Local config overrides the project value
+        fs::write(
+            dir.path().join(CONFIG_FILE_LOCAL),
+            "max_owners_per_rule = 2\n",
+        )
+        .unwrap();
+        let settings = load_settings_from_path(dir.path());
+        assert_eq!(settings.diagnostic_config().max_owners_per_rule, Some(2));
+
+        assert_eq!(
+            Settings::default().diagnostic_config().max_owners_per_rule,
+            None
+        );
+    }
+
+    #[test]
+    fn test_brace_expansion_disables_warning() {
+        use tower_lsp::lsp_types::DiagnosticSeverity;
+
+        let mut settings = Settings::default();
+        assert!(settings
+            .diagnostic_config()
+            .get(codes::BRACE_EXPANSION, DiagnosticSeverity::WARNING)
+            .is_some());
+
+        settings.merge(Settings {
+            brace_expansion: true,
+            ..Default::default()
+        });
+        assert!(settings
+            .diagnostic_config()
+            .get(codes::BRACE_EXPANSION, DiagnosticSeverity::WARNING)
+            .is_none());
+
+        // An explicit severity still wins
+        settings
+            .diagnostics
+            .insert(codes::BRACE_EXPANSION.to_string(), "hint".to_string());
+        assert_eq!(
+            settings
+                .diagnostic_config()
+                .get(codes::BRACE_EXPANSION, DiagnosticSeverity::WARNING),
+            Some(DiagnosticSeverity::HINT)
+        );
+        assert!(settings.pattern_options().brace_expansion);
+        assert!(!Settings::default().pattern_options().brace_expansion);
+    }
+
     #[test]
     fn test_settings_merge_github_token() {
         let mut base = Settings {
@@ -0,0 +1,105 @@
+//! Owners left out of GitHub validation.
+//!
+//! Some owners can't or shouldn't be checked against the API: a machine
+//! account the token can't see, an external email. Listing them with
+//! `--skip-owner` or in config keeps them from being reported:
+//!
+//! ```toml
+//! skip_owners = ["@acme/release-bot", "@acme/*-bot", "*@partner.example"]
+//! ```
+//!
+//! Patterns match whole owners, case-insensitively. `*` stands for any run of
+//! characters except `/`, so `@acme/*` covers every team in `acme` but no
+//! users.
+
+/// Owner patterns to skip during validation
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SkipOwners {
+    patterns: Vec<String>,
+}
+
+impl SkipOwners {
+    /// Skip owners matching any of `patterns`
+    pub fn new<I, S>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            patterns: patterns
+                .into_iter()
+                .map(|p| p.as_ref().trim().to_ascii_lowercase())
+                .filter(|p| !p.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Whether nothing is skipped
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Whether `owner` matches one of the patterns
+    pub fn matches(&self, owner: &str) -> bool {
+        if self.patterns.is_empty() {
+            return false;
+        }
+        let owner = owner.to_ascii_lowercase();
+        self.patterns
+            .iter()
+            .any(|pattern| wildcard_matches(pattern.as_bytes(), owner.as_bytes()))
+    }
+}
+
+/// Match `text` against `pattern`, where `*` is any run of bytes but `/`
+fn wildcard_matches(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some((b'*', rest)) => {
+            // Try every length the star could cover, up to the next `/`
+            let limit = text.iter().position(|&b| b == b'/').unwrap_or(text.len());
+            (0..=limit).any(|skip| wildcard_matches(rest, &text[skip..]))
+        }
+        Some((&first, rest)) => text.first() == Some(&first) && wildcard_matches(rest, &text[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_owners_match_case_insensitively() {
+        let skip = SkipOwners::new(["@Acme/Release-Bot", "ops@example.com"]);
+        assert!(skip.matches("@acme/release-bot"));
+        assert!(skip.matches("@ACME/RELEASE-BOT"));
+        assert!(skip.matches("Ops@Example.com"));
+        assert!(!skip.matches("@acme/release-bots"));
+        assert!(!skip.matches("@acme/release"));
+    }
+
+    #[test]
+    fn test_wildcards_stop_at_slashes() {
+        let skip = SkipOwners::new(["@acme/*-bot", "*@partner.example", "@ci-*"]);
+        assert!(skip.matches("@acme/deploy-bot"));
+        assert!(skip.matches("@acme/-bot"));
+        assert!(!skip.matches("@acme/deploy-bots"));
+        assert!(!skip.matches("@other/deploy-bot"));
+        assert!(skip.matches("someone@partner.example"));
+        assert!(skip.matches("@ci-runner"));
+        // A user pattern doesn't reach into teams
+        assert!(!skip.matches("@ci-org/team"));
+
+        let teams = SkipOwners::new(["@acme/*"]);
+        assert!(teams.matches("@acme/web"));
+        assert!(!teams.matches("@acme"));
+        assert!(!teams.matches("@alice"));
+    }
+
+    #[test]
+    fn test_empty() {
+        assert!(SkipOwners::default().is_empty());
+        assert!(SkipOwners::new(["", "  "]).is_empty());
+        assert!(!SkipOwners::default().matches("@alice"));
+    }
+}
@@ -0,0 +1,35 @@
+//! Scratch git repositories for tests, shared by the library's and the
+//! CLI's unit tests (each crate root declares this module under
+//! `#[cfg(test)]`).
+
+use std::path::Path;
+use std::process::Command;
+
+/// Run git in `dir` without signing commits, panicking if it fails
+pub fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(["-c", "commit.gpgsign=false"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap()
+        .status;
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+/// Commit everything in `dir` as `author` (`<author>@test.com`)
+pub fn commit_as(dir: &Path, author: &str, message: &str) {
+    git(dir, &["add", "-A"]);
+    git(
+        dir,
+        &[
+            "-c",
+            &format!("user.name={}", author),
+            "-c",
+            &format!("user.email={}@test.com", author.to_lowercase()),
+            "commit",
+            "-qm",
+            message,
+        ],
+    );
+}
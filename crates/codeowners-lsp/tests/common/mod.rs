@@ -0,0 +1,29 @@
+//! Helpers shared by the integration tests, which run the real binary.
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Run `codeowners-cli` with `args` from `cwd`, without colors
+pub fn cli(cwd: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_codeowners-cli"))
+        .args(args)
+        .current_dir(cwd)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+/// A git repository in `dir` holding `files` as (path, content)
+pub fn fixture_repo(dir: &Path, files: &[(&str, &str)]) {
+    Command::new("git")
+        .arg("init")
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    for (path, content) in files {
+        let path = dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+}
@@ -0,0 +1,70 @@
+//! A CODEOWNERS file with conflict markers committed by mistake fails lint
+//! with just the conflict, checked through the real binary.
+
+use std::fs;
+
+mod common;
+
+use common::{cli, fixture_repo};
+
+const CONFLICTED: &str = "\
+* @all
+<<<<<<< HEAD
+/src/ @web
+=======
+/src/ @@platform
+>>>>>>> feature/platform
+/missing/ @nobody
+";
+
+const FILES: &[(&str, &str)] = &[("CODEOWNERS", CONFLICTED), ("src/main.rs", "")];
+
+#[test]
+fn test_lint_reports_only_the_conflict() {
+    let repo = tempfile::tempdir().unwrap();
+    fixture_repo(repo.path(), FILES);
+
+    let output = cli(repo.path(), &["lint"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Unresolved merge conflict at line 2"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("3 errors"), "{stdout}");
+    // The bad owner and missing directory wait until the conflict is resolved
+    assert!(!stdout.contains("invalid-owner"), "{stdout}");
+    assert!(!stdout.contains("missing-directory"), "{stdout}");
+
+    let output = cli(repo.path(), &["lint", "--json"]);
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let found: Vec<(u64, &str)> = json["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| (d["line"].as_u64().unwrap(), d["code"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (2, "merge-conflict"),
+            (4, "merge-conflict"),
+            (6, "merge-conflict")
+        ]
+    );
+}
+
+#[test]
+fn test_lint_fix_leaves_a_conflicted_file_alone() {
+    let repo = tempfile::tempdir().unwrap();
+    fixture_repo(repo.path(), FILES);
+
+    let output = cli(repo.path(), &["lint", "--fix"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unresolved merge conflict at line 2"));
+    assert_eq!(
+        fs::read_to_string(repo.path().join("CODEOWNERS")).unwrap(),
+        CONFLICTED
+    );
+}
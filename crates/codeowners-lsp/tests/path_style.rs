@@ -0,0 +1,83 @@
+//! `--path-style relative` resolves input paths against the working
+//! directory, so it is exercised by running the real binary from inside a
+//! subdirectory of the repository.
+
+use std::process::Output;
+
+mod common;
+
+use common::{cli, fixture_repo};
+
+const FILES: &[(&str, &str)] = &[
+    ("CODEOWNERS", "*.rs @rust\n"),
+    ("src/main.rs", ""),
+    ("README.md", ""),
+];
+
+fn tsv_paths(output: &Output) -> Vec<String> {
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|row| row.split('\t').next().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_check_relative_path_style() {
+    let repo = tempfile::tempdir().unwrap();
+    fixture_repo(repo.path(), FILES);
+    let root = repo.path().canonicalize().unwrap();
+    let src = root.join("src");
+    let absolute = root.join("src/main.rs");
+    let absolute = absolute.to_str().unwrap();
+    let inputs = [absolute, "./main.rs", "../README.md", "/elsewhere/x.txt"];
+
+    let mut args = vec!["check", "--format", "tsv"];
+    args.extend(inputs);
+    let output = cli(&src, &args);
+    assert_eq!(tsv_paths(&output), inputs);
+
+    args.extend(["--path-style", "relative"]);
+    let output = cli(&src, &args);
+    assert_eq!(
+        tsv_paths(&output),
+        [
+            "src/main.rs",
+            "src/main.rs",
+            "README.md",
+            "/elsewhere/x.txt"
+        ]
+    );
+    // Relative paths also match as the repository sees them
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().filter(|l| l.ends_with("@rust")).count(), 2);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("/elsewhere/x.txt is outside the repository"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_coverage_relative_path_style() {
+    let repo = tempfile::tempdir().unwrap();
+    fixture_repo(repo.path(), FILES);
+    let root = repo.path().canonicalize().unwrap();
+    let readme = root.join("README.md");
+
+    let output = cli(
+        &root.join("src"),
+        &[
+            "coverage",
+            "--json",
+            "--path-style",
+            "relative",
+            "--files",
+            readme.to_str().unwrap(),
+            "main.rs",
+        ],
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["codeowners"], "CODEOWNERS");
+    assert_eq!(json["total"], 2);
+    assert_eq!(json["unowned_files"], serde_json::json!(["README.md"]));
+}
@@ -0,0 +1,56 @@
+//! `--repo-root` changes the process working directory, so it is exercised
+//! by running the real binary from somewhere outside the repository.
+
+mod common;
+
+use common::{cli, fixture_repo};
+
+const FILES: &[(&str, &str)] = &[
+    (".github/CODEOWNERS", "*.rs @rust\n/.github/ @ops\n"),
+    ("src/main.rs", ""),
+    ("README.md", ""),
+];
+
+#[test]
+fn test_commands_run_against_repo_root_from_elsewhere() {
+    let repo = tempfile::tempdir().unwrap();
+    let elsewhere = tempfile::tempdir().unwrap();
+    fixture_repo(repo.path(), FILES);
+    let root = repo.path().to_str().unwrap();
+
+    // Without the override there is nothing to find
+    let output = cli(elsewhere.path(), &["coverage", "--json"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = cli(
+        elsewhere.path(),
+        &["--repo-root", root, "coverage", "--json"],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["total"], 3);
+    assert_eq!(json["unowned_files"], serde_json::json!(["README.md"]));
+
+    let output = cli(
+        elsewhere.path(),
+        &["check", "--repo-root", root, "--json", "src/main.rs"],
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("@rust"));
+}
+
+#[test]
+fn test_invalid_repo_root_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let missing = dir.path().join("missing");
+
+    for (root, message) in [
+        (missing.to_str().unwrap(), "missing"),
+        (dir.path().to_str().unwrap(), "has no CODEOWNERS"),
+    ] {
+        let output = cli(dir.path(), &["--repo-root", root, "tree"]);
+        assert_eq!(output.status.code(), Some(2));
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(message), "{stderr}");
+    }
+}
@@ -0,0 +1,343 @@
+//! [`CodeownersFile`] — a parsed CODEOWNERS file ready for repeated lookups.
+//!
+//! This is the embedding API: parse once, then ask who owns any number of
+//! paths. Every pattern is compiled up front, so lookups never re-parse or
+//! re-compile. Nothing here touches the file system, prints, or exits.
+
+use std::collections::HashMap;
+
+use crate::ownership::OwnershipResult;
+use crate::parser::{parse_codeowners_file_with_positions, CodeownersLine, ParsedLine};
+use crate::pattern::{normalize_path, CompiledPattern, PatternOptions};
+use crate::validation::{owner_syntax_errors, OwnerError};
+
+/// A parsed CODEOWNERS file with precompiled patterns.
+///
+/// Ownership follows GitHub semantics: the last matching rule wins.
+///
+/// # Example
+///
+/// ```
+/// use codeowners_parser::CodeownersFile;
+///
+/// let codeowners = CodeownersFile::parse("* @core\n*.rs @rust\n/vendor/\n");
+///
+/// let result = codeowners.owners_for("src/main.rs").unwrap();
+/// assert_eq!(result.pattern, "*.rs");
+/// assert_eq!(result.owners, vec!["@rust"]);
+///
+/// // `/vendor/` wins but lists no owners, so vendored files are unowned
+/// let files = ["src/lib.rs", "vendor/dep.c", "README.md"];
+/// assert_eq!(codeowners.unowned(&files), vec!["vendor/dep.c"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CodeownersFile {
+    lines: Vec<ParsedLine>,
+    /// (index into `lines`, compiled pattern) for every rule, in file order
+    rules: Vec<(usize, CompiledPattern)>,
+    /// Malformed owners, keyed by 0-indexed line number
+    owner_errors: HashMap<u32, Vec<OwnerError>>,
+    options: PatternOptions,
+}
+
+impl CodeownersFile {
+    /// Parse CODEOWNERS content and compile every rule's pattern.
+    pub fn parse(content: &str) -> Self {
+        Self::parse_with_options(content, PatternOptions::default())
+    }
+
+    /// [`parse`](Self::parse), compiling patterns with non-default
+    /// [`PatternOptions`] (e.g. brace expansion).
+    pub fn parse_with_options(content: &str, options: PatternOptions) -> Self {
+        let lines = parse_codeowners_file_with_positions(content);
+        let rules = lines
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, line)| match &line.content {
+                CodeownersLine::Rule { pattern, .. } => {
+                    Some((idx, CompiledPattern::with_options(pattern, options)))
+                }
+                _ => None,
+            })
+            .collect();
+        let owner_errors = content
+            .lines()
+            .enumerate()
+            .filter_map(|(idx, line)| {
+                let errors = owner_syntax_errors(line);
+                (!errors.is_empty()).then_some((idx as u32, errors))
+            })
+            .collect();
+        Self {
+            lines,
+            rules,
+            owner_errors,
+            options,
+        }
+    }
+
+    /// Every parsed line, including comments and blank lines.
+    pub fn lines(&self) -> &[ParsedLine] {
+        &self.lines
+    }
+
+    /// The options the patterns were compiled with, for matching
+    /// [`lines`](Self::lines) the same way (e.g. per GitLab section).
+    pub fn pattern_options(&self) -> PatternOptions {
+        self.options
+    }
+
+    /// Malformed owner tokens on the 0-indexed `line_number`.
+    ///
+    /// Offsets are byte offsets within that line; see
+    /// [`owner_syntax_errors`](crate::validation::owner_syntax_errors).
+    pub fn owner_errors(&self, line_number: u32) -> &[OwnerError] {
+        self.owner_errors
+            .get(&line_number)
+            .map_or(&[], |errors| errors.as_slice())
+    }
+
+    /// The winning rule line for `path`, or `None` if no rule matches.
+    ///
+    /// `path` is normalized with [`normalize_path`] before matching. The returned
+    /// line's content is always a [`CodeownersLine::Rule`].
+    pub fn rule_for(&self, path: &str) -> Option<&ParsedLine> {
+        let path = normalize_path(path);
+        let path = path.as_ref();
+        self.rules
+            .iter()
+            .rev()
+            .find(|(_, pattern)| pattern.matches(path))
+            .map(|(idx, _)| &self.lines[*idx])
+    }
+
+    /// Every rule line matching `path`, in file order. The last one wins.
+    ///
+    /// `path` is normalized with [`normalize_path`] before matching.
+    pub fn matching_rules(&self, path: &str) -> Vec<&ParsedLine> {
+        let path = normalize_path(path);
+        let path = path.as_ref();
+        self.rules
+            .iter()
+            .filter(|(_, pattern)| pattern.matches(path))
+            .map(|(idx, _)| &self.lines[*idx])
+            .collect()
+    }
+
+    /// Resolve who owns `path`.
+    ///
+    /// Returns `None` if no rule matches. If the winning rule lists no
+    /// owners, the result's `owners` is empty — the file is explicitly
+    /// unowned.
+    pub fn owners_for(&self, path: &str) -> Option<OwnershipResult> {
+        let line = self.rule_for(path)?;
+        match &line.content {
+            CodeownersLine::Rule { pattern, owners } => Some(OwnershipResult {
+                line_number: line.line_number,
+                pattern: pattern.clone(),
+                owners: owners.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// The files in `files` that have no owners, in input order.
+    ///
+    /// A file is unowned if no rule matches it or its winning rule lists
+    /// no owners.
+    pub fn unowned<'f, S: AsRef<str>>(&self, files: &'f [S]) -> Vec<&'f str> {
+        files
+            .iter()
+            .map(|f| f.as_ref())
+            .filter(|f| {
+                !matches!(
+                    self.rule_for(f).map(|line| &line.content),
+                    Some(CodeownersLine::Rule { owners, .. }) if !owners.is_empty()
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ownership::check_file_ownership_parsed;
+
+    const CONTENT: &str = "\
+# Default
+* @default
+*.rs @rust
+/docs/ @docs
+/vendor/
+";
+
+    #[test]
+    fn test_matching_rules_in_file_order() {
+        let codeowners = CodeownersFile::parse(CONTENT);
+        for path in ["src/main.rs", "vendor/dep.c", "README.md", "docs/a/b.md"] {
+            let expected: Vec<u32> =
+                crate::ownership::find_matching_rules_parsed(codeowners.lines(), path)
+                    .iter()
+                    .map(|r| r.line_number)
+                    .collect();
+            let actual: Vec<u32> = codeowners
+                .matching_rules(path)
+                .iter()
+                .map(|l| l.line_number)
+                .collect();
+            assert_eq!(actual, expected, "{}", path);
+            assert_eq!(
+                actual.last().copied(),
+                codeowners.rule_for(path).map(|l| l.line_number)
+            );
+        }
+    }
+
+    #[test]
+    fn test_dot_slash_and_windows_paths() {
+        let codeowners = CodeownersFile::parse("* @core\n/src/ @backend\n*.md @docs\n");
+        for path in ["./src/main.rs", r"src\main.rs", r".\src\main.rs"] {
+            assert_eq!(
+                codeowners.owners_for(path).unwrap().owners,
+                vec!["@backend"],
+                "{}",
+                path
+            );
+        }
+        assert_eq!(
+            codeowners.owners_for(r"docs\guide.md").unwrap().pattern,
+            "*.md"
+        );
+        let files = [r".\src\lib.rs", "./vendor/x.c"];
+        assert!(codeowners.unowned(&files).is_empty());
+    }
+
+    #[test]
+    fn test_owner_errors_by_line() {
+        let codeowners = CodeownersFile::parse("* @core\n*.rs @rust @@typo\n");
+        assert!(codeowners.owner_errors(0).is_empty());
+        let errors = codeowners.owner_errors(1);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].owner, "@@typo");
+        assert_eq!(errors[0].offset, 11);
+        assert!(codeowners.owner_errors(99).is_empty());
+    }
+
+    #[test]
+    fn test_owners_for_matches_check_file_ownership_parsed() {
+        let file = CodeownersFile::parse(CONTENT);
+        for path in [
+            "src/main.rs",
+            "docs/index.md",
+            "vendor/a.c",
+            "README.md",
+            "./lib.rs",
+        ] {
+            let expected = check_file_ownership_parsed(file.lines(), path);
+            let actual = file.owners_for(path);
+            assert_eq!(
+                actual
+                    .as_ref()
+                    .map(|r| (r.line_number, &r.pattern, &r.owners)),
+                expected
+                    .as_ref()
+                    .map(|r| (r.line_number, &r.pattern, &r.owners)),
+                "mismatch for {}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_owners_for_matches_naive_lookup() {
+        let content = "\
+* @default
+*.rs @rust
+/src/ @src
+src/**/*.md @docs
+docs/ @docs-anywhere
+/src/generated/
+/Cargo.toml @build
+";
+        let file = CodeownersFile::parse(content);
+        // (path, 0-indexed line of the winning rule)
+        let cases = [
+            ("x.txt", 0),
+            ("README.md", 0),
+            ("main.rs", 1),
+            ("Cargo.toml", 6),
+            ("lib/Cargo.toml", 0),
+            ("src/main.rs", 2),
+            ("src/README.md", 3),
+            ("src/a/b/README.md", 3),
+            ("docs/x.txt", 4),
+            ("lib/docs/x.txt", 4),
+            ("src/docs/x.txt", 4),
+            ("src/generated/main.rs", 5),
+            ("src/generated/README.md", 5),
+        ];
+
+        for (path, line) in cases {
+            let naive = crate::ownership::check_file_ownership(content, path);
+            let reused = file.owners_for(path);
+            assert_eq!(
+                reused.as_ref().map(|r| r.line_number),
+                Some(line),
+                "{}",
+                path
+            );
+            assert_eq!(
+                naive.map(|r| (r.line_number, r.owners)),
+                reused.map(|r| (r.line_number, r.owners)),
+                "mismatch for {}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_with_brace_expansion() {
+        let content = "* @all\n*.{js,ts} @web\n";
+        let plain = CodeownersFile::parse(content);
+        assert_eq!(plain.owners_for("src/a.ts").unwrap().owners, vec!["@all"]);
+
+        let options = PatternOptions {
+            brace_expansion: true,
+        };
+        let expanded = CodeownersFile::parse_with_options(content, options);
+        assert_eq!(
+            expanded.owners_for("src/a.ts").unwrap().owners,
+            vec!["@web"]
+        );
+        assert_eq!(
+            expanded.owners_for("src/a.rs").unwrap().owners,
+            vec!["@all"]
+        );
+    }
+
+    #[test]
+    fn test_owners_for_no_match() {
+        let file = CodeownersFile::parse("*.rs @rust\n");
+        assert!(file.owners_for("README.md").is_none());
+        assert!(file.rule_for("README.md").is_none());
+    }
+
+    #[test]
+    fn test_unowned() {
+        let file = CodeownersFile::parse("*.rs @rust\n/vendor/\n");
+        let files = vec![
+            "src/main.rs".to_string(),
+            "README.md".to_string(),
+            "vendor/lib.rs".to_string(),
+        ];
+        assert_eq!(file.unowned(&files), vec!["README.md", "vendor/lib.rs"]);
+    }
+
+    #[test]
+    fn test_empty_file() {
+        let file = CodeownersFile::parse("");
+        assert!(file.lines().is_empty());
+        assert_eq!(file.unowned(&["a.rs"]), vec!["a.rs"]);
+    }
+}
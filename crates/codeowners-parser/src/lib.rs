@@ -21,14 +21,19 @@
 //!   implementation based on shape — `*.rs` becomes a raw `ends_with` check,
 //!   not a regex.
 //! - **Resolve ownership** for a path following "last match wins" semantics.
-//!   Pre-parse once for hot loops via [`check_file_ownership_parsed`].
+//!   Parse once into a [`CodeownersFile`] and query it with
+//!   [`CodeownersFile::owners_for`] / [`CodeownersFile::unowned`], or use
+//!   [`check_file_ownership_parsed`] on pre-parsed lines.
+//!   GitLab-style sections (union of each section's winner) are available
+//!   via [`check_file_ownership_sections`] / [`Semantics::GitLab`].
 //! - **Detect dead rules** via [`pattern_subsumes`] — used by linters to find
 //!   patterns that will never match because a later rule shadows them.
 //! - **Validate** owner format (`@user`, `@org/team`, `email@host`) and glob
 //!   syntax — with the specific subset of globs CODEOWNERS actually supports
 //!   (no `[...]` character classes, no `!` negation).
 //! - **Locate** the CODEOWNERS file in a repo (`.github/CODEOWNERS`,
-//!   `CODEOWNERS`, or `docs/CODEOWNERS`) via [`find_codeowners`].
+//!   `CODEOWNERS`, or `docs/CODEOWNERS`) via [`find_codeowners`], or every
+//!   copy in precedence order via [`find_all_codeowners`].
 //!
 //! ## What it doesn't do
 //!
@@ -67,6 +72,24 @@
 //! assert!(validate_pattern("").is_some());
 //! ```
 //!
+//! ## Embedding: parse once, query many paths
+//!
+//! [`CodeownersFile`] compiles every pattern up front and returns
+//! structured results — no printing, no exit codes.
+//!
+//! ```
+//! use codeowners_parser::CodeownersFile;
+//!
+//! let codeowners = CodeownersFile::parse("* @core\n/docs/ @docs\n/generated/\n");
+//!
+//! let owners = codeowners.owners_for("docs/guide.md").unwrap();
+//! assert_eq!(owners.owners, vec!["@docs"]);
+//! assert_eq!(owners.line_number, 1); // 0-indexed
+//!
+//! let files = ["src/main.rs", "generated/api.rs"];
+//! assert_eq!(codeowners.unowned(&files), vec!["generated/api.rs"]);
+//! ```
+//!
 //! ## Parsing with positions (for editor tooling)
 //!
 //! ```
@@ -143,19 +166,32 @@
 
 #![deny(missing_docs)]
 
+pub mod file;
 pub mod ownership;
 pub mod parser;
 pub mod pattern;
 pub mod validation;
 
+pub use file::CodeownersFile;
 pub use ownership::{
-    check_file_ownership, check_file_ownership_parsed, find_codeowners, get_repo_root,
-    OwnershipResult,
+    check_file_ownership, check_file_ownership_parsed, check_file_ownership_parsed_with_options,
+    check_file_ownership_sections, check_file_ownership_sections_with_options,
+    explain_file_ownership, explain_file_ownership_parsed, find_all_codeowners, find_codeowners,
+    find_matching_rules_parsed, get_repo_root, resolve_owners_parsed,
+    resolve_owners_parsed_with_options, FileOwnership, OwnershipResult, SectionOwnership,
+    Semantics, CODEOWNERS_LOCATIONS,
 };
 pub use parser::{
     find_inline_comment_start, find_insertion_point, find_insertion_point_with_owner,
-    find_owner_at_position, format_codeowners, parse_codeowners_file,
-    parse_codeowners_file_with_positions, serialize_codeowners, CodeownersLine, ParsedLine,
+    find_owner_at_position, format_codeowners, inline_comment, parse_codeowners_file,
+    parse_codeowners_file_with_positions, parse_section_header, serialize_codeowners, tokenize,
+    CodeownersLine, ParsedLine, SectionHeader,
+};
+pub use pattern::{
+    anchored_form, brace_expansion_overflows, expand_braces, has_brace_group, normalize_path,
+    pattern_matches, pattern_subsumes, CompiledPattern, PatternOptions, MAX_BRACE_EXPANSIONS,
+};
+pub use validation::{
+    canonical_owner, classify_owner, merge_conflict_markers, owner_syntax_errors,
+    rule_missing_pattern, validate_owner, validate_pattern, OwnerError, OwnerErrorKind, OwnerKind,
 };
-pub use pattern::{pattern_matches, pattern_subsumes, CompiledPattern};
-pub use validation::{validate_owner, validate_pattern};
@@ -6,14 +6,23 @@
 
 use std::path::{Path, PathBuf};
 
-use crate::parser::{parse_codeowners_file_with_positions, CodeownersLine, ParsedLine};
-use crate::pattern::pattern_matches;
+use crate::parser::{
+    parse_codeowners_file_with_positions, parse_section_header, CodeownersLine, ParsedLine,
+};
+use crate::pattern::{normalize_path, pattern_matches, CompiledPattern, PatternOptions};
+
+/// The locations GitHub recognizes for a CODEOWNERS file, relative to the
+/// repository root, in precedence order.
+///
+/// GitHub uses the first one that exists and ignores the rest.
+pub const CODEOWNERS_LOCATIONS: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
 
 /// Locate a CODEOWNERS file by walking up from `start`, checking
 /// `.github/CODEOWNERS`, `CODEOWNERS`, and `docs/CODEOWNERS` at each level.
 ///
 /// These are the three locations GitHub recognizes, checked in the same
-/// priority order as `git`'s own CODEOWNERS resolution.
+/// priority order as `git`'s own CODEOWNERS resolution. Use
+/// [`find_all_codeowners`] to also see lower-precedence copies.
 ///
 /// # Example
 ///
@@ -26,18 +35,41 @@ use crate::pattern::pattern_matches;
 /// }
 /// ```
 pub fn find_codeowners(start: &Path) -> Option<PathBuf> {
-    const CANDIDATES: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+    find_all_codeowners(start).into_iter().next()
+}
+
+/// Locate every CODEOWNERS file in the nearest directory (walking up from
+/// `start`) that contains at least one, ordered by precedence.
+///
+/// The first entry is the file GitHub actually uses; any further entries
+/// are stray copies that GitHub silently ignores. Returns an empty vector
+/// if no CODEOWNERS file is found.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::path::Path;
+/// use codeowners_parser::find_all_codeowners;
+///
+/// let found = find_all_codeowners(Path::new("."));
+/// for ignored in found.iter().skip(1) {
+///     println!("ignored by GitHub: {}", ignored.display());
+/// }
+/// ```
+pub fn find_all_codeowners(start: &Path) -> Vec<PathBuf> {
     let mut current = Some(start);
     while let Some(dir) = current {
-        for candidate in CANDIDATES {
-            let path = dir.join(candidate);
-            if path.is_file() {
-                return Some(path);
-            }
+        let found: Vec<PathBuf> = CODEOWNERS_LOCATIONS
+            .iter()
+            .map(|candidate| dir.join(candidate))
+            .filter(|path| path.is_file())
+            .collect();
+        if !found.is_empty() {
+            return found;
         }
         current = dir.parent();
     }
-    None
+    Vec::new()
 }
 
 /// Get the repository root from a CODEOWNERS file path.
@@ -63,7 +95,7 @@ pub fn get_repo_root(codeowners_path: &Path, fallback: &Path) -> PathBuf {
 /// which owners were assigned.
 ///
 /// Returned by [`check_file_ownership`] and [`check_file_ownership_parsed`].
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct OwnershipResult {
     /// 0-indexed line number of the matching rule in the CODEOWNERS file.
     pub line_number: u32,
@@ -75,6 +107,48 @@ pub struct OwnershipResult {
     pub owners: Vec<String>,
 }
 
+/// Every rule that matches a file, and the one that wins.
+///
+/// Returned by [`explain_file_ownership`] and
+/// [`explain_file_ownership_parsed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileOwnership {
+    /// The rule that owns the file under "last match wins", or `None` if
+    /// no rule matches. Always the last entry of `all_matches`.
+    pub winner: Option<OwnershipResult>,
+    /// Every matching rule, in file order. Rules before the winner matched
+    /// but were overridden.
+    pub all_matches: Vec<OwnershipResult>,
+}
+
+/// Find every rule matching `file_path` and the winner, parsing `content`.
+///
+/// # Example
+///
+/// ```
+/// use codeowners_parser::explain_file_ownership;
+///
+/// let ownership = explain_file_ownership("* @core\n*.rs @rust\n/docs/ @docs\n", "src/main.rs");
+/// assert_eq!(ownership.all_matches.len(), 2);
+/// assert_eq!(ownership.all_matches[0].pattern, "*");
+/// assert_eq!(ownership.winner.unwrap().pattern, "*.rs");
+/// ```
+pub fn explain_file_ownership(content: &str, file_path: &str) -> FileOwnership {
+    let lines = parse_codeowners_file_with_positions(content);
+    explain_file_ownership_parsed(&lines, file_path)
+}
+
+/// [`explain_file_ownership`] against pre-parsed lines.
+///
+/// `file_path` is normalized with [`normalize_path`] before matching.
+pub fn explain_file_ownership_parsed(lines: &[ParsedLine], file_path: &str) -> FileOwnership {
+    let all_matches = find_matching_rules_parsed(lines, file_path);
+    FileOwnership {
+        winner: all_matches.last().cloned(),
+        all_matches,
+    }
+}
+
 /// Find which CODEOWNERS rule owns `file_path`, parsing `content` each call.
 ///
 /// For hot loops where the same CODEOWNERS content is checked against many
@@ -93,8 +167,7 @@ pub struct OwnershipResult {
 /// assert_eq!(result.owners, vec!["@main-owner"]);
 /// ```
 pub fn check_file_ownership(content: &str, file_path: &str) -> Option<OwnershipResult> {
-    let lines = parse_codeowners_file_with_positions(content);
-    check_file_ownership_parsed(&lines, file_path)
+    explain_file_ownership(content, file_path).winner
 }
 
 /// Find which CODEOWNERS rule owns `file_path` against pre-parsed lines.
@@ -107,12 +180,13 @@ pub fn check_file_ownership(content: &str, file_path: &str) -> Option<OwnershipR
 /// [`parse_codeowners_file_with_positions`](crate::parser::parse_codeowners_file_with_positions)
 /// and reuse the result.
 ///
-/// Leading `./` is stripped from `file_path` before matching.
+/// `file_path` is normalized with [`normalize_path`] before matching.
 pub fn check_file_ownership_parsed(
     lines: &[ParsedLine],
     file_path: &str,
 ) -> Option<OwnershipResult> {
-    let file_path = file_path.trim_start_matches("./");
+    let file_path = normalize_path(file_path);
+    let file_path = file_path.as_ref();
 
     let mut matching_rule = None;
     for parsed_line in lines {
@@ -130,6 +204,241 @@ pub fn check_file_ownership_parsed(
     matching_rule
 }
 
+/// [`check_file_ownership_parsed`], matching patterns compiled with
+/// non-default [`PatternOptions`] (e.g. brace expansion).
+pub fn check_file_ownership_parsed_with_options(
+    lines: &[ParsedLine],
+    file_path: &str,
+    options: PatternOptions,
+) -> Option<OwnershipResult> {
+    if options == PatternOptions::default() {
+        return check_file_ownership_parsed(lines, file_path);
+    }
+    let file_path = normalize_path(file_path);
+    let file_path = file_path.as_ref();
+
+    lines
+        .iter()
+        .rev()
+        .find_map(|parsed_line| match &parsed_line.content {
+            CodeownersLine::Rule { pattern, owners }
+                if matches_with_options(pattern, file_path, options) =>
+            {
+                Some(OwnershipResult {
+                    line_number: parsed_line.line_number,
+                    pattern: pattern.clone(),
+                    owners: owners.clone(),
+                })
+            }
+            _ => None,
+        })
+}
+
+/// How ownership is resolved when several rules match a path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Semantics {
+    /// GitHub: the last matching rule in the file wins.
+    #[default]
+    GitHub,
+    /// GitLab: the last matching rule *within each section* wins, and the
+    /// path's owners are the union across sections. Rules without owners
+    /// inherit their section's default owners.
+    GitLab,
+}
+
+/// The winning rule for a path within one GitLab section.
+///
+/// Returned by [`check_file_ownership_sections`].
+#[derive(Debug, Clone)]
+pub struct SectionOwnership {
+    /// Section name, or `None` for rules before the first section header.
+    pub section: Option<String>,
+    /// The winning rule in this section. `owners` already has the
+    /// section's default owners applied if the rule lists none.
+    pub result: OwnershipResult,
+}
+
+/// Resolve ownership of `file_path` per section (GitLab semantics).
+///
+/// Lines are grouped into sections by GitLab section headers (`[Name]`,
+/// `^[Name]`); rules before the first header form an unnamed section.
+/// Sections with the same name (case-insensitive) are combined, as GitLab
+/// does. Within each section the last matching rule wins. Results are in
+/// order of each section's first appearance.
+///
+/// `file_path` is normalized with [`normalize_path`] before matching.
+///
+/// # Example
+///
+/// ```
+/// use codeowners_parser::{check_file_ownership_sections, parser::parse_codeowners_file_with_positions};
+///
+/// let lines = parse_codeowners_file_with_positions(
+///     "*.rs @rust\n[Security] @security\n/src/\n",
+/// );
+/// let sections = check_file_ownership_sections(&lines, "src/main.rs");
+/// assert_eq!(sections.len(), 2);
+/// assert_eq!(sections[0].result.owners, vec!["@rust"]);
+/// assert_eq!(sections[1].section.as_deref(), Some("Security"));
+/// assert_eq!(sections[1].result.owners, vec!["@security"]); // section default
+/// ```
+pub fn check_file_ownership_sections(
+    lines: &[ParsedLine],
+    file_path: &str,
+) -> Vec<SectionOwnership> {
+    check_file_ownership_sections_with_options(lines, file_path, PatternOptions::default())
+}
+
+/// [`check_file_ownership_sections`], matching patterns compiled with
+/// non-default [`PatternOptions`] (e.g. brace expansion).
+pub fn check_file_ownership_sections_with_options(
+    lines: &[ParsedLine],
+    file_path: &str,
+    options: PatternOptions,
+) -> Vec<SectionOwnership> {
+    let file_path = normalize_path(file_path);
+    let file_path = file_path.as_ref();
+
+    let mut results: Vec<SectionOwnership> = Vec::new();
+    // Index into `results` for the current section, keyed by lowercase name
+    let mut section_slots: Vec<Option<String>> = Vec::new();
+    let mut current: Option<String> = None;
+    let mut defaults: Vec<String> = Vec::new();
+
+    for parsed_line in lines {
+        let CodeownersLine::Rule { pattern, owners } = &parsed_line.content else {
+            continue;
+        };
+
+        if pattern.starts_with('[') || pattern.starts_with("^[") {
+            let raw = std::iter::once(pattern.as_str())
+                .chain(owners.iter().map(|o| o.as_str()))
+                .collect::<Vec<_>>()
+                .join(" ");
+            if let Some(header) = parse_section_header(&raw) {
+                current = Some(header.name);
+                defaults = header.default_owners;
+                continue;
+            }
+        }
+
+        if !matches_with_options(pattern, file_path, options) {
+            continue;
+        }
+
+        let result = OwnershipResult {
+            line_number: parsed_line.line_number,
+            pattern: pattern.clone(),
+            owners: if owners.is_empty() {
+                defaults.clone()
+            } else {
+                owners.clone()
+            },
+        };
+        let key = current.as_ref().map(|n| n.to_lowercase());
+        match section_slots.iter().position(|slot| *slot == key) {
+            Some(idx) => results[idx].result = result,
+            None => {
+                section_slots.push(key);
+                results.push(SectionOwnership {
+                    section: current.clone(),
+                    result,
+                });
+            }
+        }
+    }
+
+    results
+}
+
+/// Resolve the owners of `file_path` under the given [`Semantics`].
+///
+/// Under [`Semantics::GitHub`] this is the winning rule's owners. Under
+/// [`Semantics::GitLab`] it is the de-duplicated union of each section's
+/// winning owners, in section order. Returns an empty vector if the file
+/// has no owners.
+pub fn resolve_owners_parsed(
+    lines: &[ParsedLine],
+    file_path: &str,
+    semantics: Semantics,
+) -> Vec<String> {
+    resolve_owners_parsed_with_options(lines, file_path, semantics, PatternOptions::default())
+}
+
+/// [`resolve_owners_parsed`], matching patterns compiled with non-default
+/// [`PatternOptions`] (e.g. brace expansion).
+pub fn resolve_owners_parsed_with_options(
+    lines: &[ParsedLine],
+    file_path: &str,
+    semantics: Semantics,
+    options: PatternOptions,
+) -> Vec<String> {
+    match semantics {
+        Semantics::GitHub => check_file_ownership_parsed_with_options(lines, file_path, options)
+            .map(|r| r.owners)
+            .unwrap_or_default(),
+        Semantics::GitLab => {
+            let mut owners: Vec<String> = Vec::new();
+            for section in check_file_ownership_sections_with_options(lines, file_path, options) {
+                for owner in section.result.owners {
+                    if !owners.contains(&owner) {
+                        owners.push(owner);
+                    }
+                }
+            }
+            owners
+        }
+    }
+}
+
+/// Whether `pattern` matches the normalized `path`, compiled with `options`
+fn matches_with_options(pattern: &str, path: &str, options: PatternOptions) -> bool {
+    if options == PatternOptions::default() {
+        pattern_matches(pattern, path)
+    } else {
+        CompiledPattern::with_options(pattern, options).matches(path)
+    }
+}
+
+/// Find every CODEOWNERS rule that matches `file_path`, in file order.
+///
+/// The last entry (if any) is the winner under "last match wins" — the
+/// same result [`check_file_ownership_parsed`] returns. Earlier entries are
+/// rules that matched but were overridden. Useful for explaining why a
+/// file ended up with its owners.
+///
+/// `file_path` is normalized with [`normalize_path`] before matching.
+///
+/// # Example
+///
+/// ```
+/// use codeowners_parser::{find_matching_rules_parsed, parser::parse_codeowners_file_with_positions};
+///
+/// let lines = parse_codeowners_file_with_positions("* @default\n*.rs @rust\n/docs/ @docs\n");
+/// let matches = find_matching_rules_parsed(&lines, "src/main.rs");
+/// assert_eq!(matches.len(), 2);
+/// assert_eq!(matches[0].pattern, "*");
+/// assert_eq!(matches.last().unwrap().pattern, "*.rs"); // winner
+/// ```
+pub fn find_matching_rules_parsed(lines: &[ParsedLine], file_path: &str) -> Vec<OwnershipResult> {
+    let file_path = normalize_path(file_path);
+    let file_path = file_path.as_ref();
+
+    lines
+        .iter()
+        .filter_map(|parsed_line| match &parsed_line.content {
+            CodeownersLine::Rule { pattern, owners } if pattern_matches(pattern, file_path) => {
+                Some(OwnershipResult {
+                    line_number: parsed_line.line_number,
+                    pattern: pattern.clone(),
+                    owners: owners.clone(),
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,6 +466,26 @@ mod tests {
         assert_eq!(result.owners, vec!["@rust"]);
     }
 
+    #[test]
+    fn test_check_file_ownership_with_brace_expansion() {
+        let lines = parse_codeowners_file_with_positions("* @all\n*.{js,ts} @web\n");
+        let owners = |options| {
+            check_file_ownership_parsed_with_options(&lines, "./src/a.ts", options)
+                .unwrap()
+                .owners
+        };
+        assert_eq!(owners(PatternOptions::default()), vec!["@all"]);
+        let options = PatternOptions {
+            brace_expansion: true,
+        };
+        assert_eq!(owners(options), vec!["@web"]);
+        let result = check_file_ownership_parsed_with_options(&lines, "a.rs", options).unwrap();
+        assert_eq!(
+            (result.line_number, result.owners),
+            (0, vec!["@all".to_string()])
+        );
+    }
+
     #[test]
     fn test_check_file_ownership_strips_leading_dot_slash() {
         let content = "*.rs @rust";
@@ -164,6 +493,224 @@ mod tests {
         assert_eq!(result.owners, vec!["@rust"]);
     }
 
+    #[test]
+    fn test_check_file_ownership_with_bom_and_crlf() {
+        let content = "\u{feff}/src/ @src-team\r\n*.md @docs\r\n";
+        let result = check_file_ownership(content, "src/lib.rs").unwrap();
+        assert_eq!(result.pattern, "/src/");
+        assert_eq!(result.owners, vec!["@src-team"]);
+        let result = check_file_ownership(content, "README.md").unwrap();
+        assert_eq!(result.owners, vec!["@docs"]);
+    }
+
+    #[test]
+    fn test_explain_overlapping_rules() {
+        let content = "* @core\n\
+            /src/ @src\n\
+            *.md @docs\n\
+            /src/**/*.rs @rust\n\
+            /src/legacy/ @legacy\n";
+        let ownership = explain_file_ownership(content, "src/lib/main.rs");
+
+        let lines: Vec<u32> = ownership
+            .all_matches
+            .iter()
+            .map(|m| m.line_number)
+            .collect();
+        assert_eq!(lines, vec![0, 1, 3]);
+        let winner = ownership.winner.unwrap();
+        assert_eq!(winner, ownership.all_matches[2]);
+        assert_eq!(winner.pattern, "/src/**/*.rs");
+        assert_eq!(winner.owners, vec!["@rust"]);
+        // Same winner as the single-result API
+        assert_eq!(
+            check_file_ownership(content, "src/lib/main.rs"),
+            Some(winner)
+        );
+
+        // A later rule overrides, even when it's less specific
+        let ownership = explain_file_ownership(content, "./src/legacy/old.rs");
+        assert_eq!(ownership.all_matches.len(), 4);
+        assert_eq!(ownership.winner.unwrap().owners, vec!["@legacy"]);
+    }
+
+    #[test]
+    fn test_explain_no_match() {
+        let ownership = explain_file_ownership("/src/ @src\n", "README.md");
+        assert_eq!(ownership.winner, None);
+        assert!(ownership.all_matches.is_empty());
+    }
+
+    #[test]
+    fn test_find_matching_rules_order_and_winner() {
+        let lines = parse_codeowners_file_with_positions(
+            "* @default\n/src/ @src\n*.md @docs\n*.rs @rust\n/src/main.rs @main\n",
+        );
+        let matches = find_matching_rules_parsed(&lines, "src/main.rs");
+
+        let patterns: Vec<&str> = matches.iter().map(|m| m.pattern.as_str()).collect();
+        assert_eq!(patterns, vec!["*", "/src/", "*.rs", "/src/main.rs"]);
+        let line_numbers: Vec<u32> = matches.iter().map(|m| m.line_number).collect();
+        assert_eq!(line_numbers, vec![0, 1, 3, 4]);
+
+        // Winner agrees with check_file_ownership_parsed
+        let winner = check_file_ownership_parsed(&lines, "src/main.rs").unwrap();
+        assert_eq!(matches.last().unwrap().line_number, winner.line_number);
+    }
+
+    const SECTIONED: &str = "\
+* @default
+*.rs @rust
+[Security] @security
+/src/auth/
+/src/ @sec-src
+[Docs]
+*.md @docs
+[security]
+/src/auth/login.rs @login-sec
+";
+
+    #[test]
+    fn test_sections_github_vs_gitlab_semantics() {
+        let lines = parse_codeowners_file_with_positions(SECTIONED);
+        let files = [
+            "src/main.rs",
+            "src/auth/login.rs",
+            "README.md",
+            "Cargo.toml",
+        ];
+
+        let github: Vec<Vec<String>> = files
+            .iter()
+            .map(|f| resolve_owners_parsed(&lines, f, Semantics::GitHub))
+            .collect();
+        let gitlab: Vec<Vec<String>> = files
+            .iter()
+            .map(|f| resolve_owners_parsed(&lines, f, Semantics::GitLab))
+            .collect();
+
+        // GitHub: last match in the whole file wins
+        assert_eq!(github[0], vec!["@sec-src"]);
+        assert_eq!(github[1], vec!["@login-sec"]);
+        assert_eq!(github[2], vec!["@docs"]);
+        assert_eq!(github[3], vec!["@default"]);
+
+        // GitLab: union of each section's winner
+        assert_eq!(gitlab[0], vec!["@rust", "@sec-src"]);
+        assert_eq!(gitlab[1], vec!["@rust", "@login-sec"]);
+        assert_eq!(gitlab[2], vec!["@default", "@docs"]);
+        assert_eq!(gitlab[3], vec!["@default"]);
+    }
+
+    #[test]
+    fn test_sections_default_owners_and_merging() {
+        let lines = parse_codeowners_file_with_positions(SECTIONED);
+        let sections = check_file_ownership_sections(&lines, "src/auth/token.rs");
+
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].section, None);
+        assert_eq!(sections[0].result.pattern, "*.rs");
+        // `[Security]` and `[security]` are one section; `/src/` wins over `/src/auth/`
+        assert_eq!(sections[1].section.as_deref(), Some("Security"));
+        assert_eq!(sections[1].result.pattern, "/src/");
+
+        // `/src/auth/` has no owners -> inherits the section default
+        let lines = parse_codeowners_file_with_positions("[Security] @security\n/src/auth/\n");
+        let sections = check_file_ownership_sections(&lines, "src/auth/session.py");
+        assert_eq!(sections[0].result.owners, vec!["@security"]);
+    }
+
+    #[test]
+    fn test_sections_without_headers_match_github() {
+        let content = "* @default\n*.rs @rust\n";
+        let lines = parse_codeowners_file_with_positions(content);
+        for file in ["main.rs", "README.md"] {
+            assert_eq!(
+                resolve_owners_parsed(&lines, file, Semantics::GitLab),
+                resolve_owners_parsed(&lines, file, Semantics::GitHub)
+            );
+        }
+    }
+
+    #[test]
+    fn test_sections_with_brace_expansion() {
+        let lines = parse_codeowners_file_with_positions("[Web] @web\n*.{js,ts}\n");
+        let options = PatternOptions {
+            brace_expansion: true,
+        };
+        assert!(resolve_owners_parsed(&lines, "a.ts", Semantics::GitLab).is_empty());
+        assert_eq!(
+            resolve_owners_parsed_with_options(&lines, "a.ts", Semantics::GitLab, options),
+            vec!["@web"]
+        );
+    }
+
+    #[test]
+    fn test_find_matching_rules_none() {
+        let lines = parse_codeowners_file_with_positions("*.rs @rust\n");
+        assert!(find_matching_rules_parsed(&lines, "README.md").is_empty());
+    }
+
+    fn write_codeowners(root: &Path, location: &str, content: &str) {
+        let path = root.join(location);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_find_all_codeowners_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        write_codeowners(dir.path(), "CODEOWNERS", "* @root\n");
+        write_codeowners(dir.path(), ".github/CODEOWNERS", "* @github\n");
+
+        let found = find_all_codeowners(dir.path());
+        assert_eq!(
+            found,
+            vec![
+                dir.path().join(".github/CODEOWNERS"),
+                dir.path().join("CODEOWNERS"),
+            ]
+        );
+        assert_eq!(
+            find_codeowners(dir.path()),
+            Some(dir.path().join(".github/CODEOWNERS"))
+        );
+    }
+
+    #[test]
+    fn test_find_all_codeowners_single() {
+        let dir = tempfile::tempdir().unwrap();
+        write_codeowners(dir.path(), "docs/CODEOWNERS", "* @docs\n");
+
+        let found = find_all_codeowners(dir.path());
+        assert_eq!(found, vec![dir.path().join("docs/CODEOWNERS")]);
+    }
+
+    #[test]
+    fn test_find_all_codeowners_walks_up() {
+        let dir = tempfile::tempdir().unwrap();
+        write_codeowners(dir.path(), "CODEOWNERS", "* @root\n");
+        write_codeowners(dir.path(), "docs/CODEOWNERS", "* @docs\n");
+        let nested = dir.path().join("src/deep");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        let found = find_all_codeowners(&nested);
+        assert_eq!(
+            found,
+            vec![
+                dir.path().join("CODEOWNERS"),
+                dir.path().join("docs/CODEOWNERS"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_all_codeowners_none() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(find_all_codeowners(dir.path()).is_empty());
+        assert!(find_codeowners(dir.path()).is_none());
+    }
+
     #[test]
     fn test_get_repo_root_github_subdir() {
         let path = PathBuf::from("/project/.github/CODEOWNERS");
@@ -8,20 +8,21 @@
 //!   logical content. Use this for CLI-style tools that only need to
 //!   inspect or rewrite rules.
 
-/// A parsed CODEOWNERS line with character offsets for editor tooling.
+/// A parsed CODEOWNERS line with byte offsets into the raw line for editor
+/// tooling.
 #[derive(Debug, Clone)]
 pub struct ParsedLine {
     /// 0-indexed line number in the source file.
     pub line_number: u32,
     /// The logical content of the line (rule, comment, or empty).
     pub content: CodeownersLine,
-    /// Character offset where the pattern starts (0 for non-rule lines).
+    /// Byte offset where the pattern starts (0 for non-rule lines).
     pub pattern_start: u32,
-    /// Character offset one past the end of the pattern.
+    /// Byte offset one past the end of the pattern.
     pub pattern_end: u32,
-    /// Character offset where owners start (equals `pattern_end` if no owners).
+    /// Byte offset where owners start (equals `pattern_end` if no owners).
     pub owners_start: u32,
-    /// Character offset of the inline comment's `#`, if any.
+    /// Byte offset of the inline comment's `#`, if any.
     pub comment_start: Option<u32>,
 }
 
@@ -59,6 +60,13 @@ pub fn parse_codeowners_file_with_positions(content: &str) -> Vec<ParsedLine> {
         .lines()
         .enumerate()
         .map(|(line_num, line)| {
+            // A byte order mark and CRLF endings aren't part of the rules;
+            // positions still count the BOM's bytes so they index the raw line
+            let (line, offset) = match line.strip_prefix('\u{feff}') {
+                Some(rest) if line_num == 0 => (rest, '\u{feff}'.len_utf8()),
+                _ => (line, 0),
+            };
+            let line = line.strip_suffix('\r').unwrap_or(line);
             let trimmed = line.trim();
             if trimmed.is_empty() {
                 ParsedLine {
@@ -80,15 +88,16 @@ pub fn parse_codeowners_file_with_positions(content: &str) -> Vec<ParsedLine> {
                 }
             } else {
                 // Split by whitespace, stopping at # (end-of-line comment)
-                let parts: Vec<&str> = line
-                    .split_whitespace()
-                    .take_while(|part| !part.starts_with('#'))
+                let tokens: Vec<(usize, &str)> = tokenize(line)
+                    .take_while(|(_, token)| !token.starts_with('#'))
                     .collect();
 
                 // Find the inline comment position (first # that's a separate whitespace-delimited token)
-                let comment_start = find_inline_comment_start(line).map(|pos| pos as u32);
+                let comment_start = find_inline_comment_start(line)
+                    .and_then(|chars| line.char_indices().nth(chars))
+                    .map(|(pos, _)| (pos + offset) as u32);
 
-                if parts.is_empty() {
+                if tokens.is_empty() {
                     ParsedLine {
                         line_number: line_num as u32,
                         content: CodeownersLine::Empty,
@@ -98,20 +107,18 @@ pub fn parse_codeowners_file_with_positions(content: &str) -> Vec<ParsedLine> {
                         comment_start: None,
                     }
                 } else {
-                    // Find pattern position
-                    let pattern_start = line.find(parts[0]).unwrap_or(0) as u32;
-                    let pattern_end = pattern_start + parts[0].len() as u32;
-                    let owners_start = if parts.len() > 1 {
-                        line.find(parts[1]).unwrap_or(pattern_end as usize) as u32
-                    } else {
-                        pattern_end
-                    };
+                    let (pattern_pos, pattern) = tokens[0];
+                    let pattern_start = (pattern_pos + offset) as u32;
+                    let pattern_end = pattern_start + pattern.len() as u32;
+                    let owners_start = tokens
+                        .get(1)
+                        .map_or(pattern_end, |&(pos, _)| (pos + offset) as u32);
 
                     ParsedLine {
                         line_number: line_num as u32,
                         content: CodeownersLine::Rule {
-                            pattern: parts[0].to_string(),
-                            owners: parts[1..].iter().map(|s| s.to_string()).collect(),
+                            pattern: pattern.to_string(),
+                            owners: tokens[1..].iter().map(|(_, s)| s.to_string()).collect(),
                         },
                         pattern_start,
                         pattern_end,
@@ -124,19 +131,60 @@ pub fn parse_codeowners_file_with_positions(content: &str) -> Vec<ParsedLine> {
         .collect()
 }
 
+/// The whitespace-separated tokens of a line, each with its byte offset.
+/// A backslash escapes whitespace (`/my\ docs/`), keeping it in the token.
+///
+/// ```
+/// use codeowners_parser::parser::tokenize;
+///
+/// let tokens: Vec<_> = tokenize("@a/x\t@a  # @a").collect();
+/// assert_eq!(tokens, [(0, "@a/x"), (5, "@a"), (9, "#"), (11, "@a")]);
+///
+/// let tokens: Vec<_> = tokenize(r"/my\ docs/ @a").collect();
+/// assert_eq!(tokens, [(0, r"/my\ docs/"), (11, "@a")]);
+/// ```
+pub fn tokenize(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        let start = pos + line[pos..].find(|c: char| !c.is_whitespace())?;
+        let mut escaped = false;
+        let end = line[start..]
+            .char_indices()
+            .find(|&(_, c)| {
+                let unescaped_space = c.is_whitespace() && !escaped;
+                escaped = c == '\\' && !escaped;
+                unescaped_space
+            })
+            .map_or(line.len(), |(len, _)| start + len);
+        pos = end;
+        Some((start, &line[start..end]))
+    })
+}
+
 /// Find the char offset of an inline comment on a rule line.
-/// An inline comment starts with `#` that is preceded by whitespace.
+/// An inline comment starts with `#` that is preceded by whitespace, so an
+/// escaped `\#` in a pattern is never one.
 pub fn find_inline_comment_start(line: &str) -> Option<usize> {
     let mut in_whitespace = true;
+    let mut escaped = false;
     for (i, c) in line.chars().enumerate() {
         if c == '#' && in_whitespace {
             return Some(i);
         }
-        in_whitespace = c == ' ' || c == '\t';
+        in_whitespace = (c == ' ' || c == '\t') && !escaped;
+        escaped = c == '\\' && !escaped;
     }
     None
 }
 
+/// The text of a rule line's inline comment, from the `#` with trailing
+/// whitespace trimmed, e.g. `# owned by platform`.
+pub fn inline_comment(line: &str) -> Option<&str> {
+    let char_off = find_inline_comment_start(line)?;
+    let byte_off = line.char_indices().nth(char_off).map(|(i, _)| i)?;
+    Some(line[byte_off..].trim_end())
+}
+
 /// Parse a CODEOWNERS file into structured lines (without positions)
 #[allow(dead_code)]
 pub fn parse_codeowners_file(content: &str) -> Vec<CodeownersLine> {
@@ -345,12 +393,15 @@ pub fn find_owner_at_position(line: &str, char_idx: usize) -> Option<String> {
     None
 }
 
-/// Format a CODEOWNERS file: normalize rule spacing, preserve comments exactly
+/// Format a CODEOWNERS file: normalize rule spacing, preserve comments exactly.
+/// A byte order mark is dropped and line endings become LF.
 pub fn format_codeowners(content: &str) -> String {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
     let mut result = Vec::new();
     let mut prev_was_empty = false;
 
     for line in content.lines() {
+        let line = line.strip_suffix('\r').unwrap_or(line);
         let trimmed = line.trim();
 
         // Preserve blank lines but collapse multiple
@@ -381,24 +432,15 @@ pub fn format_codeowners(content: &str) -> String {
         let pattern = parts[0];
         let owners = &parts[1..];
 
-        // Find inline comment in the original line
-        let inline_comment = find_inline_comment_start(line).map(|char_off| {
-            line.chars()
-                .skip(char_off)
-                .collect::<String>()
-                .trim_end()
-                .to_string()
-        });
-
         let mut formatted = if owners.is_empty() {
             pattern.to_string()
         } else {
             format!("{} {}", pattern, owners.join(" "))
         };
 
-        if let Some(comment) = inline_comment {
+        if let Some(comment) = inline_comment(line) {
             formatted.push(' ');
-            formatted.push_str(&comment);
+            formatted.push_str(comment);
         }
 
         result.push(formatted);
@@ -412,10 +454,105 @@ pub fn format_codeowners(content: &str) -> String {
     output
 }
 
+/// A GitLab-style section header, e.g. `[Docs] @docs-team` or `^[Optional][2]`.
+///
+/// GitHub has no notion of sections and treats these lines as (invalid)
+/// rules; they only affect resolution under GitLab semantics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SectionHeader {
+    /// Section name without brackets, as written.
+    pub name: String,
+    /// `true` for optional sections (`^[Name]`).
+    pub optional: bool,
+    /// Default owners applied to rules in this section that list none.
+    pub default_owners: Vec<String>,
+}
+
+/// Parse a line as a GitLab section header, returning `None` if it isn't one.
+///
+/// Accepts `[Name]`, `^[Name]`, and an optional approval count `[Name][2]`,
+/// followed by zero or more default owners.
+///
+/// # Example
+///
+/// ```
+/// use codeowners_parser::parser::parse_section_header;
+///
+/// let header = parse_section_header("[Frontend Docs] @docs-team").unwrap();
+/// assert_eq!(header.name, "Frontend Docs");
+/// assert_eq!(header.default_owners, vec!["@docs-team"]);
+/// assert!(parse_section_header("/src/ @core").is_none());
+/// ```
+pub fn parse_section_header(line: &str) -> Option<SectionHeader> {
+    let trimmed = line.trim();
+    let (optional, rest) = match trimmed.strip_prefix('^') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed),
+    };
+    let rest = rest.strip_prefix('[')?;
+    let close = rest.find(']')?;
+    let name = rest[..close].trim();
+    if name.is_empty() {
+        return None;
+    }
+    let mut rest = &rest[close + 1..];
+
+    // Optional approval count: `[2]`
+    if let Some(count) = rest.strip_prefix('[') {
+        let close = count.find(']')?;
+        if !count[..close].chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        rest = &count[close + 1..];
+    }
+
+    // Anything glued to the closing bracket means this isn't a header
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    let default_owners = rest
+        .split_whitespace()
+        .take_while(|part| !part.starts_with('#'))
+        .map(|s| s.to_string())
+        .collect();
+
+    Some(SectionHeader {
+        name: name.to_string(),
+        optional,
+        default_owners,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_section_header_basic() {
+        let header = parse_section_header("[Docs]").unwrap();
+        assert_eq!(header.name, "Docs");
+        assert!(!header.optional);
+        assert!(header.default_owners.is_empty());
+    }
+
+    #[test]
+    fn test_parse_section_header_optional_with_approvals_and_owners() {
+        let header = parse_section_header("^[Backend Team][2] @org/backend @lead # note").unwrap();
+        assert_eq!(header.name, "Backend Team");
+        assert!(header.optional);
+        assert_eq!(header.default_owners, vec!["@org/backend", "@lead"]);
+    }
+
+    #[test]
+    fn test_parse_section_header_rejects_non_headers() {
+        assert!(parse_section_header("*.rs @rust").is_none());
+        assert!(parse_section_header("[]").is_none());
+        assert!(parse_section_header("[abc]def @owner").is_none());
+        assert!(parse_section_header("[Docs][x]").is_none());
+        assert!(parse_section_header("[unterminated").is_none());
+    }
+
     #[test]
     fn test_parse_empty_file() {
         let lines = parse_codeowners_file("");
@@ -506,6 +643,56 @@ mod tests {
         assert_eq!(lines[0].owners_start, 5);
     }
 
+    #[test]
+    fn test_parse_strips_bom_and_crlf() {
+        let lines = parse_codeowners_file_with_positions(
+            "\u{feff}*.rs @rust\r\n# docs\r\n/docs/ @docs # inline\r",
+        );
+        assert_eq!(
+            lines[0].content,
+            CodeownersLine::Rule {
+                pattern: "*.rs".to_string(),
+                owners: vec!["@rust".to_string()],
+            }
+        );
+        // Positions still count the BOM's three bytes, so they index the raw line
+        assert_eq!(lines[0].pattern_start, 3);
+        assert_eq!(lines[0].pattern_end, 7);
+        assert_eq!(lines[0].owners_start, 8);
+        assert_eq!(
+            lines[1].content,
+            CodeownersLine::Comment("# docs".to_string())
+        );
+        assert_eq!(
+            lines[2].content,
+            CodeownersLine::Rule {
+                pattern: "/docs/".to_string(),
+                owners: vec!["@docs".to_string()],
+            }
+        );
+        assert_eq!(lines[2].comment_start, Some(13));
+    }
+
+    #[test]
+    fn test_parse_bom_only_stripped_from_first_line() {
+        let lines = parse_codeowners_file("*.rs @rust\n\u{feff}/docs/ @docs");
+        assert_eq!(
+            lines[1],
+            CodeownersLine::Rule {
+                pattern: "\u{feff}/docs/".to_string(),
+                owners: vec!["@docs".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_format_codeowners_normalizes_bom_and_crlf() {
+        assert_eq!(
+            format_codeowners("\u{feff}# Owners\r\n*.rs   @rust\r\n"),
+            "# Owners\n*.rs @rust\n"
+        );
+    }
+
     #[test]
     fn test_serialize_roundtrip() {
         let original = "# Comment\n*.rs @owner\n/src/ @team";
@@ -716,6 +903,15 @@ mod tests {
         assert_eq!(lines[0].owners_start, 7);
     }
 
+    #[test]
+    fn test_parse_positions_owner_spelled_inside_pattern() {
+        // The owner's text also starts the pattern
+        let lines = parse_codeowners_file_with_positions("@alice/x @alice\n@bob\t@bob");
+        assert_eq!(lines[0].owners_start, 9);
+        assert_eq!(lines[1].pattern_end, 4);
+        assert_eq!(lines[1].owners_start, 5);
+    }
+
     #[test]
     fn test_parse_whitespace_only_line() {
         let lines = parse_codeowners_file("   \t   ");
@@ -822,6 +1018,75 @@ mod tests {
         assert_eq!(lines[0].comment_start, Some(12));
     }
 
+    #[test]
+    fn test_inline_comment_text() {
+        assert_eq!(
+            inline_comment("src/ @team # owned by platform  "),
+            Some("# owned by platform")
+        );
+        assert_eq!(inline_comment("docs/ @ünïcode #note"), Some("#note"));
+        assert_eq!(inline_comment("src/ @team"), None);
+        assert_eq!(inline_comment("src/ @team#not-a-comment"), None);
+    }
+
+    #[test]
+    fn test_parse_inline_comment_stops_owners() {
+        let lines = parse_codeowners_file("src/ @team # owned by @platform");
+        assert_eq!(
+            lines[0],
+            CodeownersLine::Rule {
+                pattern: "src/".to_string(),
+                owners: vec!["@team".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_escaped_space_pattern() {
+        let lines = parse_codeowners_file_with_positions("/my\\ docs/ @docs # /not\\ this");
+        assert_eq!(
+            lines[0].content,
+            CodeownersLine::Rule {
+                pattern: "/my\\ docs/".to_string(),
+                owners: vec!["@docs".to_string()],
+            }
+        );
+        assert_eq!(lines[0].owners_start, 11);
+        assert_eq!(lines[0].comment_start, Some(17));
+    }
+
+    #[test]
+    fn test_parse_escaped_hash_pattern() {
+        // A leading `\#` escapes the hash: a rule, not a comment
+        let lines = parse_codeowners_file_with_positions("\\#notes.md @docs # real comment");
+        assert_eq!(
+            lines[0].content,
+            CodeownersLine::Rule {
+                pattern: "\\#notes.md".to_string(),
+                owners: vec!["@docs".to_string()],
+            }
+        );
+        assert_eq!(lines[0].comment_start, Some(17));
+
+        // Offsets are bytes, also past non-ASCII text
+        let line = "ü/ @docs # comment";
+        let lines = parse_codeowners_file_with_positions(line);
+        assert_eq!(lines[0].owners_start, 4);
+        assert_eq!(
+            &line[lines[0].comment_start.unwrap() as usize..],
+            "# comment"
+        );
+
+        // A hash inside a pattern isn't preceded by whitespace
+        let lines = parse_codeowners_file_with_positions("docs/\\#draft/ @docs");
+        assert_eq!(lines[0].comment_start, None);
+        assert!(matches!(
+            &lines[0].content,
+            CodeownersLine::Rule { pattern, owners }
+                if pattern == "docs/\\#draft/" && owners == &vec!["@docs".to_string()]
+        ));
+    }
+
     #[test]
     fn test_parse_no_inline_comment() {
         let lines = parse_codeowners_file_with_positions("*.rs @owner");
@@ -8,12 +8,30 @@
 //!
 //! For the minority of truly globby patterns (`src/**/*.rs`), we fall through
 //! to [`fast_glob`](https://crates.io/crates/fast-glob).
+//!
+//! Brace expansion (`*.{js,ts}`) is not part of GitHub's syntax, so it is
+//! opt-in via [`PatternOptions::brace_expansion`].
+//!
+//! As on GitHub, wildcards match names with a leading dot: `*` covers
+//! `.env` and `*.yml` covers `.github/workflows/ci.yml`.
+
+use std::borrow::Cow;
+
+/// Options controlling how [`CompiledPattern::with_options`] compiles a pattern.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PatternOptions {
+    /// Expand `{a,b,c}` groups into alternative patterns at compile time.
+    ///
+    /// Off by default: GitHub does not support brace expansion.
+    pub brace_expansion: bool,
+}
 
 /// A CODEOWNERS pattern, pre-processed for repeated fast matching.
 ///
 /// Use [`CompiledPattern::new`] to compile, then [`CompiledPattern::matches`]
 /// to match paths. Construction is O(pattern length); matching is close to
 /// O(path length) for all variants except the glob ones.
+#[derive(Debug, Clone)]
 pub enum CompiledPattern {
     /// Matches everything (unanchored `*` or `**`).
     MatchAll,
@@ -31,21 +49,50 @@ pub enum CompiledPattern {
     SingleSegmentGlob(String),
     /// Multi-segment glob like `src/**/*.rs` — matched directly via `fast_glob`.
     MultiSegmentGlob(String),
-    /// Anchored directory pattern like `/src/` — matches a prefix at the root only.
+    /// Glob naming directories, like `**/logs`, `a/**/b`, `src/*/` or
+    /// `src/*/**` — matches paths that are, or lie beneath, a match. Stored
+    /// without the trailing `/` or `/**`; unanchored single-segment globs
+    /// (`logs*/`) get a `**/` prefix.
+    GlobDirectory(String),
+    /// Anchored directory pattern like `/src/` or `src/**` — matches the
+    /// directory and everything beneath it, at the root only.
     AnchoredDirectory(String),
-    /// Unanchored directory pattern like `docs/` — matches anywhere in the tree.
+    /// Unanchored directory pattern like `docs/` — matches the directory and
+    /// everything beneath it, anywhere in the tree.
     UnanchoredDirectory(String),
-    /// An exact path, always anchored (no wildcards, no trailing slash).
+    /// A pattern with a trailing `/`, like `docs/`, `/src/` or `log*/`.
+    /// GitHub only treats these as directories: they match paths strictly
+    /// beneath a directory the inner pattern matches, never a file of the
+    /// same name.
+    DirectoryContents(Box<CompiledPattern>),
+    /// An exact path anchored to the root: either written with a leading `/`
+    /// (`/build`) or containing a `/` (`src/main.rs`). No wildcards, no
+    /// trailing slash.
     Exact(String),
+    /// A bare name like `build` or `Makefile` (no `/` at all) — matches a
+    /// file or directory of that name at any depth.
+    UnanchoredExact(String),
+    /// Brace-expanded pattern — matches if any expansion matches. Only
+    /// produced when [`PatternOptions::brace_expansion`] is enabled.
+    Alternatives(Vec<CompiledPattern>),
 }
 
 impl CompiledPattern {
     /// Compile a CODEOWNERS pattern string into a specialized matcher.
     ///
     /// The leading `/` (anchor) is inspected to distinguish anchored from
-    /// unanchored directory patterns; a trailing `/` selects directory
-    /// matching over exact matching.
+    /// unanchored directory and exact patterns; a trailing `/` selects
+    /// directory matching over exact matching, wrapped in
+    /// [`CompiledPattern::DirectoryContents`].
     pub fn new(pattern: &str) -> Self {
+        let compiled = Self::compile(pattern);
+        if pattern.ends_with('/') {
+            return CompiledPattern::DirectoryContents(Box::new(compiled));
+        }
+        compiled
+    }
+
+    fn compile(pattern: &str) -> Self {
         let anchored = pattern.starts_with('/');
         let pattern = pattern.trim_start_matches('/');
 
@@ -57,8 +104,12 @@ impl CompiledPattern {
             return CompiledPattern::MatchAll;
         }
 
-        // Patterns with wildcards
-        if pattern.contains('*') {
+        // Patterns with wildcards (`?` is a single-character wildcard)
+        if pattern.contains(['*', '?']) {
+            let (base, is_dir) = split_directory_glob(pattern);
+            if !anchored && is_dir && !pattern.trim_end_matches('/').contains('/') {
+                return CompiledPattern::GlobDirectory(format!("**/{}", base));
+            }
             // Unanchored single-segment like *.rs
             if !anchored && !pattern.contains('/') {
                 // Fast path: *.ext patterns use suffix check (no glob needed)
@@ -69,9 +120,20 @@ impl CompiledPattern {
                 }
                 return CompiledPattern::SingleSegmentGlob(format!("**/{}", pattern));
             }
+            // `docs/**` is the docs directory itself and everything below it
+            if is_dir && !has_glob_syntax(base) {
+                return CompiledPattern::AnchoredDirectory(base.to_string());
+            }
+            if is_dir || !has_glob_syntax(last_segment(base)) {
+                return CompiledPattern::GlobDirectory(base.to_string());
+            }
             return CompiledPattern::MultiSegmentGlob(pattern.to_string());
         }
 
+        // Without wildcards the pattern is literal text, so drop its escapes
+        let pattern = unescape(pattern);
+        let pattern = pattern.as_ref();
+
         // Directory patterns (trailing /)
         if pattern.ends_with('/') {
             let dir = pattern.trim_end_matches('/').to_string();
@@ -82,10 +144,44 @@ impl CompiledPattern {
             }
         }
 
-        // Exact patterns (always anchored)
+        // Exact patterns: anchored unless a bare name
+        if !anchored && !pattern.contains('/') {
+            return CompiledPattern::UnanchoredExact(pattern.to_string());
+        }
         CompiledPattern::Exact(pattern.to_string())
     }
 
+    /// Compile a pattern with non-default [`PatternOptions`].
+    ///
+    /// With `brace_expansion`, each `{..}` group is expanded via
+    /// [`expand_braces`] and every alternative is compiled separately.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use codeowners_parser::pattern::{CompiledPattern, PatternOptions};
+    ///
+    /// let options = PatternOptions { brace_expansion: true };
+    /// let compiled = CompiledPattern::with_options("src/**/*.{js,ts}", options);
+    /// assert!(compiled.matches("src/app/index.ts"));
+    /// assert!(!compiled.matches("src/app/index.rs"));
+    /// ```
+    pub fn with_options(pattern: &str, options: PatternOptions) -> Self {
+        if !options.brace_expansion || !has_brace_group(pattern) {
+            return Self::new(pattern);
+        }
+
+        let mut alternatives: Vec<CompiledPattern> = expand_braces(pattern)
+            .iter()
+            .filter(|p| !p.trim_start_matches('/').is_empty())
+            .map(|p| Self::new(p))
+            .collect();
+        if alternatives.len() == 1 {
+            return alternatives.remove(0);
+        }
+        CompiledPattern::Alternatives(alternatives)
+    }
+
     /// Test whether `path` matches this pattern.
     ///
     /// Empty paths never match. Pattern semantics follow GitHub's
@@ -107,38 +203,280 @@ impl CompiledPattern {
             }
             CompiledPattern::SingleSegmentGlob(glob) => fast_glob::glob_match(glob, path),
             CompiledPattern::MultiSegmentGlob(glob) => fast_glob::glob_match(glob, path),
+            CompiledPattern::GlobDirectory(glob) => {
+                self_and_ancestors(path).any(|p| fast_glob::glob_match(glob, p))
+            }
             CompiledPattern::AnchoredDirectory(dir) => {
                 path.starts_with(dir.as_str())
                     && (path.len() == dir.len() || path.as_bytes().get(dir.len()) == Some(&b'/'))
             }
-            CompiledPattern::UnanchoredDirectory(dir) => {
-                // Match at root
-                if path.starts_with(dir.as_str())
-                    && (path.len() == dir.len() || path.as_bytes().get(dir.len()) == Some(&b'/'))
-                {
-                    return true;
-                }
-                // Match anywhere: check each path segment boundary
-                let dir_bytes = dir.as_bytes();
-                let path_bytes = path.as_bytes();
-                for (i, _) in path.match_indices('/') {
-                    let rest = &path_bytes[i + 1..];
-                    if rest.len() >= dir_bytes.len()
-                        && &rest[..dir_bytes.len()] == dir_bytes
-                        && (rest.len() == dir_bytes.len() || rest[dir_bytes.len()] == b'/')
-                    {
-                        return true;
-                    }
-                }
-                false
+            CompiledPattern::UnanchoredDirectory(dir) | CompiledPattern::UnanchoredExact(dir) => {
+                matches_at_any_depth(dir, path)
             }
             CompiledPattern::Exact(exact) => {
                 path == exact
                     || (path.starts_with(exact.as_str())
                         && path.as_bytes().get(exact.len()) == Some(&b'/'))
             }
+            CompiledPattern::DirectoryContents(dir) => path
+                .rsplit_once('/')
+                .is_some_and(|(parent, _)| dir.matches(parent)),
+            CompiledPattern::Alternatives(alternatives) => {
+                alternatives.iter().any(|p| p.matches(path))
+            }
+        }
+    }
+}
+
+/// Whether `pattern` contains an expandable `{a,b}` group.
+///
+/// Groups without a top-level comma (`{}`, `{a}`), unterminated groups,
+/// and escaped braces (`\{`) are literal and don't count.
+pub fn has_brace_group(pattern: &str) -> bool {
+    find_brace_group(pattern).is_some()
+}
+
+/// Most alternatives one pattern may expand to. Groups multiply
+/// (`{a,b}{c,d}` is four patterns), so a handful of them can otherwise
+/// produce millions; alternatives past this are dropped.
+pub const MAX_BRACE_EXPANSIONS: usize = 256;
+
+/// Expand `{a,b,c}` groups in `pattern` into every alternative, left to right.
+///
+/// Follows shell semantics: groups nest (`{a,{b,c}}`), empty alternatives
+/// are allowed (`foo{,.bak}`), and groups without a comma, unterminated
+/// groups, and backslash-escaped braces are kept literally. Escapes are
+/// preserved in the output so glob matching still treats them as literal.
+///
+/// At most [`MAX_BRACE_EXPANSIONS`] alternatives are returned; use
+/// [`brace_expansion_overflows`] to tell whether any were dropped.
+///
+/// # Example
+///
+/// ```
+/// use codeowners_parser::pattern::expand_braces;
+///
+/// assert_eq!(
+///     expand_braces("/{src,lib}/*.{js,ts}"),
+///     vec!["/src/*.js", "/src/*.ts", "/lib/*.js", "/lib/*.ts"]
+/// );
+/// assert_eq!(expand_braces("*.rs"), vec!["*.rs"]);
+/// ```
+pub fn expand_braces(pattern: &str) -> Vec<String> {
+    let mut expanded = Vec::new();
+    expand_braces_into(pattern, &mut expanded);
+    expanded
+}
+
+/// Whether `pattern` expands to more than [`MAX_BRACE_EXPANSIONS`]
+/// alternatives, so [`expand_braces`] drops some of them.
+pub fn brace_expansion_overflows(pattern: &str) -> bool {
+    !expand_braces_into(pattern, &mut Vec::new())
+}
+
+/// Push `pattern`'s alternatives onto `out`, stopping once it holds
+/// [`MAX_BRACE_EXPANSIONS`]. Returns false if alternatives were left out.
+fn expand_braces_into(pattern: &str, out: &mut Vec<String>) -> bool {
+    let Some((open, close)) = find_brace_group(pattern) else {
+        if out.len() == MAX_BRACE_EXPANSIONS {
+            return false;
+        }
+        out.push(pattern.to_string());
+        return true;
+    };
+
+    let prefix = &pattern[..open];
+    let suffix = &pattern[close + 1..];
+    split_top_level_commas(&pattern[open + 1..close])
+        .into_iter()
+        .all(|alt| expand_braces_into(&format!("{}{}{}", prefix, alt, suffix), out))
+}
+
+/// Byte offsets of the `{` and `}` of the group to expand next: the first
+/// outermost group containing a top-level comma, or failing that, the first
+/// such group nested inside a literal one (`{{a,b}}`).
+fn find_brace_group(pattern: &str) -> Option<(usize, usize)> {
+    // (offset of `{`, saw a comma at this depth)
+    let mut stack: Vec<(usize, bool)> = Vec::new();
+    let mut nested: Option<(usize, usize)> = None;
+    let mut escaped = false;
+
+    for (i, c) in pattern.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            '{' => stack.push((i, false)),
+            ',' => {
+                if let Some(top) = stack.last_mut() {
+                    top.1 = true;
+                }
+            }
+            '}' => {
+                let Some((open, has_comma)) = stack.pop() else {
+                    continue;
+                };
+                if has_comma && stack.is_empty() {
+                    return Some((open, i));
+                }
+                if has_comma && nested.is_none() {
+                    nested = Some((open, i));
+                }
+                if stack.is_empty() && nested.is_some() {
+                    return nested;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    nested
+}
+
+/// Split a brace group body on commas that aren't nested or escaped
+fn split_top_level_commas(body: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut escaped = false;
+
+    for (i, c) in body.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(&body[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&body[start..]);
+    parts
+}
+
+/// Normalize a file path for matching: `\` separators become `/` and any
+/// leading `./` is stripped, so `./src/main.rs` and `src\main.rs` both
+/// become `src/main.rs`.
+///
+/// Only paths are normalized. Patterns keep their backslashes, which escape
+/// characters rather than separate directories.
+///
+/// # Example
+///
+/// ```
+/// use codeowners_parser::pattern::normalize_path;
+///
+/// assert_eq!(normalize_path("./src/main.rs"), "src/main.rs");
+/// assert_eq!(normalize_path(r".\src\main.rs"), "src/main.rs");
+/// assert_eq!(normalize_path("src/main.rs"), "src/main.rs");
+/// ```
+pub fn normalize_path(path: &str) -> Cow<'_, str> {
+    if path.contains('\\') {
+        Cow::Owned(path.replace('\\', "/").trim_start_matches("./").to_string())
+    } else {
+        Cow::Borrowed(path.trim_start_matches("./"))
+    }
+}
+
+/// `pattern` with its anchoring spelled out, so equivalent rules compare
+/// equal: a `/` before the last character roots it (`src/api/` is
+/// `/src/api/`), while `src/`, `build` and `*.rs` match at any depth and
+/// stay as is.
+///
+/// ```
+/// use codeowners_parser::pattern::anchored_form;
+///
+/// assert_eq!(anchored_form("src/api/"), "/src/api/");
+/// assert_eq!(anchored_form("/src/api/"), "/src/api/");
+/// assert_eq!(anchored_form("build"), "build");
+/// assert_eq!(anchored_form("/build"), "/build");
+/// ```
+pub fn anchored_form(pattern: &str) -> Cow<'_, str> {
+    let rooted = pattern
+        .trim_end_matches('/')
+        .trim_start_matches('/')
+        .contains('/');
+    if pattern.starts_with('/') || !rooted {
+        Cow::Borrowed(pattern)
+    } else {
+        Cow::Owned(format!("/{}", pattern))
+    }
+}
+
+/// Strip a trailing `/` or `/**` from a glob, reporting whether it named a
+/// directory (and so covers everything beneath its matches)
+fn split_directory_glob(pattern: &str) -> (&str, bool) {
+    let base = pattern.trim_end_matches('/');
+    match base.strip_suffix("/**") {
+        Some(dir) if !dir.is_empty() => (dir, true),
+        _ => (base, base.len() < pattern.len()),
+    }
+}
+
+/// `pattern` with backslash escapes (`\ `, `\#`) resolved to the characters
+/// they stand for
+fn unescape(pattern: &str) -> Cow<'_, str> {
+    if !pattern.contains('\\') {
+        return Cow::Borrowed(pattern);
+    }
+    let mut out = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => out.extend(chars.next()),
+            c => out.push(c),
         }
     }
+    Cow::Owned(out)
+}
+
+/// Whether `s` contains glob syntax understood by `fast_glob`
+fn has_glob_syntax(s: &str) -> bool {
+    s.contains(['*', '?', '[', '{', '\\'])
+}
+
+fn last_segment(pattern: &str) -> &str {
+    pattern.rsplit('/').next().unwrap_or(pattern)
+}
+
+/// `path` followed by each of its ancestor directories, deepest first
+fn self_and_ancestors(path: &str) -> impl Iterator<Item = &str> {
+    std::iter::once(path).chain(path.rmatch_indices('/').map(move |(i, _)| &path[..i]))
+}
+
+/// Whether `path` is `prefix` or lies below it, where `prefix` may start at
+/// any segment boundary (no allocations)
+fn matches_at_any_depth(prefix: &str, path: &str) -> bool {
+    let prefix_bytes = prefix.as_bytes();
+    let path_bytes = path.as_bytes();
+    std::iter::once(0)
+        .chain(path.match_indices('/').map(|(i, _)| i + 1))
+        .any(|start| {
+            let rest = &path_bytes[start..];
+            rest.starts_with(prefix_bytes)
+                && (rest.len() == prefix_bytes.len() || rest[prefix_bytes.len()] == b'/')
+        })
+}
+
+/// Match a slash-free glob at any depth, equivalent to matching
+/// `**/{glob}` without building that string on every call.
+fn glob_match_any_depth(glob: &str, path: &str) -> bool {
+    // `*` and `?` never cross `/`, so only the file name can match
+    if !glob.contains("**") && !glob.contains('[') {
+        let name = path.rsplit('/').next().unwrap_or(path);
+        return fast_glob::glob_match(glob, name);
+    }
+    std::iter::once(0)
+        .chain(path.match_indices('/').map(|(i, _)| i + 1))
+        .any(|start| fast_glob::glob_match(glob, &path[start..]))
 }
 
 /// Simple glob pattern matching for CODEOWNERS patterns
@@ -147,18 +485,42 @@ impl CompiledPattern {
 /// - Leading `/` anchors pattern to repository root
 /// - No leading `/`:
 ///   - If ends with `/`: directory pattern, matches anywhere in tree
+/// - A trailing `/` only matches directories: `docs/` owns `docs/a.md` but
+///   not a file called `docs`
 ///   - If contains `/`: implicitly anchored to root
 ///   - If no `/` and has `*`: matches at any depth (e.g., `*.rs`)
-///   - If no `/` and no `*`: anchored to root
+///   - If no `/` and no `*`: matches a file or directory at any depth
 /// - `*` matches any characters except `/`
-/// - `**` matches zero or more directories
+/// - `**` matches zero or more directories, wherever it appears: `**/b`
+///   matches `b` at the root and `a/**/b` matches `a/b`
+///
+/// `path` is normalized with [`normalize_path`] first. Matching doesn't
+/// allocate for normalized paths, but the pattern is re-classified on every
+/// call: when matching one pattern against many paths, compile it once with
+/// [`CompiledPattern::new`].
 #[inline]
 pub fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let path = normalize_path(path);
+    let path = path.as_ref();
+
     // Empty path never matches (edge case)
     if path.is_empty() {
         return false;
     }
 
+    // A trailing `/` names a directory, so only what's inside it matches:
+    // match the parent directory against the directory semantics below
+    if pattern.ends_with('/') {
+        return path
+            .rsplit_once('/')
+            .is_some_and(|(parent, _)| directory_pattern_matches(pattern, parent));
+    }
+    directory_pattern_matches(pattern, path)
+}
+
+/// [`pattern_matches`] for a normalized, non-empty `path`, where directory
+/// patterns match the directory itself as well as its contents
+fn directory_pattern_matches(pattern: &str, path: &str) -> bool {
     // Check if pattern is anchored (has leading /)
     let anchored = pattern.starts_with('/');
     let pattern = pattern.trim_start_matches('/');
@@ -169,8 +531,8 @@ pub fn pattern_matches(pattern: &str, path: &str) -> bool {
         return true;
     }
 
-    // Handle patterns with wildcards
-    if pattern.contains('*') {
+    // Handle patterns with wildcards (`?` is a single-character wildcard)
+    if pattern.contains(['*', '?']) {
         // Anchored single-star only matches root level
         // /* should only match files directly in root, not nested
         if anchored && pattern == "*" {
@@ -178,22 +540,41 @@ pub fn pattern_matches(pattern: &str, path: &str) -> bool {
             return !path.contains('/');
         }
 
+        let (base, is_dir) = split_directory_glob(pattern);
+        if !anchored && is_dir && !pattern.trim_end_matches('/').contains('/') {
+            return self_and_ancestors(path).any(|p| glob_match_any_depth(base, p));
+        }
+
         // Single-segment patterns like *.rs (unanchored) match at any depth
         if !anchored && !pattern.contains('/') {
             // Fast path: *.ext is just a suffix check
             if let Some(ext) = pattern.strip_prefix('*') {
-                return path.ends_with(ext)
-                    && (path.len() == ext.len()
-                        || path.as_bytes()[path.len() - ext.len() - 1] != b'/');
+                if !ext.contains(['*', '?']) {
+                    return path.ends_with(ext)
+                        && (path.len() == ext.len()
+                            || path.as_bytes()[path.len() - ext.len() - 1] != b'/');
+                }
             }
-            let glob_pattern = format!("**/{}", pattern);
-            return fast_glob::glob_match(&glob_pattern, path);
+            return glob_match_any_depth(pattern, path);
+        }
+
+        // `docs/**` is the docs directory itself and everything below it
+        if is_dir && !has_glob_syntax(base) {
+            return path == base || starts_with_dir(path, base);
+        }
+        // Globs naming directories (`**/logs`, `a/**/b`) own their contents
+        if is_dir || !has_glob_syntax(last_segment(base)) {
+            return self_and_ancestors(path).any(|p| fast_glob::glob_match(base, p));
         }
 
         // Multi-segment patterns or anchored patterns: match directly
         return fast_glob::glob_match(pattern, path);
     }
 
+    // Without wildcards the pattern is literal text, so drop its escapes
+    let pattern = unescape(pattern);
+    let pattern = pattern.as_ref();
+
     // Handle directory patterns (trailing /)
     if pattern.ends_with('/') {
         let dir = pattern.trim_end_matches('/');
@@ -204,29 +585,17 @@ pub fn pattern_matches(pattern: &str, path: &str) -> bool {
                 && (path.len() == dir.len() || path.as_bytes().get(dir.len()) == Some(&b'/'));
         } else {
             // docs/ - matches any directory named "docs" anywhere
-            if path.starts_with(dir)
-                && (path.len() == dir.len() || path.as_bytes().get(dir.len()) == Some(&b'/'))
-            {
-                return true;
-            }
-            // Check each path segment boundary (no allocations)
-            let dir_bytes = dir.as_bytes();
-            let path_bytes = path.as_bytes();
-            for (i, _) in path.match_indices('/') {
-                let rest = &path_bytes[i + 1..];
-                if rest.len() >= dir_bytes.len()
-                    && &rest[..dir_bytes.len()] == dir_bytes
-                    && (rest.len() == dir_bytes.len() || rest[dir_bytes.len()] == b'/')
-                {
-                    return true;
-                }
-            }
-            return false;
+            return matches_at_any_depth(dir, path);
         }
     }
 
+    // Bare names (no / at all) match a file or directory at any depth
+    if !anchored && !pattern.contains('/') {
+        return matches_at_any_depth(pattern, path);
+    }
+
     // Handle exact/prefix patterns (no wildcards, no trailing /)
-    // These are always anchored (either explicitly or implicitly)
+    // These are anchored (either explicitly or implicitly by a /)
     path == pattern
         || (path.starts_with(pattern) && path.as_bytes().get(pattern.len()) == Some(&b'/'))
 }
@@ -254,14 +623,27 @@ pub fn pattern_subsumes(a: &str, b: &str) -> bool {
 
     // For exact paths (no wildcards, no trailing /), patterns containing /
     // are implicitly anchored, so /src/foo and src/foo are equivalent
-    let a_is_exact = !a.contains('*') && !a.ends_with('/');
-    let b_is_exact = !b.contains('*') && !b.ends_with('/');
+    let a_is_exact = !a.contains(['*', '?']) && !a.ends_with('/');
+    let b_is_exact = !b.contains(['*', '?']) && !b.ends_with('/');
     if a_is_exact && b_is_exact && a == b && a.contains('/') {
         // Both are exact paths with /, one has leading / and one doesn't
         // They match the same files, so b subsumes a
         return true;
     }
 
+    // A bare name like `build` matches that file or directory at any depth,
+    // so it subsumes every exact path that passes through it
+    if a_is_exact && b_is_exact && !b_anchored && !b.contains('/') {
+        return a.split('/').any(|segment| segment == b);
+    }
+
+    // An anchored `/*` only covers root-level paths, and not what's below
+    // them: it subsumes `/*.md` but not `/src/a.rs`, or `/build`, which also
+    // owns the `build/` directory
+    if b == "*" && b_anchored {
+        return a_anchored && !a.contains('/') && a.contains(['*', '?']) && !a.contains("**");
+    }
+
     // Universal patterns subsume everything
     if b == "*" || b == "**" {
         return true;
@@ -279,6 +661,15 @@ pub fn pattern_subsumes(a: &str, b: &str) -> bool {
         return false;
     }
 
+    // `docs/*` covers direct children only: `docs/a.md` or `docs/*.md`,
+    // but not `docs/sub/a.md`, `docs/sub/` or `docs/**`
+    if let Some(b_dir) = b.strip_suffix("/*") {
+        return match a.rsplit_once('/') {
+            Some((parent, name)) => parent == b_dir && !name.is_empty() && !name.contains("**"),
+            None => false,
+        };
+    }
+
     // Directory patterns: /src/lib/ is subsumed by /src/
     let a_dir = a
         .trim_end_matches('/')
@@ -317,7 +708,8 @@ pub fn pattern_subsumes(a: &str, b: &str) -> bool {
             // Unanchored file path not subsumed by anchored dir
             return false;
         }
-        return a == b_dir || starts_with_dir(a, b_dir);
+        // A trailing `/` doesn't match a file of the directory's name
+        return (a == b_dir && !b.ends_with('/')) || starts_with_dir(a, b_dir);
     }
 
     false
@@ -333,6 +725,332 @@ fn starts_with_dir(path: &str, dir: &str) -> bool {
 mod tests {
     use super::*;
 
+    /// Both matchers must agree; returns their shared answer
+    fn matches_both(pattern: &str, path: &str) -> bool {
+        let compiled = CompiledPattern::new(pattern).matches(path);
+        assert_eq!(
+            compiled,
+            pattern_matches(pattern, path),
+            "CompiledPattern and pattern_matches disagree on {} vs {}",
+            pattern,
+            path
+        );
+        compiled
+    }
+
+    #[test]
+    fn test_exact_file_vs_directory_vs_prefix() {
+        // (pattern, path, matches): a slash-free-at-the-end, glob-free
+        // pattern names a file or a directory, never a name prefix
+        let cases = [
+            ("/README", "README", true),
+            ("/README", "README/notes.txt", true),
+            ("/README", "README.md", false),
+            ("/README", "READMEs/a.md", false),
+            ("/README", "docs/README", false),
+            ("README", "README", true),
+            ("README", "docs/README", true),
+            ("README", "docs/README/a.md", true),
+            ("README", "README.md", false),
+            ("README", "docs/README.md", false),
+            ("README", "NOT_README", false),
+            ("docs/README", "docs/README", true),
+            ("docs/README", "docs/README.md", false),
+            ("docs/README", "docs/README/a.md", true),
+            ("docs/README", "src/docs/README", false),
+            ("/doc", "docs/a.md", false),
+            ("/docs", "docs", true),
+            ("/docs", "docs/a.md", true),
+            // A trailing slash only matches a directory
+            ("/docs/", "docs/a.md", true),
+            ("/docs/", "docsx/a.md", false),
+        ];
+        for (pattern, path, expected) in cases {
+            assert_eq!(
+                matches_both(pattern, path),
+                expected,
+                "{} vs {}",
+                pattern,
+                path
+            );
+        }
+        assert!(matches!(
+            CompiledPattern::new("/README"),
+            CompiledPattern::Exact(ref p) if p == "README"
+        ));
+    }
+
+    #[test]
+    fn test_question_mark_is_single_char_wildcard() {
+        let cases = [
+            ("/file?.txt", "file1.txt", true),
+            ("/file?.txt", "file.txt", false),
+            ("/file?.txt", "file12.txt", false),
+            ("/file?.txt", "file?.txt", true),
+            ("src/?ib.rs", "src/lib.rs", true),
+            ("src/?ib.rs", "src/a/lib.rs", false),
+            // `?` never matches a `/`
+            ("/a?b", "a/b", false),
+            ("v?.md", "docs/v1.md", true),
+            ("*.?s", "src/main.rs", true),
+            ("*.?s", "src/main.rs.bak", false),
+            ("lo?s/", "app/logs/today.txt", true),
+            ("file?.txt", "file1.txt", true),
+            ("file?.txt", "deep/dir/file1.txt", true),
+            ("file?.txt", "file10.txt", false),
+            ("file?.txt", "file/.txt", false),
+            ("/docs/file?.txt", "docs/file/.txt", false),
+            ("src/**/v?/", "src/api/v2/mod.rs", true),
+        ];
+        for (pattern, path, expected) in cases {
+            assert_eq!(
+                matches_both(pattern, path),
+                expected,
+                "{} vs {}",
+                pattern,
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_question_mark_patterns_compile_to_globs() {
+        assert!(matches!(
+            CompiledPattern::new("file?.txt"),
+            CompiledPattern::SingleSegmentGlob(ref g) if g == "**/file?.txt"
+        ));
+        assert!(matches!(
+            CompiledPattern::new("v?/"),
+            CompiledPattern::DirectoryContents(ref d)
+                if matches!(**d, CompiledPattern::GlobDirectory(ref g) if g == "**/v?")
+        ));
+        assert!(matches!(
+            CompiledPattern::new("/file?.txt"),
+            CompiledPattern::MultiSegmentGlob(_)
+        ));
+    }
+
+    #[test]
+    fn test_single_segment_glob_matches_like_double_star_prefix() {
+        let globs = [
+            "test_*",
+            "*_test.go",
+            "?.md",
+            "a*b*c",
+            "[ab]*.rs",
+            "[!x]",
+            "foo**",
+            "**bar",
+            "a**b",
+            "*.{js,ts}",
+            "README*",
+            "\\*lit",
+        ];
+        let paths = [
+            "test_a",
+            "src/test_a",
+            "src/deep/test_a.rs",
+            "x_test.go",
+            "pkg/x_test.go",
+            "a.md",
+            "docs/a.md",
+            "docs/ab.md",
+            "axbxc",
+            "a/b/c",
+            "a/xbyc",
+            "b.rs",
+            "src/a1.rs",
+            "y",
+            "a/b",
+            "foo/bar",
+            "foobar",
+            "x/foo",
+            "bar",
+            "a/b/bar",
+            "ab",
+            "a/b/ab",
+            "app.ts",
+            "src/app.js",
+            "README.md",
+            "docs/README",
+            "*lit",
+            "d/*lit",
+        ];
+        for glob in globs {
+            let prefixed = format!("**/{}", glob);
+            for path in paths {
+                assert_eq!(
+                    glob_match_any_depth(glob, path),
+                    fast_glob::glob_match(&prefixed, path),
+                    "{glob} vs {path}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path("src/main.rs"), "src/main.rs");
+        assert_eq!(normalize_path("./src/main.rs"), "src/main.rs");
+        assert_eq!(normalize_path("././src/main.rs"), "src/main.rs");
+        assert_eq!(normalize_path(r"src\main.rs"), "src/main.rs");
+        assert_eq!(normalize_path(r".\src\lib\mod.rs"), "src/lib/mod.rs");
+        assert!(matches!(normalize_path("src/main.rs"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_pattern_matches_normalizes_path() {
+        assert!(pattern_matches("/src/", "./src/main.rs"));
+        assert!(pattern_matches("/src/", r"src\main.rs"));
+        assert!(pattern_matches("src/**/*.rs", r".\src\a\b.rs"));
+        assert!(pattern_matches("*.rs", r"src\main.rs"));
+        assert!(!pattern_matches("/docs/", r".\src\main.rs"));
+    }
+
+    #[test]
+    fn test_backslash_escaped_pattern_not_normalized() {
+        // The pattern's `\*` stays an escaped literal `*`; only the path's
+        // separators are normalized
+        assert!(pattern_matches(r"docs/\*.md", "docs/*.md"));
+        assert!(pattern_matches(r"docs/\*.md", r".\docs\*.md"));
+        assert!(!pattern_matches(r"docs/\*.md", "docs/a.md"));
+
+        let compiled = CompiledPattern::new(r"docs/\*.md");
+        assert!(compiled.matches(&normalize_path(r"docs\*.md")));
+        assert!(!compiled.matches(&normalize_path(r"docs\a.md")));
+    }
+
+    #[test]
+    fn test_escaped_space_and_hash_patterns() {
+        for (pattern, path) in [
+            (r"/my\ docs/", "my docs/a.md"),
+            (r"/notes/\#1.md", "notes/#1.md"),
+            (r"/a\ b.md", "a b.md"),
+        ] {
+            assert!(pattern_matches(pattern, path), "{pattern} vs {path}");
+            assert!(
+                CompiledPattern::new(pattern).matches(path),
+                "{pattern} vs {path}"
+            );
+        }
+        assert!(!CompiledPattern::new(r"/my\ docs/").matches("my\\ docs/a.md"));
+    }
+
+    // Brace expansion tests
+    #[test]
+    fn test_expand_braces_simple() {
+        assert_eq!(
+            expand_braces("*.{js,ts,tsx}"),
+            vec!["*.js", "*.ts", "*.tsx"]
+        );
+    }
+
+    #[test]
+    fn test_expand_braces_multiple_groups() {
+        assert_eq!(
+            expand_braces("{a,b}/{c,d}"),
+            vec!["a/c", "a/d", "b/c", "b/d"]
+        );
+    }
+
+    #[test]
+    fn test_expand_braces_nested() {
+        assert_eq!(expand_braces("{a,{b,c}}.rs"), vec!["a.rs", "b.rs", "c.rs"]);
+        assert_eq!(
+            expand_braces("src/{app,lib/{x,y}}/"),
+            vec!["src/app/", "src/lib/x/", "src/lib/y/"]
+        );
+        // Literal outer group, expandable inner group
+        assert_eq!(expand_braces("{{a,b}}"), vec!["{a}", "{b}"]);
+    }
+
+    #[test]
+    fn test_expand_braces_empty_alternatives() {
+        assert_eq!(
+            expand_braces("config{,.local}.toml"),
+            vec!["config.toml", "config.local.toml"]
+        );
+        assert_eq!(expand_braces("{,}"), vec!["", ""]);
+    }
+
+    #[test]
+    fn test_expand_braces_literal_groups() {
+        assert_eq!(expand_braces("{}"), vec!["{}"]);
+        assert_eq!(expand_braces("{single}"), vec!["{single}"]);
+        assert_eq!(expand_braces("{a,b"), vec!["{a,b"]);
+        assert_eq!(expand_braces("a,b}"), vec!["a,b}"]);
+        assert!(!has_brace_group("docs/{}/"));
+    }
+
+    #[test]
+    fn test_expand_braces_escaped() {
+        assert_eq!(expand_braces("\\{a,b\\}"), vec!["\\{a,b\\}"]);
+        assert!(!has_brace_group("\\{a,b\\}"));
+        assert_eq!(expand_braces("{a\\,b,c}"), vec!["a\\,b", "c"]);
+    }
+
+    #[test]
+    fn test_expand_braces_capped() {
+        // 2^10 alternatives
+        let pattern = "{a,b}".repeat(10);
+        let expanded = expand_braces(&pattern);
+        assert_eq!(expanded.len(), MAX_BRACE_EXPANSIONS);
+        assert_eq!(expanded[0], "aaaaaaaaaa");
+        assert!(brace_expansion_overflows(&pattern));
+
+        // 2^8 fits exactly
+        let pattern = "{a,b}".repeat(8);
+        assert_eq!(expand_braces(&pattern).len(), MAX_BRACE_EXPANSIONS);
+        assert!(!brace_expansion_overflows(&pattern));
+        assert!(!brace_expansion_overflows("*.rs"));
+    }
+
+    #[test]
+    fn test_compiled_pattern_brace_expansion() {
+        let on = PatternOptions {
+            brace_expansion: true,
+        };
+        let compiled = CompiledPattern::with_options("*.{js,ts}", on);
+        assert!(matches!(compiled, CompiledPattern::Alternatives(ref alts) if alts.len() == 2));
+        assert!(compiled.matches("src/index.js"));
+        assert!(compiled.matches("index.ts"));
+        assert!(!compiled.matches("index.rs"));
+
+        // Anchoring is preserved per alternative
+        let compiled = CompiledPattern::with_options("/{src,lib}/", on);
+        assert!(compiled.matches("src/main.rs"));
+        assert!(compiled.matches("lib/mod.rs"));
+        assert!(!compiled.matches("vendor/src/main.rs"));
+    }
+
+    #[test]
+    fn test_compiled_pattern_brace_expansion_off_by_default() {
+        let compiled =
+            CompiledPattern::with_options("config{,.local}.toml", PatternOptions::default());
+        assert!(matches!(compiled, CompiledPattern::UnanchoredExact(_)));
+        assert!(!compiled.matches("config.toml"));
+
+        let on = PatternOptions {
+            brace_expansion: true,
+        };
+        let compiled = CompiledPattern::with_options("config{,.local}.toml", on);
+        assert!(compiled.matches("config.toml"));
+        assert!(compiled.matches("config.local.toml"));
+    }
+
+    #[test]
+    fn test_compiled_pattern_brace_expansion_empty_alternative_and_literal_group() {
+        let on = PatternOptions {
+            brace_expansion: true,
+        };
+        // The empty alternative leaves the directory pattern `docs/`
+        let compiled = CompiledPattern::with_options("docs/{x,}", on);
+        assert!(compiled.matches("docs/x"));
+        assert!(compiled.matches("docs/"));
+        let compiled = CompiledPattern::with_options("{a}", on);
+        assert!(matches!(compiled, CompiledPattern::UnanchoredExact(_)));
+    }
+
     // =============================================================================
     // GITHUB CODEOWNERS CONFORMANCE TEST SUITE
     // =============================================================================
@@ -349,6 +1067,8 @@ mod tests {
     // 7. Patterns are case-sensitive
     // 8. `[abc]` character ranges NOT supported (unlike gitignore)
     // 9. `!` negation patterns NOT supported (unlike gitignore)
+    // 10. A glob naming a directory (`**/logs`, `a/**/b`, `docs/**`) also matches
+    //     everything beneath it; `docs/*` only matches direct children
     // =============================================================================
 
     // ---------------------------------------------------------------------------
@@ -487,12 +1207,11 @@ mod tests {
     // ---------------------------------------------------------------------------
     // CATEGORY 6: SINGLE-SEGMENT UNANCHORED PATTERNS (no `/` at all)
     // ---------------------------------------------------------------------------
-    // `Makefile` (no slashes) - behavior depends on interpretation
-    // In gitignore: matches anywhere. In CODEOWNERS: typically anchored.
+    // `Makefile` (no slashes) - as in gitignore, matches at any depth.
+    // `/Makefile` is anchored to the root.
 
     #[test]
     fn test_single_segment_exact() {
-        // Single segment without slashes - anchored to root
         assert!(pattern_matches("Makefile", "Makefile"));
         assert!(pattern_matches("README.md", "README.md"));
     }
@@ -505,10 +1224,55 @@ mod tests {
     }
 
     #[test]
-    fn test_single_segment_no_match_nested() {
-        // Should NOT match same name nested elsewhere (anchored behavior)
-        assert!(!pattern_matches("Makefile", "build/Makefile"));
-        assert!(!pattern_matches("src", "project/src/file.rs"));
+    fn test_single_segment_matches_nested() {
+        assert!(pattern_matches("Makefile", "build/Makefile"));
+        assert!(pattern_matches("src", "project/src/file.rs"));
+        // Whole segments only
+        assert!(!pattern_matches("Makefile", "build/Makefile.old"));
+        assert!(!pattern_matches("src", "project/mysrc/file.rs"));
+    }
+
+    #[test]
+    fn test_anchored_vs_unanchored_name() {
+        for (path, root_only, any_depth) in [
+            ("build", true, true),
+            ("build/out.o", true, true),
+            ("src/build", false, true),
+            ("src/build/out.o", false, true),
+            ("builder/x", false, false),
+            ("src/prebuild", false, false),
+        ] {
+            assert_eq!(
+                pattern_matches("/build", path),
+                root_only,
+                "/build vs {path}"
+            );
+            assert_eq!(pattern_matches("build", path), any_depth, "build vs {path}");
+            assert_eq!(CompiledPattern::new("/build").matches(path), root_only);
+            assert_eq!(CompiledPattern::new("build").matches(path), any_depth);
+        }
+
+        assert!(matches!(
+            CompiledPattern::new("/build"),
+            CompiledPattern::Exact(ref p) if p == "build"
+        ));
+        assert!(matches!(
+            CompiledPattern::new("build"),
+            CompiledPattern::UnanchoredExact(ref p) if p == "build"
+        ));
+        // An internal slash anchors too
+        assert!(matches!(
+            CompiledPattern::new("src/build"),
+            CompiledPattern::Exact(_)
+        ));
+    }
+
+    #[test]
+    fn test_subsumes_anchored_by_unanchored_name() {
+        assert!(pattern_subsumes("/build", "build"));
+        assert!(pattern_subsumes("src/build/out.o", "build"));
+        assert!(!pattern_subsumes("build", "/build"));
+        assert!(!pattern_subsumes("/builder", "build"));
     }
 
     // ---------------------------------------------------------------------------
@@ -605,6 +1369,44 @@ mod tests {
         assert!(!pattern_matches("docs/**", "other/readme.md"));
     }
 
+    #[test]
+    fn test_single_star_vs_double_star_directory() {
+        // `docs/*` is one more segment: direct children only
+        for pattern in ["docs/*", "/docs/*"] {
+            assert!(matches_both(pattern, "docs/a.md"), "{}", pattern);
+            assert!(!matches_both(pattern, "docs/sub/a.md"), "{}", pattern);
+            assert!(
+                !matches_both(pattern, "docs/sub/deeper/a.md"),
+                "{}",
+                pattern
+            );
+            assert!(!matches_both(pattern, "docs"), "{}", pattern);
+            assert!(!matches_both(pattern, "src/docs/a.md"), "{}", pattern);
+        }
+        // `docs/**` is every descendant
+        for pattern in ["docs/**", "/docs/**"] {
+            assert!(matches_both(pattern, "docs/a.md"), "{}", pattern);
+            assert!(matches_both(pattern, "docs/sub/a.md"), "{}", pattern);
+            assert!(matches_both(pattern, "docs/sub/deeper/a.md"), "{}", pattern);
+            assert!(!matches_both(pattern, "src/docs/a.md"), "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn test_subsumes_single_star_only_covers_direct_children() {
+        assert!(pattern_subsumes("docs/a.md", "docs/*"));
+        assert!(pattern_subsumes("/docs/a.md", "/docs/*"));
+        assert!(pattern_subsumes("docs/*.md", "docs/*"));
+        assert!(pattern_subsumes("/docs/*", "docs/*"));
+        assert!(!pattern_subsumes("docs/sub/a.md", "docs/*"));
+        assert!(!pattern_subsumes("docs/sub/", "docs/*"));
+        assert!(!pattern_subsumes("docs/**", "docs/*"));
+        assert!(!pattern_subsumes("docs/", "docs/*"));
+        // The other way around still holds
+        assert!(pattern_subsumes("docs/*", "docs/**"));
+        assert!(pattern_subsumes("docs/*", "docs/"));
+    }
+
     #[test]
     fn test_double_star_middle() {
         // a/**/b matches a/b, a/x/b, a/x/y/b
@@ -626,6 +1428,119 @@ mod tests {
         assert!(!pattern_matches("docs/**/*.md", "src/readme.md"));
     }
 
+    #[test]
+    fn test_double_star_matches_zero_or_more_directories() {
+        // Wherever it appears, `**` between slashes stands for zero or more
+        // whole directories, so `a/**/b` matches `a/b` as on GitHub
+        #[rustfmt::skip]
+        let cases = [
+            // Leading
+            ("**/b.txt",         "b.txt",             true),
+            ("**/b.txt",         "x/b.txt",           true),
+            ("**/b.txt",         "x/y/b.txt",         true),
+            ("**/b.txt",         "xb.txt",            false),
+            ("/**/b.txt",        "b.txt",             true),
+            ("/**/b.txt",        "x/y/b.txt",         true),
+            ("**/b",             "b",                 true),
+            ("**/b",             "b/c.txt",           true),
+            ("**/b",             "x/y/b/c.txt",       true),
+            ("**/b/",            "b/c.txt",           true),
+            ("**/*.rs",          "main.rs",           true),
+            ("**/*.rs",          "src/a/main.rs",     true),
+            ("**/src/*.rs",      "src/main.rs",       true),
+            ("**/src/*.rs",      "x/src/main.rs",     true),
+            ("**/src/*.rs",      "src/x/main.rs",     false),
+            // Middle
+            ("a/**/b",           "a/b",               true),
+            ("a/**/b",           "a/x/b",             true),
+            ("a/**/b",           "a/x/y/b",           true),
+            ("a/**/b",           "a/b/c.txt",         true),
+            ("a/**/b",           "a/b.txt",           false),
+            ("a/**/b",           "a/xb",              false),
+            ("a/**/b",           "ab",                false),
+            ("a/**/b",           "x/a/b",             false),
+            ("/a/**/b",          "a/b",               true),
+            ("a/**/b/",          "a/b/c.txt",         true),
+            ("a/**/b/",          "a/x/b/c.txt",       true),
+            ("a/**/b.txt",       "a/b.txt",           true),
+            ("a/**/b.txt",       "a/x/y/b.txt",       true),
+            ("a/**/*.rs",        "a/main.rs",         true),
+            ("a/**/*.rs",        "a/x/main.rs",       true),
+            ("a/**/**/b",        "a/b",               true),
+            ("a/**/b/**/c.txt",  "a/b/c.txt",         true),
+            ("a/**/b/**/c.txt",  "a/x/b/y/c.txt",     true),
+            ("**/a/**/b.txt",    "a/b.txt",           true),
+            ("**/a/**/b.txt",    "x/a/y/b.txt",       true),
+            // Trailing
+            ("a/**",             "a/b.txt",           true),
+            ("a/**",             "a/x/y/b.txt",       true),
+            ("a/**",             "ab/c.txt",          false),
+            ("a/**",             "x/a/b.txt",         false),
+            ("/a/**",            "a/b.txt",           true),
+            ("a/b/**",           "a/b/c.txt",         true),
+            ("a/b/**",           "a/c.txt",           false),
+            ("**/b/**",          "b/c.txt",           true),
+            ("**/b/**",          "x/y/b/c.txt",       true),
+            ("**/b/**",          "x/bc/d.txt",        false),
+        ];
+        let braces = PatternOptions {
+            brace_expansion: true,
+        };
+        for (pattern, path, expected) in cases {
+            assert_eq!(matches_both(pattern, path), expected, "{pattern} vs {path}");
+            assert_eq!(
+                CompiledPattern::with_options(pattern, braces).matches(path),
+                expected,
+                "braces {pattern} vs {path}"
+            );
+        }
+        // An alternation with `**` in it keeps the same semantics
+        let compiled = CompiledPattern::with_options("{a,c}/**/b", braces);
+        assert!(compiled.matches("a/b"));
+        assert!(compiled.matches("c/x/b"));
+        assert!(!compiled.matches("d/b"));
+    }
+
+    #[test]
+    fn test_trailing_slash_never_matches_a_file_of_the_same_name() {
+        // GitHub only treats a trailing `/` as a directory: the pattern owns
+        // what's inside, and a file with the directory's name isn't matched
+        #[rustfmt::skip]
+        let cases = [
+            ("src/main.rs/",     "src/main.rs",       false),
+            ("src/main.rs/",     "src/main.rs/a.rs",  true),
+            ("/src/main.rs/",    "src/main.rs",       false),
+            ("docs/",            "docs",              false),
+            ("docs/",            "x/docs",            false),
+            ("docs/",            "docs/a.md",         true),
+            ("docs/",            "x/docs/a.md",       true),
+            ("docs/",            "docs.md",           false),
+            ("/docs/",           "docs",              false),
+            ("/docs/",           "docs/a.md",         true),
+            ("/docs/",           "x/docs/a.md",       false),
+            ("Makefile/",        "tools/Makefile",    false),
+            ("log*/",            "logs",              false),
+            ("log*/",            "x/logs",            false),
+            ("log*/",            "x/logs/today.txt",  true),
+            ("src/*/",           "src/api",           false),
+            ("src/*/",           "src/api/a.rs",      true),
+            ("**/b/",            "x/b",               false),
+            ("a/**/b/",          "a/x/b",             false),
+            ("*/",               "README.md",         false),
+            ("*/",               "src/main.rs",       true),
+            // Without the slash, the same names match files too
+            ("src/main.rs",      "src/main.rs",       true),
+            ("docs",             "x/docs",            true),
+            ("log*",             "x/logs",            true),
+            // `/**` keeps owning the directory path itself
+            ("docs/**",          "docs",              true),
+            ("src/*/**",         "src/api",           true),
+        ];
+        for (pattern, path, expected) in cases {
+            assert_eq!(matches_both(pattern, path), expected, "{pattern} vs {path}");
+        }
+    }
+
     // ---------------------------------------------------------------------------
     // CATEGORY 10: CASE SENSITIVITY
     // ---------------------------------------------------------------------------
@@ -712,6 +1627,80 @@ mod tests {
         assert!(pattern_matches("**/docs/**", "src/docs/file.txt"));
     }
 
+    #[test]
+    fn test_github_reference_directory_globs() {
+        // A glob that names a directory owns everything beneath it, unless its
+        // last segment is a wildcard (`docs/*` stops at direct children)
+        #[rustfmt::skip]
+        let cases = [
+            // Trailing /** includes the directory itself
+            ("docs/**",      "docs",                     true),
+            ("docs/**",      "docs/a/b.md",              true),
+            ("/docs/**",     "docs",                     true),
+            ("docs/**",      "src/docs/a.md",            false),
+            ("docs/**",      "docsx/a.md",               false),
+            ("src/*/**",     "src/api",                  true),
+            ("src/*/**",     "src/api/v1/h.rs",          true),
+            ("src/*/**",     "src",                      false),
+            // /**/ matches zero or more directories
+            ("a/**/b",       "a/b",                      true),
+            ("a/**/b",       "a/x/y/b",                  true),
+            ("a/**/b",       "a/b/c.txt",                true),
+            ("a/**/b",       "a/x/b/c/d.txt",            true),
+            ("a/**/b",       "a/bc",                     false),
+            ("a/**/b",       "x/a/b",                    false),
+            ("/a/**/b/",     "a/x/b/c.txt",              true),
+            // GitHub docs: **/logs owns any file in any logs directory
+            ("**/logs",      "build/logs/a.log",         true),
+            ("**/logs",      "deeply/nested/logs/a.log", true),
+            ("**/logs",      "logs/a.log",               true),
+            ("**/logs",      "build/logsx/a.log",        false),
+            // GitHub docs: docs/* stops at direct children
+            ("docs/*",       "docs/getting-started.md",  true),
+            ("docs/*",       "docs/build-app/trouble.md", false),
+            ("docs/*/",      "docs/build-app/trouble.md", true),
+            ("docs/**/*.md", "docs/a.md",                true),
+            ("docs/**/*.md", "docs/a.md/b.txt",          false),
+            // Unanchored single-segment directory globs match at any depth
+            ("log*/",        "logs/a.txt",               true),
+            ("log*/",        "src/logs/a.txt",           true),
+            ("log*/",        "src/blog/a.txt",           false),
+        ];
+        for (pattern, path, expected) in cases {
+            assert_eq!(
+                pattern_matches(pattern, path),
+                expected,
+                "{pattern} vs {path}"
+            );
+            assert_eq!(
+                CompiledPattern::new(pattern).matches(path),
+                expected,
+                "compiled {pattern} vs {path}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_compiled_double_star_shapes() {
+        assert!(matches!(
+            CompiledPattern::new("docs/**"),
+            CompiledPattern::AnchoredDirectory(ref d) if d == "docs"
+        ));
+        assert!(matches!(
+            CompiledPattern::new("a/**/b"),
+            CompiledPattern::GlobDirectory(ref g) if g == "a/**/b"
+        ));
+        assert!(matches!(
+            CompiledPattern::new("log*/"),
+            CompiledPattern::DirectoryContents(ref d)
+                if matches!(**d, CompiledPattern::GlobDirectory(ref g) if g == "**/log*")
+        ));
+        assert!(matches!(
+            CompiledPattern::new("src/**/*.rs"),
+            CompiledPattern::MultiSegmentGlob(_)
+        ));
+    }
+
     // ---------------------------------------------------------------------------
     // CATEGORY 13: EDGE CASES
     // ---------------------------------------------------------------------------
@@ -734,6 +1723,26 @@ mod tests {
         assert!(pattern_matches(".github/", ".github/workflows/ci.yml"));
     }
 
+    #[test]
+    fn test_wildcards_match_dotfiles() {
+        // Unlike shell globbing, GitHub lets wildcards match a leading dot
+        // in file and directory names
+        assert!(matches_both("*", ".env"));
+        assert!(matches_both("/*", ".env"));
+        assert!(matches_both("*.yml", ".github/workflows/x.yml"));
+        assert!(matches_both("*.yml", ".ci.yml"));
+        assert!(matches_both("*.env*", ".env.local"));
+        assert!(matches_both("**/*.yml", ".github/workflows/x.yml"));
+        assert!(matches_both("*/*.yml", ".github/x.yml"));
+        assert!(matches_both("src/*", "src/.env"));
+        assert!(matches_both("src/**/*.rs", "src/.hidden/a.rs"));
+        assert!(matches_both("docs/*.md", "docs/.draft.md"));
+        assert!(matches_both("**/workflows", ".github/workflows/x.yml"));
+        assert!(matches_both("**/.env", "config/.env"));
+        assert!(matches_both(".*", "config/.env"));
+        assert!(!matches_both(".*", "config/env"));
+    }
+
     #[test]
     fn test_deeply_nested() {
         let deep = "a/b/c/d/e/f/g/h/i/j/k/file.txt";
@@ -780,6 +1789,20 @@ mod tests {
         assert!(pattern_subsumes("*.rs", "**"));
         assert!(pattern_subsumes("/src/lib/", "**"));
         assert!(pattern_subsumes("docs/", "**")); // unanchored dir subsumed by **
+        assert!(pattern_subsumes("/src/lib/", "/**"));
+    }
+
+    #[test]
+    fn test_subsumes_anchored_star_only_covers_root_files() {
+        assert!(pattern_subsumes("/*.md", "/*"));
+        assert!(pattern_subsumes("/?", "/*"));
+        assert!(!pattern_subsumes("/src/a.rs", "/*"));
+        assert!(!pattern_subsumes("/src/", "/*"));
+        // Exact names also own a directory's contents
+        assert!(!pattern_subsumes("/build", "/*"));
+        // Unanchored patterns match at any depth
+        assert!(!pattern_subsumes("*.md", "/*"));
+        assert!(!pattern_subsumes("Makefile", "/*"));
     }
 
     #[test]
@@ -835,6 +1858,9 @@ mod tests {
     #[test]
     fn test_subsumes_file_in_dir() {
         assert!(pattern_subsumes("src/main.rs", "src/"));
+        // `src/main.rs/` only owns a directory's contents, not the file
+        assert!(!pattern_subsumes("src/main.rs", "src/main.rs/"));
+        assert!(pattern_subsumes("src/main.rs", "src/main.rs/**"));
         assert!(pattern_subsumes("src/main.rs", "src/**"));
         assert!(!pattern_subsumes("src/main.rs", "lib/"));
     }
@@ -1002,7 +2028,7 @@ mod tests {
         // (This tests the !a_anchored && b_anchored case in subsumes)
         // Note: "lib/foo.rs" contains / so is implicitly anchored
         // For truly unanchored file, use single segment
-        assert!(!pattern_subsumes("foo.rs", "/src/")); // foo.rs is single segment, implicitly anchored
+        assert!(!pattern_subsumes("foo.rs", "/src/")); // foo.rs matches at any depth
     }
 
     #[test]
@@ -1022,8 +2048,9 @@ mod tests {
         // Test the exact length boundary in AnchoredDirectory
         let p = CompiledPattern::new("/src/");
 
-        // Path exactly equal to dir (edge case) - matches because it's the dir itself
-        assert!(p.matches("src")); // Directory pattern matches the directory itself
+        // Path exactly equal to dir: a trailing slash only owns what's inside,
+        // so a file named `src` isn't matched
+        assert!(!p.matches("src"));
 
         // With content after
         assert!(p.matches("src/file.rs"));
@@ -5,17 +5,222 @@
 //! pattern. They do not verify that the user/team exists on GitHub or that
 //! the pattern actually matches any files in the repository.
 
+use std::borrow::Cow;
+
 use glob::Pattern;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
-static TEAM_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^@[a-zA-Z0-9-]+/[a-zA-Z0-9-]+$").unwrap());
-static USER_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^@[a-zA-Z0-9-]+$").unwrap());
+use crate::parser::parse_section_header;
+
 static EMAIL_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[^\s@]+@[^\s@]+\.[^\s@]+$").unwrap());
+static TOKEN_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\S+").unwrap());
+
+/// GitHub's limit on user and organization handle length
+const MAX_HANDLE_LEN: usize = 39;
+
+/// The kind of a well-formed owner token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OwnerKind {
+    /// `@org/team`
+    Team,
+    /// `@user`
+    User,
+    /// `user@example.com`
+    Email,
+}
+
+/// Why an owner token is malformed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OwnerErrorKind {
+    /// Neither `@`-prefixed nor an email (`user`)
+    MissingAt,
+    /// More than one leading `@` (`@@user`)
+    DoubleAt,
+    /// Nothing after the `@` (`@`)
+    EmptyHandle,
+    /// Team reference with an empty org or team (`@org/`, `@/team`)
+    EmptyTeamPart,
+    /// Team reference with more than one `/` (`@org/team/extra`)
+    TooManySegments,
+    /// Handle or team slug with characters GitHub doesn't allow
+    InvalidHandle,
+    /// Contains `@` but isn't a valid email address
+    InvalidEmail,
+}
+
+impl OwnerErrorKind {
+    /// Short human-readable reason
+    pub fn reason(self) -> &'static str {
+        match self {
+            OwnerErrorKind::MissingAt => "missing leading '@'",
+            OwnerErrorKind::DoubleAt => "more than one leading '@'",
+            OwnerErrorKind::EmptyHandle => "empty handle after '@'",
+            OwnerErrorKind::EmptyTeamPart => "team reference needs both @org and team",
+            OwnerErrorKind::TooManySegments => "team reference has more than one '/'",
+            OwnerErrorKind::InvalidHandle => {
+                "handles may only contain letters, digits and '-' (not leading), up to 39 characters"
+            }
+            OwnerErrorKind::InvalidEmail => "not a valid email address",
+        }
+    }
+}
+
+/// A malformed owner token found by [`owner_syntax_errors`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnerError {
+    /// The owner token as written
+    pub owner: String,
+    /// Byte offset of the token within the line
+    pub offset: usize,
+    /// What is wrong with it
+    pub kind: OwnerErrorKind,
+}
+
+impl OwnerError {
+    /// Diagnostic message for this error
+    pub fn message(&self) -> String {
+        format!(
+            "Invalid owner format '{}': {}. Expected @user, @org/team, or email@domain.com",
+            self.owner,
+            self.kind.reason()
+        )
+    }
+}
+
+/// A GitHub user or organization handle. Case is irrelevant - GitHub
+/// lowercases handles when resolving them.
+fn is_valid_handle(handle: &str) -> bool {
+    !handle.is_empty()
+        && handle.len() <= MAX_HANDLE_LEN
+        && !handle.starts_with('-')
+        && handle
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+/// A team slug as written after `@org/`
+fn is_valid_team_slug(slug: &str) -> bool {
+    slug.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+fn parse_owner(owner: &str) -> Result<OwnerKind, OwnerErrorKind> {
+    if owner.starts_with("@@") {
+        return Err(OwnerErrorKind::DoubleAt);
+    }
+    let Some(handle) = owner.strip_prefix('@') else {
+        return if !owner.contains('@') {
+            Err(OwnerErrorKind::MissingAt)
+        } else if EMAIL_RE.is_match(owner) {
+            Ok(OwnerKind::Email)
+        } else {
+            Err(OwnerErrorKind::InvalidEmail)
+        };
+    };
+    if handle.is_empty() {
+        return Err(OwnerErrorKind::EmptyHandle);
+    }
+
+    let parts: Vec<&str> = handle.split('/').collect();
+    match parts.as_slice() {
+        [user] if is_valid_handle(user) => Ok(OwnerKind::User),
+        [_] => Err(OwnerErrorKind::InvalidHandle),
+        parts if parts.iter().any(|p| p.is_empty()) => Err(OwnerErrorKind::EmptyTeamPart),
+        [org, team] if is_valid_handle(org) && is_valid_team_slug(team) => Ok(OwnerKind::Team),
+        [_, _] => Err(OwnerErrorKind::InvalidHandle),
+        _ => Err(OwnerErrorKind::TooManySegments),
+    }
+}
+
+/// Classify an owner token, returning `None` if it is malformed
+/// (e.g. `@`, `@org/team/extra`, or a bare name)
+pub fn classify_owner(owner: &str) -> Option<OwnerKind> {
+    parse_owner(owner).ok()
+}
+
+/// The canonical spelling of an owner: GitHub handles and teams are
+/// case-insensitive, so they are lowercased; for emails only the domain is,
+/// since the local part can be case-sensitive. Malformed owners are
+/// returned unchanged.
+///
+/// # Example
+///
+/// ```
+/// use codeowners_parser::validation::canonical_owner;
+///
+/// assert_eq!(canonical_owner("@MyOrg/Web-Team"), "@myorg/web-team");
+/// assert_eq!(canonical_owner("Jane.Doe@Example.COM"), "Jane.Doe@example.com");
+/// assert_eq!(canonical_owner("@@Typo"), "@@Typo");
+/// ```
+pub fn canonical_owner(owner: &str) -> Cow<'_, str> {
+    let lowered = match classify_owner(owner) {
+        Some(OwnerKind::User | OwnerKind::Team) => owner.to_ascii_lowercase(),
+        Some(OwnerKind::Email) => match owner.rsplit_once('@') {
+            Some((local, domain)) => format!("{}@{}", local, domain.to_ascii_lowercase()),
+            None => return Cow::Borrowed(owner),
+        },
+        None => return Cow::Borrowed(owner),
+    };
+    if lowered == owner {
+        Cow::Borrowed(owner)
+    } else {
+        Cow::Owned(lowered)
+    }
+}
+
+/// Check every owner token on a CODEOWNERS line, including a GitLab section
+/// header's default owners. Comments, blank lines and patterns are skipped.
+///
+/// # Example
+///
+/// ```
+/// use codeowners_parser::validation::{owner_syntax_errors, OwnerErrorKind};
+///
+/// let errors = owner_syntax_errors("/src/ @core @@typo # fine");
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors[0].owner, "@@typo");
+/// assert_eq!(errors[0].offset, 12);
+/// assert_eq!(errors[0].kind, OwnerErrorKind::DoubleAt);
+/// ```
+pub fn owner_syntax_errors(line: &str) -> Vec<OwnerError> {
+    let trimmed = line.trim_start();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return Vec::new();
+    }
+
+    // Owners follow the pattern, or the closing bracket of a section header
+    // (whose name may contain spaces)
+    let owners_start = if parse_section_header(line).is_some() {
+        section_header_end(line)
+    } else {
+        TOKEN_RE.find(line).map_or(line.len(), |m| m.end())
+    };
+
+    TOKEN_RE
+        .find_iter(&line[owners_start..])
+        .take_while(|m| !m.as_str().starts_with('#'))
+        .filter_map(|m| {
+            parse_owner(m.as_str()).err().map(|kind| OwnerError {
+                owner: m.as_str().to_string(),
+                offset: owners_start + m.start(),
+                kind,
+            })
+        })
+        .collect()
+}
+
+/// Byte offset just past a section header's `]` (or its `[count]`)
+fn section_header_end(line: &str) -> usize {
+    let name_end = line.find(']').map_or(line.len(), |i| i + 1);
+    match line[name_end..].strip_prefix('[') {
+        Some(count) => count.find(']').map_or(line.len(), |i| name_end + i + 2),
+        None => name_end,
+    }
+}
 
 /// Validate an owner format - returns error message if invalid
 pub fn validate_owner(owner: &str) -> Option<String> {
-    if TEAM_RE.is_match(owner) || USER_RE.is_match(owner) || EMAIL_RE.is_match(owner) {
+    if classify_owner(owner).is_some() {
         None
     } else {
         Some(format!(
@@ -25,6 +230,33 @@ pub fn validate_owner(owner: &str) -> Option<String> {
     }
 }
 
+/// Whether a rule's pattern is really a `@user` or `@org/team` owner, i.e.
+/// the line is most likely missing its file pattern (`@web` instead of
+/// `*.ts @web`). Paths that can't be an owner stay patterns: `@types/`,
+/// `/@scope/pkg`. An `@org/team` lookalike followed by owners is just as
+/// likely an npm scope path (`@babel/core @web`), so only a bare `@user`,
+/// or a line with no other tokens, counts.
+///
+/// # Example
+///
+/// ```
+/// use codeowners_parser::validation::rule_missing_pattern;
+///
+/// assert!(rule_missing_pattern("@web", &[]));
+/// assert!(rule_missing_pattern("@web", &["@docs".to_string()]));
+/// assert!(rule_missing_pattern("@org/web", &[]));
+/// assert!(!rule_missing_pattern("@babel/core", &["@web".to_string()]));
+/// assert!(!rule_missing_pattern("@types/", &[]));
+/// assert!(!rule_missing_pattern("packages/@scope/*", &[]));
+/// ```
+pub fn rule_missing_pattern(pattern: &str, owners: &[String]) -> bool {
+    match classify_owner(pattern) {
+        Some(OwnerKind::User) => true,
+        Some(OwnerKind::Team) => owners.is_empty(),
+        _ => false,
+    }
+}
+
 /// Validate a glob pattern - returns error message if invalid
 pub fn validate_pattern(pattern: &str) -> Option<String> {
     // Strip leading slash for glob validation
@@ -43,10 +275,67 @@ pub fn validate_pattern(pattern: &str) -> Option<String> {
     None
 }
 
+/// Line numbers (0-indexed) of git merge-conflict markers: `<<<<<<<`,
+/// `|||||||`, `=======` and `>>>>>>>` at the start of a line, alone or
+/// followed by whitespace and a label. Parsed as rules these become
+/// nonsense patterns, so callers should report them before anything else.
+///
+/// # Example
+///
+/// ```
+/// use codeowners_parser::validation::merge_conflict_markers;
+///
+/// let content = "\
+/// <<<<<<< HEAD
+/// /src/ @web
+/// =======
+/// /src/ @platform
+/// >>>>>>> main
+/// ";
+/// assert_eq!(merge_conflict_markers(content), vec![0, 2, 4]);
+/// assert!(merge_conflict_markers("/src/ @web\n").is_empty());
+/// ```
+pub fn merge_conflict_markers(content: &str) -> Vec<u32> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| is_conflict_marker(line))
+        .map(|(idx, _)| idx as u32)
+        .collect()
+}
+
+fn is_conflict_marker(line: &str) -> bool {
+    ["<<<<<<<", "|||||||", "=======", ">>>>>>>"]
+        .iter()
+        .any(|marker| match line.strip_prefix(marker) {
+            Some(rest) => rest.is_empty() || rest.starts_with(char::is_whitespace),
+            None => false,
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_merge_conflict_markers() {
+        let content = "* @all\n<<<<<<< HEAD\n/src/ @web\n||||||| base\n/src/ @old\n=======\r\n/src/ @platform\n>>>>>>> feature/x\n";
+        assert_eq!(merge_conflict_markers(content), vec![1, 3, 5, 7]);
+
+        // Markers only count at the start of a line, at their exact length
+        for line in [
+            "# <<<<<<< HEAD",
+            " =======",
+            "<<<<<<<<",
+            "========",
+            "<<<<<<",
+            ">>>>>>>main",
+            "/docs/ @a # =======",
+        ] {
+            assert!(merge_conflict_markers(line).is_empty(), "{line}");
+        }
+    }
+
     // Owner validation tests
     #[test]
     fn test_valid_user() {
@@ -106,6 +395,180 @@ mod tests {
         assert!(validate_owner("@org//team").is_some());
     }
 
+    fn error_kinds(line: &str) -> Vec<(String, usize, OwnerErrorKind)> {
+        owner_syntax_errors(line)
+            .into_iter()
+            .map(|e| (e.owner, e.offset, e.kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_syntax_error_double_at() {
+        assert_eq!(
+            error_kinds("*.rs @@user"),
+            vec![("@@user".to_string(), 5, OwnerErrorKind::DoubleAt)]
+        );
+    }
+
+    #[test]
+    fn test_syntax_error_missing_at() {
+        assert_eq!(
+            error_kinds("/src/ @ok user"),
+            vec![("user".to_string(), 10, OwnerErrorKind::MissingAt)]
+        );
+    }
+
+    #[test]
+    fn test_syntax_error_empty_handle() {
+        assert_eq!(
+            error_kinds("* @"),
+            vec![("@".to_string(), 2, OwnerErrorKind::EmptyHandle)]
+        );
+    }
+
+    #[test]
+    fn test_syntax_error_empty_team_part() {
+        assert_eq!(
+            error_kinds("* @org/ @/team @org//team"),
+            vec![
+                ("@org/".to_string(), 2, OwnerErrorKind::EmptyTeamPart),
+                ("@/team".to_string(), 8, OwnerErrorKind::EmptyTeamPart),
+                ("@org//team".to_string(), 15, OwnerErrorKind::EmptyTeamPart),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_syntax_error_too_many_segments() {
+        assert_eq!(
+            error_kinds("* @org/team/extra"),
+            vec![(
+                "@org/team/extra".to_string(),
+                2,
+                OwnerErrorKind::TooManySegments
+            )]
+        );
+    }
+
+    #[test]
+    fn test_syntax_error_invalid_handle() {
+        let long = format!("* @{}", "a".repeat(40));
+        assert_eq!(error_kinds(&long)[0].2, OwnerErrorKind::InvalidHandle);
+        assert_eq!(
+            error_kinds("* @user_name @-user @org.x/team @org/team_x"),
+            vec![
+                ("@user_name".to_string(), 2, OwnerErrorKind::InvalidHandle),
+                ("@-user".to_string(), 13, OwnerErrorKind::InvalidHandle),
+                ("@org.x/team".to_string(), 20, OwnerErrorKind::InvalidHandle),
+                ("@org/team_x".to_string(), 32, OwnerErrorKind::InvalidHandle),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_syntax_error_invalid_email() {
+        assert_eq!(
+            error_kinds("* user@localhost dev@"),
+            vec![
+                (
+                    "user@localhost".to_string(),
+                    2,
+                    OwnerErrorKind::InvalidEmail
+                ),
+                ("dev@".to_string(), 17, OwnerErrorKind::InvalidEmail),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_syntax_valid_edge_cases() {
+        // Plus-tagged emails, hyphenated team slugs, uppercase handles
+        assert!(owner_syntax_errors(
+            "* dev+codeowners@example.com @My-Org/platform-team @UPPER @a"
+        )
+        .is_empty());
+        let max = format!("* @{}", "a".repeat(39));
+        assert!(owner_syntax_errors(&max).is_empty());
+    }
+
+    #[test]
+    fn test_syntax_skips_pattern_comments_and_blank_lines() {
+        // A pattern that looks like a bad owner is not an owner
+        assert!(owner_syntax_errors("user @ok").is_empty());
+        assert!(owner_syntax_errors("# @@comment").is_empty());
+        assert!(owner_syntax_errors("   ").is_empty());
+        assert!(owner_syntax_errors("* @ok # @@not-an-owner").is_empty());
+    }
+
+    #[test]
+    fn test_syntax_section_header_default_owners() {
+        assert!(owner_syntax_errors("[Frontend Docs] @docs").is_empty());
+        assert_eq!(
+            error_kinds("^[My Section][2] @ok bad"),
+            vec![("bad".to_string(), 21, OwnerErrorKind::MissingAt)]
+        );
+    }
+
+    #[test]
+    fn test_canonical_owner_handles() {
+        assert_eq!(canonical_owner("@User"), "@user");
+        assert_eq!(
+            canonical_owner("@MyOrg/Platform-Team"),
+            "@myorg/platform-team"
+        );
+        assert!(matches!(canonical_owner("@org/team"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_canonical_owner_email_keeps_local_part() {
+        assert_eq!(
+            canonical_owner("Jane.Doe@Example.COM"),
+            "Jane.Doe@example.com"
+        );
+        assert_eq!(
+            canonical_owner("Dev+Tag@sub.example.org"),
+            "Dev+Tag@sub.example.org"
+        );
+    }
+
+    #[test]
+    fn test_canonical_owner_malformed_unchanged() {
+        assert_eq!(canonical_owner("@@User"), "@@User");
+        assert_eq!(canonical_owner("Name"), "Name");
+    }
+
+    #[test]
+    fn test_classify_team() {
+        assert_eq!(classify_owner("@org/team"), Some(OwnerKind::Team));
+        assert_eq!(classify_owner("@my-org/my-team"), Some(OwnerKind::Team));
+    }
+
+    #[test]
+    fn test_classify_user() {
+        assert_eq!(classify_owner("@user"), Some(OwnerKind::User));
+        assert_eq!(classify_owner("@user-123"), Some(OwnerKind::User));
+    }
+
+    #[test]
+    fn test_classify_email() {
+        assert_eq!(classify_owner("dev@example.com"), Some(OwnerKind::Email));
+        assert_eq!(
+            classify_owner("first.last+tag@sub.example.org"),
+            Some(OwnerKind::Email)
+        );
+    }
+
+    #[test]
+    fn test_classify_malformed() {
+        assert_eq!(classify_owner("@"), None);
+        assert_eq!(classify_owner(""), None);
+        assert_eq!(classify_owner("@org/team/extra"), None);
+        assert_eq!(classify_owner("@org/"), None);
+        assert_eq!(classify_owner("@/team"), None);
+        assert_eq!(classify_owner("username"), None);
+        assert_eq!(classify_owner("user@localhost"), None);
+    }
+
     // Pattern validation tests
     #[test]
     fn test_valid_patterns() {
@@ -123,6 +586,30 @@ mod tests {
         assert!(validate_pattern("/").is_some());
     }
 
+    #[test]
+    fn test_rule_missing_pattern() {
+        for owner in ["@web", "@org/web", "@Org/Web-Team"] {
+            assert!(rule_missing_pattern(owner, &[]), "{owner}");
+        }
+        let owners = ["@web".to_string()];
+        assert!(rule_missing_pattern("@docs", &owners));
+        // npm scope paths followed by owners
+        for pattern in ["@babel/core", "@types/node"] {
+            assert!(!rule_missing_pattern(pattern, &owners), "{pattern}");
+        }
+        for pattern in [
+            "*.rs",
+            "@types/",
+            "/@scope/pkg",
+            "**/@types/**",
+            "@scope/*.ts",
+            "user@example.com",
+            "@",
+        ] {
+            assert!(!rule_missing_pattern(pattern, &[]), "{pattern}");
+        }
+    }
+
     #[test]
     fn test_invalid_glob_syntax() {
         // Unclosed bracket